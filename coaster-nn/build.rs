@@ -0,0 +1,25 @@
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(cuda_kernels)");
+    if env::var_os("CARGO_FEATURE_CUDA").is_none() {
+        return;
+    }
+
+    // the elementwise kernels are compiled to PTX, which the driver compiles for the device at hand
+    let source = "src/frameworks/cuda/arithmetic.cu";
+    println!("cargo:rerun-if-changed={}", source);
+    println!("cargo:rerun-if-env-changed=NVCC");
+    println!("cargo:rerun-if-env-changed=PATH");
+    let nvcc = env::var("NVCC").unwrap_or_else(|_| "nvcc".to_string());
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("arithmetic.ptx");
+    // without the kernels the elementwise operations report that they are not supported
+    match Command::new(&nvcc).arg("--ptx").arg("-o").arg(&out).arg(source).status() {
+        Ok(status) if status.success() => println!("cargo:rustc-cfg=cuda_kernels"),
+        Ok(_) => println!("cargo:warning=`{}` failed to compile {}, the CUDA elementwise operations are disabled", nvcc, source),
+        Err(err) => println!("cargo:warning=Unable to run `{}`, the CUDA elementwise operations are disabled, set NVCC to its path: {}", nvcc, err),
+    }
+}
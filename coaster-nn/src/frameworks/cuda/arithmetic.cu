@@ -0,0 +1,88 @@
+// Elementwise operations cuDNN does not provide, compiled to PTX by the build script.
+//
+// The operation codes are mapped from `BinaryOperation` and `UnaryOperation`
+// in `kernels.rs`, keep both in sync.
+
+#define MAX_RANK 8
+
+// Dimensions of the result and strides of both operands broadcast to them,
+// with a stride of zero along the broadcast dimensions.
+struct BroadcastShape {
+    unsigned int rank;
+    unsigned int dims[MAX_RANK];
+    unsigned int a_strides[MAX_RANK];
+    unsigned int b_strides[MAX_RANK];
+};
+
+template <typename T>
+__device__ T binary_op(unsigned int op, T a, T b) {
+    switch (op) {
+    case 0: return a + b;
+    case 1: return a - b;
+    case 2: return a * b;
+    case 3: return a / b;
+    case 4: return pow(a, b);
+    case 5: return fmin(a, b);
+    default: return fmax(a, b);
+    }
+}
+
+template <typename T>
+__device__ T unary_op(unsigned int op, T x, T min, T max) {
+    switch (op) {
+    case 0: return -x;
+    case 1: return fabs(x);
+    case 2: return exp(x);
+    case 3: return log(x);
+    case 4: return sqrt(x);
+    default: return fmin(fmax(x, min), max);
+    }
+}
+
+template <typename T>
+__device__ void binary(unsigned int op, const T *a, const T *b, T *result,
+                       unsigned int n, BroadcastShape shape) {
+    for (unsigned int i = blockIdx.x * blockDim.x + threadIdx.x; i < n; i += blockDim.x * gridDim.x) {
+        unsigned int rest = i;
+        unsigned int a_offset = 0;
+        unsigned int b_offset = 0;
+        for (int d = shape.rank - 1; d >= 0; --d) {
+            unsigned int idx = rest % shape.dims[d];
+            rest /= shape.dims[d];
+            a_offset += idx * shape.a_strides[d];
+            b_offset += idx * shape.b_strides[d];
+        }
+        result[i] = binary_op(op, a[a_offset], b[b_offset]);
+    }
+}
+
+template <typename T>
+__device__ void unary(unsigned int op, const T *x, T *result, unsigned int n, T min, T max) {
+    for (unsigned int i = blockIdx.x * blockDim.x + threadIdx.x; i < n; i += blockDim.x * gridDim.x) {
+        result[i] = unary_op(op, x[i], min, max);
+    }
+}
+
+extern "C" {
+
+__global__ void binary_f32(unsigned int op, const float *a, const float *b, float *result,
+                           unsigned int n, BroadcastShape shape) {
+    binary(op, a, b, result, n, shape);
+}
+
+__global__ void binary_f64(unsigned int op, const double *a, const double *b, double *result,
+                           unsigned int n, BroadcastShape shape) {
+    binary(op, a, b, result, n, shape);
+}
+
+__global__ void unary_f32(unsigned int op, const float *x, float *result, unsigned int n,
+                          float min, float max) {
+    unary(op, x, result, n, min, max);
+}
+
+__global__ void unary_f64(unsigned int op, const double *x, double *result, unsigned int n,
+                          double min, double max) {
+    unary(op, x, result, n, min, max);
+}
+
+}
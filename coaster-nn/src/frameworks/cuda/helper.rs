@@ -31,3 +31,18 @@ macro_rules! trans_mut {
         unsafe { ::std::mem::transmute::<u64, *mut ::libc::c_void>(*$mem.id_c()) }
     )
 }
+
+/// Layer normalization is not provided by cuDNN, so the configuration only
+/// keeps the parameters around.
+#[derive(Debug, Copy, Clone)]
+pub struct LayerNormalizationConfig {
+    pub epsilon: f64,
+}
+
+/// Group normalization is not provided by cuDNN, so the configuration only
+/// keeps the parameters around.
+#[derive(Debug, Copy, Clone)]
+pub struct GroupNormalizationConfig {
+    pub groups: usize,
+    pub epsilon: f64,
+}
@@ -0,0 +1,176 @@
+//! Launches the elementwise kernels of `arithmetic.cu`, which cuDNN does not provide.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+
+use crate::co::Error;
+use crate::co::frameworks::cuda::{Driver, Function, Module};
+use crate::co::plugin::Error as PluginError;
+use crate::cudnn::utils::{DataType, DataTypeInfo};
+use crate::plugin::{BinaryOperation, UnaryOperation};
+
+/// The kernels compiled to PTX by the build script, if `nvcc` was available.
+#[cfg(cuda_kernels)]
+const PTX: Option<&str> = Some(concat!(include_str!(concat!(env!("OUT_DIR"), "/arithmetic.ptx")), "\0"));
+#[cfg(not(cuda_kernels))]
+const PTX: Option<&str> = None;
+
+/// The highest rank of Tensors the kernels can broadcast, `MAX_RANK` in `arithmetic.cu`.
+const MAX_RANK: usize = 8;
+const BLOCK_SIZE: u32 = 256;
+const MAX_GRID_SIZE: u32 = 65535;
+
+/// Mirrors `BroadcastShape` of `arithmetic.cu`.
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+struct BroadcastShape {
+    rank: u32,
+    dims: [u32; MAX_RANK],
+    a_strides: [u32; MAX_RANK],
+    b_strides: [u32; MAX_RANK],
+}
+
+thread_local! {
+    /// The module of the kernels loaded into each context, by the id of the context.
+    static MODULES: RefCell<HashMap<isize, Module>> = RefCell::new(HashMap::new());
+}
+
+fn binary_op_code(op: BinaryOperation) -> u32 {
+    match op {
+        BinaryOperation::Add => 0,
+        BinaryOperation::Sub => 1,
+        BinaryOperation::Mul => 2,
+        BinaryOperation::Div => 3,
+        BinaryOperation::Pow => 4,
+        BinaryOperation::Min => 5,
+        BinaryOperation::Max => 6,
+    }
+}
+
+fn unary_op_code(op: UnaryOperation) -> u32 {
+    match op {
+        UnaryOperation::Neg => 0,
+        UnaryOperation::Abs => 1,
+        UnaryOperation::Exp => 2,
+        UnaryOperation::Log => 3,
+        UnaryOperation::Sqrt => 4,
+        UnaryOperation::Clamp { .. } => 5,
+    }
+}
+
+/// Returns the kernel `name` for elements of type `T`, loading the module into
+/// the context `context` on first use.
+fn kernel<T: DataTypeInfo>(context: isize, name: &str) -> Result<Function, Error> {
+    let suffix = match <T as DataTypeInfo>::cudnn_data_type() {
+        DataType::Float => "f32",
+        DataType::Double => "f64",
+        DataType::Half => {
+            return Err(Error::Plugin(PluginError::Plugin("Half precision is not yet supported by the CUDA elementwise kernels.")))
+        }
+    };
+    let ptx = match PTX {
+        Some(ptx) => ptx,
+        None => {
+            return Err(Error::Plugin(PluginError::Plugin("The CUDA elementwise kernels are not supported, \
+             coaster-nn was built without nvcc.")))
+        }
+    };
+    let module = MODULES.with(|modules| {
+        let mut modules = modules.borrow_mut();
+        if let Some(module) = modules.get(&context) {
+            return Ok(*module);
+        }
+        let image = CStr::from_bytes_with_nul(ptx.as_bytes()).unwrap();
+        let module = match Driver::load_module(image) {
+            Ok(module) => module,
+            Err(_) => return Err(Error::Plugin(PluginError::Plugin("Unable to load the CUDA elementwise kernels."))),
+        };
+        modules.insert(context, module);
+        Ok(module)
+    })?;
+    let name = CString::new(format!("{}_{}", name, suffix)).unwrap();
+    match Driver::get_function(&module, &name) {
+        Ok(function) => Ok(function),
+        Err(_) => Err(Error::Plugin(PluginError::Plugin("Unable to find CUDA elementwise kernel."))),
+    }
+}
+
+/// Launches `function` with enough threads for `n` elements.
+fn launch(function: &Function, n: u32, params: &mut [*mut ::libc::c_void]) -> Result<(), Error> {
+    if n == 0 {
+        return Ok(());
+    }
+    let grid = u32::min((n - 1) / BLOCK_SIZE + 1, MAX_GRID_SIZE);
+    match unsafe { Driver::launch_kernel(function, (grid, 1, 1), (BLOCK_SIZE, 1, 1), params) } {
+        Ok(_) => Ok(()),
+        Err(_) => Err(Error::Plugin(PluginError::Plugin("Unable to launch CUDA elementwise kernel."))),
+    }
+}
+
+/// Strides to index a Tensor of dimensions `dims` broadcast to `shape`,
+/// with a stride of zero along the broadcast dimensions.
+fn broadcast_strides(dims: &[usize], shape: &[usize]) -> [u32; MAX_RANK] {
+    let offset = shape.len() - dims.len();
+    let mut strides = [0; MAX_RANK];
+    let mut stride = 1;
+    for (i, &dim) in dims.iter().enumerate().rev() {
+        if dim != 1 {
+            strides[offset + i] = stride as u32;
+        }
+        stride *= dim;
+    }
+    strides
+}
+
+/// Number of elements `n` as the kernels index them.
+fn element_count(n: usize) -> Result<u32, Error> {
+    if n > u32::max_value() as usize {
+        return Err(Error::Plugin(PluginError::Plugin("Tensor is too large for the CUDA elementwise kernels.")));
+    }
+    Ok(n as u32)
+}
+
+/// Computes `op` for the elements at the device pointers `a` and `b` broadcast
+/// to `shape`, and saves them to `result`, which may be `a`.
+pub fn binary_operation<T: DataTypeInfo>(context: isize, op: BinaryOperation,
+                                         a: u64, a_dims: &[usize],
+                                         b: u64, b_dims: &[usize],
+                                         result: u64, shape: &[usize])
+                                         -> Result<(), Error> {
+    if shape.len() > MAX_RANK {
+        return Err(Error::Plugin(PluginError::Plugin("Tensor rank is too high for the CUDA elementwise kernels.")));
+    }
+    let mut n = element_count(shape.iter().product())?;
+    let mut broadcast = BroadcastShape::default();
+    broadcast.rank = shape.len() as u32;
+    for (d, &dim) in shape.iter().enumerate() {
+        broadcast.dims[d] = dim as u32;
+    }
+    broadcast.a_strides = broadcast_strides(a_dims, shape);
+    broadcast.b_strides = broadcast_strides(b_dims, shape);
+    let function = kernel::<T>(context, "binary")?;
+    let (mut op, mut a, mut b, mut result) = (binary_op_code(op), a, b, result);
+    launch(&function, n, &mut [&mut op as *mut u32 as *mut ::libc::c_void,
+                               &mut a as *mut u64 as *mut ::libc::c_void,
+                               &mut b as *mut u64 as *mut ::libc::c_void,
+                               &mut result as *mut u64 as *mut ::libc::c_void,
+                               &mut n as *mut u32 as *mut ::libc::c_void,
+                               &mut broadcast as *mut BroadcastShape as *mut ::libc::c_void])
+}
+
+/// Computes `op` for the `n` elements at the device pointer `x` and saves them
+/// to `result`, which may be `x`. `min` and `max` are the bounds of `Clamp`.
+pub fn unary_operation<T: DataTypeInfo>(context: isize, op: UnaryOperation,
+                                        x: u64, result: u64, n: usize, min: T, max: T)
+                                        -> Result<(), Error> {
+    let mut n = element_count(n)?;
+    let function = kernel::<T>(context, "unary")?;
+    let (mut op, mut x, mut result, mut min, mut max) = (unary_op_code(op), x, result, min, max);
+    launch(&function, n, &mut [&mut op as *mut u32 as *mut ::libc::c_void,
+                               &mut x as *mut u64 as *mut ::libc::c_void,
+                               &mut result as *mut u64 as *mut ::libc::c_void,
+                               &mut n as *mut u32 as *mut ::libc::c_void,
+                               &mut min as *mut T as *mut ::libc::c_void,
+                               &mut max as *mut T as *mut ::libc::c_void])
+}
@@ -11,6 +11,7 @@ use crate::plugin::*;
 
 #[macro_use]
 pub mod helper;
+mod kernels;
 
 fn rnn_sequence_descriptors(sequence_length: i32,
                             input_size: i32,
@@ -113,8 +114,25 @@ pub trait ICudnnDesc<T> {
     /// This should be used in operations where the shape doesn't really matter
     /// e.g. activation like ReLU.
     fn cudnn_tensor_desc_flat(&self) -> Result<TensorDescriptor, PluginError>;
+    /// Creates a TensorDescriptor similar to `cudnn_tensor_desc`,
+    /// but will create a fitting 4D tensor if the actual tensor would be 2D/3D.
+    ///
+    /// The channels stay in the second dimension, as required by batch normalization.
+    fn cudnn_tensor_desc_batch_norm(&self) -> Result<TensorDescriptor, PluginError>;
+    /// Creates the TensorDescriptor of the per channel parameters of a batch
+    /// normalization over this tensor, i.e. `1xCx1x1`.
+    fn cudnn_batch_norm_param_desc(&self) -> Result<TensorDescriptor, PluginError>;
 
     fn cudnn_filter_desc(&self) -> Result<FilterDescriptor, PluginError>;
+    /// Creates a TensorDescriptor similar to `cudnn_tensor_desc`,
+    /// but will create a fitting 4D tensor if the actual tensor would be 3D.
+    ///
+    /// A unit height is inserted before the single spatial dimension, as cuDNN
+    /// convolutions and pooling need at least two spatial dimensions.
+    fn cudnn_tensor_desc_conv(&self) -> Result<TensorDescriptor, PluginError>;
+    /// Creates a FilterDescriptor similar to `cudnn_filter_desc`,
+    /// but will create a fitting 4D filter if the actual filter would be 3D.
+    fn cudnn_filter_desc_conv(&self) -> Result<FilterDescriptor, PluginError>;
 
     fn cudnn_convolution_desc(&self,
                               filter: &SharedTensor<T>)
@@ -206,6 +224,14 @@ impl ConvBackwardFilterAlgo {
                  ConvBackwardFilterAlgo::Auto. Use `find_cudnn_algo` to find an \
                  algorithm.")))
             }
+            GEMM => {
+                return Err(Error::Plugin(PluginError::Plugin("cuDNN does not provide an explicit GEMM \
+                 convolution backward filter algorithm.")))
+            }
+            Direct => {
+                return Err(Error::Plugin(PluginError::Plugin("cuDNN does not provide a direct \
+                 convolution backward filter algorithm.")))
+            }
             ImplicitGEMM => CUDNN_CONVOLUTION_BWD_FILTER_ALGO_1,
             ImplicitGEMMSum => CUDNN_CONVOLUTION_BWD_FILTER_ALGO_0,
             ImplicitPrecompiledGEMMSum => CUDNN_CONVOLUTION_BWD_FILTER_ALGO_3,
@@ -264,6 +290,14 @@ impl ConvBackwardDataAlgo {
                  ConvBackwardDataAlgo::Auto. Use `find_cudnn_algo` to find \
                  an algorithm.")))
             }
+            GEMM => {
+                return Err(Error::Plugin(PluginError::Plugin("cuDNN does not provide an explicit GEMM \
+                 convolution backward data algorithm.")))
+            }
+            Direct => {
+                return Err(Error::Plugin(PluginError::Plugin("cuDNN does not provide a direct \
+                 convolution backward data algorithm.")))
+            }
             ImplicitGEMM => CUDNN_CONVOLUTION_BWD_DATA_ALGO_1,
             ImplicitGEMMSum => CUDNN_CONVOLUTION_BWD_DATA_ALGO_0,
             FFT => CUDNN_CONVOLUTION_BWD_DATA_ALGO_FFT,
@@ -363,6 +397,34 @@ impl<T> ICudnnDesc<T> for SharedTensor<T>
         }
     }
 
+    fn cudnn_tensor_desc_batch_norm(&self) -> Result<TensorDescriptor, PluginError> {
+        let mut override_desc = self.desc().clone();
+        while override_desc.len() < 4 {
+            override_desc.push(1);
+        }
+        match TensorDescriptor::new(&override_desc.dims_i32().clone(),
+                                    &override_desc.default_stride_i32().clone(),
+                                    <T as DataTypeInfo>::cudnn_data_type()) {
+            Ok(desc) => Ok(desc),
+            Err(_) => Err(PluginError::Plugin("Unable to create CuDNN TensorDescriptor.")),
+        }
+    }
+
+    fn cudnn_batch_norm_param_desc(&self) -> Result<TensorDescriptor, PluginError> {
+        let actual_desc = self.desc();
+        if actual_desc.len() < 2 {
+            return Err(PluginError::Plugin("Batch normalization requires at least a batch and a channel dimension."));
+        }
+        let mut param_desc = vec![1; usize::max(4, actual_desc.len())];
+        param_desc[1] = actual_desc[1];
+        match TensorDescriptor::new(&param_desc.dims_i32().clone(),
+                                    &param_desc.default_stride_i32().clone(),
+                                    <T as DataTypeInfo>::cudnn_data_type()) {
+            Ok(desc) => Ok(desc),
+            Err(_) => Err(PluginError::Plugin("Unable to create CuDNN TensorDescriptor.")),
+        }
+    }
+
     fn cudnn_filter_desc(&self) -> Result<FilterDescriptor, PluginError> {
         match FilterDescriptor::new(&self.desc().dims_i32().clone(),
                                     <T as DataTypeInfo>::cudnn_data_type()) {
@@ -371,6 +433,31 @@ impl<T> ICudnnDesc<T> for SharedTensor<T>
         }
     }
 
+    fn cudnn_tensor_desc_conv(&self) -> Result<TensorDescriptor, PluginError> {
+        let mut override_desc = self.desc().clone();
+        if override_desc.len() == 3 {
+            override_desc.insert(2, 1);
+        }
+        match TensorDescriptor::new(&override_desc.dims_i32().clone(),
+                                    &override_desc.default_stride_i32().clone(),
+                                    <T as DataTypeInfo>::cudnn_data_type()) {
+            Ok(desc) => Ok(desc),
+            Err(_) => Err(PluginError::Plugin("Unable to create CuDNN TensorDescriptor.")),
+        }
+    }
+
+    fn cudnn_filter_desc_conv(&self) -> Result<FilterDescriptor, PluginError> {
+        let mut override_desc = self.desc().clone();
+        if override_desc.len() == 3 {
+            override_desc.insert(2, 1);
+        }
+        match FilterDescriptor::new(&override_desc.dims_i32().clone(),
+                                    <T as DataTypeInfo>::cudnn_data_type()) {
+            Ok(desc) => Ok(desc),
+            Err(_) => Err(PluginError::Plugin("Unable to create CuDNN FilterDescriptor.")),
+        }
+    }
+
     //fn cudnn_tensor_desc_rnn(&self) -> Result<TensorDescriptor, PluginError> {
     //    let actual_desc : Vec<usize> = self.desc().clone();
     //    unimplemented!()
@@ -425,6 +512,9 @@ impl<T> NN<T> for Backend<Cuda>
     type CPOOL = utils::PoolingConfig;
     type CDROP = utils::DropoutConfig;
     type CRNN = utils::RnnConfig;
+    type CBN = utils::BatchNormalizationConfig;
+    type CLN = helper::LayerNormalizationConfig;
+    type CGN = helper::GroupNormalizationConfig;
 
     fn init_nn() {
         //let _ = cudnn_framework.id_c();
@@ -436,6 +526,9 @@ impl<T> NNOperationConfig<T> for utils::RnnConfig where T: Float + DataTypeInfo
 impl<T> NNOperationConfig<T> for utils::NormalizationConfig where T: Float + DataTypeInfo {}
 impl<T> NNOperationConfig<T> for utils::PoolingConfig where T: Float + DataTypeInfo {}
 impl<T> NNOperationConfig<T> for utils::DropoutConfig where T: Float + DataTypeInfo {}
+impl<T> NNOperationConfig<T> for utils::BatchNormalizationConfig where T: Float + DataTypeInfo {}
+impl<T> NNOperationConfig<T> for helper::LayerNormalizationConfig where T: Float + DataTypeInfo {}
+impl<T> NNOperationConfig<T> for helper::GroupNormalizationConfig where T: Float + DataTypeInfo {}
 
 impl<T> Sigmoid<T> for Backend<Cuda>
     where T: Float + DataTypeInfo + Default
@@ -501,24 +594,46 @@ impl<T> ConvolutionConfig<T> for crate::cudnn::utils::ConvolutionConfig
 impl<T> Convolution<T> for Backend<Cuda>
     where T: Float + DataTypeInfo
 {
-    fn new_convolution_config(&self,
-                              src: &SharedTensor<T>,
-                              dest: &SharedTensor<T>,
-                              filter: &SharedTensor<T>,
-                              algo_fwd: ConvForwardAlgo,
-                              algo_bwd_filter: ConvBackwardFilterAlgo,
-                              algo_bwd_data: ConvBackwardDataAlgo,
-                              stride: &[i32],
-                              zero_padding: &[i32])
-                              -> Result<Self::CC, Error> {
+    fn new_grouped_convolution_config(&self,
+                                      src: &SharedTensor<T>,
+                                      dest: &SharedTensor<T>,
+                                      filter: &SharedTensor<T>,
+                                      algo_fwd: ConvForwardAlgo,
+                                      algo_bwd_filter: ConvBackwardFilterAlgo,
+                                      algo_bwd_data: ConvBackwardDataAlgo,
+                                      stride: &[i32],
+                                      zero_padding: &[i32],
+                                      dilation: &[i32],
+                                      groups: usize)
+                                      -> Result<Self::CC, Error> {
+        let (input_dim, filter_dim) = (src.desc(), filter.desc());
+        if groups == 0 || filter_dim[0] % groups != 0 || input_dim[1] != filter_dim[1] * groups {
+            return Err(Error::Plugin(PluginError::Plugin("Channels of input and filter do not match the convolution groups.")));
+        }
+        let spatial_dims = input_dim.len() - 2;
+        if filter_dim.len() != input_dim.len() || dest.desc().len() != input_dim.len() ||
+           stride.len() != spatial_dims || zero_padding.len() != spatial_dims || dilation.len() != spatial_dims {
+            return Err(Error::Plugin(PluginError::Plugin("Input, filter, output, stride, padding and dilation do not agree on the number of spatial dimensions.")));
+        }
+        // 1D convolutions are run as 2D convolutions over a unit height
+        let (mut stride, mut zero_padding, mut dilation) = (stride.to_vec(), zero_padding.to_vec(), dilation.to_vec());
+        if spatial_dims == 1 {
+            stride.insert(0, 1);
+            zero_padding.insert(0, 0);
+            dilation.insert(0, 1);
+        }
         let cudnn_framework = self.framework().cudnn();
-        let src_desc = src.cudnn_tensor_desc()?;
-        let dest_desc = dest.cudnn_tensor_desc()?;
-        let filter_desc = filter.cudnn_filter_desc()?;
-        let conv_desc = crate::cudnn::ConvolutionDescriptor::new(zero_padding,
-                                                                 stride,
-                                                                 <T as DataTypeInfo>::cudnn_data_type())
-            .unwrap();
+        let src_desc = src.cudnn_tensor_desc_conv()?;
+        let dest_desc = dest.cudnn_tensor_desc_conv()?;
+        let filter_desc = filter.cudnn_filter_desc_conv()?;
+        let conv_desc = match crate::cudnn::ConvolutionDescriptor::new_grouped(&zero_padding,
+                                                                               &stride,
+                                                                               &dilation,
+                                                                               groups as i32,
+                                                                               <T as DataTypeInfo>::cudnn_data_type()) {
+            Ok(desc) => desc,
+            Err(_) => return Err(Error::Plugin(PluginError::Plugin("Unable to create CuDNN ConvolutionDescriptor."))),
+        };
 
         let useable_algo_fwd =
             algo_fwd.find_cudnn_algo(cudnn_framework, &filter_desc, &conv_desc, &src_desc, &dest_desc)?;
@@ -585,7 +700,7 @@ impl<T> Convolution<T> for Backend<Cuda>
         let cudnn_framework = self.framework().cudnn();
         let scal_params: crate::cudnn::utils::ScalParams<T> = crate::cudnn::utils::ScalParams::default();
 
-        let r_desc = result.cudnn_tensor_desc()?;
+        let r_desc = result.cudnn_tensor_desc_conv()?;
         let f_mem = read!(filter, self);
         let x_mem = read!(x, self);
         let r_mem = write_only!(result, self);
@@ -594,7 +709,7 @@ impl<T> Convolution<T> for Backend<Cuda>
         match cudnn_framework.convolution_forward(config,
                                                   trans_mut!(w_mem),
                                                   trans!(f_mem),
-                                                  &x.cudnn_tensor_desc()?, // src_desc
+                                                  &x.cudnn_tensor_desc_conv()?, // src_desc
                                                   trans!(x_mem),
                                                   &r_desc,
                                                   trans_mut!(r_mem),
@@ -619,9 +734,9 @@ impl<T> Convolution<T> for Backend<Cuda>
         let w_mem = write_only!(workspace, self);
         match cudnn_framework.convolution_backward_filter(config,
                                                           trans_mut!(w_mem),
-                                                          &src_data.cudnn_tensor_desc()?,
+                                                          &src_data.cudnn_tensor_desc_conv()?,
                                                           trans!(s_mem),
-                                                          &dest_diff.cudnn_tensor_desc()?,
+                                                          &dest_diff.cudnn_tensor_desc_conv()?,
                                                           trans!(dd_mem),
                                                           trans_mut!(df_mem),
                                                           scal_params) {
@@ -640,7 +755,7 @@ impl<T> Convolution<T> for Backend<Cuda>
         let cudnn_framework = self.framework().cudnn();
         let scal_params: crate::cudnn::utils::ScalParams<T> = crate::cudnn::utils::ScalParams::default();
 
-        let dr_desc = result_diff.cudnn_tensor_desc()?;
+        let dr_desc = result_diff.cudnn_tensor_desc_conv()?;
         let f_mem = read!(filter, self);
         let dx_mem = read!(x_diff, self);
         let dr_mem = write_only!(result_diff, self);
@@ -648,7 +763,7 @@ impl<T> Convolution<T> for Backend<Cuda>
         match cudnn_framework.convolution_backward_data(config,
                                                         trans_mut!(w_mem),
                                                         trans!(f_mem),
-                                                        &x_diff.cudnn_tensor_desc()?,
+                                                        &x_diff.cudnn_tensor_desc_conv()?,
                                                         trans!(dx_mem),
                                                         &dr_desc,
                                                         trans_mut!(dr_mem),
@@ -876,7 +991,7 @@ impl<T> Rnn<T> for Backend<Cuda> where T: Float + DataTypeInfo {
 
         let x_desc = rnn_sequence_descriptors(
             sequence_length,
-            src_description[1] as i32,
+            (src_description.size() / (sequence_length * batch_size) as usize) as i32,
             hidden_size,
             batch_size,
             num_layers,
@@ -931,7 +1046,7 @@ impl<T> Rnn<T> for Backend<Cuda> where T: Float + DataTypeInfo {
         let src_dimensions = src.desc().clone();
         let sequence_descriptors = rnn_sequence_descriptors(
             *rnn_config.sequence_length(),
-            src_dimensions[1] as i32,
+            (src_dimensions.size() / (*rnn_config.sequence_length() as usize * src_dimensions[0])) as i32,
             rnn_config.hidden_size,
             src_dimensions[0] as i32,
             rnn_config.num_layers,
@@ -983,7 +1098,7 @@ impl<T> Rnn<T> for Backend<Cuda> where T: Float + DataTypeInfo {
         let src_dimensions = src.desc().clone();
         let sequence_descriptors = rnn_sequence_descriptors(
             *rnn_config.sequence_length(),
-            src_dimensions[1] as i32,
+            (src_dimensions.size() / (*rnn_config.sequence_length() as usize * src_dimensions[0])) as i32,
             rnn_config.hidden_size,
             src_dimensions[0] as i32,
             rnn_config.num_layers,
@@ -1042,7 +1157,7 @@ impl<T> Rnn<T> for Backend<Cuda> where T: Float + DataTypeInfo {
         let src_dimensions = src.desc().clone();
         let sequence_descriptors = rnn_sequence_descriptors(
             *rnn_config.sequence_length(),
-            src_dimensions[1] as i32,
+            (src_dimensions.size() / (*rnn_config.sequence_length() as usize * src_dimensions[0])) as i32,
             rnn_config.hidden_size,
             src_dimensions[0] as i32,
             rnn_config.num_layers,
@@ -1323,6 +1438,561 @@ impl<T> TanhPointwise<T> for Backend<Cuda>
     }
 }
 
+/// Implements an activation that cuDNN does not provide, returning an error
+/// for every operation.
+macro_rules! impl_unsupported_activation_for_cuda {
+    ($name:expr, $op:ident, $op_pw:ident,
+     fn $f:ident, fn $f_grad:ident, fn $f_pw:ident, fn $f_pw_grad:ident, ($($param:ident),*)) => (
+        impl<T> $op<T> for Backend<Cuda>
+            where T: Float + Default + DataTypeInfo
+        {
+            #[allow(unused_variables)]
+            fn $f(&self, x: &SharedTensor<T>, result: &mut SharedTensor<T>$(, $param: f64)*)
+                  -> Result<(), Error> {
+                Err(Error::Plugin(PluginError::Plugin(concat!($name, " is not yet supported by the CUDA backend."))))
+            }
+
+            #[allow(unused_variables)]
+            fn $f_grad(&self, x: &SharedTensor<T>, x_diff: &SharedTensor<T>,
+                       result: &SharedTensor<T>, result_diff: &mut SharedTensor<T>$(, $param: f64)*)
+                       -> Result<(), Error> {
+                Err(Error::Plugin(PluginError::Plugin(concat!($name, " is not yet supported by the CUDA backend."))))
+            }
+        }
+
+        impl<T> $op_pw<T> for Backend<Cuda>
+            where T: Float + Default + DataTypeInfo
+        {
+            #[allow(unused_variables)]
+            fn $f_pw(&self, x: &mut SharedTensor<T>$(, $param: f64)*) -> Result<(), Error> {
+                Err(Error::Plugin(PluginError::Plugin(concat!($name, " is not yet supported by the CUDA backend."))))
+            }
+
+            #[allow(unused_variables)]
+            fn $f_pw_grad(&self, x: &SharedTensor<T>, x_diff: &mut SharedTensor<T>$(, $param: f64)*)
+                          -> Result<(), Error> {
+                Err(Error::Plugin(PluginError::Plugin(concat!($name, " is not yet supported by the CUDA backend."))))
+            }
+        }
+    );
+}
+
+impl_unsupported_activation_for_cuda!("LeakyReLU", LeakyRelu, LeakyReluPointwise,
+    fn leaky_relu, fn leaky_relu_grad, fn leaky_relu_pointwise, fn leaky_relu_pointwise_grad, (slope));
+impl_unsupported_activation_for_cuda!("ELU", Elu, EluPointwise,
+    fn elu, fn elu_grad, fn elu_pointwise, fn elu_pointwise_grad, (alpha));
+impl_unsupported_activation_for_cuda!("SELU", Selu, SeluPointwise,
+    fn selu, fn selu_grad, fn selu_pointwise, fn selu_pointwise_grad, ());
+impl_unsupported_activation_for_cuda!("GELU", Gelu, GeluPointwise,
+    fn gelu, fn gelu_grad, fn gelu_pointwise, fn gelu_pointwise_grad, ());
+impl_unsupported_activation_for_cuda!("Swish", Swish, SwishPointwise,
+    fn swish, fn swish_grad, fn swish_pointwise, fn swish_pointwise_grad, ());
+impl_unsupported_activation_for_cuda!("Softplus", Softplus, SoftplusPointwise,
+    fn softplus, fn softplus_grad, fn softplus_pointwise, fn softplus_pointwise_grad, ());
+impl_unsupported_activation_for_cuda!("HardTanh", HardTanh, HardTanhPointwise,
+    fn hard_tanh, fn hard_tanh_grad, fn hard_tanh_pointwise, fn hard_tanh_pointwise_grad, (min_value, max_value));
+
+/// Creates the TensorDescriptor of a Tensor of dimensions `dims` prepended by
+/// dimensions of size 1 up to `rank`, so cuDNN can broadcast it.
+fn cudnn_broadcast_tensor_desc<T>(dims: &[usize], rank: usize) -> Result<TensorDescriptor, PluginError>
+    where T: DataTypeInfo
+{
+    let mut desc: TensorDesc = vec![1; rank - dims.len()];
+    desc.extend_from_slice(dims);
+    match TensorDescriptor::new(&desc.dims_i32().clone(),
+                                &desc.default_stride_i32().clone(),
+                                <T as DataTypeInfo>::cudnn_data_type()) {
+        Ok(desc) => Ok(desc),
+        Err(_) => Err(PluginError::Plugin("Unable to create CuDNN TensorDescriptor.")),
+    }
+}
+
+/// Computes the shape `a` and `b` broadcast against each other.
+fn broadcast_shape(a: &[usize], b: &[usize]) -> Result<TensorDesc, Error> {
+    let rank = usize::max(a.len(), b.len());
+    let dim = |dims: &[usize], i: usize| {
+        if i + dims.len() < rank { 1 } else { dims[i + dims.len() - rank] }
+    };
+    (0..rank).map(|i| match (dim(a, i), dim(b, i)) {
+        (da, db) if da == db || db == 1 => Ok(da),
+        (1, db) => Ok(db),
+        _ => Err(Error::Plugin(PluginError::Plugin("Tensor dimensions can not be broadcast."))),
+    }).collect()
+}
+
+/// Converts the bounds of `Clamp`, which must not be crossed.
+fn clamp_bounds<T: Float>(op: UnaryOperation) -> Result<(T, T), Error> {
+    let (min, max) = match op {
+        UnaryOperation::Clamp { min, max } => (min, max),
+        _ => return Ok((T::zero(), T::zero())),
+    };
+    if min > max {
+        return Err(Error::Plugin(PluginError::Plugin("Clamp requires min to not exceed max.")));
+    }
+    match (T::from(min), T::from(max)) {
+        (Some(min), Some(max)) => Ok((min, max)),
+        _ => Err(Error::Plugin(PluginError::Plugin("Clamp bounds are not representable."))),
+    }
+}
+
+impl<T> Arithmetic<T> for Backend<Cuda>
+    where T: Float + Default + DataTypeInfo
+{
+    fn binary_operation(&self, op: BinaryOperation,
+                        a: &SharedTensor<T>, b: &SharedTensor<T>, result: &mut SharedTensor<T>)
+                        -> Result<(), Error> {
+        let shape = broadcast_shape(a.desc(), b.desc())?;
+        if *result.desc() != shape {
+            return Err(Error::Plugin(PluginError::Plugin("Result is not of the broadcast shape of the operands.")));
+        }
+        let a_mem = read!(a, self);
+        let b_mem = read!(b, self);
+        let r_mem = write_only!(result, self);
+        kernels::binary_operation::<T>(self.device().id(), op,
+                                       *a_mem.id_c(), a.desc(),
+                                       *b_mem.id_c(), b.desc(),
+                                       *r_mem.id_c(), &shape)
+    }
+
+    fn unary_operation(&self, op: UnaryOperation, x: &SharedTensor<T>, result: &mut SharedTensor<T>)
+                       -> Result<(), Error> {
+        let (min, max) = clamp_bounds::<T>(op)?;
+        if x.desc().size() != result.desc().size() {
+            return Err(Error::Plugin(PluginError::Plugin("Result is not of the size of the operand.")));
+        }
+        let x_mem = read!(x, self);
+        let r_mem = write_only!(result, self);
+        kernels::unary_operation::<T>(self.device().id(), op,
+                                      *x_mem.id_c(), *r_mem.id_c(), x.desc().size(), min, max)
+    }
+}
+
+impl<T> ArithmeticPointwise<T> for Backend<Cuda>
+    where T: Float + Default + DataTypeInfo
+{
+    fn binary_operation_pointwise(&self, op: BinaryOperation, a: &mut SharedTensor<T>, b: &SharedTensor<T>)
+                                  -> Result<(), Error> {
+        let shape = broadcast_shape(a.desc(), b.desc())?;
+        if *a.desc() != shape {
+            return Err(Error::Plugin(PluginError::Plugin("Operand can not be broadcast to the shape of the result.")));
+        }
+        let b_mem = read!(b, self);
+        let a_mem = read_write!(a, self);
+        kernels::binary_operation::<T>(self.device().id(), op,
+                                       *a_mem.id_c(), &shape,
+                                       *b_mem.id_c(), b.desc(),
+                                       *a_mem.id_c(), &shape)
+    }
+
+    fn unary_operation_pointwise(&self, op: UnaryOperation, x: &mut SharedTensor<T>)
+                                 -> Result<(), Error> {
+        let (min, max) = clamp_bounds::<T>(op)?;
+        let n = x.desc().size();
+        let x_mem = read_write!(x, self);
+        kernels::unary_operation::<T>(self.device().id(), op,
+                                      *x_mem.id_c(), *x_mem.id_c(), n, min, max)
+    }
+}
+
+/// Computes the dimensions of `x` reduced along `axes` with the reduced dimensions
+/// kept as size 1, and checks that `result` is of the shape requested by `keep_dims`.
+fn reduction_dims(x: &[usize], axes: &[usize], keep_dims: bool, result: &[usize]) -> Result<TensorDesc, Error> {
+    let mut reduced = vec![false; x.len()];
+    for &axis in axes {
+        if axis >= x.len() || reduced[axis] {
+            return Err(Error::Plugin(PluginError::Plugin("Reduction axes have to be distinct dimensions of the input.")));
+        }
+        reduced[axis] = true;
+    }
+    let kept: TensorDesc = x.iter().zip(&reduced).map(|(&dim, &r)| if r { 1 } else { dim }).collect();
+    let mut expected: TensorDesc = if keep_dims {
+        kept.clone()
+    } else {
+        x.iter().zip(&reduced).filter(|&(_, &r)| !r).map(|(&dim, _)| dim).collect()
+    };
+    if expected.is_empty() {
+        expected.push(1);
+    }
+    if *result != *expected {
+        return Err(Error::Plugin(PluginError::Plugin("Result is not of the shape of the reduced input.")));
+    }
+    Ok(kept)
+}
+
+impl<T> Reduction<T> for Backend<Cuda>
+    where T: Float + Default + DataTypeInfo
+{
+    fn reduce(&self, op: ReductionOperation, x: &SharedTensor<T>, axes: &[usize], keep_dims: bool,
+              result: &mut SharedTensor<T>)
+              -> Result<(), Error> {
+        use crate::cudnn::cudnnReduceTensorOp_t::*;
+        let kept = reduction_dims(x.desc(), axes, keep_dims, result.desc())?;
+        let cudnn_op = match op {
+            ReductionOperation::Sum => CUDNN_REDUCE_TENSOR_ADD,
+            ReductionOperation::Mean => CUDNN_REDUCE_TENSOR_AVG,
+            ReductionOperation::Max => CUDNN_REDUCE_TENSOR_MAX,
+            ReductionOperation::Min => CUDNN_REDUCE_TENSOR_MIN,
+            ReductionOperation::Norm1 => CUDNN_REDUCE_TENSOR_NORM1,
+            ReductionOperation::Norm2 => CUDNN_REDUCE_TENSOR_NORM2,
+        };
+        let cudnn_framework = self.framework().cudnn();
+        let scal_params: crate::cudnn::utils::ScalParams<T> = crate::cudnn::utils::ScalParams::default();
+        let reduce_desc = match ReduceTensorDescriptor::new(cudnn_op,
+                                                            <T as DataTypeInfo>::cudnn_data_type(),
+                                                            cudnnReduceTensorIndices_t::CUDNN_REDUCE_TENSOR_NO_INDICES) {
+            Ok(desc) => desc,
+            Err(_) => return Err(Error::Plugin(PluginError::Plugin("Unable to create CuDNN ReduceTensorDescriptor."))),
+        };
+        let rank = usize::max(4, x.desc().len());
+        let x_desc = cudnn_broadcast_tensor_desc::<T>(x.desc(), rank)?;
+        let r_desc = cudnn_broadcast_tensor_desc::<T>(&kept, rank)?;
+        let x_mem = read!(x, self);
+        let r_mem = write_only!(result, self);
+
+        match cudnn_framework.reduce_tensor(&reduce_desc,
+                                            &x_desc,
+                                            trans!(x_mem),
+                                            &r_desc,
+                                            trans_mut!(r_mem),
+                                            ::std::ptr::null_mut(),
+                                            0,
+                                            scal_params) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::Plugin(PluginError::Plugin("Unable to execute CUDA cuDNN Reduce Tensor."))),
+        }
+    }
+
+    fn argmax(&self, x: &SharedTensor<T>, axis: usize, keep_dims: bool, result: &mut SharedTensor<u32>)
+              -> Result<(), Error> {
+        let kept = reduction_dims(x.desc(), &[axis], keep_dims, result.desc())?;
+        let cudnn_framework = self.framework().cudnn();
+        let scal_params: crate::cudnn::utils::ScalParams<T> = crate::cudnn::utils::ScalParams::default();
+        let reduce_desc = match ReduceTensorDescriptor::new(cudnnReduceTensorOp_t::CUDNN_REDUCE_TENSOR_MAX,
+                                                            <T as DataTypeInfo>::cudnn_data_type(),
+                                                            cudnnReduceTensorIndices_t::CUDNN_REDUCE_TENSOR_FLATTENED_INDICES) {
+            Ok(desc) => desc,
+            Err(_) => return Err(Error::Plugin(PluginError::Plugin("Unable to create CuDNN ReduceTensorDescriptor."))),
+        };
+        let rank = usize::max(4, x.desc().len());
+        let x_desc = cudnn_broadcast_tensor_desc::<T>(x.desc(), rank)?;
+        let r_desc = cudnn_broadcast_tensor_desc::<T>(&kept, rank)?;
+        let indices_size = match cudnn_framework.reduction_indices_size(&reduce_desc, &x_desc, &r_desc) {
+            Ok(size) if size <= kept.size() * ::std::mem::size_of::<u32>() => size,
+            _ => return Err(Error::Plugin(PluginError::Plugin("Unable to determine CUDA cuDNN reduction indices size."))),
+        };
+        // the maximum values are not needed, only their indices
+        let values = match crate::cudnn::cuda::CudaDeviceMemory::new(kept.size() * ::std::mem::size_of::<T>()) {
+            Ok(values) => values,
+            Err(_) => return Err(Error::Plugin(PluginError::Plugin("Unable to allocate CUDA memory for the reduction."))),
+        };
+        let x_mem = read!(x, self);
+        let r_mem = write_only!(result, self);
+
+        match cudnn_framework.reduce_tensor(&reduce_desc,
+                                            &x_desc,
+                                            trans!(x_mem),
+                                            &r_desc,
+                                            *values.id_c(),
+                                            trans_mut!(r_mem),
+                                            indices_size,
+                                            scal_params) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::Plugin(PluginError::Plugin("Unable to execute CUDA cuDNN Reduce Tensor."))),
+        }
+    }
+}
+
+/// Checks that `axes` is a permutation of the dimensions of `x`.
+fn check_permutation(x: &[usize], axes: &[usize]) -> Result<(), Error> {
+    let mut seen = vec![false; x.len()];
+    if axes.len() != x.len() {
+        return Err(Error::Plugin(PluginError::Plugin("Permutation has to name every dimension of the input.")));
+    }
+    for &axis in axes {
+        if axis >= x.len() || seen[axis] {
+            return Err(Error::Plugin(PluginError::Plugin("Permutation has to name every dimension of the input once.")));
+        }
+        seen[axis] = true;
+    }
+    Ok(())
+}
+
+/// Creates the TensorDescriptors to copy a Tensor with strides `strides` into a
+/// contiguous Tensor of dimensions `dims`, with at least four dimensions each.
+fn cudnn_strided_copy_descs<T>(dims: &[usize], strides: &[usize])
+                               -> Result<(TensorDescriptor, TensorDescriptor), PluginError>
+    where T: DataTypeInfo
+{
+    let padding = 4usize.saturating_sub(dims.len());
+    let size: usize = dims.iter().product();
+    let mut src_dims: TensorDesc = vec![1; padding];
+    src_dims.extend_from_slice(dims);
+    let mut src_strides = vec![size as i32; padding];
+    src_strides.extend(strides.iter().map(|&stride| stride as i32));
+    let src_desc = match TensorDescriptor::new(&src_dims.dims_i32(),
+                                               &src_strides,
+                                               <T as DataTypeInfo>::cudnn_data_type()) {
+        Ok(desc) => desc,
+        Err(_) => return Err(PluginError::Plugin("Unable to create CuDNN TensorDescriptor.")),
+    };
+    match TensorDescriptor::new(&src_dims.dims_i32(),
+                                &src_dims.default_stride_i32(),
+                                <T as DataTypeInfo>::cudnn_data_type()) {
+        Ok(dest_desc) => Ok((src_desc, dest_desc)),
+        Err(_) => Err(PluginError::Plugin("Unable to create CuDNN TensorDescriptor.")),
+    }
+}
+
+impl<T> Permute<T> for Backend<Cuda>
+    where T: Float + Default + DataTypeInfo
+{
+    fn permute(&self, x: &SharedTensor<T>, result: &mut SharedTensor<T>, axes: &[usize])
+               -> Result<(), Error> {
+        check_permutation(x.desc(), axes)?;
+        let x_stride = x.desc().default_stride();
+        let shape: Vec<usize> = axes.iter().map(|&axis| x.desc()[axis]).collect();
+        let strides: Vec<usize> = axes.iter().map(|&axis| x_stride[axis]).collect();
+        if *result.desc() != shape {
+            return Err(Error::Plugin(PluginError::Plugin("Result is not of the shape of the permuted input.")));
+        }
+        let cudnn_framework = self.framework().cudnn();
+        let scal_params: crate::cudnn::utils::ScalParams<T> = crate::cudnn::utils::ScalParams::default();
+        let (x_desc, r_desc) = cudnn_strided_copy_descs::<T>(&shape, &strides)?;
+        let x_mem = read!(x, self);
+        let r_mem = write_only!(result, self);
+
+        match cudnn_framework.transform_tensor(&x_desc,
+                                               trans!(x_mem),
+                                               &r_desc,
+                                               trans_mut!(r_mem),
+                                               scal_params) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::Plugin(PluginError::Plugin("Unable to execute CUDA cuDNN Permute."))),
+        }
+    }
+
+    fn permute_grad(&self, x_diff: &SharedTensor<T>, result_diff: &mut SharedTensor<T>, axes: &[usize])
+                    -> Result<(), Error> {
+        check_permutation(x_diff.desc(), axes)?;
+        // the `axes[i]`-th dimension of the input is the `i`-th one of the output
+        let x_diff_stride = x_diff.desc().default_stride();
+        let mut shape = vec![0; axes.len()];
+        let mut strides = vec![0; axes.len()];
+        for (i, &axis) in axes.iter().enumerate() {
+            shape[axis] = x_diff.desc()[i];
+            strides[axis] = x_diff_stride[i];
+        }
+        if *result_diff.desc() != shape {
+            return Err(Error::Plugin(PluginError::Plugin("Result is not of the shape of the input of the permutation.")));
+        }
+        let cudnn_framework = self.framework().cudnn();
+        let scal_params: crate::cudnn::utils::ScalParams<T> = crate::cudnn::utils::ScalParams::default();
+        let (dx_desc, dr_desc) = cudnn_strided_copy_descs::<T>(&shape, &strides)?;
+        let dx_mem = read!(x_diff, self);
+        let dr_mem = write_only!(result_diff, self);
+
+        match cudnn_framework.transform_tensor(&dx_desc,
+                                               trans!(dx_mem),
+                                               &dr_desc,
+                                               trans_mut!(dr_mem),
+                                               scal_params) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::Plugin(PluginError::Plugin("Unable to execute CUDA cuDNN Permute backward."))),
+        }
+    }
+}
+
+/// Checks that `parts` joined along `axis` are of the dimensions `whole`.
+fn check_concat(parts: &[TensorDesc], whole: &[usize], axis: usize) -> Result<(), Error> {
+    if parts.is_empty() {
+        return Err(Error::Plugin(PluginError::Plugin("Concatenation needs at least one part.")));
+    }
+    if axis >= whole.len() {
+        return Err(Error::Plugin(PluginError::Plugin("Concatenation axis exceeds the dimensions of the Tensor.")));
+    }
+    let mut joined = 0;
+    for part in parts {
+        let matches = part.len() == whole.len() &&
+            part.iter().zip(whole.iter()).enumerate().all(|(d, (p, w))| d == axis || p == w);
+        if !matches {
+            return Err(Error::Plugin(PluginError::Plugin("Parts of a concatenation may only differ in the dimension of the axis.")));
+        }
+        joined += part[axis];
+    }
+    if joined != whole[axis] {
+        return Err(Error::Plugin(PluginError::Plugin("Parts of a concatenation do not add up to the joined Tensor.")));
+    }
+    Ok(())
+}
+
+/// Copies the `parts` into consecutive slices of `whole` along `axis`.
+///
+/// Every part is written through a strided view into `whole`, which starts at
+/// the offset of the part along `axis`.
+fn cudnn_join<T>(backend: &Backend<Cuda>, parts: &[&SharedTensor<T>], whole: &mut SharedTensor<T>, axis: usize)
+                 -> Result<(), Error>
+    where T: Float + Default + DataTypeInfo
+{
+    let descs: Vec<TensorDesc> = parts.iter().map(|part| part.desc().clone()).collect();
+    check_concat(&descs, whole.desc(), axis)?;
+    let cudnn_framework = backend.framework().cudnn();
+    let whole_stride = whole.desc().default_stride();
+    let whole_mem = whole.write_only(backend.device()).unwrap();
+    let mut offset = 0;
+    for (part, desc) in parts.iter().zip(&descs) {
+        if desc[axis] == 0 {
+            continue;
+        }
+        let scal_params: crate::cudnn::utils::ScalParams<T> = crate::cudnn::utils::ScalParams::default();
+        let (view_desc, part_desc) = cudnn_strided_copy_descs::<T>(desc, &whole_stride)?;
+        let part_mem = part.read(backend.device()).unwrap();
+        let view = (trans_mut!(whole_mem) as *mut T).wrapping_add(offset * whole_stride[axis]);
+
+        if cudnn_framework.transform_tensor(&part_desc,
+                                            trans!(part_mem),
+                                            &view_desc,
+                                            view as *mut ::libc::c_void,
+                                            scal_params).is_err() {
+            return Err(Error::Plugin(PluginError::Plugin("Unable to execute CUDA cuDNN Concat.")));
+        }
+        offset += desc[axis];
+    }
+    Ok(())
+}
+
+/// Copies consecutive slices of `whole` along `axis` into the `parts`.
+///
+/// Every part is read through a strided view into `whole`, which starts at
+/// the offset of the part along `axis`.
+fn cudnn_split<T>(backend: &Backend<Cuda>, whole: &SharedTensor<T>, parts: &mut [&mut SharedTensor<T>], axis: usize)
+                  -> Result<(), Error>
+    where T: Float + Default + DataTypeInfo
+{
+    let descs: Vec<TensorDesc> = parts.iter().map(|part| part.desc().clone()).collect();
+    check_concat(&descs, whole.desc(), axis)?;
+    let cudnn_framework = backend.framework().cudnn();
+    let whole_stride = whole.desc().default_stride();
+    let whole_mem = whole.read(backend.device()).unwrap();
+    let mut offset = 0;
+    for (part, desc) in parts.iter_mut().zip(&descs) {
+        if desc[axis] == 0 {
+            continue;
+        }
+        let scal_params: crate::cudnn::utils::ScalParams<T> = crate::cudnn::utils::ScalParams::default();
+        let (view_desc, part_desc) = cudnn_strided_copy_descs::<T>(desc, &whole_stride)?;
+        let part_mem = part.write_only(backend.device()).unwrap();
+        let view = (trans!(whole_mem) as *const T).wrapping_add(offset * whole_stride[axis]);
+
+        if cudnn_framework.transform_tensor(&view_desc,
+                                            view as *const ::libc::c_void,
+                                            &part_desc,
+                                            trans_mut!(part_mem),
+                                            scal_params).is_err() {
+            return Err(Error::Plugin(PluginError::Plugin("Unable to execute CUDA cuDNN Split.")));
+        }
+        offset += desc[axis];
+    }
+    Ok(())
+}
+
+impl<T> Concat<T> for Backend<Cuda>
+    where T: Float + Default + DataTypeInfo
+{
+    fn concat(&self, inputs: &[&SharedTensor<T>], result: &mut SharedTensor<T>, axis: usize)
+              -> Result<(), Error> {
+        cudnn_join(self, inputs, result, axis)
+    }
+
+    fn concat_grad(&self, result_diff: &SharedTensor<T>, inputs_diff: &mut [&mut SharedTensor<T>], axis: usize)
+                   -> Result<(), Error> {
+        cudnn_split(self, result_diff, inputs_diff, axis)
+    }
+}
+
+impl<T> Split<T> for Backend<Cuda>
+    where T: Float + Default + DataTypeInfo
+{
+    fn split(&self, x: &SharedTensor<T>, results: &mut [&mut SharedTensor<T>], axis: usize)
+             -> Result<(), Error> {
+        cudnn_split(self, x, results, axis)
+    }
+
+    fn split_grad(&self, results_diff: &[&SharedTensor<T>], x_diff: &mut SharedTensor<T>, axis: usize)
+                  -> Result<(), Error> {
+        cudnn_join(self, results_diff, x_diff, axis)
+    }
+}
+
+impl<T> Embedding<T> for Backend<Cuda>
+    where T: Float + Default + DataTypeInfo
+{
+    #[allow(unused_variables)]
+    fn embedding(&self, weight: &SharedTensor<T>, indices: &SharedTensor<T>, result: &mut SharedTensor<T>)
+                 -> Result<(), Error> {
+        Err(Error::Plugin(PluginError::Plugin("Embedding is not yet supported by the CUDA backend.")))
+    }
+
+    #[allow(unused_variables)]
+    fn embedding_grad(&self, indices: &SharedTensor<T>, result_diff: &SharedTensor<T>,
+                      weight_diff: &mut SharedTensor<T>, padding_idx: Option<usize>)
+                      -> Result<(), Error> {
+        Err(Error::Plugin(PluginError::Plugin("Embedding is not yet supported by the CUDA backend.")))
+    }
+}
+
+impl<T> Upsample<T> for Backend<Cuda>
+    where T: Float + Default + DataTypeInfo
+{
+    #[allow(unused_variables)]
+    fn upsample(&self, mode: UpsampleMode, x: &SharedTensor<T>, result: &mut SharedTensor<T>)
+                -> Result<(), Error> {
+        Err(Error::Plugin(PluginError::Plugin("Upsample is not yet supported by the CUDA backend.")))
+    }
+
+    #[allow(unused_variables)]
+    fn upsample_grad(&self, mode: UpsampleMode, result_diff: &SharedTensor<T>, x_diff: &mut SharedTensor<T>)
+                     -> Result<(), Error> {
+        Err(Error::Plugin(PluginError::Plugin("Upsample is not yet supported by the CUDA backend.")))
+    }
+}
+
+impl<T> Pad<T> for Backend<Cuda>
+    where T: Float + Default + DataTypeInfo
+{
+    #[allow(unused_variables)]
+    fn pad(&self, mode: PadMode<T>, x: &SharedTensor<T>, padding: &[(usize, usize)], result: &mut SharedTensor<T>)
+           -> Result<(), Error> {
+        Err(Error::Plugin(PluginError::Plugin("Pad is not yet supported by the CUDA backend.")))
+    }
+
+    #[allow(unused_variables)]
+    fn pad_grad(&self, mode: PadMode<T>, result_diff: &SharedTensor<T>, padding: &[(usize, usize)],
+                x_diff: &mut SharedTensor<T>)
+                -> Result<(), Error> {
+        Err(Error::Plugin(PluginError::Plugin("Pad is not yet supported by the CUDA backend.")))
+    }
+}
+
+impl<T> SoftmaxCrossEntropy<T> for Backend<Cuda>
+    where T: Float + Default + DataTypeInfo
+{
+    #[allow(unused_variables)]
+    fn softmax_cross_entropy(&self, x: &SharedTensor<T>, target: &SharedTensor<T>,
+                             class_weights: Option<&SharedTensor<T>>, ignore_index: Option<usize>,
+                             result: &mut SharedTensor<T>)
+                             -> Result<(), Error> {
+        Err(Error::Plugin(PluginError::Plugin("Softmax cross-entropy is not yet supported by the CUDA backend.")))
+    }
+
+    #[allow(unused_variables)]
+    fn softmax_cross_entropy_grad(&self, x: &SharedTensor<T>, target: &SharedTensor<T>,
+                                  class_weights: Option<&SharedTensor<T>>, ignore_index: Option<usize>,
+                                  result_diff: &SharedTensor<T>, x_diff: &mut SharedTensor<T>)
+                                  -> Result<(), Error> {
+        Err(Error::Plugin(PluginError::Plugin("Softmax cross-entropy is not yet supported by the CUDA backend.")))
+    }
+}
+
 impl<T> Softmax<T> for Backend<Cuda>
     where T: Float + Default + DataTypeInfo
 {
@@ -1480,20 +2150,241 @@ impl<T> LRN<T> for Backend<Cuda>
     }
 }
 
+impl<T> BatchNormalization<T> for Backend<Cuda>
+    where T: Float + Default + DataTypeInfo
+{
+    fn new_batch_normalization_config(&self, epsilon: f64, momentum: f64)
+                                      -> Result<Self::CBN, Error> {
+        let cudnn_framework = self.framework().cudnn();
+        match cudnn_framework.init_batch_normalization(cudnnBatchNormMode_t::CUDNN_BATCHNORM_SPATIAL,
+                                                       epsilon,
+                                                       momentum) {
+            Ok(config) => Ok(config),
+            Err(_) => Err(Error::Plugin(PluginError::Plugin("Unable to create CuDNN batch normalization config."))),
+        }
+    }
+
+    fn batch_normalization_training(&self,
+                                    x: &SharedTensor<T>,
+                                    scale: &SharedTensor<T>,
+                                    bias: &SharedTensor<T>,
+                                    running_mean: &mut SharedTensor<T>,
+                                    running_variance: &mut SharedTensor<T>,
+                                    saved_mean: &mut SharedTensor<T>,
+                                    saved_inv_variance: &mut SharedTensor<T>,
+                                    result: &mut SharedTensor<T>,
+                                    config: &Self::CBN)
+                                    -> Result<(), Error> {
+        let cudnn_framework = self.framework().cudnn();
+        let scal_params: crate::cudnn::utils::ScalParams<T> = crate::cudnn::utils::ScalParams::default();
+        let x_desc = x.cudnn_tensor_desc_batch_norm()?;
+        let r_desc = result.cudnn_tensor_desc_batch_norm()?;
+        let p_desc = x.cudnn_batch_norm_param_desc()?;
+        let x_mem = read!(x, self);
+        let scale_mem = read!(scale, self);
+        let bias_mem = read!(bias, self);
+        let mean_mem = read_write!(running_mean, self);
+        let var_mem = read_write!(running_variance, self);
+        let saved_mean_mem = write_only!(saved_mean, self);
+        let saved_inv_var_mem = write_only!(saved_inv_variance, self);
+        let r_mem = write_only!(result, self);
+        match cudnn_framework.batch_normalization_forward_training(config,
+                                                                   &x_desc,
+                                                                   trans!(x_mem),
+                                                                   &r_desc,
+                                                                   trans_mut!(r_mem),
+                                                                   &p_desc,
+                                                                   trans!(scale_mem),
+                                                                   trans!(bias_mem),
+                                                                   trans_mut!(mean_mem),
+                                                                   trans_mut!(var_mem),
+                                                                   trans_mut!(saved_mean_mem),
+                                                                   trans_mut!(saved_inv_var_mem),
+                                                                   scal_params) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::Plugin(PluginError::Plugin("Unable to execute CUDA cuDNN batch normalization Forward Training."))),
+        }
+    }
+
+    fn batch_normalization_inference(&self,
+                                     x: &SharedTensor<T>,
+                                     scale: &SharedTensor<T>,
+                                     bias: &SharedTensor<T>,
+                                     mean: &SharedTensor<T>,
+                                     variance: &SharedTensor<T>,
+                                     result: &mut SharedTensor<T>,
+                                     config: &Self::CBN)
+                                     -> Result<(), Error> {
+        let cudnn_framework = self.framework().cudnn();
+        let scal_params: crate::cudnn::utils::ScalParams<T> = crate::cudnn::utils::ScalParams::default();
+        let x_desc = x.cudnn_tensor_desc_batch_norm()?;
+        let r_desc = result.cudnn_tensor_desc_batch_norm()?;
+        let p_desc = x.cudnn_batch_norm_param_desc()?;
+        let x_mem = read!(x, self);
+        let scale_mem = read!(scale, self);
+        let bias_mem = read!(bias, self);
+        let mean_mem = read!(mean, self);
+        let var_mem = read!(variance, self);
+        let r_mem = write_only!(result, self);
+        match cudnn_framework.batch_normalization_forward_inference(config,
+                                                                    &x_desc,
+                                                                    trans!(x_mem),
+                                                                    &r_desc,
+                                                                    trans_mut!(r_mem),
+                                                                    &p_desc,
+                                                                    trans!(scale_mem),
+                                                                    trans!(bias_mem),
+                                                                    trans!(mean_mem),
+                                                                    trans!(var_mem),
+                                                                    scal_params) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::Plugin(PluginError::Plugin("Unable to execute CUDA cuDNN batch normalization Forward Inference."))),
+        }
+    }
+
+    fn batch_normalization_grad(&self,
+                                x: &SharedTensor<T>,
+                                result_diff: &SharedTensor<T>,
+                                scale: &SharedTensor<T>,
+                                saved_mean: &SharedTensor<T>,
+                                saved_inv_variance: &SharedTensor<T>,
+                                x_diff: &mut SharedTensor<T>,
+                                scale_diff: &mut SharedTensor<T>,
+                                bias_diff: &mut SharedTensor<T>,
+                                config: &Self::CBN)
+                                -> Result<(), Error> {
+        let cudnn_framework = self.framework().cudnn();
+        let scal_params: crate::cudnn::utils::ScalParams<T> = crate::cudnn::utils::ScalParams::default();
+        let x_desc = x.cudnn_tensor_desc_batch_norm()?;
+        let dr_desc = result_diff.cudnn_tensor_desc_batch_norm()?;
+        let dx_desc = x_diff.cudnn_tensor_desc_batch_norm()?;
+        let p_desc = x.cudnn_batch_norm_param_desc()?;
+        let x_mem = read!(x, self);
+        let dr_mem = read!(result_diff, self);
+        let scale_mem = read!(scale, self);
+        let saved_mean_mem = read!(saved_mean, self);
+        let saved_inv_var_mem = read!(saved_inv_variance, self);
+        let dx_mem = write_only!(x_diff, self);
+        let dscale_mem = write_only!(scale_diff, self);
+        let dbias_mem = write_only!(bias_diff, self);
+        match cudnn_framework.batch_normalization_backward(config,
+                                                           &x_desc,
+                                                           trans!(x_mem),
+                                                           &dr_desc,
+                                                           trans!(dr_mem),
+                                                           &dx_desc,
+                                                           trans_mut!(dx_mem),
+                                                           &p_desc,
+                                                           trans!(scale_mem),
+                                                           trans_mut!(dscale_mem),
+                                                           trans_mut!(dbias_mem),
+                                                           trans!(saved_mean_mem),
+                                                           trans!(saved_inv_var_mem),
+                                                           scal_params) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::Plugin(PluginError::Plugin("Unable to execute CUDA cuDNN batch normalization Backward."))),
+        }
+    }
+}
+
+impl<T> LayerNorm<T> for Backend<Cuda>
+    where T: Float + Default + DataTypeInfo
+{
+    fn new_layer_normalization_config(&self, epsilon: f64) -> Result<Self::CLN, Error> {
+        Ok(helper::LayerNormalizationConfig { epsilon })
+    }
+
+    #[allow(unused_variables)]
+    fn layer_normalization(&self,
+                           x: &SharedTensor<T>,
+                           scale: &SharedTensor<T>,
+                           bias: &SharedTensor<T>,
+                           saved_mean: &mut SharedTensor<T>,
+                           saved_inv_variance: &mut SharedTensor<T>,
+                           result: &mut SharedTensor<T>,
+                           config: &Self::CLN)
+                           -> Result<(), Error> {
+        Err(Error::Plugin(PluginError::Plugin("Layer normalization is not yet supported by the CUDA backend.")))
+    }
+
+    #[allow(unused_variables)]
+    fn layer_normalization_grad(&self,
+                                x: &SharedTensor<T>,
+                                result_diff: &SharedTensor<T>,
+                                scale: &SharedTensor<T>,
+                                saved_mean: &SharedTensor<T>,
+                                saved_inv_variance: &SharedTensor<T>,
+                                x_diff: &mut SharedTensor<T>,
+                                scale_diff: &mut SharedTensor<T>,
+                                bias_diff: &mut SharedTensor<T>,
+                                config: &Self::CLN)
+                                -> Result<(), Error> {
+        Err(Error::Plugin(PluginError::Plugin("Layer normalization is not yet supported by the CUDA backend.")))
+    }
+}
+
+impl<T> GroupNorm<T> for Backend<Cuda>
+    where T: Float + Default + DataTypeInfo
+{
+    fn new_group_normalization_config(&self, groups: usize, epsilon: f64) -> Result<Self::CGN, Error> {
+        Ok(helper::GroupNormalizationConfig { groups, epsilon })
+    }
+
+    #[allow(unused_variables)]
+    fn group_normalization(&self,
+                           x: &SharedTensor<T>,
+                           scale: &SharedTensor<T>,
+                           bias: &SharedTensor<T>,
+                           saved_mean: &mut SharedTensor<T>,
+                           saved_inv_variance: &mut SharedTensor<T>,
+                           result: &mut SharedTensor<T>,
+                           config: &Self::CGN)
+                           -> Result<(), Error> {
+        Err(Error::Plugin(PluginError::Plugin("Group normalization is not yet supported by the CUDA backend.")))
+    }
+
+    #[allow(unused_variables)]
+    fn group_normalization_grad(&self,
+                                x: &SharedTensor<T>,
+                                result_diff: &SharedTensor<T>,
+                                scale: &SharedTensor<T>,
+                                saved_mean: &SharedTensor<T>,
+                                saved_inv_variance: &SharedTensor<T>,
+                                x_diff: &mut SharedTensor<T>,
+                                scale_diff: &mut SharedTensor<T>,
+                                bias_diff: &mut SharedTensor<T>,
+                                config: &Self::CGN)
+                                -> Result<(), Error> {
+        Err(Error::Plugin(PluginError::Plugin("Group normalization is not yet supported by the CUDA backend.")))
+    }
+}
+
 impl<T> Pooling<T> for Backend<Cuda>
     where T: Float + Default + DataTypeInfo
 {
-    fn new_pooling_config(&self,
-                          window: &[i32],
-                          stride: &[i32],
-                          padding: &[i32])
-                          -> Result<Self::CPOOL, Error> {
-        let pooling_avg = crate::cudnn::PoolingDescriptor::new(crate::cudnn::cudnnPoolingMode_t::CUDNN_POOLING_AVERAGE_COUNT_EXCLUDE_PADDING, window, padding, stride).unwrap();
+    fn new_pooling_config_with_avg_mode(&self,
+                                        window: &[i32],
+                                        stride: &[i32],
+                                        padding: &[i32],
+                                        avg_mode: PoolingAvgMode)
+                                        -> Result<Self::CPOOL, Error> {
+        let avg_mode = match avg_mode {
+            PoolingAvgMode::IncludePadding => crate::cudnn::cudnnPoolingMode_t::CUDNN_POOLING_AVERAGE_COUNT_INCLUDE_PADDING,
+            PoolingAvgMode::ExcludePadding => crate::cudnn::cudnnPoolingMode_t::CUDNN_POOLING_AVERAGE_COUNT_EXCLUDE_PADDING,
+        };
+        // 1D pooling is run as 2D pooling over a unit height
+        let (mut window, mut stride, mut padding) = (window.to_vec(), stride.to_vec(), padding.to_vec());
+        if window.len() == 1 {
+            window.insert(0, 1);
+            stride.insert(0, 1);
+            padding.insert(0, 0);
+        }
+        let pooling_avg = crate::cudnn::PoolingDescriptor::new(avg_mode, &window, &padding, &stride).unwrap();
         let pooling_max =
             crate::cudnn::PoolingDescriptor::new(crate::cudnn::cudnnPoolingMode_t::CUDNN_POOLING_MAX,
-                                            window,
-                                            padding,
-                                            stride)
+                                            &window,
+                                            &padding,
+                                            &stride)
                     .unwrap();
         Ok(crate::cudnn::utils::PoolingConfig::new(pooling_avg, pooling_max))
     }
@@ -1506,11 +2397,11 @@ impl<T> Pooling<T> for Backend<Cuda>
         let cudnn_framework = self.framework().cudnn();
         let scal_params: crate::cudnn::utils::ScalParams<T> = crate::cudnn::utils::ScalParams::default();
 
-        let r_desc = result.cudnn_tensor_desc()?;
+        let r_desc = result.cudnn_tensor_desc_conv()?;
         let x_mem = read!(x, self);
         let r_mem = write_only!(result, self);
         match cudnn_framework.pooling_max_forward(config,
-                                                  &x.cudnn_tensor_desc()?,
+                                                  &x.cudnn_tensor_desc_conv()?,
                                                   trans!(x_mem),
                                                   &r_desc,
                                                   trans_mut!(r_mem),
@@ -1530,17 +2421,17 @@ impl<T> Pooling<T> for Backend<Cuda>
                         -> Result<(), Error> {
         let cudnn_framework = self.framework().cudnn();
         let scal_params: crate::cudnn::utils::ScalParams<T> = crate::cudnn::utils::ScalParams::default();
-        let dr_desc = result_diff.cudnn_tensor_desc()?;
+        let dr_desc = result_diff.cudnn_tensor_desc_conv()?;
         let x_mem = read!(x, self);
         let dx_mem = read!(x_diff, self);
         let r_mem = read!(result, self);
         let dr_mem = write_only!(result_diff, self);
         match cudnn_framework.pooling_max_backward(config,
-                                                   &x.cudnn_tensor_desc()?,
+                                                   &x.cudnn_tensor_desc_conv()?,
                                                    trans!(x_mem),
-                                                   &x_diff.cudnn_tensor_desc()?,
+                                                   &x_diff.cudnn_tensor_desc_conv()?,
                                                    trans!(dx_mem),
-                                                   &result.cudnn_tensor_desc()?,
+                                                   &result.cudnn_tensor_desc_conv()?,
                                                    trans!(r_mem),
                                                    &dr_desc,
                                                    trans_mut!(dr_mem),
@@ -1557,11 +2448,11 @@ impl<T> Pooling<T> for Backend<Cuda>
                    -> Result<(), Error> {
         let cudnn_framework = self.framework().cudnn();
         let scal_params: crate::cudnn::utils::ScalParams<T> = crate::cudnn::utils::ScalParams::default();
-        let r_desc = result.cudnn_tensor_desc()?;
+        let r_desc = result.cudnn_tensor_desc_conv()?;
         let x_mem = read!(x, self);
         let r_mem = write_only!(result, self);
         match cudnn_framework.pooling_avg_forward(config,
-                                                  &x.cudnn_tensor_desc()?,
+                                                  &x.cudnn_tensor_desc_conv()?,
                                                   trans!(x_mem),
                                                   &r_desc,
                                                   trans_mut!(r_mem),
@@ -1581,17 +2472,17 @@ impl<T> Pooling<T> for Backend<Cuda>
                         -> Result<(), Error> {
         let cudnn_framework = self.framework().cudnn();
         let scal_params: crate::cudnn::utils::ScalParams<T> = crate::cudnn::utils::ScalParams::default();
-        let dr_desc = result_diff.cudnn_tensor_desc()?;
+        let dr_desc = result_diff.cudnn_tensor_desc_conv()?;
         let x_mem = read!(x, self);
         let dx_mem = read!(x_diff, self);
         let r_mem = read!(result, self);
         let dr_mem = write_only!(result_diff, self);
         match cudnn_framework.pooling_avg_backward(config,
-                                                   &x.cudnn_tensor_desc()?,
+                                                   &x.cudnn_tensor_desc_conv()?,
                                                    trans!(x_mem),
-                                                   &x_diff.cudnn_tensor_desc()?,
+                                                   &x_diff.cudnn_tensor_desc_conv()?,
                                                    trans!(dx_mem),
-                                                   &result.cudnn_tensor_desc()?,
+                                                   &result.cudnn_tensor_desc_conv()?,
                                                    trans!(r_mem),
                                                    &dr_desc,
                                                    trans_mut!(dr_mem),
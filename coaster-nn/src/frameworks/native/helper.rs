@@ -1,15 +1,42 @@
 //! Provides useful macros for easier NN implementation for native.
 
+
 use crate::co;
 use crate::co::plugin::Error as PluginError;
 use crate::co::plugin::numeric_helpers::Float;
 use crate::co::frameworks::native::flatbox::FlatBox;
-use crate::{DirectionMode, RnnInputMode};
+use crate::{ConvBackwardDataAlgo, ConvBackwardFilterAlgo, ConvForwardAlgo, DirectionMode, PoolingAvgMode,
+            RnnInputMode};
 use RnnNetworkMode;
 
 #[derive(Debug, Copy, Clone)]
 #[allow(missing_docs)]
-pub struct NormalizationConfig;
+pub struct NormalizationConfig {
+    pub n: u32,
+    pub alpha: f64,
+    pub beta: f64,
+    pub k: f64,
+}
+
+#[derive(Debug, Copy, Clone)]
+#[allow(missing_docs)]
+pub struct BatchNormalizationConfig {
+    pub epsilon: f64,
+    pub momentum: f64,
+}
+
+#[derive(Debug, Copy, Clone)]
+#[allow(missing_docs)]
+pub struct LayerNormalizationConfig {
+    pub epsilon: f64,
+}
+
+#[derive(Debug, Copy, Clone)]
+#[allow(missing_docs)]
+pub struct GroupNormalizationConfig {
+    pub groups: usize,
+    pub epsilon: f64,
+}
 
 #[derive(Debug, Clone)]
 #[allow(missing_docs)]
@@ -18,6 +45,9 @@ pub struct PoolingConfig {
     pub padding: Vec<i32>,
     //TODO: check datatype
     pub stride: Vec<i32>,
+    pub avg_mode: PoolingAvgMode,
+    /// The windows are derived from the input and output sizes, see `new_adaptive_pooling_config`.
+    pub adaptive: bool,
 }
 
 
@@ -97,6 +127,95 @@ pub fn tanh_grad<T: Float>(x: T, dx: T) -> T {
     (T::one() - x.powi(2)) * dx
 }
 
+/// Computes the LeakyReLU Function on the CPU
+pub fn leaky_relu<T: Float>(x: T, slope: T) -> T {
+    if x > T::zero() { x } else { slope * x }
+}
+
+/// Computes the LeakyReLU Gradient on the CPU
+pub fn leaky_relu_grad<T: Float>(x: T, dx: T, slope: T) -> T {
+    if x > T::zero() { dx } else { slope * dx }
+}
+
+/// Computes the ELU Function on the CPU
+pub fn elu<T: Float>(x: T, alpha: T) -> T {
+    if x > T::zero() { x } else { alpha * x.exp_m1() }
+}
+
+/// Computes the ELU Gradient w.r.t. the input `x` on the CPU
+pub fn elu_grad<T: Float>(x: T, dx: T, alpha: T) -> T {
+    if x > T::zero() { dx } else { alpha * x.exp() * dx }
+}
+
+/// The `alpha` of SELU
+pub const SELU_ALPHA: f64 = 1.673_263_242_354_377_3;
+/// The `scale` of SELU
+pub const SELU_SCALE: f64 = 1.050_700_987_355_480_5;
+
+/// Computes the SELU Function on the CPU
+pub fn selu<T: Float>(x: T) -> T {
+    T::from(SELU_SCALE).unwrap() * elu(x, T::from(SELU_ALPHA).unwrap())
+}
+
+/// Computes the SELU Gradient w.r.t. the input `x` on the CPU
+pub fn selu_grad<T: Float>(x: T, dx: T) -> T {
+    T::from(SELU_SCALE).unwrap() * elu_grad(x, dx, T::from(SELU_ALPHA).unwrap())
+}
+
+// tanh approximation of x * Φ(x), see https://arxiv.org/abs/1606.08415
+const GELU_SQRT_2_PI: f64 = 0.797_884_560_802_865_4;
+const GELU_COEFF: f64 = 0.044_715;
+
+/// Computes the GELU Function on the CPU
+pub fn gelu<T: Float>(x: T) -> T {
+    let c = T::from(GELU_SQRT_2_PI).unwrap();
+    let k = T::from(GELU_COEFF).unwrap();
+    let half = T::from(0.5).unwrap();
+    half * x * (T::one() + (c * (x + k * x.powi(3))).tanh())
+}
+
+/// Computes the GELU Gradient w.r.t. the input `x` on the CPU
+pub fn gelu_grad<T: Float>(x: T, dx: T) -> T {
+    let c = T::from(GELU_SQRT_2_PI).unwrap();
+    let k = T::from(GELU_COEFF).unwrap();
+    let half = T::from(0.5).unwrap();
+    let t = (c * (x + k * x.powi(3))).tanh();
+    let du = c * (T::one() + T::from(3.0).unwrap() * k * x.powi(2));
+    (half * (T::one() + t) + half * x * (T::one() - t.powi(2)) * du) * dx
+}
+
+/// Computes the Swish Function on the CPU
+pub fn swish<T: Float>(x: T) -> T {
+    x * sigmoid(x)
+}
+
+/// Computes the Swish Gradient w.r.t. the input `x` on the CPU
+pub fn swish_grad<T: Float>(x: T, dx: T) -> T {
+    let s = sigmoid(x);
+    s * (T::one() + x * (T::one() - s)) * dx
+}
+
+/// Computes the Softplus Function on the CPU
+pub fn softplus<T: Float>(x: T) -> T {
+    // stable for large |x|
+    x.max(T::zero()) + (-x.abs()).exp().ln_1p()
+}
+
+/// Computes the Softplus Gradient w.r.t. the input `x` on the CPU
+pub fn softplus_grad<T: Float>(x: T, dx: T) -> T {
+    sigmoid(x) * dx
+}
+
+/// Computes the HardTanh Function on the CPU
+pub fn hard_tanh<T: Float>(x: T, min_value: T, max_value: T) -> T {
+    x.max(min_value).min(max_value)
+}
+
+/// Computes the HardTanh Gradient on the CPU
+pub fn hard_tanh_grad<T: Float>(x: T, dx: T, min_value: T, max_value: T) -> T {
+    if x > min_value && x < max_value { dx } else { T::zero() }
+}
+
 /// sigmoid impl generation macro
 #[macro_export]
 macro_rules! impl_ops_sigmoid_for {
@@ -104,7 +223,8 @@ macro_rules! impl_ops_sigmoid_for {
         impl Sigmoid<$t> for $b {
             fn sigmoid(&self, x: &SharedTensor<$t>, result: &mut SharedTensor<$t>)
                        -> Result<(), Error> {
-                map1(read!(x, $t, self),
+                map1(self.device().thread_pool(),
+                     read!(x, $t, self),
                      write_only!(result, $t, self),
                      crate::frameworks::native::helper::sigmoid)
             }
@@ -116,7 +236,8 @@ macro_rules! impl_ops_sigmoid_for {
                 result: &SharedTensor<$t>,
                 result_diff: &mut SharedTensor<$t>)
                 -> Result<(), Error> {
-                map2(read!(x, $t, self),
+                map2(self.device().thread_pool(),
+                     read!(x, $t, self),
                      read!(x_diff, $t, self),
                      write_only!(result_diff, $t, self),
                      crate::frameworks::native::helper::sigmoid_grad)
@@ -126,7 +247,8 @@ macro_rules! impl_ops_sigmoid_for {
         impl SigmoidPointwise<$t> for $b {
             fn sigmoid_pointwise(&self, x: &mut SharedTensor<$t>)
                        -> Result<(), Error> {
-                map1_inplace(read_write!(x, $t, self),
+                map1_inplace(self.device().thread_pool(),
+                     read_write!(x, $t, self),
                      crate::frameworks::native::helper::sigmoid)
             }
 
@@ -136,7 +258,8 @@ macro_rules! impl_ops_sigmoid_for {
                 x_diff: &mut SharedTensor<$t>)
                 -> Result<(),  $crate::co::error::Error> {
                     return
-                map2_inplace(read!(x, $t, self),
+                map2_inplace(self.device().thread_pool(),
+                     read!(x, $t, self),
                      read_write!(x_diff, $t, self),
                      crate::frameworks::native::helper::sigmoid_grad)
             }
@@ -151,7 +274,8 @@ macro_rules! impl_ops_relu_for {
         impl Relu<$t> for $b {
             fn relu(&self, x: &SharedTensor<$t>, result: &mut SharedTensor<$t>)
                     -> Result<(), $crate::co::error::Error> {
-                map1(read!(x, $t, self),
+                map1(self.device().thread_pool(),
+                     read!(x, $t, self),
                      write_only!(result, $t, self),
                      crate::frameworks::native::helper::relu)
             }
@@ -163,7 +287,8 @@ macro_rules! impl_ops_relu_for {
                 result: &SharedTensor<$t>,
                 result_diff: &mut SharedTensor<$t>)
                 -> Result<(), Error> {
-                map2(read!(x, $t, self),
+                map2(self.device().thread_pool(),
+                     read!(x, $t, self),
                      read!(x_diff, $t, self),
                      write_only!(result_diff, $t, self),
                      crate::frameworks::native::helper::relu_grad)
@@ -173,7 +298,8 @@ macro_rules! impl_ops_relu_for {
 
             fn relu_pointwise(&self, x: &mut SharedTensor<$t>)
                     -> Result<(), $crate::co::error::Error> {
-                map1_inplace(read_write!(x, $t, self),
+                map1_inplace(self.device().thread_pool(),
+                     read_write!(x, $t, self),
                      crate::frameworks::native::helper::relu)
             }
 
@@ -182,7 +308,8 @@ macro_rules! impl_ops_relu_for {
                 x: &SharedTensor<$t>,
                 x_diff: &mut SharedTensor<$t>)
                 -> Result<(), $crate::co::error::Error> {
-                map2_inplace(read!(x, $t, self),
+                map2_inplace(self.device().thread_pool(),
+                     read!(x, $t, self),
                      read_write!(x_diff, $t, self),
                      crate::frameworks::native::helper::relu_grad)
             }
@@ -197,7 +324,8 @@ macro_rules! impl_ops_tanh_for {
         impl $crate::plugin::Tanh<$t> for $b {
             fn tanh(&self, x: &SharedTensor<$t>, result: &mut SharedTensor<$t>)
                     -> Result<(), $crate::co::error::Error> {
-                map1(read!(x, $t, self),
+                map1(self.device().thread_pool(),
+                     read!(x, $t, self),
                      write_only!(result, $t, self),
                      crate::frameworks::native::helper::tanh)
             }
@@ -209,7 +337,8 @@ macro_rules! impl_ops_tanh_for {
                 result: &SharedTensor<$t>,
                 result_diff: &mut SharedTensor<$t>)
                 -> Result<(), Error> {
-                map2(read!(x, $t, self),
+                map2(self.device().thread_pool(),
+                     read!(x, $t, self),
                      read!(x_diff, $t, self),
                      write_only!(result_diff, $t, self),
                      crate::frameworks::native::helper::tanh_grad)
@@ -218,7 +347,8 @@ macro_rules! impl_ops_tanh_for {
         impl $crate::plugin::TanhPointwise<$t> for $b {
             fn tanh_pointwise(&self, x: &mut SharedTensor<$t>)
                     -> Result<(), $crate::co::error::Error> {
-                map1_inplace(read_write!(x, $t, self),
+                map1_inplace(self.device().thread_pool(),
+                     read_write!(x, $t, self),
                      crate::frameworks::native::helper::tanh)
             }
 
@@ -227,7 +357,8 @@ macro_rules! impl_ops_tanh_for {
                 x: &SharedTensor<$t>,
                 x_diff: &mut SharedTensor<$t>)
                 -> Result<(), Error> {
-                map2_inplace(read!(x, $t, self),
+                map2_inplace(self.device().thread_pool(),
+                     read!(x, $t, self),
                      read_write!(x_diff, $t, self),
                      crate::frameworks::native::helper::tanh_grad)
             }
@@ -241,10 +372,20 @@ pub struct ConvolutionConfig {
     pub filter_shape: Vec<usize>,
     pub stride: Vec<i32>,
     pub padding: Vec<i32>,
+    /// Distance between the input elements a filter is applied to.
+    pub dilation: Vec<i32>,
+    /// Number of independent convolutions the channels are split into.
+    pub groups: usize,
+    /// Either `GEMM` or `Direct`.
+    pub algo_fwd: ConvForwardAlgo,
+    /// Either `GEMM` or `Direct`.
+    pub algo_bwd_filter: ConvBackwardFilterAlgo,
+    /// Either `GEMM` or `Direct`.
+    pub algo_bwd_data: ConvBackwardDataAlgo,
 }
 
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 #[allow(missing_docs)]
 // TODO: Keep parallel with impl in Cuda
 pub struct RnnConfig {
@@ -262,6 +403,32 @@ pub struct RnnConfig {
     pub input_mode: RnnInputMode,
     /// RNN Direction
     pub direction_mode: DirectionMode,
+    /// Number of Time Steps
+    pub sequence_length: usize,
+    /// Batch Size
+    pub batch_size: usize,
+    /// Size of the workspace in bytes, which holds the intermediate results
+    /// of the backward pass w.r.t. the data for the one w.r.t. the weights
+    pub workspace_size: usize,
+}
+
+impl RnnConfig {
+    /// Number of gates, each with its own set of weights
+    pub fn num_gates(&self) -> usize {
+        match self.rnn_type {
+            RnnNetworkMode::ReLU | RnnNetworkMode::Tanh => 1,
+            RnnNetworkMode::LSTM => 4,
+            RnnNetworkMode::GRU => 3,
+        }
+    }
+
+    /// Number of directions the sequence is traversed in
+    pub fn num_directions(&self) -> usize {
+        match self.direction_mode {
+            DirectionMode::UniDirectional => 1,
+            DirectionMode::BiDirectional => 2,
+        }
+    }
 }
 
 /// softmax impl generation macro
@@ -271,38 +438,40 @@ macro_rules! impl_ops_softmax_for {
         impl $crate::plugin::Softmax<$t> for $b {
             fn softmax(&self, x: &SharedTensor<$t>, result: &mut SharedTensor<$t>)
                        -> Result<(), Error> {
+                let pool = self.device().thread_pool();
+                let instance = softmax_instance_len(x.desc());
                 let xs = read!(x, $t, self);
                 let rs = write_only!(result, $t, self);
+                lens_eq(xs, rs)?;
 
-                map1(xs, rs, |v| v.exp())?;
-
-                let mut sum: $t = 0.0; // iter_arith is not stable yet
-                for r in &*rs {
-                    sum += *r;
-                }
-                for r in rs {
-                    *r /= sum;
+                for (xs, rs) in xs.chunks(instance).zip(rs.chunks_mut(instance)) {
+                    let max_x = max(pool, xs);
+                    map1(pool, xs, rs, |v| (v - max_x).exp())?;
+                    let sum = sum_map(pool, rs, |r| r);
+                    map1_inplace(pool, rs, |r| r / sum)?;
                 }
                 Ok(())
             }
 
-            // TODO: check
             fn softmax_grad(
                 &self,
                 x: &SharedTensor<$t>,
                 x_diff: &SharedTensor<$t>,
                 result_diff: &mut SharedTensor<$t>) -> Result<(), Error> {
 
+                let pool = self.device().thread_pool();
+                let instance = softmax_instance_len(x.desc());
                 let xs = read!(x, $t, self);
                 let dxs = read!(x_diff, $t, self);
                 let drs = write_only!(result_diff, $t, self);
+                lens_eq(xs, dxs)?;
+                lens_eq(xs, drs)?;
 
-                let mut dot: $t = 0.0;
-                for (t, dt) in xs.iter().zip(dxs.iter()) {
-                    dot += t * dt;
+                for ((xs, dxs), drs) in xs.chunks(instance).zip(dxs.chunks(instance)).zip(drs.chunks_mut(instance)) {
+                    let dot = dot(pool, xs, dxs)?;
+                    map2(pool, xs, dxs, drs, |t, dt| t * (dt - dot))?;
                 }
-
-                map2(xs, dxs, drs, |t, dt| t * (dt - dot))
+                Ok(())
             }
         }
     );
@@ -315,99 +484,38 @@ macro_rules! impl_ops_log_softmax_for {
         impl $crate::plugin::LogSoftmax<$t> for $b {
             fn log_softmax(&self, x: &SharedTensor<$t>, result: &mut SharedTensor<$t>)
                            -> Result<(), $crate::co::error::Error> {
+                let pool = self.device().thread_pool();
+                let instance = softmax_instance_len(x.desc());
                 let xs = read!(x, $t, self);
                 let rs = write_only!(result, $t, self);
+                lens_eq(xs, rs)?;
 
-                let max_x = xs.iter().fold(::std::$t::NEG_INFINITY,
-                                           |acc, &t| acc.max(t));
-
-                let mut logsum : $t = 0.0;
-                for t in xs {
-                    logsum += (-(max_x - t)).exp();
+                for (xs, rs) in xs.chunks(instance).zip(rs.chunks_mut(instance)) {
+                    let max_x = max(pool, xs);
+                    let logsum = max_x + sum_map(pool, xs, |t| (-(max_x - t)).exp()).ln();
+                    map1(pool, xs, rs, |t| t - logsum)?;
                 }
-                logsum = max_x + logsum.ln();
-
-                map1(xs, rs, |t| t - logsum)
+                Ok(())
             }
 
             fn log_softmax_grad(&self, x: &SharedTensor<$t>, x_diff: &SharedTensor<$t>,
                                 result_diff: &mut SharedTensor<$t>)
                                 -> Result<(), $crate::co::error::Error> {
+                let pool = self.device().thread_pool();
+                let instance = softmax_instance_len(x.desc());
                 let xs = read!(x, $t, self);
                 let dxs = read!(x_diff, $t, self);
                 let drs = write_only!(result_diff, $t, self);
+                lens_eq(xs, dxs)?;
+                lens_eq(xs, drs)?;
 
-                let mut sum : $t = 0.0;
-                for &grad_val in dxs.iter() {
-                    sum += grad_val;
+                for ((xs, dxs), drs) in xs.chunks(instance).zip(dxs.chunks(instance)).zip(drs.chunks_mut(instance)) {
+                    let sum = sum_map(pool, dxs, |dt| dt);
+                    map2(pool, xs, dxs, drs, |t, dt| dt - t.exp() * sum)?;
                 }
-                map2(xs, dxs, drs, |t, dt| dt - t.exp() * sum)
-            }
-        }
-    );
-}
-
-
-/// lrn impl generation macro
-/// TODO it's all unimplemented!() right now
-#[macro_export]
-macro_rules! impl_ops_lrn_for {
-    ($t:ident, $b:ty) => (
-        impl ::plugin::LRN<$t> for $b {
-            fn new_lrn_config(
-                &self,
-                n: u32,
-                alpha: f64,
-                beta: f64,
-                k: f64
-            ) -> Result<Self::CLRN, ::co::error::Error> {
-                unimplemented!();
-                Ok(::frameworks::native::helper::NormalizationConfig)
-            }
-
-            fn lrn(
-                &self,
-                x: &mut SharedTensor<$t>,
-                result: &mut SharedTensor<$t>,
-                config: &Self::CLRN
-            ) -> Result<(), ::co::error::Error> {
-                unimplemented!();
-                Ok(())
-            }
-
-            fn lrn_plain(
-                &self,
-                x: &SharedTensor<$t>,
-                result: &mut SharedTensor<$t>,
-                config: &Self::CLRN
-            ) -> Result<(), ::co::error::Error> {
-                unimplemented!();
-                Ok(())
-            }
-
-            fn lrn_grad(
-                &self,
-                x: &mut SharedTensor<$t>,
-                x_diff: &mut SharedTensor<$t>,
-                result: &mut SharedTensor<$t>,
-                result_diff: &mut SharedTensor<$t>,
-                config: &Self::CLRN
-            ) -> Result<(), ::co::error::Error> {
-                unimplemented!();
-                Ok(())
-            }
-
-            fn lrn_grad_plain(
-                &self,
-                x: &SharedTensor<$t>,
-                x_diff: &SharedTensor<$t>,
-                result: &SharedTensor<$t>,
-                result_diff: &mut SharedTensor<$t>,
-                config: &Self::CLRN
-            ) -> Result<(), ::co::error::Error> {
-                unimplemented!();
                 Ok(())
             }
         }
     );
 }
+
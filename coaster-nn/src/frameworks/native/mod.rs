@@ -13,12 +13,19 @@ use rand::{Rng, SeedableRng};
 #[cfg(feature = "native")]
 use rand_hc as hc128;
 
+use rayon::ThreadPool;
+use rayon::prelude::*;
+
 use crate::co::Error;
+use crate::co::frameworks::native::Cpu;
+use crate::co::frameworks::native::flatbox::FlatBox;
 use crate::co::plugin::Error as PluginError;
 use crate::co::plugin::numeric_helpers::Bounded;
 use crate::co::plugin::numeric_helpers::Float;
 use crate::co::prelude::*;
 use crate::plugin::*;
+use crate::coblas::plugin::Gemm;
+use crate::coblas::transpose::Transpose;
 
 #[macro_use]
 pub mod helper;
@@ -33,47 +40,328 @@ fn lens_eq<T>(xs: &[T], ys: &[T]) -> Result<(), Error> {
 }
 
 
-fn map1_inplace<T, F>(src: &mut [T], f: F) -> Result<(), Error>
-    where T: Float,
-          F: Fn(T) -> T
+/// Number of elements below which elementwise kernels and reductions are not
+/// split up any further across threads.
+const PARALLEL_CHUNK: usize = 4096;
+
+fn map1_inplace<T, F>(pool: &ThreadPool, src: &mut [T], f: F) -> Result<(), Error>
+    where T: Float + Send + Sync,
+          F: Fn(T) -> T + Sync
 {
-    for i in 0..src.len() {
-        src[i] = f(src[i]);
-    }
+    pool.install(|| {
+        src.par_iter_mut()
+            .with_min_len(PARALLEL_CHUNK)
+            .for_each(|s| *s = f(*s));
+    });
     Ok(())
 }
 
-fn map2_inplace<T, F>(src1: &[T], src2: &mut [T], f: F) -> Result<(), Error>
-    where T: Float,
-          F: Fn(T, T) -> T
+fn map2_inplace<T, F>(pool: &ThreadPool, src1: &[T], src2: &mut [T], f: F) -> Result<(), Error>
+    where T: Float + Send + Sync,
+          F: Fn(T, T) -> T + Sync
 {
     lens_eq(src1, src2)?;
-    for i in 0..src2.len() {
-        src2[i] = f(src1[i], src2[i]);
-    }
+    pool.install(|| {
+        src2.par_iter_mut()
+            .zip(src1.par_iter())
+            .with_min_len(PARALLEL_CHUNK)
+            .for_each(|(s2, &s1)| *s2 = f(s1, *s2));
+    });
     Ok(())
 }
 
-fn map1<T, F>(src: &[T], dst: &mut [T], f: F) -> Result<(), Error>
-    where T: Float,
-          F: Fn(T) -> T
+fn map1<T, F>(pool: &ThreadPool, src: &[T], dst: &mut [T], f: F) -> Result<(), Error>
+    where T: Float + Send + Sync,
+          F: Fn(T) -> T + Sync
 {
     lens_eq(dst, src)?;
-    for i in 0..dst.len() {
-        dst[i] = f(src[i]);
-    }
+    pool.install(|| {
+        dst.par_iter_mut()
+            .zip(src.par_iter())
+            .with_min_len(PARALLEL_CHUNK)
+            .for_each(|(d, &s)| *d = f(s));
+    });
     Ok(())
 }
 
-fn map2<T, F>(src1: &[T], src2: &[T], dst: &mut [T], f: F) -> Result<(), Error>
-    where T: Float,
-          F: Fn(T, T) -> T
+fn map2<T, F>(pool: &ThreadPool, src1: &[T], src2: &[T], dst: &mut [T], f: F) -> Result<(), Error>
+    where T: Float + Send + Sync,
+          F: Fn(T, T) -> T + Sync
 {
     lens_eq(dst, src1)?;
     lens_eq(dst, src2)?;
-    for i in 0..dst.len() {
-        dst[i] = f(src1[i], src2[i]);
+    pool.install(|| {
+        dst.par_iter_mut()
+            .zip(src1.par_iter().zip(src2.par_iter()))
+            .with_min_len(PARALLEL_CHUNK)
+            .for_each(|(d, (&s1, &s2))| *d = f(s1, s2));
+    });
+    Ok(())
+}
+
+/// Sums up `f` of all elements of `src`.
+///
+/// The partial sums are formed over fixed size chunks, so the result does not
+/// depend on the number of threads.
+fn sum_map<T, F>(pool: &ThreadPool, src: &[T], f: F) -> T
+    where T: Float + Send + Sync,
+          F: Fn(T) -> T + Sync
+{
+    let partial: Vec<T> = pool.install(|| {
+        src.par_chunks(PARALLEL_CHUNK)
+            .map(|chunk| chunk.iter().fold(T::zero(), |acc, &x| acc + f(x)))
+            .collect()
+    });
+    partial.into_iter().fold(T::zero(), |acc, x| acc + x)
+}
+
+/// Computes the dot product of `src1` and `src2` like `sum_map`.
+fn dot<T>(pool: &ThreadPool, src1: &[T], src2: &[T]) -> Result<T, Error>
+    where T: Float + Send + Sync
+{
+    lens_eq(src1, src2)?;
+    let partial: Vec<T> = pool.install(|| {
+        src1.par_chunks(PARALLEL_CHUNK)
+            .zip(src2.par_chunks(PARALLEL_CHUNK))
+            .map(|(c1, c2)| c1.iter().zip(c2).fold(T::zero(), |acc, (&x, &y)| acc + x * y))
+            .collect()
+    });
+    Ok(partial.into_iter().fold(T::zero(), |acc, x| acc + x))
+}
+
+/// Finds the maximum of all elements of `src`, negative infinity if it is empty.
+fn max<T>(pool: &ThreadPool, src: &[T]) -> T
+    where T: Float + Send + Sync
+{
+    pool.install(|| {
+        src.par_iter()
+            .with_min_len(PARALLEL_CHUNK)
+            .cloned()
+            .reduce(T::neg_infinity, T::max)
+    })
+}
+
+/// Returns the length of the consecutive blocks of `desc` that a softmax normalizes separately.
+///
+/// Follows the layout of the CUDA backend: the first dimension of a 2D input and of
+/// inputs with four or more dimensions is the batch, other inputs form a single instance.
+fn softmax_instance_len(desc: &TensorDesc) -> usize {
+    let len = if desc.len() == 2 || desc.len() >= 4 {
+        desc[1..].iter().product()
+    } else {
+        desc.size()
+    };
+    len.max(1)
+}
+
+/// Advance a multi dimensional index in row major order.
+///
+/// Returns `false` once all indices within `dims` have been visited.
+fn next_index(idx: &mut [usize], dims: &[usize]) -> bool {
+    for d in (0..idx.len()).rev() {
+        idx[d] += 1;
+        if idx[d] < dims[d] {
+            return true;
+        }
+        idx[d] = 0;
+    }
+    false
+}
+
+/// Offset of the input element a filter element is applied to for a given
+/// output position, or `None` if it falls into the padding.
+/// The filter elements are `dilation` input elements apart.
+fn window_input_offset(output_idx: &[usize],
+                       filter_idx: &[usize],
+                       input_dim: &[usize],
+                       input_stride: &[usize],
+                       stride: &[i32],
+                       padding: &[i32],
+                       dilation: &[i32])
+                       -> Option<usize> {
+    let mut offset = 0;
+    for d in 0..output_idx.len() {
+        let i = (output_idx[d] * stride[d] as usize + filter_idx[d] * dilation[d] as usize) as isize -
+                padding[d] as isize;
+        if i < 0 || i as usize >= input_dim[d] {
+            return None;
+        }
+        offset += i as usize * input_stride[d];
+    }
+    Some(offset)
+}
+
+/// Visit every pooling window of a single `[batch, channel]` plane, passing
+/// the output offset, the offsets of the input elements within the window
+/// which are not padding and the full window size to `f`. The offsets are
+/// relative to the start of the plane.
+fn for_each_pooling_window<F>(input_dim: &TensorDesc,
+                              output_dim: &TensorDesc,
+                              config: &helper::PoolingConfig,
+                              mut f: F)
+    where F: FnMut(usize, &[usize], usize)
+{
+    let input_stride = input_dim.default_stride();
+    let output_stride = output_dim.default_stride();
+    let (input_size, output_size) = (&input_dim[2..], &output_dim[2..]);
+    let spatial_dims = input_size.len();
+
+    let mut output_idx = vec![0; spatial_dims];
+    let mut window_idx = vec![0; spatial_dims];
+    let mut window_start = vec![0isize; spatial_dims];
+    let mut window = vec![0; spatial_dims];
+    let mut offsets = Vec::new();
+
+    loop {
+        for d in 0..spatial_dims {
+            if config.adaptive {
+                // the window spans [floor(o * input / output), ceil((o + 1) * input / output))
+                let (i, o) = (input_size[d], output_size[d]);
+                let begin = output_idx[d] * i / o;
+                let end = ((output_idx[d] + 1) * i).div_ceil(o);
+                window_start[d] = begin as isize;
+                window[d] = end - begin;
+            } else {
+                window_start[d] = (output_idx[d] * config.stride[d] as usize) as isize - config.padding[d] as isize;
+                window[d] = config.window[d] as usize;
+            }
+        }
+
+        offsets.clear();
+        for w in window_idx.iter_mut() {
+            *w = 0;
+        }
+        loop {
+            let mut offset = Some(0);
+            for d in 0..spatial_dims {
+                let i = window_start[d] + window_idx[d] as isize;
+                if i < 0 || i as usize >= input_size[d] {
+                    offset = None;
+                    break;
+                }
+                offset = offset.map(|offset| offset + i as usize * input_stride[2 + d]);
+            }
+            if let Some(i) = offset {
+                offsets.push(i);
+            }
+            if !next_index(&mut window_idx, &window) {
+                break;
+            }
+        }
+        let o = output_idx.iter()
+            .zip(&output_stride[2..])
+            .fold(0, |acc, (idx, s)| acc + idx * s);
+        f(o, &offsets, window.iter().product());
+        if !next_index(&mut output_idx, output_size) {
+            break;
+        }
     }
+}
+
+
+/// Unfolds the windows of a single batch item `[channels, spatial..]` into
+/// the columns of `col`, one column per output position. The channels are
+/// processed in parallel on the current thread pool.
+#[allow(clippy::too_many_arguments)]
+fn im2col<T>(input: &[T],
+             input_dim: &[usize],
+             input_stride: &[usize],
+             filter_dim: &[usize],
+             output_dim: &[usize],
+             stride: &[i32],
+             padding: &[i32],
+             dilation: &[i32],
+             col: &mut [T])
+    where T: Default + Copy + Send + Sync
+{
+    let output_size: usize = output_dim.iter().product();
+    let filter_size: usize = filter_dim.iter().product();
+
+    col[..input_dim[0] * filter_size * output_size]
+        .par_chunks_mut(filter_size * output_size)
+        .zip(input.par_chunks(input_stride[0]))
+        .for_each(|(col, input)| {
+            let mut filter_idx = vec![0; filter_dim.len()];
+            let mut output_idx = vec![0; output_dim.len()];
+            for col_row in col.chunks_mut(output_size) {
+                for o in output_idx.iter_mut() {
+                    *o = 0;
+                }
+                for v in col_row.iter_mut() {
+                    *v = match window_input_offset(&output_idx,
+                                                   &filter_idx,
+                                                   &input_dim[1..],
+                                                   &input_stride[1..],
+                                                   stride,
+                                                   padding,
+                                                   dilation) {
+                        Some(i) => input[i],
+                        None => Default::default(),
+                    };
+                    next_index(&mut output_idx, output_dim);
+                }
+                next_index(&mut filter_idx, filter_dim);
+            }
+        });
+}
+
+/// Folds the columns of `col` back into a single batch item, accumulating
+/// the values of overlapping windows. The channels are processed in parallel
+/// on the current thread pool.
+#[allow(clippy::too_many_arguments)]
+fn col2im<T>(col: &[T],
+             input_dim: &[usize],
+             input_stride: &[usize],
+             filter_dim: &[usize],
+             output_dim: &[usize],
+             stride: &[i32],
+             padding: &[i32],
+             dilation: &[i32],
+             input: &mut [T])
+    where T: Add<T, Output = T> + Default + Copy + Send + Sync
+{
+    let output_size: usize = output_dim.iter().product();
+    let filter_size: usize = filter_dim.iter().product();
+
+    input[..input_dim[0] * input_stride[0]]
+        .par_chunks_mut(input_stride[0])
+        .zip(col.par_chunks(filter_size * output_size))
+        .for_each(|(input, col)| {
+            let mut filter_idx = vec![0; filter_dim.len()];
+            let mut output_idx = vec![0; output_dim.len()];
+            for col_row in col.chunks(output_size) {
+                for o in output_idx.iter_mut() {
+                    *o = 0;
+                }
+                for &v in col_row {
+                    if let Some(i) = window_input_offset(&output_idx,
+                                                         &filter_idx,
+                                                         &input_dim[1..],
+                                                         &input_stride[1..],
+                                                         stride,
+                                                         padding,
+                                                         dilation) {
+                        input[i] = input[i] + v;
+                    }
+                    next_index(&mut output_idx, output_dim);
+                }
+                next_index(&mut filter_idx, filter_dim);
+            }
+        });
+}
+
+/// Provides a `[1]` tensor holding `value`, the form in which the BLAS
+/// operations take their scalar arguments.
+fn native_scalar<T: Copy>(dev: &Cpu, value: T) -> Result<SharedTensor<T>, Error> {
+    let mut scalar = SharedTensor::<T>::new(&[1]);
+    scalar.write_only(dev)?.as_mut_slice::<T>()[0] = value;
+    Ok(scalar)
+}
+
+/// Copies `data` into the native memory of `tensor`.
+fn write_native<T: Copy>(dev: &Cpu, tensor: &mut SharedTensor<T>, data: &[T]) -> Result<(), Error> {
+    let size = tensor.desc().size();
+    tensor.write_only(dev)?.as_mut_slice::<T>().copy_from_slice(&data[..size]);
     Ok(())
 }
 
@@ -87,6 +375,9 @@ impl<T> NN<T> for Backend<Native>
     // type CACTI = helper::ActivationConfig;
     type CDROP = helper::DropoutConfig;
     type CRNN = helper::RnnConfig;
+    type CBN = helper::BatchNormalizationConfig;
+    type CLN = helper::LayerNormalizationConfig;
+    type CGN = helper::GroupNormalizationConfig;
 
     fn init_nn() {}
 }
@@ -102,6 +393,9 @@ impl<'a, T> ConvolutionConfig<T> for helper::ConvolutionConfig
 impl<'a, T> RnnConfig<T> for helper::RnnConfig
 where T: Add<T, Output = T> + Mul<T, Output = T> + Default + Copy
 {
+    fn workspace_size(&self) -> usize {
+        self.workspace_size
+    }
 }
 impl<T> NNOperationConfig<T> for helper::NormalizationConfig
     where T: Add<T, Output = T> + Mul<T, Output = T> + Default + Copy
@@ -111,6 +405,18 @@ impl<T> NNOperationConfig<T> for helper::PoolingConfig
     where T: Add<T, Output = T> + Mul<T, Output = T> + Default + Copy
 {
 }
+impl<T> NNOperationConfig<T> for helper::BatchNormalizationConfig
+    where T: Add<T, Output = T> + Mul<T, Output = T> + Default + Copy
+{
+}
+impl<T> NNOperationConfig<T> for helper::LayerNormalizationConfig
+    where T: Add<T, Output = T> + Mul<T, Output = T> + Default + Copy
+{
+}
+impl<T> NNOperationConfig<T> for helper::GroupNormalizationConfig
+    where T: Add<T, Output = T> + Mul<T, Output = T> + Default + Copy
+{
+}
 // impl<T> NNOperationConfig<T> for helper::ActivationConfig
 //     where T: Add<T, Output = T> + Mul<T, Output = T> + Default + Copy
 // {
@@ -126,45 +432,68 @@ impl<T> NNOperationConfig<T> for helper::RnnConfig
 }
 
 impl<T> Convolution<T> for Backend<Native>
-    where T: Add<T, Output = T> + Mul<T, Output = T> + Default + Copy
+    where T: Float + Default + Send + Sync,
+          Backend<Native>: Gemm<T>
 {
-    fn new_convolution_config(&self,
-                              src: &SharedTensor<T>,
-                              dest: &SharedTensor<T>,
-                              filter: &SharedTensor<T>,
-                              algo_fwd: ConvForwardAlgo,
-                              algo_bwd_filter: ConvBackwardFilterAlgo,
-                              algo_bwd_data: ConvBackwardDataAlgo,
-                              stride: &[i32],
-                              zero_padding: &[i32])
-                              -> Result<Self::CC, Error> {
-        // TODO: check dimensions of config
-        match algo_fwd {
+    fn new_grouped_convolution_config(&self,
+                                      src: &SharedTensor<T>,
+                                      dest: &SharedTensor<T>,
+                                      filter: &SharedTensor<T>,
+                                      algo_fwd: ConvForwardAlgo,
+                                      algo_bwd_filter: ConvBackwardFilterAlgo,
+                                      algo_bwd_data: ConvBackwardDataAlgo,
+                                      stride: &[i32],
+                                      zero_padding: &[i32],
+                                      dilation: &[i32],
+                                      groups: usize)
+                                      -> Result<Self::CC, Error> {
+        let (input_dim, filter_dim) = (src.desc(), filter.desc());
+        if groups == 0 || filter_dim[0] % groups != 0 || input_dim[1] != filter_dim[1] * groups {
+            return Err(PluginError::Operation("Channels of input and filter do not match the convolution groups").into());
+        }
+        let spatial_dims = input_dim.len() - 2;
+        if filter_dim.len() != input_dim.len() || dest.desc().len() != input_dim.len() ||
+           stride.len() != spatial_dims || zero_padding.len() != spatial_dims {
+            return Err(PluginError::Operation("Input, filter, output, stride and padding do not agree on the number of spatial dimensions").into());
+        }
+        if dilation.len() != stride.len() || dilation.iter().any(|&d| d < 1) {
+            return Err(PluginError::Operation("Dilation has to be positive for every spatial dimension").into());
+        }
+        // Auto prefers the explicit matrix product over the direct convolution.
+        let algo_fwd = match algo_fwd {
             ConvForwardAlgo::Auto |
-            ConvForwardAlgo::ImplicitGEMM => {}
+            ConvForwardAlgo::GEMM => ConvForwardAlgo::GEMM,
+            ConvForwardAlgo::Direct => ConvForwardAlgo::Direct,
             _ => {
                 return Err(Error::Plugin(PluginError::Plugin("Unimplemented.")));
             }
-        }
-        match algo_bwd_filter {
+        };
+        let algo_bwd_filter = match algo_bwd_filter {
             ConvBackwardFilterAlgo::Auto |
-            ConvBackwardFilterAlgo::ImplicitGEMM => {}
+            ConvBackwardFilterAlgo::GEMM => ConvBackwardFilterAlgo::GEMM,
+            ConvBackwardFilterAlgo::Direct => ConvBackwardFilterAlgo::Direct,
             _ => {
                 return Err(Error::Plugin(PluginError::Plugin("Unimplemented.")));
             }
-        }
-        match algo_bwd_data {
+        };
+        let algo_bwd_data = match algo_bwd_data {
             ConvBackwardDataAlgo::Auto |
-            ConvBackwardDataAlgo::ImplicitGEMM => {}
+            ConvBackwardDataAlgo::GEMM => ConvBackwardDataAlgo::GEMM,
+            ConvBackwardDataAlgo::Direct => ConvBackwardDataAlgo::Direct,
             _ => {
                 return Err(Error::Plugin(PluginError::Plugin("Unimplemented.")));
             }
-        }
+        };
 
         Ok(helper::ConvolutionConfig {
-               filter_shape: filter.desc().clone(),
+               filter_shape: filter_dim.clone(),
                stride: stride.to_vec(),
                padding: zero_padding.to_vec(),
+               dilation: dilation.to_vec(),
+               groups: groups,
+               algo_fwd: algo_fwd,
+               algo_bwd_filter: algo_bwd_filter,
+               algo_bwd_data: algo_bwd_data,
            })
     }
 
@@ -172,233 +501,107 @@ impl<T> Convolution<T> for Backend<Native>
                    filter: &SharedTensor<T>,
                    x: &SharedTensor<T>,
                    result: &mut SharedTensor<T>,
-                   _workspace: &mut SharedTensor<u8>,
+                   workspace: &mut SharedTensor<u8>,
                    config: &Self::CC)
                    -> Result<(), Error> {
         let dev = self.device();
 
-        let input_dim = x.desc();
-        let input = x.read(dev)
-            .unwrap()
-            .as_slice::<T>();
+        let input_dim = x.desc().clone();
         let input_stride = input_dim.default_stride();
+        let input = x.read(dev)?.as_slice::<T>();
 
         let output_dim = result.desc().clone();
-        // this is ok, we only read parts we already wrote
-        let output = result
-            .write_only(dev)
-            .unwrap()
-            .as_mut_slice::<T>();
-
         let output_stride = output_dim.default_stride();
-        {
-            for o in output.iter_mut() {
-                *o = Default::default();
-            }
-        }
+        let output = result.write_only(dev)?.as_mut_slice::<T>();
 
-        let filter_dim = filter.desc();
-        let filter = filter
-            .read(dev)
-            .unwrap()
-            .as_slice::<T>();
+        let filter_dim = filter.desc().clone();
         let filter_stride = filter_dim.default_stride();
+        let filter = filter.read(dev)?.as_slice::<T>();
 
         // sanity check
         assert!(input_dim[0] == output_dim[0]);
         assert!(filter_dim[0] == output_dim[1]);
-        assert!(input_dim[1] == filter_dim[1]);
-
-        // TODO: specializations for spatial input
-
-        // recursively sum up elementwise multiplication of the hyperplanes.
-        fn filter_<T>(input: &[T],
-                      input_stride: &[usize],
-                      input_dim: &[usize],
-                      input_offset: usize,
-                      input_idx_base: &[usize],
-                      filter: &[T],
-                      filter_stride: &[usize],
-                      filter_dim: &[usize],
-                      filter_offset: usize,
-                      padding: &[i32],
-                      depth: usize,
-                      depth_end: usize,
-                      acc: Option<T>)
-                      -> T
-            where T: Add<T, Output = T> + Mul<T, Output = T> + Default + Copy
-        {
-            let mut acc = acc.unwrap_or_default();
-
-            let p = padding[0] as usize;
-            let input_idx_end = input_dim[0] + 2 * p;
-
-            for filter_idx in 0..filter_dim[0] {
-                let input_idx = input_idx_base[0] + filter_idx;
-                let i_offset = input_offset + (input_idx - p) * input_stride[0];
-                let f_offset = filter_offset + filter_idx * filter_stride[0];
-
-                let v = if input_idx < p || input_idx + 1 > input_idx_end - p {
-                    Default::default()
-                } else if depth + 1 >= depth_end {
-                    input[i_offset] * filter[f_offset]
-                } else {
-                    filter_(input,
-                            &input_stride[1..],
-                            &input_dim[1..],
-                            i_offset,
-                            &input_idx_base[1..],
-                            filter,
-                            &filter_stride[1..],
-                            &filter_dim[1..],
-                            f_offset,
-                            &padding[1..],
-                            depth + 1,
-                            depth_end,
-                            None)
-                };
-                acc = acc + v;
-            }
-            return acc;
-        }
-
-        // depth == 0 is the first level
-        fn conv<T>(input: &[T],
-                   input_stride: &[usize],
-                   input_dim: &[usize],
-                   top_input_offset: usize,
-                   input_offset: usize,
-                   input_idx_base: &mut [usize],
-                   filter: &[T],
-                   filter_stride: &[usize],
-                   filter_dim: &[usize],
-                   filter_offset: usize,
-                   depth: usize,
-                   padding: &[i32],
-                   stride: &[i32],
-                   output: &mut [T],
-                   output_stride: &[usize],
-                   output_dim: &[usize],
-                   output_offset: usize)
-            where T: Add<T, Output = T> + Mul<T, Output = T> + Default + Copy
-        {
-            let p = padding[depth] as usize;
-            //let input_end = input_dim[depth] + 2 * p - (filter_dim[depth]);
-
-            for output_idx in 0..output_dim[0] {
-                let input_i = output_idx * stride[0] as usize;
-                input_idx_base[depth] = input_i;
-                let input_offset = input_offset + input_i * input_stride[depth];
-                let output_offset = output_offset + output_idx * output_stride[0];
-
-                if depth + 1 < input_dim.len() {
-                    conv(input,
-                         input_stride,
-                         input_dim,
-                         top_input_offset,
-                         input_offset,
-                         input_idx_base,
-                         filter,
-                         filter_stride,
-                         filter_dim,
-                         filter_offset,
-                         depth + 1,
-                         padding,
-                         &stride[1..],
-                         output,
-                         &output_stride[1..],
-                         &output_dim[1..],
-                         output_offset);
-                } else {
-                    let v = filter_(input,
-                                    input_stride,
-                                    input_dim,
-                                    top_input_offset,
-                                    &input_idx_base[..],
-                                    filter,
-                                    filter_stride,
-                                    filter_dim,
-                                    filter_offset,
-                                    padding,
-                                    0,
-                                    input_dim.len(),
-                                    None);
-                    output[output_offset] = output[output_offset] + v;
-                }
-            }
-        }
-
-        fn conv_k_d1<T>(_batch: usize,
-                        input: &[T],
-                        input_stride: &[usize],
-                        input_dim: &[usize],
-                        input_offset: usize,
-                        input_idx_base: &mut [usize],
-                        filter: &[T],
-                        filter_stride: &[usize],
-                        filter_dim: &[usize],
-                        padding: &[i32],
-                        stride: &[i32],
-                        output: &mut [T],
-                        output_stride: &[usize],
-                        output_dim: &[usize],
-                        output_offset: usize)
-            where T: Add<T, Output = T> + Mul<T, Output = T> + Default + Copy
-        {
-            for k in 0..filter_dim[0] {
-                let output_offset = output_offset + k * output_stride[0];
-                let filter_offset = k * filter_stride[0];
-                for d1 in 0..input_dim[0] {
-                    let input_offset = input_offset + d1 * input_stride[0];
-                    let filter_offset = filter_offset + d1 * filter_stride[1];
-
-                    conv(input,
-                         &input_stride[1..],
-                         &input_dim[1..],
-                         input_offset,
-                         input_offset,
-                         input_idx_base,
-                         filter,
-                         &filter_stride[2..],
-                         &filter_dim[2..],
-                         filter_offset,
-                         0,
-                         padding,
-                         stride,
-                         output,
-                         &output_stride[1..],
-                         &output_dim[1..],
-                         output_offset);
+        assert!(input_dim[1] == filter_dim[1] * config.groups);
+
+        // every group maps `cg` input channels to `kg` output channels
+        let (cg, kg) = (filter_dim[1], filter_dim[0] / config.groups);
+
+        if let ConvForwardAlgo::GEMM = config.algo_fwd {
+            // y[n, g] = w[g] * im2col(x[n, g])
+            let (cf, os) = (filter_stride[0], output_stride[1]);
+            let mut group_dim = input_dim[1..].to_vec();
+            group_dim[0] = cg;
+            let (one, zero) = (native_scalar(dev, T::one())?, native_scalar(dev, T::zero())?);
+            let mut w = SharedTensor::<T>::new(&[kg, cf]);
+            let mut col = SharedTensor::<T>::new(&[cf, os]);
+            let mut y = SharedTensor::<T>::new(&[kg, os]);
+            for g in 0..config.groups {
+                write_native(dev, &mut w, &filter[g * kg * cf..])?;
+                for (x, output) in input.chunks(input_stride[0]).zip(output.chunks_mut(output_stride[0])) {
+                    let col_data = col.write_only(dev)?.as_mut_slice::<T>();
+                    dev.thread_pool().install(|| {
+                        im2col(&x[g * cg * input_stride[1]..], &group_dim, &input_stride[1..], &filter_dim[2..],
+                               &output_dim[2..], &config.stride, &config.padding, &config.dilation, col_data)
+                    });
+                    self.gemm(&one, Transpose::NoTrans, &w, Transpose::NoTrans, &col, &zero, &mut y)?;
+                    output[g * kg * os..(g + 1) * kg * os].copy_from_slice(y.read(dev)?.as_slice::<T>());
                 }
             }
+            return Ok(());
         }
 
-        let mut input_idx = Vec::new();
-        input_idx.resize(input_dim.len() - 2, 0);
-        let mut output_idx = Vec::new();
-        output_idx.resize(output_dim.len(), 0);
-
-        let batches = input_dim[0];
-        for batch in 0..batches {
-            let input_offset = batch * input_stride[0];
-            let output_offset = batch * output_stride[0];
-
-            conv_k_d1(batch,
-                      input,
-                      &input_stride[1..],
-                      &input_dim[1..],
-                      input_offset,
-                      &mut input_idx[..],
-                      filter,
-                      &filter_stride[..],
-                      &filter_dim[..],
-                      &config.padding[..],
-                      &config.stride[..],
-                      output,
-                      &output_stride[1..],
-                      &output_dim[1..],
-                      output_offset);
-        }
+        let spatial_dims = input_dim.len() - 2;
+
+        // y[n, k, o] = sum over c, f of w[k, c, f] * x[n, g * cg + c, o * stride + f * dilation - padding]
+        // the batch items are independent of each other
+        dev.thread_pool().install(|| {
+            output.par_chunks_mut(output_stride[0])
+                .zip(input.par_chunks(input_stride[0]))
+                .for_each(|(output, input)| {
+                    let mut output_idx = vec![0; spatial_dims];
+                    let mut filter_idx = vec![0; spatial_dims];
+                    for k in 0..filter_dim[0] {
+                        let group_offset = k / kg * cg * input_stride[1];
+                        for o in output_idx.iter_mut() {
+                            *o = 0;
+                        }
+                        loop {
+                            let mut acc: T = Default::default();
+                            for c in 0..cg {
+                                let input_offset = group_offset + c * input_stride[1];
+                                let filter_offset = k * filter_stride[0] + c * filter_stride[1];
+                                for f in filter_idx.iter_mut() {
+                                    *f = 0;
+                                }
+                                loop {
+                                    if let Some(i) = window_input_offset(&output_idx,
+                                                                       &filter_idx,
+                                                                       &input_dim[2..],
+                                                                       &input_stride[2..],
+                                                                       &config.stride,
+                                                                       &config.padding,
+                                                                       &config.dilation) {
+                                        let f = filter_idx.iter()
+                                            .zip(&filter_stride[2..])
+                                            .fold(filter_offset, |acc, (idx, s)| acc + idx * s);
+                                        acc = acc + input[input_offset + i] * filter[f];
+                                    }
+                                    if !next_index(&mut filter_idx, &filter_dim[2..]) {
+                                        break;
+                                    }
+                                }
+                            }
+                            let o = output_idx.iter()
+                                .zip(&output_stride[2..])
+                                .fold(k * output_stride[1], |acc, (idx, s)| acc + idx * s);
+                            output[o] = acc;
+                            if !next_index(&mut output_idx, &output_dim[2..]) {
+                                break;
+                            }
+                        }
+                    }
+                });
+        });
 
         Ok(())
     }
@@ -410,7 +613,112 @@ impl<T> Convolution<T> for Backend<Native>
                                workspace: &mut SharedTensor<u8>,
                                config: &Self::CC)
                                -> Result<(), Error> {
-        unimplemented!()
+        let dev = self.device();
+
+        let input_dim = src_data.desc().clone();
+        let input_stride = input_dim.default_stride();
+        let input = src_data.read(dev)?.as_slice::<T>();
+
+        let output_dim = dest_diff.desc().clone();
+        let output_stride = output_dim.default_stride();
+        let output_diff = dest_diff.read(dev)?.as_slice::<T>();
+
+        let filter_dim = filter_diff.desc().clone();
+        let filter_stride = filter_dim.default_stride();
+        let filter_diff = filter_diff.write_only(dev)?.as_mut_slice::<T>();
+
+        // sanity check
+        assert!(input_dim[0] == output_dim[0]);
+        assert!(filter_dim[0] == output_dim[1]);
+        assert!(input_dim[1] == filter_dim[1] * config.groups);
+
+        // every group maps `cg` input channels to `kg` output channels
+        let (cg, kg) = (filter_dim[1], filter_dim[0] / config.groups);
+
+        if let ConvBackwardFilterAlgo::GEMM = config.algo_bwd_filter {
+            // dw[g] = sum over n of dy[n, g] * im2col(x[n, g])^T
+            let (cf, os) = (filter_stride[0], output_stride[1]);
+            let mut group_dim = input_dim[1..].to_vec();
+            group_dim[0] = cg;
+            let (one, zero) = (native_scalar(dev, T::one())?, native_scalar(dev, T::zero())?);
+            let mut dy = SharedTensor::<T>::new(&[kg, os]);
+            let mut col = SharedTensor::<T>::new(&[cf, os]);
+            let mut dw = SharedTensor::<T>::new(&[kg, cf]);
+            for (g, filter_diff) in filter_diff.chunks_mut(kg * cf).enumerate() {
+                for v in filter_diff.iter_mut() {
+                    *v = Default::default();
+                }
+                let samples = input.chunks(input_stride[0]).zip(output_diff.chunks(output_stride[0]));
+                for (n, (x, output_diff)) in samples.enumerate() {
+                    let col_data = col.write_only(dev)?.as_mut_slice::<T>();
+                    dev.thread_pool().install(|| {
+                        im2col(&x[g * cg * input_stride[1]..], &group_dim, &input_stride[1..], &filter_dim[2..],
+                               &output_dim[2..], &config.stride, &config.padding, &config.dilation, col_data)
+                    });
+                    write_native(dev, &mut dy, &output_diff[g * kg * os..])?;
+                    // the first batch item overwrites the accumulator
+                    let beta = if n == 0 { &zero } else { &one };
+                    self.gemm(&one, Transpose::NoTrans, &dy, Transpose::Trans, &col, beta, &mut dw)?;
+                }
+                if input_dim[0] > 0 {
+                    filter_diff.copy_from_slice(dw.read(dev)?.as_slice::<T>());
+                }
+            }
+            return Ok(());
+        }
+
+        let spatial_dims = input_dim.len() - 2;
+
+        // dw[k, c, f] = sum over n, o of dy[n, k, o] * x[n, g * cg + c, o * stride + f * dilation - padding]
+        // the filters of the output channels are computed in parallel
+        dev.thread_pool().install(|| {
+            filter_diff.par_chunks_mut(filter_stride[0]).enumerate().for_each(|(k, filter_diff)| {
+                let mut output_idx = vec![0; spatial_dims];
+                let mut filter_idx = vec![0; spatial_dims];
+                let group_offset = k / kg * cg * input_stride[1];
+                for c in 0..cg {
+                    for f in filter_idx.iter_mut() {
+                        *f = 0;
+                    }
+                    loop {
+                        let mut acc: T = Default::default();
+                        for n in 0..input_dim[0] {
+                            let input_offset = n * input_stride[0] + group_offset + c * input_stride[1];
+                            let output_offset = n * output_stride[0] + k * output_stride[1];
+                            for o in output_idx.iter_mut() {
+                                *o = 0;
+                            }
+                            loop {
+                                if let Some(i) = window_input_offset(&output_idx,
+                                                                   &filter_idx,
+                                                                   &input_dim[2..],
+                                                                   &input_stride[2..],
+                                                                   &config.stride,
+                                                                   &config.padding,
+                                                                   &config.dilation) {
+                                    let o = output_idx.iter()
+                                        .zip(&output_stride[2..])
+                                        .fold(output_offset, |acc, (idx, s)| acc + idx * s);
+                                    acc = acc + output_diff[o] * input[input_offset + i];
+                                }
+                                if !next_index(&mut output_idx, &output_dim[2..]) {
+                                    break;
+                                }
+                            }
+                        }
+                        let f = filter_idx.iter()
+                            .zip(&filter_stride[2..])
+                            .fold(c * filter_stride[1], |acc, (idx, s)| acc + idx * s);
+                        filter_diff[f] = acc;
+                        if !next_index(&mut filter_idx, &filter_dim[2..]) {
+                            break;
+                        }
+                    }
+                }
+            });
+        });
+
+        Ok(())
     }
 
     fn convolution_grad_data(&self,
@@ -420,23 +728,142 @@ impl<T> Convolution<T> for Backend<Native>
                              workspace: &mut SharedTensor<u8>,
                              config: &Self::CC)
                              -> Result<(), Error> {
-        unimplemented!()
+        let dev = self.device();
+
+        let filter_dim = filter.desc().clone();
+        let filter_stride = filter_dim.default_stride();
+        let filter = filter.read(dev)?.as_slice::<T>();
+
+        let output_dim = x_diff.desc().clone();
+        let output_stride = output_dim.default_stride();
+        let output_diff = x_diff.read(dev)?.as_slice::<T>();
+
+        let input_dim = result_diff.desc().clone();
+        let input_stride = input_dim.default_stride();
+        let input_diff = result_diff.write_only(dev)?.as_mut_slice::<T>();
+        for x in input_diff.iter_mut() {
+            *x = Default::default();
+        }
+
+        // sanity check
+        assert!(input_dim[0] == output_dim[0]);
+        assert!(filter_dim[0] == output_dim[1]);
+        assert!(input_dim[1] == filter_dim[1] * config.groups);
+
+        // every group maps `cg` input channels to `kg` output channels
+        let (cg, kg) = (filter_dim[1], filter_dim[0] / config.groups);
+
+        if let ConvBackwardDataAlgo::GEMM = config.algo_bwd_data {
+            // dx[n, g] = col2im(w[g]^T * dy[n, g])
+            let (cf, os) = (filter_stride[0], output_stride[1]);
+            let mut group_dim = input_dim[1..].to_vec();
+            group_dim[0] = cg;
+            let (one, zero) = (native_scalar(dev, T::one())?, native_scalar(dev, T::zero())?);
+            let mut w = SharedTensor::<T>::new(&[kg, cf]);
+            let mut dy = SharedTensor::<T>::new(&[kg, os]);
+            let mut col = SharedTensor::<T>::new(&[cf, os]);
+            for g in 0..config.groups {
+                write_native(dev, &mut w, &filter[g * kg * cf..])?;
+                for (dx, output_diff) in input_diff.chunks_mut(input_stride[0]).zip(output_diff.chunks(output_stride[0])) {
+                    write_native(dev, &mut dy, &output_diff[g * kg * os..])?;
+                    self.gemm(&one, Transpose::Trans, &w, Transpose::NoTrans, &dy, &zero, &mut col)?;
+                    let col_data = col.read(dev)?.as_slice::<T>();
+                    dev.thread_pool().install(|| {
+                        col2im(col_data, &group_dim, &input_stride[1..], &filter_dim[2..], &output_dim[2..],
+                               &config.stride, &config.padding, &config.dilation, &mut dx[g * cg * input_stride[1]..])
+                    });
+                }
+            }
+            return Ok(());
+        }
+
+        let spatial_dims = input_dim.len() - 2;
+
+        // dx[n, g * cg + c, o * stride + f * dilation - padding] += dy[n, k, o] * w[k, c, f]
+        // the batch items are independent of each other
+        dev.thread_pool().install(|| {
+            input_diff.par_chunks_mut(input_stride[0])
+                .zip(output_diff.par_chunks(output_stride[0]))
+                .for_each(|(input_diff, output_diff)| {
+                    let mut output_idx = vec![0; spatial_dims];
+                    let mut filter_idx = vec![0; spatial_dims];
+                    for k in 0..filter_dim[0] {
+                        let output_offset = k * output_stride[1];
+                        let group_offset = k / kg * cg * input_stride[1];
+                        for o in output_idx.iter_mut() {
+                            *o = 0;
+                        }
+                        loop {
+                            let o = output_idx.iter()
+                                .zip(&output_stride[2..])
+                                .fold(output_offset, |acc, (idx, s)| acc + idx * s);
+                            let dy = output_diff[o];
+                            for c in 0..cg {
+                                let input_offset = group_offset + c * input_stride[1];
+                                let filter_offset = k * filter_stride[0] + c * filter_stride[1];
+                                for f in filter_idx.iter_mut() {
+                                    *f = 0;
+                                }
+                                loop {
+                                    if let Some(i) = window_input_offset(&output_idx,
+                                                                       &filter_idx,
+                                                                       &input_dim[2..],
+                                                                       &input_stride[2..],
+                                                                       &config.stride,
+                                                                       &config.padding,
+                                                                       &config.dilation) {
+                                        let f = filter_idx.iter()
+                                            .zip(&filter_stride[2..])
+                                            .fold(filter_offset, |acc, (idx, s)| acc + idx * s);
+                                        input_diff[input_offset + i] = input_diff[input_offset + i] +
+                                                                       dy * filter[f];
+                                    }
+                                    if !next_index(&mut filter_idx, &filter_dim[2..]) {
+                                        break;
+                                    }
+                                }
+                            }
+                            if !next_index(&mut output_idx, &output_dim[2..]) {
+                                break;
+                            }
+                        }
+                    }
+                });
+        });
+
+        Ok(())
     }
 }
 
-
 impl<T> Pooling<T> for Backend<Native>
-    where T: Add<T, Output = T> + Mul<T, Output = T> + Default + Copy + PartialOrd + Bounded
+    where T: Float + Default + Bounded + Send + Sync
 {
-    fn new_pooling_config(&self,
-                          window: &[i32],
-                          stride: &[i32],
-                          padding: &[i32])
-                          -> Result<Self::CPOOL, Error> {
+    fn new_pooling_config_with_avg_mode(&self,
+                                        window: &[i32],
+                                        stride: &[i32],
+                                        padding: &[i32],
+                                        avg_mode: PoolingAvgMode)
+                                        -> Result<Self::CPOOL, Error> {
         Ok(helper::PoolingConfig {
                window: window.to_vec(),
                stride: stride.to_vec(),
                padding: padding.to_vec(),
+               avg_mode: avg_mode,
+               adaptive: false,
+           })
+    }
+
+    fn new_adaptive_pooling_config(&self, input: &[usize], output: &[usize]) -> Result<Self::CPOOL, Error> {
+        if input.len() != output.len() || output.contains(&0) {
+            return Err(PluginError::Operation("Adaptive pooling requires a positive output size per input dimension").into());
+        }
+        // the windows are derived from the actual tensors on every call
+        Ok(helper::PoolingConfig {
+               window: vec![],
+               stride: vec![],
+               padding: vec![],
+               avg_mode: PoolingAvgMode::ExcludePadding,
+               adaptive: true,
            })
     }
 
@@ -447,179 +874,32 @@ impl<T> Pooling<T> for Backend<Native>
                    -> Result<(), Error> {
         let dev = self.device();
 
-        let input_dim = x.desc(); // [4, 4, 4, 4]
-        let input = x.read(dev)
-            .unwrap()
-            .as_slice::<T>();
-        let input_stride = input_dim.default_stride(); // [64, 16, 4, 1];
-
-        let output_dim = result.desc().clone(); // [4,4,2,2]
-        // this is ok, we only read parts we already wrote
-        let output = result
-            .write_only(dev)
-            .unwrap()
-            .as_mut_slice::<T>();
-        let output_stride = output_dim.default_stride(); // [16, 4, 2, 1]
-        {
-            for o in output.iter_mut() {
-                *o = Default::default();
-            }
-        }
-
-        fn max_pooling_<T>(input: &[T],
-                           input_stride: &[usize],
-                           input_dim: &[usize],
-                           input_offset: usize,
-                           input_idx_base: &[usize],
-                           window: &[i32],
-                           padding: &[i32],
-                           depth: usize,
-                           depth_end: usize,
-                           current_max: Option<T>)
-                           -> T
-            where T: Add<T, Output = T> + Mul<T, Output = T> + Default + Copy + PartialOrd + Bounded
-        {
-            let mut current_max = current_max.unwrap_or(T::min_value());
-
-            let p = padding[0] as usize;
-            let input_idx_end = input_dim[0] + 2 * p;
-
-            for window_idx in 0..window[0] {
-                let input_idx = input_idx_base[0] + window_idx as usize;
-
-                let v = if input_idx < p || input_idx + 1 > input_idx_end - p {
-                    T::min_value()
-                } else {
-                    let i_mem_offset = input_offset + (input_idx - p) * input_stride[0];
-                    if depth + 1 >= depth_end {
-                        input[i_mem_offset]
-                    } else {
-                        max_pooling_(input,
-                                     &input_stride[1..],
-                                     &input_dim[1..],
-                                     i_mem_offset,
-                                     &input_idx_base[1..],
-                                     &window[1..],
-                                     &padding[1..],
-                                     depth + 1,
-                                     depth_end,
-                                     None)
-                    }
-                };
-                // TODO: Handle NAN, inf and so on
-                current_max = if current_max >= v {
-                    current_max
-                } else if current_max < v {
-                    v
-                } else {
-                //TODO honour the configuration to pass on NaN or not, see cudnn API
-                    panic!("NaN")
-                };
-            }
-            current_max
-        }
-
-        fn recurse<T>(input: &[T],
-                      input_stride: &[usize],
-                      input_dim: &[usize],
-                      top_input_offset: usize,
-                      input_offset: usize,
-                      input_idx_base: &mut [usize],
-                      window: &[i32],
-                      depth: usize,
-                      stride: &[i32],
-                      padding: &[i32],
-                      output: &mut [T],
-                      output_stride: &[usize],
-                      output_dim: &[usize],
-                      output_offset: usize)
-            where T: Add<T, Output = T> + Mul<T, Output = T> + Default + Copy + PartialOrd + Bounded
-        {
-            let p = padding[depth] as usize; // 0
-            let w = window[depth] as usize; // 2
-
-            for output_idx in 0..output_dim[0] {
-                let input_idx = output_idx * stride[0] as usize;
-                input_idx_base[depth] = input_idx;
-                // memory offset of linear input_idx
-                let input_offset = input_offset + input_idx * input_stride[depth];
-                let output_offset = output_offset + output_idx * output_stride[0];
-                //println!("input_offset {} <- output_offset {}", input_offset, output_offset);
-
-                if depth + 1 < input_dim.len() {
-                    recurse(input,
-                            input_stride,
-                            input_dim,
-                            top_input_offset,
-                            input_offset,
-                            input_idx_base,
-                            window,
-                            depth + 1,
-                            &stride[1..],
-                            padding,
-                            output,
-                            &output_stride[1..],
-                            &output_dim[1..],
-                            output_offset);
-                } else {
-                    let v = max_pooling_(input,
-                                         input_stride,
-                                         input_dim,
-                                         top_input_offset,
-                                         &input_idx_base[..],
-                                         window,
-                                         padding,
-                                         0,
-                                         input_dim.len(),
-                                         None);
-                    output[output_offset] = v;
-                }
-            }
-        }
-
-
-        let mut input_idx = Vec::new();
-        input_idx.resize(input_dim.len() - 2, 0);
-        let mut output_idx = Vec::new();
-        output_idx.resize(output_dim.len(), 0);
-
-        let window = &config.window[..];
-        let stride = &config.stride[..];
-        let padding = &config.padding[..];
-        // do everything for each batch
-        for batch in 0..input_dim[0] {
-            // iterate over the batches!
-            let input_offset = batch * input_stride[0];
-            let output_offset = batch * output_stride[0];
-
-            // iterate over the chanels
-            for d1 in 0..input_dim[1] {
-                let input_offset = input_offset + d1 * input_stride[1];
-                let output_offset = output_offset + d1 * output_stride[1];
-                // pass on the remaining dimensions (no batches, no channels, thus [2..]
-                recurse(input,
-                        &input_stride[2..],
-                        &input_dim[2..],
-                        input_offset,
-                        input_offset,
-                        &mut input_idx,
-                        &window,
-                        0,
-                        &stride,
-                        &padding,
-                        output,
-                        &output_stride[2..],
-                        &output_dim[2..],
-                        output_offset);
-            }
-        }
+        let input_dim = x.desc().clone();
+        let input = x.read(dev)?.as_slice::<T>();
+        let output_dim = result.desc().clone();
+        let output = result.write_only(dev)?.as_mut_slice::<T>();
+
+        // do everything for each batch and channel
+        let input_plane = input_dim.default_stride()[1];
+        let output_plane = output_dim.default_stride()[1];
+        dev.thread_pool().install(|| {
+            output.par_chunks_mut(output_plane)
+                .zip(input.par_chunks(input_plane))
+                .for_each(|(output, input)| {
+                    for_each_pooling_window(&input_dim, &output_dim, config, |o, window, _| {
+                        // padding never wins
+                        output[o] = window.iter().fold(<T as Bounded>::min_value(), |max, &i| {
+                            if input[i] > max { input[i] } else { max }
+                        });
+                    });
+                });
+        });
 
         Ok(())
     }
 
     // x, x_diff are known outputs of the forward propagation
     // result is the previous layer which derivate we want to know
-    // FIXME verify
     fn pooling_max_grad(&self,
                         x: &SharedTensor<T>,
                         x_diff: &SharedTensor<T>,
@@ -627,190 +907,40 @@ impl<T> Pooling<T> for Backend<Native>
                         result_diff: &mut SharedTensor<T>,
                         config: &Self::CPOOL)
                         -> Result<(), Error> {
-
         let dev = self.device();
 
-        let input_dim = x.desc(); // []
-        println!("x dims {:?}", input_dim);
-        let input = x.read(dev)
-            .unwrap()
-            .as_slice::<T>();
-        let input_stride = input_dim.default_stride(); // [];
-
-        let x_diff_dim = x_diff.desc(); // []
-        let x_diff = x_diff.read(dev).unwrap().as_slice::<T>();
-        println!("x_diff dims {:?}", x_diff_dim);
-
-        let output_dim = result_diff.desc().clone(); // []
-        println!("result dims {:?}", result.desc());
-        println!("result_diff dims {:?}", output_dim);
-
-        // this is ok, we only read parts we already wrote
-        let output = result_diff
-            .write_only(dev)
-            .unwrap()
-            .as_mut_slice::<T>();
-        let output_stride = output_dim.default_stride(); // []
-        {
-            for o in output.iter_mut() {
-                *o = Default::default();
-            }
-        }
-
-        fn max_pooling_<T>(input: &[T],
-                           input_stride: &[usize],
-                           input_dim: &[usize],
-                           input_offset: usize,
-                           input_idx_base: &[usize],
-                           window: &[i32],
-                           padding: &[i32],
-                           depth: usize,
-                           depth_end: usize,
-                           current_max: Option<T>,
-                           current_max_index: Option<usize>)
-                           -> (T, usize)
-            where T: Add<T, Output = T> + Mul<T, Output = T> + Default + Copy + PartialOrd + Bounded
-        {
-            let mut current_max = (current_max.unwrap_or(T::min_value()), current_max_index.unwrap_or(0usize));
-
-            let p = padding[0] as usize;
-            let input_idx_end = input_dim[0] + 2 * p;
-
-            for window_idx in 0..window[0] {
-                let input_idx = input_idx_base[0] + window_idx as usize;
-
-                let (v, v_index) = if input_idx < p || input_idx + 1 > input_idx_end - p {
-                    (T::min_value(), 0usize)
-                } else {
-                    let i_mem_offset = input_offset + (input_idx - p) * input_stride[0];
-                    if depth + 1 >= depth_end {
-                        (input[i_mem_offset], i_mem_offset)
-                    } else {
-                        max_pooling_(input,
-                                     &input_stride[1..],
-                                     &input_dim[1..],
-                                     i_mem_offset,
-                                     &input_idx_base[1..],
-                                     &window[1..],
-                                     &padding[1..],
-                                     depth + 1,
-                                     depth_end,
-                                     None,
-                                     None)
-                    }
-                };
-                current_max = if current_max.0 >= v {
-                    current_max
-                } else if current_max.0 < v {
-                    (v, v_index)
-                } else {
-                //TODO honour the configuration to pass on NaN or not, see cudnn API
-                    panic!("NaN")
-                };
-            }
-            current_max
-        }
-
-        fn recurse<T>(input: &[T],
-                      input_stride: &[usize],
-                      input_dim: &[usize],
-                      top_input_offset: usize,
-                      input_offset: usize,
-                      input_idx_base: &mut [usize],
-                      window: &[i32],
-                      depth: usize,
-                      stride: &[i32],
-                      padding: &[i32],
-                      output: &mut [T],
-                      output_stride: &[usize],
-                      output_dim: &[usize],
-                      output_offset: usize,
-                      dx: &[T])
-            where T: Add<T, Output = T> + Mul<T, Output = T> + Default + Copy + PartialOrd + Bounded
-        {
-            let p = padding[depth] as usize; // 0
-            let w = window[depth] as usize; // 2
-
-            for output_idx in 0..output_dim[0] {
-                let input_idx = output_idx * stride[0] as usize;
-                input_idx_base[depth] = input_idx;
-                // memory offset of linear input_idx
-                let input_offset = input_offset + input_idx * input_stride[depth];
-                let output_offset = output_offset + output_idx * output_stride[0];
-                //println!("input_offset {} <- output_offset {}", input_offset, output_offset);
-
-                if depth + 1 < input_dim.len() {
-                    recurse(input,
-                            input_stride,
-                            input_dim,
-                            top_input_offset,
-                            input_offset,
-                            input_idx_base,
-                            window,
-                            depth + 1,
-                            &stride[1..],
-                            padding,
-                            output,
-                            &output_stride[1..],
-                            &output_dim[1..],
-                            output_offset,
-                            dx);
-                } else {
-                    let (val, index) = max_pooling_(input,
-                                         input_stride,
-                                         input_dim,
-                                         top_input_offset,
-                                         &input_idx_base[..],
-                                         window,
-                                         padding,
-                                         0,
-                                         input_dim.len(),
-                                         None, None);
-                    // if the stride is 1 and the size is i.e. multiple outputs of the forward propagation
-                    // can map back to one input
-                    // TODO sum up
-                    output[index] = dx[0]; // FIXME we need a second index for this shit
-                }
-            }
+        let output_dim = x_diff.desc().clone();
+        let output_diff = x_diff.read(dev)?.as_slice::<T>();
+        let input_dim = result.desc().clone();
+        let input = result.read(dev)?.as_slice::<T>();
+        let input_diff = result_diff.write_only(dev)?.as_mut_slice::<T>();
+        for x in input_diff.iter_mut() {
+            *x = Default::default();
         }
 
+        let input_plane = input_dim.default_stride()[1];
+        let output_plane = output_dim.default_stride()[1];
+        dev.thread_pool().install(|| {
+            input_diff.par_chunks_mut(input_plane)
+                .zip(input.par_chunks(input_plane))
+                .zip(output_diff.par_chunks(output_plane))
+                .for_each(|((input_diff, input), output_diff)| {
+                    for_each_pooling_window(&input_dim, &output_dim, config, |o, window, _| {
+                        // the gradient is routed to the first maximum within the window
+                        let mut max: Option<usize> = None;
+                        for &i in window {
+                            max = match max {
+                                Some(m) if input[m] >= input[i] => max,
+                                _ => Some(i),
+                            };
+                        }
+                        if let Some(i) = max {
+                            input_diff[i] = input_diff[i] + output_diff[o];
+                        }
+                    });
+                });
+        });
 
-        let mut input_idx = Vec::new();
-        input_idx.resize(input_dim.len() - 2, 0);
-        let mut output_idx = Vec::new();
-        output_idx.resize(output_dim.len(), 0);
-
-        let window = &config.window[..];
-        let stride = &config.stride[..];
-        let padding = &config.padding[..];
-        // do everything for each batch
-        for batch in 0..input_dim[0] {
-            // iterate over the batches!
-            let input_offset = batch * input_stride[0];
-            let output_offset = batch * output_stride[0];
-
-            // iterate over the chanels
-            for d1 in 0..input_dim[1] {
-                let input_offset = input_offset + d1 * input_stride[1];
-                let output_offset = output_offset + d1 * output_stride[1];
-                // pass on the remaining dimensions (no batches, no channels, thus [2..]
-                recurse(input,
-                        &input_stride[2..],
-                        &input_dim[2..],
-                        input_offset,
-                        input_offset,
-                        &mut input_idx,
-                        &window,
-                        0,
-                        &stride,
-                        &padding,
-                        output,
-                        &output_stride[2..],
-                        &output_dim[2..],
-                        output_offset,
-                        x_diff);
-            }
-        }
         Ok(())
     }
 
@@ -819,9 +949,39 @@ impl<T> Pooling<T> for Backend<Native>
                    result: &mut SharedTensor<T>,
                    config: &Self::CPOOL)
                    -> Result<(), Error> {
-        return Err(Error::Plugin(PluginError::Plugin("Unimplemented.")));
+        let dev = self.device();
+
+        let input_dim = x.desc().clone();
+        let input = x.read(dev)?.as_slice::<T>();
+        let output_dim = result.desc().clone();
+        let output = result.write_only(dev)?.as_mut_slice::<T>();
+
+        let input_plane = input_dim.default_stride()[1];
+        let output_plane = output_dim.default_stride()[1];
+        dev.thread_pool().install(|| {
+            output.par_chunks_mut(output_plane)
+                .zip(input.par_chunks(input_plane))
+                .for_each(|(output, input)| {
+                    for_each_pooling_window(&input_dim, &output_dim, config, |o, window, window_size| {
+                        let count = match config.avg_mode {
+                            PoolingAvgMode::IncludePadding => window_size,
+                            PoolingAvgMode::ExcludePadding => window.len(),
+                        };
+                        let sum = window.iter().fold(T::zero(), |acc, &i| acc + input[i]);
+                        output[o] = if count > 0 {
+                            sum / T::from(count).unwrap()
+                        } else {
+                            T::zero()
+                        };
+                    });
+                });
+        });
+
+        Ok(())
     }
 
+    // x, x_diff are known outputs of the forward propagation
+    // result is the previous layer which derivate we want to know
     fn pooling_avg_grad(&self,
                         x: &SharedTensor<T>,
                         x_diff: &SharedTensor<T>,
@@ -829,73 +989,1251 @@ impl<T> Pooling<T> for Backend<Native>
                         result_diff: &mut SharedTensor<T>,
                         config: &Self::CPOOL)
                         -> Result<(), Error> {
-        return Err(Error::Plugin(PluginError::Plugin("Unimplemented.")));
-    }
-}
+        let dev = self.device();
 
-impl<T> Rnn<T> for Backend<Native>
-    where T: Float + Default + Copy + PartialOrd + Bounded {
-    fn new_rnn_config(&self, src: &SharedTensor<T>, dropout_probability: Option<f32>, dropout_seed: Option<u64>, sequence_length: i32, network_mode: RnnNetworkMode, input_mode: RnnInputMode, direction_mode: DirectionMode, algorithm: RnnAlgorithm, hidden_size: i32, num_layers: i32, batch_size: i32) -> Result<Self::CRNN, Error> {
-        // TODO: Implement Config to hold parameters regarding the RNN
-        unimplemented!()
-    }
+        let output_dim = x_diff.desc().clone();
+        let output_diff = x_diff.read(dev)?.as_slice::<T>();
+        let input_dim = result_diff.desc().clone();
+        let input_diff = result_diff.write_only(dev)?.as_mut_slice::<T>();
+        for x in input_diff.iter_mut() {
+            *x = T::zero();
+        }
 
-    fn generate_rnn_weight_description(
-        &self,
-        rnn_config: &Self::CRNN,
-        batch_size: i32,
-        input_size: i32,
-    ) -> Result<Vec<usize>, Error> {
-        // This will end up being the tensor descriptor for the weights associated with the RNN pass
-        unimplemented!()
-    }
+        let input_plane = input_dim.default_stride()[1];
+        let output_plane = output_dim.default_stride()[1];
+        dev.thread_pool().install(|| {
+            input_diff.par_chunks_mut(input_plane)
+                .zip(output_diff.par_chunks(output_plane))
+                .for_each(|(input_diff, output_diff)| {
+                    for_each_pooling_window(&input_dim, &output_dim, config, |o, window, window_size| {
+                        let count = match config.avg_mode {
+                            PoolingAvgMode::IncludePadding => window_size,
+                            PoolingAvgMode::ExcludePadding => window.len(),
+                        };
+                        if count > 0 {
+                            let dy = output_diff[o] / T::from(count).unwrap();
+                            for &i in window {
+                                input_diff[i] = input_diff[i] + dy;
+                            }
+                        }
+                    });
+                });
+        });
 
-    fn rnn_forward(
-        &self,
-        src: &SharedTensor<T>,
-        output: &mut SharedTensor<T>,
-        rnn_config: &Self::CRNN,
-        weight: &SharedTensor<T>,
-        workspace: &mut SharedTensor<u8>,
-    ) -> Result<(), Error> {
-        // TODO: Implement RNN Forward Pass
-        unimplemented!()
+        Ok(())
     }
+}
 
-    fn rnn_backward_data(&self,
-                         src: &SharedTensor<T>,
-                         src_gradient: &mut SharedTensor<T>,
-                         output: &SharedTensor<T>,
-                         output_gradient: &SharedTensor<T>,
-                         rnn_config: &Self::CRNN,
-                         weight: &SharedTensor<T>,
-                         workspace: &mut SharedTensor<u8>)
-                         -> Result<(), Error> {
-        // TODO: Implement Backward Pass for RNN for the Input
-        unimplemented!()
+/// Computes the normalization term `k + alpha / n * sum(x^2)` over the
+/// channels in the window of every element of the `[batch, channels, ...]`
+/// tensor `x`.
+fn lrn_scale<T: Float>(x: &[T], dims: &TensorDesc, config: &helper::NormalizationConfig) -> Result<Vec<T>, Error> {
+    if dims.len() < 2 {
+        return Err(PluginError::Operation("LRN requires at least a batch and a channel dimension").into());
     }
-
-    fn rnn_backward_weights(&self,
-                            src: &SharedTensor<T>,
-                            output: &SharedTensor<T>,
-                            filter: &mut SharedTensor<T>,
-                            rnn_config: &Self::CRNN,
-                            workspace: &mut SharedTensor<u8>)
-                            -> Result<(), Error> {
-        // TODO: Implement Backward Pass with respect to Weights
-        unimplemented!()
+    let channels = dims[1];
+    let inner: usize = dims[2..].iter().product();
+    let n = config.n as usize;
+    let alpha = T::from(config.alpha / config.n as f64).unwrap();
+    let k = T::from(config.k).unwrap();
+
+    let mut scale = vec![k; x.len()];
+    for (x, scale) in x.chunks(channels * inner).zip(scale.chunks_mut(channels * inner)) {
+        for c in 0..channels {
+            let first = c.saturating_sub((n - 1) / 2);
+            let last = usize::min(c + n / 2, channels - 1);
+            for i in 0..inner {
+                let sum = (first..=last).fold(T::zero(), |acc, c| acc + x[c * inner + i] * x[c * inner + i]);
+                scale[c * inner + i] = k + alpha * sum;
+            }
+        }
     }
+    Ok(scale)
 }
 
-#[cfg(feature = "native")]
-impl<T> Dropout<T> for Backend<Native>
-    where T: Float + Add<T, Output = T> + Mul<T, Output = T> + Default + Copy + PartialOrd + Bounded
+impl<T> LRN<T> for Backend<Native>
+    where T: Float + Default
 {
-    fn new_dropout_config(&self,
-                      probability: f32,
-                      seed: u64,
-                      )
-                      -> Result<Self::CDROP, Error> {
+    fn new_lrn_config(&self,
+                      n: u32,
+                      alpha: f64,
+                      beta: f64,
+                      k: f64)
+                      -> Result<Self::CLRN, Error> {
+        if n == 0 {
+            return Err(PluginError::Operation("LRN window size must be positive").into());
+        }
+        Ok(helper::NormalizationConfig {
+            n: n,
+            alpha: alpha,
+            beta: beta,
+            k: k,
+        })
+    }
+
+    fn lrn(&self,
+           x: &SharedTensor<T>,
+           result: &mut SharedTensor<T>,
+           config: &Self::CLRN)
+           -> Result<(), Error> {
+        let dev = self.device();
+        let dims = x.desc().clone();
+        let input = x.read(dev)?.as_slice::<T>();
+        let output = result.write_only(dev)?.as_mut_slice::<T>();
+        lens_eq(input, output)?;
+
+        let beta = T::from(config.beta).unwrap();
+        let scale = lrn_scale(input, &dims, config)?;
+        for ((y, &x), &s) in output.iter_mut().zip(input).zip(&scale) {
+            *y = x * s.powf(-beta);
+        }
+        Ok(())
+    }
+
+    // x, x_diff are known outputs of the forward propagation
+    // result is the previous layer which derivate we want to know
+    fn lrn_grad(&self,
+                x: &SharedTensor<T>,
+                x_diff: &SharedTensor<T>,
+                result: &SharedTensor<T>,
+                result_diff: &mut SharedTensor<T>,
+                config: &Self::CLRN)
+                -> Result<(), Error> {
+        let dev = self.device();
+        let dims = result.desc().clone();
+        let output_diff = x_diff.read(dev)?.as_slice::<T>();
+        let input = result.read(dev)?.as_slice::<T>();
+        let input_diff = result_diff.write_only(dev)?.as_mut_slice::<T>();
+        lens_eq(input, output_diff)?;
+        lens_eq(input, input_diff)?;
+
+        let channels = dims[1];
+        let inner: usize = dims[2..].iter().product();
+        let n = config.n as usize;
+        let beta = T::from(config.beta).unwrap();
+        let factor = T::from(2.0 * config.alpha * config.beta / config.n as f64).unwrap();
+        let scale = lrn_scale(input, &dims, config)?;
+
+        // dy * x * s^(-beta - 1) of every element, to be summed up over the
+        // windows containing a channel
+        let weighted: Vec<T> = output_diff.iter()
+            .zip(input)
+            .zip(&scale)
+            .map(|((&dy, &x), &s)| dy * x * s.powf(-beta - T::one()))
+            .collect();
+
+        let batch_size = channels * inner;
+        for b in 0..input.len() / batch_size {
+            for c in 0..channels {
+                let first = c.saturating_sub(n / 2);
+                let last = usize::min(c + (n - 1) / 2, channels - 1);
+                for i in 0..inner {
+                    let idx = b * batch_size + c * inner + i;
+                    let sum = (first..=last)
+                        .fold(T::zero(), |acc, c| acc + weighted[b * batch_size + c * inner + i]);
+                    input_diff[idx] = output_diff[idx] * scale[idx].powf(-beta) - factor * input[idx] * sum;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks the shapes of a batch normalization and returns the number of
+/// channels and the size of the spatial dimensions of `x`.
+fn batch_norm_dims<T>(x: &TensorDesc, params: &[&[T]]) -> Result<(usize, usize), Error> {
+    if x.len() < 2 {
+        return Err(PluginError::Operation("Batch normalization requires at least a batch and a channel dimension").into());
+    }
+    if x.size() == 0 {
+        return Err(PluginError::Operation("Batch normalization requires a non-empty input").into());
+    }
+    let channels = x[1];
+    if params.iter().any(|p| p.len() != channels) {
+        return Err(PluginError::Operation("Batch normalization parameters must hold one value per channel").into());
+    }
+    Ok((channels, x[2..].iter().product()))
+}
+
+/// Sums up `f(c, i)` over the indices `i` of all elements of channel `c` in a
+/// `[batch, channels, inner]` tensor of `len` elements, for every channel.
+fn channel_sums<T, F>(pool: &ThreadPool, len: usize, channels: usize, inner: usize, f: F) -> Vec<T>
+    where T: Float + Send,
+          F: Fn(usize, usize) -> T + Sync
+{
+    pool.install(|| {
+        (0..channels).into_par_iter().map(|c| {
+            let mut sum = T::zero();
+            for start in (c * inner..len).step_by(channels * inner) {
+                for i in start..start + inner {
+                    sum = sum + f(c, i);
+                }
+            }
+            sum
+        }).collect()
+    })
+}
+
+/// Sets every element `i` of channel `c` in the `[batch, channels, inner]`
+/// tensor `dst` to `f(c, i)`.
+fn channel_map<T, F>(pool: &ThreadPool, dst: &mut [T], channels: usize, inner: usize, f: F)
+    where T: Float + Send,
+          F: Fn(usize, usize) -> T + Sync
+{
+    pool.install(|| {
+        dst.par_chunks_mut(inner)
+            .with_min_len(usize::max(1, PARALLEL_CHUNK / inner))
+            .enumerate()
+            .for_each(|(j, plane)| {
+                for (k, y) in plane.iter_mut().enumerate() {
+                    *y = f(j % channels, j * inner + k);
+                }
+            })
+    })
+}
+
+impl<T> BatchNormalization<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn new_batch_normalization_config(&self, epsilon: f64, momentum: f64)
+                                      -> Result<Self::CBN, Error> {
+        if epsilon < 0.0 {
+            return Err(PluginError::Operation("Batch normalization epsilon must not be negative").into());
+        }
+        if !(0.0..=1.0).contains(&momentum) {
+            return Err(PluginError::Operation("Batch normalization momentum must be within [0, 1]").into());
+        }
+        Ok(helper::BatchNormalizationConfig { epsilon, momentum })
+    }
+
+    fn batch_normalization_training(&self,
+                                    x: &SharedTensor<T>,
+                                    scale: &SharedTensor<T>,
+                                    bias: &SharedTensor<T>,
+                                    running_mean: &mut SharedTensor<T>,
+                                    running_variance: &mut SharedTensor<T>,
+                                    saved_mean: &mut SharedTensor<T>,
+                                    saved_inv_variance: &mut SharedTensor<T>,
+                                    result: &mut SharedTensor<T>,
+                                    config: &Self::CBN)
+                                    -> Result<(), Error> {
+        let dev = self.device();
+        let pool = dev.thread_pool();
+        let dims = x.desc().clone();
+        let input = x.read(dev)?.as_slice::<T>();
+        let gamma = scale.read(dev)?.as_slice::<T>();
+        let beta = bias.read(dev)?.as_slice::<T>();
+        let (channels, inner) = batch_norm_dims(&dims, &[gamma, beta])?;
+        let output = result.write_only(dev)?.as_mut_slice::<T>();
+        lens_eq(input, output)?;
+
+        let m = T::from(input.len() / channels).unwrap();
+        let mean: Vec<T> = channel_sums(pool, input.len(), channels, inner, |_, i| input[i])
+            .into_iter()
+            .map(|sum| sum / m)
+            .collect();
+        let variance: Vec<T> = channel_sums(pool, input.len(), channels, inner, |c, i| (input[i] - mean[c]).powi(2))
+            .into_iter()
+            .map(|sum| sum / m)
+            .collect();
+        let epsilon = T::from(config.epsilon).unwrap();
+        let inv_std: Vec<T> = variance.iter().map(|&var| (var + epsilon).sqrt().recip()).collect();
+
+        channel_map(pool, output, channels, inner, |c, i| {
+            gamma[c] * (input[i] - mean[c]) * inv_std[c] + beta[c]
+        });
+
+        // the running variance is an estimate of the population variance
+        let momentum = T::from(config.momentum).unwrap();
+        let correction = if m > T::one() { m / (m - T::one()) } else { T::one() };
+        let run_mean = running_mean.read_write(dev)?.as_mut_slice::<T>();
+        let run_var = running_variance.read_write(dev)?.as_mut_slice::<T>();
+        lens_eq(run_mean, &mean)?;
+        lens_eq(run_var, &variance)?;
+        for c in 0..channels {
+            run_mean[c] = (T::one() - momentum) * run_mean[c] + momentum * mean[c];
+            run_var[c] = (T::one() - momentum) * run_var[c] + momentum * variance[c] * correction;
+        }
+
+        let save_mean = saved_mean.write_only(dev)?.as_mut_slice::<T>();
+        let save_inv_var = saved_inv_variance.write_only(dev)?.as_mut_slice::<T>();
+        lens_eq(save_mean, &mean)?;
+        lens_eq(save_inv_var, &inv_std)?;
+        save_mean.copy_from_slice(&mean);
+        save_inv_var.copy_from_slice(&inv_std);
+        Ok(())
+    }
+
+    fn batch_normalization_inference(&self,
+                                     x: &SharedTensor<T>,
+                                     scale: &SharedTensor<T>,
+                                     bias: &SharedTensor<T>,
+                                     mean: &SharedTensor<T>,
+                                     variance: &SharedTensor<T>,
+                                     result: &mut SharedTensor<T>,
+                                     config: &Self::CBN)
+                                     -> Result<(), Error> {
+        let dev = self.device();
+        let dims = x.desc().clone();
+        let input = x.read(dev)?.as_slice::<T>();
+        let gamma = scale.read(dev)?.as_slice::<T>();
+        let beta = bias.read(dev)?.as_slice::<T>();
+        let mean = mean.read(dev)?.as_slice::<T>();
+        let variance = variance.read(dev)?.as_slice::<T>();
+        let (channels, inner) = batch_norm_dims(&dims, &[gamma, beta, mean, variance])?;
+        let output = result.write_only(dev)?.as_mut_slice::<T>();
+        lens_eq(input, output)?;
+
+        let epsilon = T::from(config.epsilon).unwrap();
+        let inv_std: Vec<T> = variance.iter().map(|&var| (var + epsilon).sqrt().recip()).collect();
+        channel_map(dev.thread_pool(), output, channels, inner, |c, i| {
+            gamma[c] * (input[i] - mean[c]) * inv_std[c] + beta[c]
+        });
+        Ok(())
+    }
+
+    fn batch_normalization_grad(&self,
+                                x: &SharedTensor<T>,
+                                result_diff: &SharedTensor<T>,
+                                scale: &SharedTensor<T>,
+                                saved_mean: &SharedTensor<T>,
+                                saved_inv_variance: &SharedTensor<T>,
+                                x_diff: &mut SharedTensor<T>,
+                                scale_diff: &mut SharedTensor<T>,
+                                bias_diff: &mut SharedTensor<T>,
+                                config: &Self::CBN)
+                                -> Result<(), Error> {
+        let dev = self.device();
+        let pool = dev.thread_pool();
+        let dims = x.desc().clone();
+        let input = x.read(dev)?.as_slice::<T>();
+        let output_diff = result_diff.read(dev)?.as_slice::<T>();
+        let gamma = scale.read(dev)?.as_slice::<T>();
+        let mean = saved_mean.read(dev)?.as_slice::<T>();
+        let inv_std = saved_inv_variance.read(dev)?.as_slice::<T>();
+        let (channels, inner) = batch_norm_dims(&dims, &[gamma, mean, inv_std])?;
+        let input_diff = x_diff.write_only(dev)?.as_mut_slice::<T>();
+        lens_eq(input, output_diff)?;
+        lens_eq(input, input_diff)?;
+
+        let x_hat = |c: usize, i: usize| (input[i] - mean[c]) * inv_std[c];
+        let sum_dy = channel_sums(pool, input.len(), channels, inner, |_, i| output_diff[i]);
+        let sum_dy_x_hat = channel_sums(pool, input.len(), channels, inner, |c, i| output_diff[i] * x_hat(c, i));
+
+        let m = T::from(input.len() / channels).unwrap();
+        channel_map(pool, input_diff, channels, inner, |c, i| {
+            gamma[c] * inv_std[c] / m * (m * output_diff[i] - sum_dy[c] - x_hat(c, i) * sum_dy_x_hat[c])
+        });
+
+        let gamma_diff = scale_diff.write_only(dev)?.as_mut_slice::<T>();
+        let beta_diff = bias_diff.write_only(dev)?.as_mut_slice::<T>();
+        lens_eq(gamma_diff, &sum_dy_x_hat)?;
+        lens_eq(beta_diff, &sum_dy)?;
+        gamma_diff.copy_from_slice(&sum_dy_x_hat);
+        beta_diff.copy_from_slice(&sum_dy);
+        Ok(())
+    }
+}
+
+/// Normalizes every row of `row_len` consecutive elements of `input` to zero
+/// mean and unit variance and applies the affine transform with the parameters
+/// at index `param(i)` for the element `i`.
+///
+/// Stores the mean and inverse standard deviation of every row in `mean` and
+/// `inv_std`.
+fn row_normalization<T, P>(pool: &ThreadPool,
+                           input: &[T],
+                           output: &mut [T],
+                           row_len: usize,
+                           epsilon: T,
+                           gamma: &[T],
+                           beta: &[T],
+                           param: P,
+                           mean: &mut [T],
+                           inv_std: &mut [T])
+    where T: Float + Send + Sync,
+          P: Fn(usize) -> usize + Sync
+{
+    let n = T::from(row_len).unwrap();
+    pool.install(|| {
+        output.par_chunks_mut(row_len)
+            .zip(input.par_chunks(row_len))
+            .zip(mean.par_iter_mut().zip(inv_std.par_iter_mut()))
+            .enumerate()
+            .with_min_len(usize::max(1, PARALLEL_CHUNK / row_len))
+            .for_each(|(r, ((y, x), (mu, s)))| {
+                let m = x.iter().fold(T::zero(), |acc, &v| acc + v) / n;
+                let var = x.iter().fold(T::zero(), |acc, &v| acc + (v - m).powi(2)) / n;
+                let inv = (var + epsilon).sqrt().recip();
+                for (k, (y, &x)) in y.iter_mut().zip(x).enumerate() {
+                    let p = param(r * row_len + k);
+                    *y = gamma[p] * (x - m) * inv + beta[p];
+                }
+                *mu = m;
+                *s = inv;
+            })
+    });
+}
+
+/// Computes the gradients of `row_normalization` w.r.t. the input, written
+/// to `input_diff`, and w.r.t. the affine parameters, written to `gamma_diff`
+/// and `beta_diff`.
+fn row_normalization_grad<T, P>(pool: &ThreadPool,
+                                input: &[T],
+                                output_diff: &[T],
+                                input_diff: &mut [T],
+                                row_len: usize,
+                                gamma: &[T],
+                                param: P,
+                                mean: &[T],
+                                inv_std: &[T],
+                                gamma_diff: &mut [T],
+                                beta_diff: &mut [T])
+    where T: Float + Send + Sync,
+          P: Fn(usize) -> usize + Sync
+{
+    let n = T::from(row_len).unwrap();
+    let min_rows = usize::max(1, PARALLEL_CHUNK / row_len);
+    pool.install(|| {
+        input_diff.par_chunks_mut(row_len)
+            .zip(input.par_chunks(row_len).zip(output_diff.par_chunks(row_len)))
+            .enumerate()
+            .with_min_len(min_rows)
+            .for_each(|(r, (dx, (x, dy)))| {
+                let x_hat = |k: usize| (x[k] - mean[r]) * inv_std[r];
+                let dx_hat = |k: usize| dy[k] * gamma[param(r * row_len + k)];
+                let (sum, sum_x_hat) = (0..row_len).fold((T::zero(), T::zero()), |(sum, sum_x_hat), k| {
+                    (sum + dx_hat(k), sum_x_hat + dx_hat(k) * x_hat(k))
+                });
+                for (k, dx) in dx.iter_mut().enumerate() {
+                    *dx = inv_std[r] / n * (n * dx_hat(k) - sum - x_hat(k) * sum_x_hat);
+                }
+            })
+    });
+
+    let params = gamma.len();
+    let (dgamma, dbeta) = pool.install(|| {
+        input.par_chunks(row_len)
+            .zip(output_diff.par_chunks(row_len))
+            .enumerate()
+            .with_min_len(min_rows)
+            .fold(|| (vec![T::zero(); params], vec![T::zero(); params]), |(mut dgamma, mut dbeta), (r, (x, dy))| {
+                for (k, (&x, &dy)) in x.iter().zip(dy).enumerate() {
+                    let p = param(r * row_len + k);
+                    dgamma[p] = dgamma[p] + dy * (x - mean[r]) * inv_std[r];
+                    dbeta[p] = dbeta[p] + dy;
+                }
+                (dgamma, dbeta)
+            })
+            .reduce(|| (vec![T::zero(); params], vec![T::zero(); params]), |(mut dgamma, mut dbeta), (g, b)| {
+                for p in 0..params {
+                    dgamma[p] = dgamma[p] + g[p];
+                    dbeta[p] = dbeta[p] + b[p];
+                }
+                (dgamma, dbeta)
+            })
+    });
+    gamma_diff.copy_from_slice(&dgamma);
+    beta_diff.copy_from_slice(&dbeta);
+}
+
+/// Returns the number of samples and the number of elements per sample of a
+/// layer normalization of `x` with parameters of shape `params`.
+fn layer_norm_dims(x: &TensorDesc, params: &TensorDesc) -> Result<(usize, usize), Error> {
+    if params.is_empty() || params.len() > x.len() || x[x.len() - params.len()..] != params[..] {
+        return Err(PluginError::Operation("Layer normalization parameters must match the trailing dimensions of the input").into());
+    }
+    if x.size() == 0 {
+        return Err(PluginError::Operation("Layer normalization requires a non-empty input").into());
+    }
+    let row_len = params.size();
+    Ok((x.size() / row_len, row_len))
+}
+
+impl<T> LayerNorm<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn new_layer_normalization_config(&self, epsilon: f64) -> Result<Self::CLN, Error> {
+        if epsilon < 0.0 {
+            return Err(PluginError::Operation("Layer normalization epsilon must not be negative").into());
+        }
+        Ok(helper::LayerNormalizationConfig { epsilon })
+    }
+
+    fn layer_normalization(&self,
+                           x: &SharedTensor<T>,
+                           scale: &SharedTensor<T>,
+                           bias: &SharedTensor<T>,
+                           saved_mean: &mut SharedTensor<T>,
+                           saved_inv_variance: &mut SharedTensor<T>,
+                           result: &mut SharedTensor<T>,
+                           config: &Self::CLN)
+                           -> Result<(), Error> {
+        let dev = self.device();
+        let (rows, row_len) = layer_norm_dims(x.desc(), scale.desc())?;
+        let input = x.read(dev)?.as_slice::<T>();
+        let gamma = scale.read(dev)?.as_slice::<T>();
+        let beta = bias.read(dev)?.as_slice::<T>();
+        let output = result.write_only(dev)?.as_mut_slice::<T>();
+        let mean = saved_mean.write_only(dev)?.as_mut_slice::<T>();
+        let inv_std = saved_inv_variance.write_only(dev)?.as_mut_slice::<T>();
+        lens_eq(input, output)?;
+        lens_eq(gamma, beta)?;
+        if mean.len() != rows || inv_std.len() != rows {
+            return Err(PluginError::Operation("Layer normalization statistics must hold one value per sample").into());
+        }
+
+        row_normalization(dev.thread_pool(), input, output, row_len, T::from(config.epsilon).unwrap(),
+                          gamma, beta, |i| i % row_len, mean, inv_std);
+        Ok(())
+    }
+
+    fn layer_normalization_grad(&self,
+                                x: &SharedTensor<T>,
+                                result_diff: &SharedTensor<T>,
+                                scale: &SharedTensor<T>,
+                                saved_mean: &SharedTensor<T>,
+                                saved_inv_variance: &SharedTensor<T>,
+                                x_diff: &mut SharedTensor<T>,
+                                scale_diff: &mut SharedTensor<T>,
+                                bias_diff: &mut SharedTensor<T>,
+                                config: &Self::CLN)
+                                -> Result<(), Error> {
+        let dev = self.device();
+        let (rows, row_len) = layer_norm_dims(x.desc(), scale.desc())?;
+        let input = x.read(dev)?.as_slice::<T>();
+        let output_diff = result_diff.read(dev)?.as_slice::<T>();
+        let gamma = scale.read(dev)?.as_slice::<T>();
+        let mean = saved_mean.read(dev)?.as_slice::<T>();
+        let inv_std = saved_inv_variance.read(dev)?.as_slice::<T>();
+        let input_diff = x_diff.write_only(dev)?.as_mut_slice::<T>();
+        let gamma_diff = scale_diff.write_only(dev)?.as_mut_slice::<T>();
+        let beta_diff = bias_diff.write_only(dev)?.as_mut_slice::<T>();
+        lens_eq(input, output_diff)?;
+        lens_eq(input, input_diff)?;
+        lens_eq(gamma, gamma_diff)?;
+        lens_eq(gamma, beta_diff)?;
+        if mean.len() != rows || inv_std.len() != rows {
+            return Err(PluginError::Operation("Layer normalization statistics must hold one value per sample").into());
+        }
+
+        row_normalization_grad(dev.thread_pool(), input, output_diff, input_diff, row_len, gamma,
+                               |i| i % row_len, mean, inv_std, gamma_diff, beta_diff);
+        Ok(())
+    }
+}
+
+/// Returns the number of channels, the number of groups of all samples and the
+/// number of elements per group of a group normalization of `x`.
+fn group_norm_dims<T>(x: &TensorDesc, groups: usize, params: &[&[T]]) -> Result<(usize, usize, usize), Error> {
+    if x.len() < 2 {
+        return Err(PluginError::Operation("Group normalization requires at least a batch and a channel dimension").into());
+    }
+    if x.size() == 0 {
+        return Err(PluginError::Operation("Group normalization requires a non-empty input").into());
+    }
+    let channels = x[1];
+    if !channels.is_multiple_of(groups) {
+        return Err(PluginError::Operation("Group normalization requires the channels to be a multiple of the groups").into());
+    }
+    if params.iter().any(|p| p.len() != channels) {
+        return Err(PluginError::Operation("Group normalization parameters must hold one value per channel").into());
+    }
+    let rows = x[0] * groups;
+    Ok((channels, rows, x.size() / rows))
+}
+
+impl<T> GroupNorm<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn new_group_normalization_config(&self, groups: usize, epsilon: f64) -> Result<Self::CGN, Error> {
+        if groups == 0 {
+            return Err(PluginError::Operation("Group normalization requires at least one group").into());
+        }
+        if epsilon < 0.0 {
+            return Err(PluginError::Operation("Group normalization epsilon must not be negative").into());
+        }
+        Ok(helper::GroupNormalizationConfig { groups, epsilon })
+    }
+
+    fn group_normalization(&self,
+                           x: &SharedTensor<T>,
+                           scale: &SharedTensor<T>,
+                           bias: &SharedTensor<T>,
+                           saved_mean: &mut SharedTensor<T>,
+                           saved_inv_variance: &mut SharedTensor<T>,
+                           result: &mut SharedTensor<T>,
+                           config: &Self::CGN)
+                           -> Result<(), Error> {
+        let dev = self.device();
+        let dims = x.desc().clone();
+        let input = x.read(dev)?.as_slice::<T>();
+        let gamma = scale.read(dev)?.as_slice::<T>();
+        let beta = bias.read(dev)?.as_slice::<T>();
+        let (channels, rows, row_len) = group_norm_dims(&dims, config.groups, &[gamma, beta])?;
+        let output = result.write_only(dev)?.as_mut_slice::<T>();
+        let mean = saved_mean.write_only(dev)?.as_mut_slice::<T>();
+        let inv_std = saved_inv_variance.write_only(dev)?.as_mut_slice::<T>();
+        lens_eq(input, output)?;
+        if mean.len() != rows || inv_std.len() != rows {
+            return Err(PluginError::Operation("Group normalization statistics must hold one value per group and sample").into());
+        }
+
+        let inner = input.len() / (dims[0] * channels);
+        row_normalization(dev.thread_pool(), input, output, row_len, T::from(config.epsilon).unwrap(),
+                          gamma, beta, |i| (i / inner) % channels, mean, inv_std);
+        Ok(())
+    }
+
+    fn group_normalization_grad(&self,
+                                x: &SharedTensor<T>,
+                                result_diff: &SharedTensor<T>,
+                                scale: &SharedTensor<T>,
+                                saved_mean: &SharedTensor<T>,
+                                saved_inv_variance: &SharedTensor<T>,
+                                x_diff: &mut SharedTensor<T>,
+                                scale_diff: &mut SharedTensor<T>,
+                                bias_diff: &mut SharedTensor<T>,
+                                config: &Self::CGN)
+                                -> Result<(), Error> {
+        let dev = self.device();
+        let dims = x.desc().clone();
+        let input = x.read(dev)?.as_slice::<T>();
+        let output_diff = result_diff.read(dev)?.as_slice::<T>();
+        let gamma = scale.read(dev)?.as_slice::<T>();
+        let mean = saved_mean.read(dev)?.as_slice::<T>();
+        let inv_std = saved_inv_variance.read(dev)?.as_slice::<T>();
+        let (channels, rows, row_len) = group_norm_dims(&dims, config.groups, &[gamma])?;
+        let input_diff = x_diff.write_only(dev)?.as_mut_slice::<T>();
+        let gamma_diff = scale_diff.write_only(dev)?.as_mut_slice::<T>();
+        let beta_diff = bias_diff.write_only(dev)?.as_mut_slice::<T>();
+        lens_eq(input, output_diff)?;
+        lens_eq(input, input_diff)?;
+        lens_eq(gamma, gamma_diff)?;
+        lens_eq(gamma, beta_diff)?;
+        if mean.len() != rows || inv_std.len() != rows {
+            return Err(PluginError::Operation("Group normalization statistics must hold one value per group and sample").into());
+        }
+
+        let inner = input.len() / (dims[0] * channels);
+        row_normalization_grad(dev.thread_pool(), input, output_diff, input_diff, row_len, gamma,
+                               |i| (i / inner) % channels, mean, inv_std, gamma_diff, beta_diff);
+        Ok(())
+    }
+}
+
+/// Offsets of the parameters of one layer and direction within the weight
+/// tensor.
+///
+/// The layout matches cuDNN: layers are stored one after another, with the
+/// forward direction preceding the backward one. Each of those holds the
+/// input matrix `W`, the recurrent matrix `R` and their biases `bW` and `bR`,
+/// with the gates stacked in cuDNN order (`i, f, g, o` for LSTM and `r, z, n`
+/// for GRU) and matrices in row major order `[gates * hidden, input]`.
+struct RnnPseudoLayer {
+    input_size: usize,
+    /// Absent for the first layer if the input is skipped.
+    w: Option<usize>,
+    r: usize,
+    bw: usize,
+    br: usize,
+}
+
+fn rnn_pseudo_layers(config: &helper::RnnConfig, input_size: usize) -> (Vec<RnnPseudoLayer>, usize) {
+    let hidden = config.hidden_size;
+    let gates = config.num_gates() * hidden;
+    let directions = config.num_directions();
+
+    let mut layers = Vec::with_capacity(config.num_layers * directions);
+    let mut offset = 0;
+    for layer in 0..config.num_layers {
+        let layer_input_size = if layer == 0 { input_size } else { hidden * directions };
+        for _ in 0..directions {
+            let w = match config.input_mode {
+                RnnInputMode::SkipInput if layer == 0 => None,
+                _ => {
+                    offset += gates * layer_input_size;
+                    Some(offset - gates * layer_input_size)
+                }
+            };
+            let r = offset;
+            offset += gates * hidden;
+            let bw = offset;
+            offset += gates;
+            let br = offset;
+            offset += gates;
+            layers.push(RnnPseudoLayer {
+                input_size: layer_input_size,
+                w: w,
+                r: r,
+                bw: bw,
+                br: br,
+            });
+        }
+    }
+    (layers, offset)
+}
+
+/// Intermediate results of the forward pass required to compute the gradients.
+struct RnnForwardState<T> {
+    /// Output of each layer, `[sequence, batch, hidden * directions]`.
+    outputs: Vec<Vec<T>>,
+    /// Activated gates of each layer and direction, `[sequence, batch, gates * hidden]`.
+    gates: Vec<Vec<T>>,
+    /// Cell state for LSTM, the recurrent part of the new gate for GRU,
+    /// `[sequence, batch, hidden]`.
+    extra: Vec<Vec<T>>,
+}
+
+/// Computes `y += m * x` for a row major matrix `m`.
+fn rnn_gemv<T: Float>(m: &[T], x: &[T], y: &mut [T]) {
+    for (row, y) in m.chunks(x.len()).zip(y.iter_mut()) {
+        *y = row.iter().zip(x).fold(*y, |acc, (&m, &x)| acc + m * x);
+    }
+}
+
+/// Computes `y += m^T * x` for a row major matrix `m`.
+fn rnn_gemv_transposed<T: Float>(m: &[T], x: &[T], y: &mut [T]) {
+    for (row, &x) in m.chunks(y.len()).zip(x) {
+        for (y, &m) in y.iter_mut().zip(row) {
+            *y = *y + m * x;
+        }
+    }
+}
+
+/// Computes `m += x * y^T` for a row major matrix `m`.
+fn rnn_ger<T: Float>(x: &[T], y: &[T], m: &mut [T]) {
+    for (row, &x) in m.chunks_mut(y.len()).zip(x) {
+        for (m, &y) in row.iter_mut().zip(y) {
+            *m = *m + x * y;
+        }
+    }
+}
+
+fn rnn_forward_pass<T: Float>(config: &helper::RnnConfig,
+                              batch_size: usize,
+                              input_size: usize,
+                              x: &[T],
+                              weight: &[T])
+                              -> RnnForwardState<T> {
+    let sequence_length = config.sequence_length;
+    let hidden = config.hidden_size;
+    let directions = config.num_directions();
+    let num_gates = config.num_gates();
+    let gates_size = num_gates * hidden;
+    let output_size = hidden * directions;
+    let (layers, _) = rnn_pseudo_layers(config, input_size);
+
+    let mut state = RnnForwardState {
+        outputs: Vec::with_capacity(config.num_layers),
+        gates: Vec::with_capacity(layers.len()),
+        extra: Vec::with_capacity(layers.len()),
+    };
+    let mut input_part = vec![T::zero(); gates_size];
+    let mut recurrent_part = vec![T::zero(); gates_size];
+    let zero_state = vec![T::zero(); hidden];
+
+    for layer_idx in 0..config.num_layers {
+        let mut output = vec![T::zero(); sequence_length * batch_size * output_size];
+        for direction in 0..directions {
+            let layer = &layers[layer_idx * directions + direction];
+            let input: &[T] = if layer_idx == 0 { x } else { &state.outputs[layer_idx - 1] };
+            let mut gates = vec![T::zero(); sequence_length * batch_size * gates_size];
+            let mut extra = vec![T::zero(); sequence_length * batch_size * hidden];
+
+            for step in 0..sequence_length {
+                let t = if direction == 0 { step } else { sequence_length - 1 - step };
+                let t_prev = if step == 0 {
+                    None
+                } else if direction == 0 {
+                    Some(t - 1)
+                } else {
+                    Some(t + 1)
+                };
+                for b in 0..batch_size {
+                    let x_t = &input[(t * batch_size + b) * layer.input_size..][..layer.input_size];
+                    let h_prev: Vec<T> = match t_prev {
+                        Some(t_prev) => {
+                            output[(t_prev * batch_size + b) * output_size + direction * hidden..][..hidden].to_vec()
+                        }
+                        None => zero_state.clone(),
+                    };
+
+                    input_part.copy_from_slice(&weight[layer.bw..layer.bw + gates_size]);
+                    match layer.w {
+                        Some(w) => rnn_gemv(&weight[w..w + gates_size * layer.input_size], x_t, &mut input_part),
+                        None => {
+                            for gate in input_part.chunks_mut(hidden) {
+                                for (g, &x) in gate.iter_mut().zip(x_t) {
+                                    *g = *g + x;
+                                }
+                            }
+                        }
+                    }
+                    recurrent_part.copy_from_slice(&weight[layer.br..layer.br + gates_size]);
+                    rnn_gemv(&weight[layer.r..layer.r + gates_size * hidden], &h_prev, &mut recurrent_part);
+
+                    let offset = t * batch_size + b;
+                    let gates_t = &mut gates[offset * gates_size..][..gates_size];
+                    let h_t = &mut output[offset * output_size + direction * hidden..][..hidden];
+                    for j in 0..hidden {
+                        match config.rnn_type {
+                            RnnNetworkMode::ReLU => {
+                                gates_t[j] = helper::relu(input_part[j] + recurrent_part[j]);
+                                h_t[j] = gates_t[j];
+                            }
+                            RnnNetworkMode::Tanh => {
+                                gates_t[j] = helper::tanh(input_part[j] + recurrent_part[j]);
+                                h_t[j] = gates_t[j];
+                            }
+                            RnnNetworkMode::LSTM => {
+                                let c_prev = match t_prev {
+                                    Some(t_prev) => extra[(t_prev * batch_size + b) * hidden + j],
+                                    None => T::zero(),
+                                };
+                                let a = |gate: usize| input_part[gate * hidden + j] + recurrent_part[gate * hidden + j];
+                                let i = helper::sigmoid(a(0));
+                                let f = helper::sigmoid(a(1));
+                                let g = helper::tanh(a(2));
+                                let o = helper::sigmoid(a(3));
+                                let c = f * c_prev + i * g;
+                                gates_t[j] = i;
+                                gates_t[hidden + j] = f;
+                                gates_t[2 * hidden + j] = g;
+                                gates_t[3 * hidden + j] = o;
+                                extra[offset * hidden + j] = c;
+                                h_t[j] = o * helper::tanh(c);
+                            }
+                            RnnNetworkMode::GRU => {
+                                let r = helper::sigmoid(input_part[j] + recurrent_part[j]);
+                                let z = helper::sigmoid(input_part[hidden + j] + recurrent_part[hidden + j]);
+                                let n = helper::tanh(input_part[2 * hidden + j] + r * recurrent_part[2 * hidden + j]);
+                                gates_t[j] = r;
+                                gates_t[hidden + j] = z;
+                                gates_t[2 * hidden + j] = n;
+                                extra[offset * hidden + j] = recurrent_part[2 * hidden + j];
+                                h_t[j] = (T::one() - z) * n + z * h_prev[j];
+                            }
+                        }
+                    }
+                }
+            }
+            state.gates.push(gates);
+            state.extra.push(extra);
+        }
+        state.outputs.push(output);
+    }
+    state
+}
+
+/// Number of elements of the outputs of a layer and of the gate gradients of
+/// a layer and direction, which make up the workspace of the backward passes.
+fn rnn_workspace_sizes(config: &helper::RnnConfig, batch_size: usize) -> (usize, usize) {
+    let steps = config.sequence_length * batch_size;
+    (steps * config.hidden_size * config.num_directions(), steps * config.num_gates() * config.hidden_size)
+}
+
+/// Provides the outputs of all layers and the gradients w.r.t. the input and
+/// the recurrent part of the gates of all layers and directions from the
+/// workspace.
+fn rnn_workspace<'a, T>(workspace: &'a mut FlatBox,
+                        config: &helper::RnnConfig,
+                        batch_size: usize)
+                        -> Result<(&'a mut [T], &'a mut [T], &'a mut [T]), Error> {
+    let (output_len, gates_len) = rnn_workspace_sizes(config, batch_size);
+    let outputs_len = config.num_layers * output_len;
+    let parts_len = config.num_layers * config.num_directions() * gates_len;
+    if workspace.byte_size() < (outputs_len + 2 * parts_len) * ::std::mem::size_of::<T>() {
+        return Err(PluginError::Operation("RNN workspace too small").into());
+    }
+    let (outputs, parts) = workspace.as_mut_slice::<T>().split_at_mut(outputs_len);
+    let (d_input_parts, d_recurrent_parts) = parts.split_at_mut(parts_len);
+    Ok((outputs, d_input_parts, &mut d_recurrent_parts[..parts_len]))
+}
+
+/// Backpropagation through time, writing the gradient w.r.t. the input to
+/// `dx` and the gradients w.r.t. the input and the recurrent part of the
+/// gates of every layer and direction to `d_input_parts` and
+/// `d_recurrent_parts`, `[layer * directions + direction, sequence, batch, gates * hidden]`.
+fn rnn_backward_pass<T: Float>(config: &helper::RnnConfig,
+                               batch_size: usize,
+                               input_size: usize,
+                               weight: &[T],
+                               state: &RnnForwardState<T>,
+                               dy: &[T],
+                               dx: &mut [T],
+                               d_input_parts: &mut [T],
+                               d_recurrent_parts: &mut [T]) {
+    let sequence_length = config.sequence_length;
+    let hidden = config.hidden_size;
+    let directions = config.num_directions();
+    let gates_size = config.num_gates() * hidden;
+    let output_size = hidden * directions;
+    let (layers, _) = rnn_pseudo_layers(config, input_size);
+    let (_, gates_len) = rnn_workspace_sizes(config, batch_size);
+
+    let mut d_output: Vec<T> = dy.to_vec();
+
+    for layer_idx in (0..config.num_layers).rev() {
+        let output = &state.outputs[layer_idx];
+        let layer_input_size = layers[layer_idx * directions].input_size;
+        let mut d_input = vec![T::zero(); sequence_length * batch_size * layer_input_size];
+
+        for direction in 0..directions {
+            let pseudo_layer = layer_idx * directions + direction;
+            let layer = &layers[pseudo_layer];
+            let gates = &state.gates[pseudo_layer];
+            let extra = &state.extra[pseudo_layer];
+            let d_input_parts = &mut d_input_parts[pseudo_layer * gates_len..][..gates_len];
+            let d_recurrent_parts = &mut d_recurrent_parts[pseudo_layer * gates_len..][..gates_len];
+            let mut dh_next = vec![T::zero(); batch_size * hidden];
+            let mut dc_next = vec![T::zero(); batch_size * hidden];
+
+            for step in (0..sequence_length).rev() {
+                let t = if direction == 0 { step } else { sequence_length - 1 - step };
+                let t_prev = if step == 0 {
+                    None
+                } else if direction == 0 {
+                    Some(t - 1)
+                } else {
+                    Some(t + 1)
+                };
+                for b in 0..batch_size {
+                    let offset = t * batch_size + b;
+                    let gates_t = &gates[offset * gates_size..][..gates_size];
+                    let h_prev = |j: usize| match t_prev {
+                        Some(t_prev) => output[(t_prev * batch_size + b) * output_size + direction * hidden + j],
+                        None => T::zero(),
+                    };
+                    let dh_next_b = &mut dh_next[b * hidden..][..hidden];
+                    let dc_next_b = &mut dc_next[b * hidden..][..hidden];
+                    let d_input_part = &mut d_input_parts[offset * gates_size..][..gates_size];
+                    let d_recurrent_part = &mut d_recurrent_parts[offset * gates_size..][..gates_size];
+
+                    for j in 0..hidden {
+                        let dh = d_output[offset * output_size + direction * hidden + j] + dh_next_b[j];
+                        match config.rnn_type {
+                            RnnNetworkMode::ReLU => {
+                                let da = if gates_t[j] > T::zero() { dh } else { T::zero() };
+                                d_input_part[j] = da;
+                                d_recurrent_part[j] = da;
+                                dh_next_b[j] = T::zero();
+                            }
+                            RnnNetworkMode::Tanh => {
+                                let da = dh * (T::one() - gates_t[j] * gates_t[j]);
+                                d_input_part[j] = da;
+                                d_recurrent_part[j] = da;
+                                dh_next_b[j] = T::zero();
+                            }
+                            RnnNetworkMode::LSTM => {
+                                let (i, f, g, o) = (gates_t[j],
+                                                    gates_t[hidden + j],
+                                                    gates_t[2 * hidden + j],
+                                                    gates_t[3 * hidden + j]);
+                                let c = extra[offset * hidden + j];
+                                let c_prev = match t_prev {
+                                    Some(t_prev) => extra[(t_prev * batch_size + b) * hidden + j],
+                                    None => T::zero(),
+                                };
+                                let tanh_c = helper::tanh(c);
+                                let dc = dc_next_b[j] + dh * o * (T::one() - tanh_c * tanh_c);
+                                d_input_part[j] = dc * g * i * (T::one() - i);
+                                d_input_part[hidden + j] = dc * c_prev * f * (T::one() - f);
+                                d_input_part[2 * hidden + j] = dc * i * (T::one() - g * g);
+                                d_input_part[3 * hidden + j] = dh * tanh_c * o * (T::one() - o);
+                                for gate in 0..4 {
+                                    d_recurrent_part[gate * hidden + j] = d_input_part[gate * hidden + j];
+                                }
+                                dc_next_b[j] = dc * f;
+                                dh_next_b[j] = T::zero();
+                            }
+                            RnnNetworkMode::GRU => {
+                                let (r, z, n) = (gates_t[j], gates_t[hidden + j], gates_t[2 * hidden + j]);
+                                let recurrent_n = extra[offset * hidden + j];
+                                let dn = dh * (T::one() - z) * (T::one() - n * n);
+                                let dr = dn * recurrent_n * r * (T::one() - r);
+                                let dz = dh * (h_prev(j) - n) * z * (T::one() - z);
+                                d_input_part[j] = dr;
+                                d_input_part[hidden + j] = dz;
+                                d_input_part[2 * hidden + j] = dn;
+                                d_recurrent_part[j] = dr;
+                                d_recurrent_part[hidden + j] = dz;
+                                d_recurrent_part[2 * hidden + j] = dn * r;
+                                dh_next_b[j] = dh * z;
+                            }
+                        }
+                    }
+
+                    // gradient w.r.t. the input
+                    let dx_t = &mut d_input[offset * layer.input_size..][..layer.input_size];
+                    match layer.w {
+                        Some(w) => {
+                            let w_size = gates_size * layer.input_size;
+                            rnn_gemv_transposed(&weight[w..w + w_size], d_input_part, dx_t);
+                        }
+                        None => {
+                            for gate in d_input_part.chunks(hidden) {
+                                for (dx, &da) in dx_t.iter_mut().zip(gate) {
+                                    *dx = *dx + da;
+                                }
+                            }
+                        }
+                    }
+
+                    // gradient w.r.t. the previous hidden state
+                    if t_prev.is_some() {
+                        let r_size = gates_size * hidden;
+                        rnn_gemv_transposed(&weight[layer.r..layer.r + r_size], d_recurrent_part, dh_next_b);
+                    }
+                }
+            }
+        }
+        d_output = d_input;
+    }
+    dx.copy_from_slice(&d_output);
+}
+
+/// Computes the gradient w.r.t. the weights from the input `x`, the outputs
+/// of all layers and the gate gradients of `rnn_backward_pass`.
+fn rnn_weight_gradient<T: Float>(config: &helper::RnnConfig,
+                                 batch_size: usize,
+                                 input_size: usize,
+                                 x: &[T],
+                                 outputs: &[T],
+                                 d_input_parts: &[T],
+                                 d_recurrent_parts: &[T],
+                                 dw: &mut [T]) {
+    let sequence_length = config.sequence_length;
+    let hidden = config.hidden_size;
+    let directions = config.num_directions();
+    let gates_size = config.num_gates() * hidden;
+    let output_size = hidden * directions;
+    let (layers, _) = rnn_pseudo_layers(config, input_size);
+    let (output_len, gates_len) = rnn_workspace_sizes(config, batch_size);
+
+    for v in dw.iter_mut() {
+        *v = T::zero();
+    }
+    for layer_idx in 0..config.num_layers {
+        let output = &outputs[layer_idx * output_len..][..output_len];
+        let input: &[T] = if layer_idx == 0 { x } else { &outputs[(layer_idx - 1) * output_len..][..output_len] };
+
+        for direction in 0..directions {
+            let pseudo_layer = layer_idx * directions + direction;
+            let layer = &layers[pseudo_layer];
+            let d_input_parts = &d_input_parts[pseudo_layer * gates_len..][..gates_len];
+            let d_recurrent_parts = &d_recurrent_parts[pseudo_layer * gates_len..][..gates_len];
+
+            for t in 0..sequence_length {
+                let t_prev = if direction == 0 {
+                    t.checked_sub(1)
+                } else if t + 1 < sequence_length {
+                    Some(t + 1)
+                } else {
+                    None
+                };
+                for b in 0..batch_size {
+                    let offset = t * batch_size + b;
+                    let d_input_part = &d_input_parts[offset * gates_size..][..gates_size];
+                    let d_recurrent_part = &d_recurrent_parts[offset * gates_size..][..gates_size];
+
+                    // input weights and their bias
+                    for (db, &da) in dw[layer.bw..layer.bw + gates_size].iter_mut().zip(d_input_part) {
+                        *db = *db + da;
+                    }
+                    if let Some(w) = layer.w {
+                        let x_t = &input[offset * layer.input_size..][..layer.input_size];
+                        rnn_ger(d_input_part, x_t, &mut dw[w..w + gates_size * layer.input_size]);
+                    }
+
+                    // recurrent weights and their bias
+                    for (db, &da) in dw[layer.br..layer.br + gates_size].iter_mut().zip(d_recurrent_part) {
+                        *db = *db + da;
+                    }
+                    if let Some(t_prev) = t_prev {
+                        let h_prev = &output[(t_prev * batch_size + b) * output_size + direction * hidden..][..hidden];
+                        rnn_ger(d_recurrent_part, h_prev, &mut dw[layer.r..layer.r + gates_size * hidden]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Swaps the two outer dimensions of `data`, `[outer, inner, size]`.
+///
+/// The passes work on time major data, while the input and output of the
+/// operations are batch major, `[batch, sequence, size]`.
+fn rnn_swap_major<T: Copy>(data: &[T], outer: usize, inner: usize, size: usize) -> Vec<T> {
+    let mut swapped = Vec::with_capacity(data.len());
+    for i in 0..inner {
+        for o in 0..outer {
+            swapped.extend_from_slice(&data[(o * inner + i) * size..][..size]);
+        }
+    }
+    swapped
+}
+
+/// Determines the batch and input size of the batch major input `src`.
+fn rnn_input_dims<T>(src: &SharedTensor<T>, config: &helper::RnnConfig) -> Result<(usize, usize), Error> {
+    let batch_size = src.desc()[0];
+    let step_size = config.sequence_length * batch_size;
+    if step_size == 0 || src.desc().size() % step_size != 0 {
+        return Err(PluginError::Operation("RNN input size is not a multiple of sequence length and batch size").into());
+    }
+    Ok((batch_size, src.desc().size() / step_size))
+}
+
+impl<T> Rnn<T> for Backend<Native>
+    where T: Float + Default + Copy + PartialOrd + Bounded {
+    fn new_rnn_config(&self,
+                      src: &SharedTensor<T>,
+                      dropout_probability: Option<f32>,
+                      dropout_seed: Option<u64>,
+                      sequence_length: i32,
+                      network_mode: RnnNetworkMode,
+                      input_mode: RnnInputMode,
+                      direction_mode: DirectionMode,
+                      algorithm: RnnAlgorithm,
+                      hidden_size: i32,
+                      num_layers: i32,
+                      batch_size: i32)
+                      -> Result<Self::CRNN, Error> {
+        // dropout between the layers is not implemented by the native backend
+        let dropout_probability = dropout_probability.unwrap_or(0.5);
+        if num_layers > 1 && dropout_probability != 0.0 {
+            return Err(PluginError::Operation("RNN dropout between layers is not supported by the native backend").into());
+        }
+        let mut config = helper::RnnConfig {
+            hidden_size: hidden_size as usize,
+            num_layers: num_layers as usize,
+            dropout_probability: dropout_probability,
+            dropout_seed: dropout_seed.unwrap_or(0),
+            rnn_type: network_mode,
+            input_mode: input_mode,
+            direction_mode: direction_mode,
+            sequence_length: sequence_length as usize,
+            batch_size: batch_size as usize,
+            workspace_size: 0,
+        };
+        let (output_len, gates_len) = rnn_workspace_sizes(&config, config.batch_size);
+        let directions = config.num_directions();
+        config.workspace_size = config.num_layers * (output_len + 2 * directions * gates_len) *
+                                ::std::mem::size_of::<T>();
+        Ok(config)
+    }
+
+    fn generate_rnn_weight_description(
+        &self,
+        rnn_config: &Self::CRNN,
+        batch_size: i32,
+        input_size: i32,
+    ) -> Result<Vec<usize>, Error> {
+        let (_, weight_size) = rnn_pseudo_layers(rnn_config, input_size as usize);
+        Ok(vec![weight_size, 1, 1])
+    }
+
+    fn rnn_forward(
+        &self,
+        src: &SharedTensor<T>,
+        output: &mut SharedTensor<T>,
+        rnn_config: &Self::CRNN,
+        weight: &SharedTensor<T>,
+        workspace: &mut SharedTensor<u8>,
+    ) -> Result<(), Error> {
+        let (batch_size, input_size) = rnn_input_dims(src, rnn_config)?;
+        let (_, weight_size) = rnn_pseudo_layers(rnn_config, input_size);
+        let output_width = rnn_config.hidden_size * rnn_config.num_directions();
+        let output_size = rnn_config.sequence_length * batch_size * output_width;
+        if weight.desc().size() != weight_size || output.desc().size() != output_size {
+            return Err(PluginError::Operation("RNN weight or output dimension mismatch").into());
+        }
+
+        let dev = self.device();
+        let x = rnn_swap_major(src.read(dev)?.as_slice::<T>(), batch_size, rnn_config.sequence_length, input_size);
+        let w = weight.read(dev)?.as_slice::<T>();
+        let mut state = rnn_forward_pass(rnn_config, batch_size, input_size, &x, w);
+        let y = state.outputs.pop().unwrap();
+        let y = rnn_swap_major(&y, rnn_config.sequence_length, batch_size, output_width);
+        output.write_only(dev)?.as_mut_slice::<T>().copy_from_slice(&y);
+        Ok(())
+    }
+
+    fn rnn_backward_data(&self,
+                         src: &SharedTensor<T>,
+                         src_gradient: &mut SharedTensor<T>,
+                         output: &SharedTensor<T>,
+                         output_gradient: &SharedTensor<T>,
+                         rnn_config: &Self::CRNN,
+                         weight: &SharedTensor<T>,
+                         workspace: &mut SharedTensor<u8>)
+                         -> Result<(), Error> {
+        let (batch_size, input_size) = rnn_input_dims(src, rnn_config)?;
+        let (_, weight_size) = rnn_pseudo_layers(rnn_config, input_size);
+        let output_width = rnn_config.hidden_size * rnn_config.num_directions();
+        let output_size = rnn_config.sequence_length * batch_size * output_width;
+        if weight.desc().size() != weight_size || output_gradient.desc().size() != output_size ||
+           src_gradient.desc().size() != src.desc().size() {
+            return Err(PluginError::Operation("RNN weight or gradient dimension mismatch").into());
+        }
+
+        let dev = self.device();
+        let x = src.read(dev)?.as_slice::<T>();
+        let w = weight.read(dev)?.as_slice::<T>();
+        let dy = output_gradient.read(dev)?.as_slice::<T>();
+        let (outputs, d_input_parts, d_recurrent_parts) =
+            rnn_workspace::<T>(workspace.write_only(dev)?, rnn_config, batch_size)?;
+        let sequence_length = rnn_config.sequence_length;
+        let x = rnn_swap_major(x, batch_size, sequence_length, input_size);
+        let dy = rnn_swap_major(dy, batch_size, sequence_length, output_width);
+        let mut dx = vec![T::zero(); x.len()];
+
+        // the intermediate results of the forward pass are not kept around, recompute them
+        let state = rnn_forward_pass(rnn_config, batch_size, input_size, &x, w);
+        rnn_backward_pass(rnn_config, batch_size, input_size, w, &state, &dy, &mut dx, d_input_parts, d_recurrent_parts);
+        let dx = rnn_swap_major(&dx, sequence_length, batch_size, input_size);
+        src_gradient.write_only(dev)?.as_mut_slice::<T>().copy_from_slice(&dx);
+
+        // keep what the weight gradient needs, as the output gradient is not passed on to it
+        for (chunk, output) in outputs.chunks_mut(outputs.len() / rnn_config.num_layers).zip(&state.outputs) {
+            chunk.copy_from_slice(output);
+        }
+        Ok(())
+    }
+
+    fn rnn_backward_weights(&self,
+                            src: &SharedTensor<T>,
+                            output: &SharedTensor<T>,
+                            filter: &mut SharedTensor<T>,
+                            rnn_config: &Self::CRNN,
+                            workspace: &mut SharedTensor<u8>)
+                            -> Result<(), Error> {
+        let (batch_size, input_size) = rnn_input_dims(src, rnn_config)?;
+        let (_, weight_size) = rnn_pseudo_layers(rnn_config, input_size);
+        if filter.desc().size() != weight_size {
+            return Err(PluginError::Operation("RNN weight gradient dimension mismatch").into());
+        }
+
+        // the gate gradients are stored in the workspace by rnn_backward_data
+        let dev = self.device();
+        let x = rnn_swap_major(src.read(dev)?.as_slice::<T>(), batch_size, rnn_config.sequence_length, input_size);
+        let (outputs, d_input_parts, d_recurrent_parts) =
+            rnn_workspace::<T>(workspace.read_write(dev)?, rnn_config, batch_size)?;
+        let dw = filter.write_only(dev)?.as_mut_slice::<T>();
+        rnn_weight_gradient(rnn_config, batch_size, input_size, &x, outputs, d_input_parts, d_recurrent_parts, dw);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "native")]
+impl<T> Dropout<T> for Backend<Native>
+    where T: Float + Add<T, Output = T> + Mul<T, Output = T> + Default + Copy + PartialOrd + Bounded
+{
+    fn new_dropout_config(&self,
+                      probability: f32,
+                      seed: u64,
+                      )
+                      -> Result<Self::CDROP, Error> {
         Ok(helper::DropoutConfig{probability, seed})
     }
 
@@ -950,13 +2288,1174 @@ impl<T> Dropout<T> for Backend<Native>
     }
 }
 
+/// Converts the parameter of an activation to the element type.
+fn activation_param<T: Float>(value: f64) -> Result<T, Error> {
+    T::from(value).ok_or_else(|| PluginError::Operation("Activation parameter is not representable").into())
+}
+
+/// Fails if an activation can not compute its gradient from its output.
+fn non_negative_param(value: f64) -> Result<(), Error> {
+    if value < 0.0 {
+        return Err(PluginError::Operation("Pointwise gradient requires a non-negative activation parameter").into());
+    }
+    Ok(())
+}
+
+impl<T> LeakyRelu<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn leaky_relu(&self, x: &SharedTensor<T>, result: &mut SharedTensor<T>, slope: f64)
+                  -> Result<(), Error> {
+        let slope = activation_param(slope)?;
+        map1(self.device().thread_pool(),
+             read!(x, T, self),
+             write_only!(result, T, self),
+             |x| helper::leaky_relu(x, slope))
+    }
+
+    fn leaky_relu_grad(&self, x: &SharedTensor<T>, x_diff: &SharedTensor<T>,
+                       result: &SharedTensor<T>, result_diff: &mut SharedTensor<T>, slope: f64)
+                       -> Result<(), Error> {
+        let slope = activation_param(slope)?;
+        map2(self.device().thread_pool(),
+             read!(result, T, self),
+             read!(x_diff, T, self),
+             write_only!(result_diff, T, self),
+             |x, dx| helper::leaky_relu_grad(x, dx, slope))
+    }
+}
+
+impl<T> LeakyReluPointwise<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn leaky_relu_pointwise(&self, x: &mut SharedTensor<T>, slope: f64)
+                            -> Result<(), Error> {
+        let slope = activation_param(slope)?;
+        map1_inplace(self.device().thread_pool(),
+                     read_write!(x, T, self),
+                     |x| helper::leaky_relu(x, slope))
+    }
+
+    fn leaky_relu_pointwise_grad(&self, x: &SharedTensor<T>, x_diff: &mut SharedTensor<T>, slope: f64)
+                                 -> Result<(), Error> {
+        // the output has the sign of the input
+        non_negative_param(slope)?;
+        let slope = activation_param(slope)?;
+        map2_inplace(self.device().thread_pool(),
+                     read!(x, T, self),
+                     read_write!(x_diff, T, self),
+                     |y, dx| helper::leaky_relu_grad(y, dx, slope))
+    }
+}
+
+impl<T> Elu<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn elu(&self, x: &SharedTensor<T>, result: &mut SharedTensor<T>, alpha: f64)
+           -> Result<(), Error> {
+        let alpha = activation_param(alpha)?;
+        map1(self.device().thread_pool(),
+             read!(x, T, self),
+             write_only!(result, T, self),
+             |x| helper::elu(x, alpha))
+    }
+
+    fn elu_grad(&self, x: &SharedTensor<T>, x_diff: &SharedTensor<T>,
+                result: &SharedTensor<T>, result_diff: &mut SharedTensor<T>, alpha: f64)
+                -> Result<(), Error> {
+        let alpha = activation_param(alpha)?;
+        map2(self.device().thread_pool(),
+             read!(result, T, self),
+             read!(x_diff, T, self),
+             write_only!(result_diff, T, self),
+             |x, dx| helper::elu_grad(x, dx, alpha))
+    }
+}
+
+impl<T> EluPointwise<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn elu_pointwise(&self, x: &mut SharedTensor<T>, alpha: f64) -> Result<(), Error> {
+        let alpha = activation_param(alpha)?;
+        map1_inplace(self.device().thread_pool(),
+                     read_write!(x, T, self),
+                     |x| helper::elu(x, alpha))
+    }
+
+    fn elu_pointwise_grad(&self, x: &SharedTensor<T>, x_diff: &mut SharedTensor<T>, alpha: f64)
+                          -> Result<(), Error> {
+        // for negative inputs `alpha * exp(x) = y + alpha`
+        non_negative_param(alpha)?;
+        let alpha = activation_param(alpha)?;
+        map2_inplace(self.device().thread_pool(),
+                     read!(x, T, self),
+                     read_write!(x_diff, T, self),
+                     |y, dx| if y > T::zero() { dx } else { (y + alpha) * dx })
+    }
+}
+
+impl<T> Selu<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn selu(&self, x: &SharedTensor<T>, result: &mut SharedTensor<T>) -> Result<(), Error> {
+        map1(self.device().thread_pool(),
+             read!(x, T, self),
+             write_only!(result, T, self),
+             helper::selu)
+    }
+
+    fn selu_grad(&self, x: &SharedTensor<T>, x_diff: &SharedTensor<T>,
+                 result: &SharedTensor<T>, result_diff: &mut SharedTensor<T>)
+                 -> Result<(), Error> {
+        map2(self.device().thread_pool(),
+             read!(result, T, self),
+             read!(x_diff, T, self),
+             write_only!(result_diff, T, self),
+             helper::selu_grad)
+    }
+}
+
+impl<T> SeluPointwise<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn selu_pointwise(&self, x: &mut SharedTensor<T>) -> Result<(), Error> {
+        map1_inplace(self.device().thread_pool(),
+                     read_write!(x, T, self),
+                     helper::selu)
+    }
+
+    fn selu_pointwise_grad(&self, x: &SharedTensor<T>, x_diff: &mut SharedTensor<T>)
+                           -> Result<(), Error> {
+        // for negative inputs `scale * alpha * exp(x) = y + scale * alpha`
+        let scale: T = activation_param(helper::SELU_SCALE)?;
+        let scale_alpha: T = activation_param(helper::SELU_SCALE * helper::SELU_ALPHA)?;
+        map2_inplace(self.device().thread_pool(),
+                     read!(x, T, self),
+                     read_write!(x_diff, T, self),
+                     |y, dx| if y > T::zero() { scale * dx } else { (y + scale_alpha) * dx })
+    }
+}
+
+impl<T> Gelu<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn gelu(&self, x: &SharedTensor<T>, result: &mut SharedTensor<T>) -> Result<(), Error> {
+        map1(self.device().thread_pool(),
+             read!(x, T, self),
+             write_only!(result, T, self),
+             helper::gelu)
+    }
+
+    fn gelu_grad(&self, x: &SharedTensor<T>, x_diff: &SharedTensor<T>,
+                 result: &SharedTensor<T>, result_diff: &mut SharedTensor<T>)
+                 -> Result<(), Error> {
+        map2(self.device().thread_pool(),
+             read!(result, T, self),
+             read!(x_diff, T, self),
+             write_only!(result_diff, T, self),
+             helper::gelu_grad)
+    }
+}
+
+impl<T> GeluPointwise<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn gelu_pointwise(&self, x: &mut SharedTensor<T>) -> Result<(), Error> {
+        map1_inplace(self.device().thread_pool(),
+                     read_write!(x, T, self),
+                     helper::gelu)
+    }
+
+    fn gelu_pointwise_grad(&self, x: &SharedTensor<T>, x_diff: &mut SharedTensor<T>)
+                           -> Result<(), Error> {
+        map2_inplace(self.device().thread_pool(),
+                     read!(x, T, self),
+                     read_write!(x_diff, T, self),
+                     helper::gelu_grad)
+    }
+}
+
+impl<T> Swish<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn swish(&self, x: &SharedTensor<T>, result: &mut SharedTensor<T>) -> Result<(), Error> {
+        map1(self.device().thread_pool(),
+             read!(x, T, self),
+             write_only!(result, T, self),
+             helper::swish)
+    }
+
+    fn swish_grad(&self, x: &SharedTensor<T>, x_diff: &SharedTensor<T>,
+                  result: &SharedTensor<T>, result_diff: &mut SharedTensor<T>)
+                  -> Result<(), Error> {
+        map2(self.device().thread_pool(),
+             read!(result, T, self),
+             read!(x_diff, T, self),
+             write_only!(result_diff, T, self),
+             helper::swish_grad)
+    }
+}
+
+impl<T> SwishPointwise<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn swish_pointwise(&self, x: &mut SharedTensor<T>) -> Result<(), Error> {
+        map1_inplace(self.device().thread_pool(),
+                     read_write!(x, T, self),
+                     helper::swish)
+    }
+
+    fn swish_pointwise_grad(&self, x: &SharedTensor<T>, x_diff: &mut SharedTensor<T>)
+                            -> Result<(), Error> {
+        map2_inplace(self.device().thread_pool(),
+                     read!(x, T, self),
+                     read_write!(x_diff, T, self),
+                     helper::swish_grad)
+    }
+}
+
+impl<T> Softplus<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn softplus(&self, x: &SharedTensor<T>, result: &mut SharedTensor<T>) -> Result<(), Error> {
+        map1(self.device().thread_pool(),
+             read!(x, T, self),
+             write_only!(result, T, self),
+             helper::softplus)
+    }
+
+    fn softplus_grad(&self, x: &SharedTensor<T>, x_diff: &SharedTensor<T>,
+                     result: &SharedTensor<T>, result_diff: &mut SharedTensor<T>)
+                     -> Result<(), Error> {
+        map2(self.device().thread_pool(),
+             read!(result, T, self),
+             read!(x_diff, T, self),
+             write_only!(result_diff, T, self),
+             helper::softplus_grad)
+    }
+}
+
+impl<T> SoftplusPointwise<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn softplus_pointwise(&self, x: &mut SharedTensor<T>) -> Result<(), Error> {
+        map1_inplace(self.device().thread_pool(),
+                     read_write!(x, T, self),
+                     helper::softplus)
+    }
+
+    fn softplus_pointwise_grad(&self, x: &SharedTensor<T>, x_diff: &mut SharedTensor<T>)
+                               -> Result<(), Error> {
+        // sigmoid(x) = 1 - exp(-y)
+        map2_inplace(self.device().thread_pool(),
+                     read!(x, T, self),
+                     read_write!(x_diff, T, self),
+                     |y, dx| -(-y).exp_m1() * dx)
+    }
+}
+
+/// Converts the bounds of HardTanh to the element type.
+fn hard_tanh_bounds<T: Float>(min_value: f64, max_value: f64) -> Result<(T, T), Error> {
+    if min_value > max_value {
+        return Err(PluginError::Operation("HardTanh requires min_value to not exceed max_value").into());
+    }
+    Ok((activation_param(min_value)?, activation_param(max_value)?))
+}
+
+impl<T> HardTanh<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn hard_tanh(&self, x: &SharedTensor<T>, result: &mut SharedTensor<T>,
+                 min_value: f64, max_value: f64)
+                 -> Result<(), Error> {
+        let (min_value, max_value) = hard_tanh_bounds(min_value, max_value)?;
+        map1(self.device().thread_pool(),
+             read!(x, T, self),
+             write_only!(result, T, self),
+             |x| helper::hard_tanh(x, min_value, max_value))
+    }
+
+    fn hard_tanh_grad(&self, x: &SharedTensor<T>, x_diff: &SharedTensor<T>,
+                      result: &SharedTensor<T>, result_diff: &mut SharedTensor<T>,
+                      min_value: f64, max_value: f64)
+                      -> Result<(), Error> {
+        let (min_value, max_value) = hard_tanh_bounds(min_value, max_value)?;
+        map2(self.device().thread_pool(),
+             read!(result, T, self),
+             read!(x_diff, T, self),
+             write_only!(result_diff, T, self),
+             |x, dx| helper::hard_tanh_grad(x, dx, min_value, max_value))
+    }
+}
+
+impl<T> HardTanhPointwise<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn hard_tanh_pointwise(&self, x: &mut SharedTensor<T>, min_value: f64, max_value: f64)
+                           -> Result<(), Error> {
+        let (min_value, max_value) = hard_tanh_bounds(min_value, max_value)?;
+        map1_inplace(self.device().thread_pool(),
+                     read_write!(x, T, self),
+                     |x| helper::hard_tanh(x, min_value, max_value))
+    }
+
+    fn hard_tanh_pointwise_grad(&self, x: &SharedTensor<T>, x_diff: &mut SharedTensor<T>,
+                                min_value: f64, max_value: f64)
+                                -> Result<(), Error> {
+        // the output is only within the bounds where the input is
+        let (min_value, max_value) = hard_tanh_bounds(min_value, max_value)?;
+        map2_inplace(self.device().thread_pool(),
+                     read!(x, T, self),
+                     read_write!(x_diff, T, self),
+                     |y, dx| helper::hard_tanh_grad(y, dx, min_value, max_value))
+    }
+}
+
+/// Computes the shape `a` and `b` are broadcast to.
+fn broadcast_shape(a: &[usize], b: &[usize]) -> Result<TensorDesc, Error> {
+    let rank = usize::max(a.len(), b.len());
+    let dim = |dims: &[usize], i: usize| {
+        if i + dims.len() < rank { 1 } else { dims[i + dims.len() - rank] }
+    };
+    (0..rank).map(|i| match (dim(a, i), dim(b, i)) {
+        (da, db) if da == db || db == 1 => Ok(da),
+        (1, db) => Ok(db),
+        _ => Err(PluginError::Operation("Tensor dimensions can not be broadcast").into()),
+    }).collect()
+}
+
+/// Strides to index a Tensor of dimensions `dims` broadcast to `shape`,
+/// with a stride of zero along the broadcast dimensions.
+fn broadcast_strides(dims: &[usize], shape: &[usize]) -> Vec<usize> {
+    let offset = shape.len() - dims.len();
+    let mut strides = vec![0; shape.len()];
+    let mut stride = 1;
+    for (i, &dim) in dims.iter().enumerate().rev() {
+        if dim != 1 {
+            strides[offset + i] = stride;
+        }
+        stride *= dim;
+    }
+    strides
+}
+
+/// Offset of the first element of the `row`-th innermost row of `shape`, given
+/// the `strides` of the dimensions of `shape` in the indexed memory.
+fn row_offset(row: usize, shape: &[usize], strides: &[usize]) -> usize {
+    let mut rest = row;
+    let mut offset = 0;
+    for d in (0..shape.len().saturating_sub(1)).rev() {
+        offset += rest % shape[d] * strides[d];
+        rest /= shape[d];
+    }
+    offset
+}
+
+/// Applies `f` to the elements of `a` and `b` broadcast to `shape`, which `dst` is of.
+fn broadcast_map2<T, F>(pool: &ThreadPool,
+                        a: &[T], a_dims: &[usize],
+                        b: &[T], b_dims: &[usize],
+                        shape: &[usize], dst: &mut [T], f: F)
+                        -> Result<(), Error>
+    where T: Float + Send + Sync,
+          F: Fn(T, T) -> T + Sync
+{
+    if a.len() == dst.len() && b.len() == dst.len() {
+        return map2(pool, a, b, dst, f);
+    }
+    let a_strides = broadcast_strides(a_dims, shape);
+    let b_strides = broadcast_strides(b_dims, shape);
+    let inner = usize::max(1, shape.last().cloned().unwrap_or(1));
+    let a_inner = a_strides.last().cloned().unwrap_or(0);
+    let b_inner = b_strides.last().cloned().unwrap_or(0);
+    pool.install(|| {
+        dst.par_chunks_mut(inner)
+            .with_min_len(usize::max(1, PARALLEL_CHUNK / inner))
+            .enumerate()
+            .for_each(|(row, d)| {
+                let a_offset = row_offset(row, shape, &a_strides);
+                let b_offset = row_offset(row, shape, &b_strides);
+                for (i, d) in d.iter_mut().enumerate() {
+                    *d = f(a[a_offset + i * a_inner], b[b_offset + i * b_inner]);
+                }
+            });
+    });
+    Ok(())
+}
+
+/// Like `broadcast_map2`, but with `dst` as the first operand, so only `src`
+/// is broadcast.
+fn broadcast_map2_inplace<T, F>(pool: &ThreadPool, src: &[T], src_dims: &[usize], shape: &[usize], dst: &mut [T], f: F)
+                                -> Result<(), Error>
+    where T: Float + Send + Sync,
+          F: Fn(T, T) -> T + Sync
+{
+    if src.len() == dst.len() {
+        return map2_inplace(pool, src, dst, |s, d| f(d, s));
+    }
+    let strides = broadcast_strides(src_dims, shape);
+    let inner = usize::max(1, shape.last().cloned().unwrap_or(1));
+    let src_inner = strides.last().cloned().unwrap_or(0);
+    pool.install(|| {
+        dst.par_chunks_mut(inner)
+            .with_min_len(usize::max(1, PARALLEL_CHUNK / inner))
+            .enumerate()
+            .for_each(|(row, d)| {
+                let offset = row_offset(row, shape, &strides);
+                for (i, d) in d.iter_mut().enumerate() {
+                    *d = f(*d, src[offset + i * src_inner]);
+                }
+            });
+    });
+    Ok(())
+}
+
+fn binary_operation_fn<T: Float>(op: BinaryOperation) -> impl Fn(T, T) -> T + Sync {
+    move |a: T, b: T| match op {
+        BinaryOperation::Add => a + b,
+        BinaryOperation::Sub => a - b,
+        BinaryOperation::Mul => a * b,
+        BinaryOperation::Div => a / b,
+        BinaryOperation::Pow => a.powf(b),
+        BinaryOperation::Min => a.min(b),
+        BinaryOperation::Max => a.max(b),
+    }
+}
+
+fn unary_operation_fn<T: Float + Sync>(op: UnaryOperation) -> Result<impl Fn(T) -> T + Sync, Error> {
+    let (min, max) = match op {
+        UnaryOperation::Clamp { min, max } => {
+            if min > max {
+                return Err(PluginError::Operation("Clamp requires min to not exceed max").into());
+            }
+            (activation_param(min)?, activation_param(max)?)
+        }
+        _ => (T::zero(), T::zero()),
+    };
+    Ok(move |x: T| match op {
+        UnaryOperation::Neg => -x,
+        UnaryOperation::Abs => x.abs(),
+        UnaryOperation::Exp => x.exp(),
+        UnaryOperation::Log => x.ln(),
+        UnaryOperation::Sqrt => x.sqrt(),
+        UnaryOperation::Clamp { .. } => x.max(min).min(max),
+    })
+}
+
+impl<T> Arithmetic<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn binary_operation(&self, op: BinaryOperation,
+                        a: &SharedTensor<T>, b: &SharedTensor<T>, result: &mut SharedTensor<T>)
+                        -> Result<(), Error> {
+        let shape = broadcast_shape(a.desc(), b.desc())?;
+        if *result.desc() != shape {
+            return Err(PluginError::Operation("Result is not of the broadcast shape of the operands").into());
+        }
+        broadcast_map2(self.device().thread_pool(),
+                       read!(a, T, self), a.desc(),
+                       read!(b, T, self), b.desc(),
+                       &shape,
+                       write_only!(result, T, self),
+                       binary_operation_fn(op))
+    }
+
+    fn unary_operation(&self, op: UnaryOperation, x: &SharedTensor<T>, result: &mut SharedTensor<T>)
+                       -> Result<(), Error> {
+        map1(self.device().thread_pool(),
+             read!(x, T, self),
+             write_only!(result, T, self),
+             unary_operation_fn(op)?)
+    }
+}
+
+impl<T> ArithmeticPointwise<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn binary_operation_pointwise(&self, op: BinaryOperation, a: &mut SharedTensor<T>, b: &SharedTensor<T>)
+                                  -> Result<(), Error> {
+        let shape = broadcast_shape(a.desc(), b.desc())?;
+        if *a.desc() != shape {
+            return Err(PluginError::Operation("Operand can not be broadcast to the shape of the result").into());
+        }
+        broadcast_map2_inplace(self.device().thread_pool(),
+                               read!(b, T, self), b.desc(),
+                               &shape,
+                               read_write!(a, T, self),
+                               binary_operation_fn(op))
+    }
+
+    fn unary_operation_pointwise(&self, op: UnaryOperation, x: &mut SharedTensor<T>)
+                                 -> Result<(), Error> {
+        map1_inplace(self.device().thread_pool(),
+                     read_write!(x, T, self),
+                     unary_operation_fn(op)?)
+    }
+}
+
+/// Computes the dimensions of `x` reduced along `axes` with the reduced dimensions
+/// kept as size 1, and checks that `result` is of the shape requested by `keep_dims`.
+fn reduction_dims(x: &[usize], axes: &[usize], keep_dims: bool, result: &[usize]) -> Result<TensorDesc, Error> {
+    let mut reduced = vec![false; x.len()];
+    for &axis in axes {
+        if axis >= x.len() || reduced[axis] {
+            return Err(PluginError::Operation("Reduction axes have to be distinct dimensions of the input").into());
+        }
+        reduced[axis] = true;
+    }
+    let kept: TensorDesc = x.iter().zip(&reduced).map(|(&dim, &r)| if r { 1 } else { dim }).collect();
+    let mut expected: TensorDesc = if keep_dims {
+        kept.clone()
+    } else {
+        x.iter().zip(&reduced).filter(|&(_, &r)| !r).map(|(&dim, _)| dim).collect()
+    };
+    if expected.is_empty() {
+        expected.push(1);
+    }
+    if *result != *expected {
+        return Err(PluginError::Operation("Result is not of the shape of the reduced input").into());
+    }
+    Ok(kept)
+}
+
+/// Offsets of all elements of a Tensor of dimensions `dims`, that are reduced
+/// into the same element of a result of dimensions `kept`, relative to the first one.
+fn reduced_offsets(dims: &TensorDesc, kept: &[usize]) -> Vec<usize> {
+    let strides = dims.default_stride();
+    let window: Vec<usize> = dims.iter().zip(kept).map(|(&dim, &k)| if k == dim { 1 } else { dim }).collect();
+    if window.iter().any(|&dim| dim == 0) {
+        return Vec::new();
+    }
+    let mut offsets = Vec::with_capacity(window.iter().product());
+    let mut idx = vec![0; window.len()];
+    loop {
+        offsets.push(idx.iter().zip(&strides).map(|(i, s)| i * s).sum());
+        if !next_index(&mut idx, &window) {
+            break;
+        }
+    }
+    offsets
+}
+
+/// Offset of the first element reduced into the `i`-th element of a result of dimensions `kept`.
+fn reduction_base(i: usize, kept: &[usize], strides: &[usize]) -> usize {
+    let mut rest = i;
+    let mut offset = 0;
+    for d in (0..kept.len()).rev() {
+        offset += rest % kept[d] * strides[d];
+        rest /= kept[d];
+    }
+    offset
+}
+
+/// Stores `f` of the elements of `x` reduced into each element of `dst`.
+fn reduce_map<T, R, F>(pool: &ThreadPool, x: &[T], dims: &TensorDesc, kept: &[usize], dst: &mut [R], f: F)
+    where T: Float + Send + Sync,
+          R: Send,
+          F: Fn(&mut dyn Iterator<Item = T>) -> R + Sync
+{
+    let strides = dims.default_stride();
+    let offsets = reduced_offsets(dims, kept);
+    pool.install(|| {
+        dst.par_iter_mut()
+            .enumerate()
+            .with_min_len(usize::max(1, PARALLEL_CHUNK / usize::max(1, offsets.len())))
+            .for_each(|(i, d)| {
+                let base = reduction_base(i, kept, &strides);
+                *d = f(&mut offsets.iter().map(|&offset| x[base + offset]));
+            });
+    });
+}
+
+impl<T> Reduction<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn reduce(&self, op: ReductionOperation, x: &SharedTensor<T>, axes: &[usize], keep_dims: bool,
+              result: &mut SharedTensor<T>)
+              -> Result<(), Error> {
+        let kept = reduction_dims(x.desc(), axes, keep_dims, result.desc())?;
+        let count = T::from(x.desc().size() / usize::max(1, kept.size())).unwrap();
+        reduce_map(self.device().thread_pool(),
+                   read!(x, T, self),
+                   x.desc(),
+                   &kept,
+                   write_only!(result, T, self),
+                   |xs| match op {
+                       ReductionOperation::Sum => xs.fold(T::zero(), |acc, x| acc + x),
+                       ReductionOperation::Mean => xs.fold(T::zero(), |acc, x| acc + x) / count,
+                       ReductionOperation::Max => xs.fold(T::neg_infinity(), T::max),
+                       ReductionOperation::Min => xs.fold(T::infinity(), T::min),
+                       ReductionOperation::Norm1 => xs.fold(T::zero(), |acc, x| acc + x.abs()),
+                       ReductionOperation::Norm2 => xs.fold(T::zero(), |acc, x| acc + x * x).sqrt(),
+                   });
+        Ok(())
+    }
+
+    fn argmax(&self, x: &SharedTensor<T>, axis: usize, keep_dims: bool, result: &mut SharedTensor<u32>)
+              -> Result<(), Error> {
+        let kept = reduction_dims(x.desc(), &[axis], keep_dims, result.desc())?;
+        reduce_map(self.device().thread_pool(),
+                   read!(x, T, self),
+                   x.desc(),
+                   &kept,
+                   write_only!(result, u32, self),
+                   |xs| {
+                       let (index, _) = xs.enumerate().fold((0, T::neg_infinity()), |(best, max), (i, x)| {
+                           if x > max { (i, x) } else { (best, max) }
+                       });
+                       index as u32
+                   });
+        Ok(())
+    }
+}
+
+/// Checks that `axes` is a permutation of the dimensions of `x`.
+fn check_permutation(x: &[usize], axes: &[usize]) -> Result<(), Error> {
+    let mut seen = vec![false; x.len()];
+    if axes.len() != x.len() {
+        return Err(PluginError::Operation("Permutation has to name every dimension of the input").into());
+    }
+    for &axis in axes {
+        if axis >= x.len() || seen[axis] {
+            return Err(PluginError::Operation("Permutation has to name every dimension of the input once").into());
+        }
+        seen[axis] = true;
+    }
+    Ok(())
+}
+
+/// Copies `src` to `dst` of dimensions `shape`, where the element of `dst` at index
+/// `idx` is read from `src` at offset `sum(idx[i] * strides[i])`.
+fn strided_copy<T>(pool: &ThreadPool, src: &[T], strides: &[usize], shape: &[usize], dst: &mut [T])
+    where T: Float + Send + Sync
+{
+    let inner = usize::max(1, shape.last().cloned().unwrap_or(1));
+    let inner_stride = strides.last().cloned().unwrap_or(0);
+    pool.install(|| {
+        dst.par_chunks_mut(inner)
+            .with_min_len(usize::max(1, PARALLEL_CHUNK / inner))
+            .enumerate()
+            .for_each(|(row, d)| {
+                let offset = row_offset(row, shape, strides);
+                for (i, d) in d.iter_mut().enumerate() {
+                    *d = src[offset + i * inner_stride];
+                }
+            });
+    });
+}
+
+impl<T> Permute<T> for Backend<Native>
+    where T: Float + Default + Send + Sync
+{
+    fn permute(&self, x: &SharedTensor<T>, result: &mut SharedTensor<T>, axes: &[usize])
+               -> Result<(), Error> {
+        check_permutation(x.desc(), axes)?;
+        let x_stride = x.desc().default_stride();
+        let shape: Vec<usize> = axes.iter().map(|&axis| x.desc()[axis]).collect();
+        let strides: Vec<usize> = axes.iter().map(|&axis| x_stride[axis]).collect();
+        if *result.desc() != shape {
+            return Err(PluginError::Operation("Result is not of the shape of the permuted input").into());
+        }
+        strided_copy(self.device().thread_pool(),
+                     read!(x, T, self),
+                     &strides,
+                     &shape,
+                     write_only!(result, T, self));
+        Ok(())
+    }
+
+    fn permute_grad(&self, x_diff: &SharedTensor<T>, result_diff: &mut SharedTensor<T>, axes: &[usize])
+                    -> Result<(), Error> {
+        check_permutation(x_diff.desc(), axes)?;
+        // the `axes[i]`-th dimension of the input is the `i`-th one of the output
+        let x_diff_stride = x_diff.desc().default_stride();
+        let mut shape = vec![0; axes.len()];
+        let mut strides = vec![0; axes.len()];
+        for (i, &axis) in axes.iter().enumerate() {
+            shape[axis] = x_diff.desc()[i];
+            strides[axis] = x_diff_stride[i];
+        }
+        if *result_diff.desc() != shape {
+            return Err(PluginError::Operation("Result is not of the shape of the input of the permutation").into());
+        }
+        strided_copy(self.device().thread_pool(),
+                     read!(x_diff, T, self),
+                     &strides,
+                     &shape,
+                     write_only!(result_diff, T, self));
+        Ok(())
+    }
+}
+
+/// Checks that `parts` joined along `axis` are of the dimensions `whole`.
+///
+/// Returns the number of consecutive elements every part contributes to each of
+/// the blocks `whole` consists of.
+fn concat_blocks(parts: &[TensorDesc], whole: &TensorDesc, axis: usize) -> Result<Vec<usize>, Error> {
+    if parts.is_empty() {
+        return Err(PluginError::Operation("Concatenation needs at least one part").into());
+    }
+    if axis >= whole.len() {
+        return Err(PluginError::Operation("Concatenation axis exceeds the dimensions of the Tensor").into());
+    }
+    let mut joined = 0;
+    for part in parts {
+        let matches = part.len() == whole.len() &&
+            part.iter().zip(whole.iter()).enumerate().all(|(d, (p, w))| d == axis || p == w);
+        if !matches {
+            return Err(PluginError::Operation("Parts of a concatenation may only differ in the dimension of the axis").into());
+        }
+        joined += part[axis];
+    }
+    if joined != whole[axis] {
+        return Err(PluginError::Operation("Parts of a concatenation do not add up to the joined Tensor").into());
+    }
+    let inner: usize = whole[axis + 1..].iter().product();
+    Ok(parts.iter().map(|part| part[axis] * inner).collect())
+}
+
+/// Copies the `parts` into consecutive blocks of `whole`.
+fn join_blocks<T: Copy>(parts: &[&[T]], blocks: &[usize], whole: &mut [T]) {
+    let total: usize = blocks.iter().sum();
+    let mut offset = 0;
+    for (part, &block) in parts.iter().zip(blocks) {
+        if block == 0 {
+            continue;
+        }
+        for (outer, chunk) in part.chunks(block).enumerate() {
+            let start = outer * total + offset;
+            whole[start..start + block].copy_from_slice(chunk);
+        }
+        offset += block;
+    }
+}
+
+/// Copies consecutive blocks of `whole` into the `parts`.
+fn split_blocks<T: Copy>(whole: &[T], blocks: &[usize], parts: &mut [&mut [T]]) {
+    let total: usize = blocks.iter().sum();
+    let mut offset = 0;
+    for (part, &block) in parts.iter_mut().zip(blocks) {
+        if block == 0 {
+            continue;
+        }
+        for (outer, chunk) in part.chunks_mut(block).enumerate() {
+            let start = outer * total + offset;
+            chunk.copy_from_slice(&whole[start..start + block]);
+        }
+        offset += block;
+    }
+}
+
+impl<T> Concat<T> for Backend<Native>
+    where T: Float + Default
+{
+    fn concat(&self, inputs: &[&SharedTensor<T>], result: &mut SharedTensor<T>, axis: usize)
+              -> Result<(), Error> {
+        let descs: Vec<TensorDesc> = inputs.iter().map(|x| x.desc().clone()).collect();
+        let blocks = concat_blocks(&descs, result.desc(), axis)?;
+        let parts: Vec<&[T]> = inputs.iter().map(|x| read!(x, T, self)).collect();
+        join_blocks(&parts, &blocks, write_only!(result, T, self));
+        Ok(())
+    }
+
+    fn concat_grad(&self, result_diff: &SharedTensor<T>, inputs_diff: &mut [&mut SharedTensor<T>], axis: usize)
+                   -> Result<(), Error> {
+        let descs: Vec<TensorDesc> = inputs_diff.iter().map(|dx| dx.desc().clone()).collect();
+        let blocks = concat_blocks(&descs, result_diff.desc(), axis)?;
+        let mut parts: Vec<&mut [T]> = inputs_diff.iter_mut().map(|dx| write_only!(dx, T, self)).collect();
+        split_blocks(read!(result_diff, T, self), &blocks, &mut parts);
+        Ok(())
+    }
+}
+
+impl<T> Split<T> for Backend<Native>
+    where T: Float + Default
+{
+    fn split(&self, x: &SharedTensor<T>, results: &mut [&mut SharedTensor<T>], axis: usize)
+             -> Result<(), Error> {
+        let descs: Vec<TensorDesc> = results.iter().map(|r| r.desc().clone()).collect();
+        let blocks = concat_blocks(&descs, x.desc(), axis)?;
+        let mut parts: Vec<&mut [T]> = results.iter_mut().map(|r| write_only!(r, T, self)).collect();
+        split_blocks(read!(x, T, self), &blocks, &mut parts);
+        Ok(())
+    }
+
+    fn split_grad(&self, results_diff: &[&SharedTensor<T>], x_diff: &mut SharedTensor<T>, axis: usize)
+                  -> Result<(), Error> {
+        let descs: Vec<TensorDesc> = results_diff.iter().map(|dr| dr.desc().clone()).collect();
+        let blocks = concat_blocks(&descs, x_diff.desc(), axis)?;
+        let parts: Vec<&[T]> = results_diff.iter().map(|dr| read!(dr, T, self)).collect();
+        join_blocks(&parts, &blocks, write_only!(x_diff, T, self));
+        Ok(())
+    }
+}
+
+/// Checks that `indices` select rows of `weight` into `result`.
+///
+/// Returns the embedding dimension and the row index for each of the `indices`.
+fn embedding_rows<T: Float>(weight: &TensorDesc, indices: &[T], indices_desc: &TensorDesc, result: &TensorDesc)
+                            -> Result<(usize, Vec<usize>), Error> {
+    if weight.len() != 2 || weight[1] == 0 {
+        return Err(PluginError::Operation("Embedding table needs to be of dimensions [vocab_size, embedding_dim]").into());
+    }
+    let (vocab_size, dim) = (weight[0], weight[1]);
+    let mut shape = indices_desc.clone();
+    shape.push(dim);
+    if *result != shape {
+        return Err(PluginError::Operation("Result is not of the shape of the indices followed by the embedding dimension").into());
+    }
+    indices.iter()
+        .map(|&index| match index.to_usize() {
+            Some(row) if row < vocab_size && T::from(row).unwrap() == index => Ok(row),
+            _ => Err(PluginError::Operation("Embedding index is not a row of the table").into()),
+        })
+        .collect::<Result<Vec<usize>, Error>>()
+        .map(|rows| (dim, rows))
+}
+
+impl<T> Embedding<T> for Backend<Native>
+    where T: Float + Default
+{
+    fn embedding(&self, weight: &SharedTensor<T>, indices: &SharedTensor<T>, result: &mut SharedTensor<T>)
+                 -> Result<(), Error> {
+        let (dim, rows) = embedding_rows(weight.desc(), read!(indices, T, self), indices.desc(), result.desc())?;
+        let table = read!(weight, T, self);
+        let result = write_only!(result, T, self);
+        for (chunk, row) in result.chunks_mut(dim).zip(rows) {
+            chunk.copy_from_slice(&table[row * dim..(row + 1) * dim]);
+        }
+        Ok(())
+    }
+
+    fn embedding_grad(&self, indices: &SharedTensor<T>, result_diff: &SharedTensor<T>,
+                      weight_diff: &mut SharedTensor<T>, padding_idx: Option<usize>)
+                      -> Result<(), Error> {
+        let (dim, rows) = embedding_rows(weight_diff.desc(), read!(indices, T, self), indices.desc(),
+                                         result_diff.desc())?;
+        let result_diff = read!(result_diff, T, self);
+        let weight_diff = write_only!(weight_diff, T, self);
+        for w in weight_diff.iter_mut() {
+            *w = T::zero();
+        }
+        for (chunk, row) in result_diff.chunks(dim).zip(rows) {
+            if Some(row) == padding_idx {
+                continue;
+            }
+            for (w, &dy) in weight_diff[row * dim..(row + 1) * dim].iter_mut().zip(chunk) {
+                *w = *w + dy;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The offsets into an input plane, and their weights, of every element of an upsampled plane.
+type UpsampleTaps<T> = Vec<Vec<(usize, T)>>;
+
+/// Computes the input elements, and their weights, every element of an upsampled plane is
+/// computed from.
+///
+/// Returns the number of elements of an input plane and of a result plane along with the
+/// elements, which are offsets into the input plane.
+fn upsample_taps<T: Float>(mode: UpsampleMode, x: &TensorDesc, result: &TensorDesc)
+                           -> Result<(usize, usize, UpsampleTaps<T>), Error> {
+    if x.len() < 3 || x.len() > 5 || result.len() != x.len() {
+        return Err(PluginError::Operation("Upsample needs inputs of [N, C] followed by one to three spatial dimensions").into());
+    }
+    if x[..2] != result[..2] {
+        return Err(PluginError::Operation("Upsample result differs from the input in the batch or channel dimension").into());
+    }
+    if x[2..].iter().chain(&result[2..]).any(|&dim| dim == 0) {
+        return Err(PluginError::Operation("Upsample spatial dimensions need to be greater than zero").into());
+    }
+
+    let mut taps = vec![vec![(0, T::one())]];
+    for (&input, &output) in x[2..].iter().zip(&result[2..]) {
+        let scale = input as f64 / output as f64;
+        let dim_taps = (0..output).map(|o| match mode {
+            UpsampleMode::Nearest => vec![(((o as f64 * scale).floor() as usize).min(input - 1), 1.0)],
+            UpsampleMode::Bilinear => {
+                let source = ((o as f64 + 0.5) * scale - 0.5).max(0.0);
+                let lower = (source.floor() as usize).min(input - 1);
+                let upper = (lower + 1).min(input - 1);
+                let lambda = source - lower as f64;
+                vec![(lower, 1.0 - lambda), (upper, lambda)]
+            }
+        }).collect::<Vec<_>>();
+        taps = taps.iter()
+            .flat_map(|outer| dim_taps.iter().map(move |inner| {
+                outer.iter()
+                    .flat_map(|&(i, w)| inner.iter().map(move |&(j, v)| (i * input + j, w * T::from(v).unwrap())))
+                    .collect()
+            }))
+            .collect();
+    }
+    Ok((x[2..].iter().product(), result[2..].iter().product(), taps))
+}
+
+impl<T> Upsample<T> for Backend<Native>
+    where T: Float + Default
+{
+    fn upsample(&self, mode: UpsampleMode, x: &SharedTensor<T>, result: &mut SharedTensor<T>)
+                -> Result<(), Error> {
+        let (in_plane, out_plane, taps) = upsample_taps::<T>(mode, x.desc(), result.desc())?;
+        let x = read!(x, T, self);
+        let result = write_only!(result, T, self);
+        for (x_plane, r_plane) in x.chunks(in_plane).zip(result.chunks_mut(out_plane)) {
+            for (r, element_taps) in r_plane.iter_mut().zip(&taps) {
+                *r = element_taps.iter().fold(T::zero(), |acc, &(i, w)| acc + w * x_plane[i]);
+            }
+        }
+        Ok(())
+    }
+
+    fn upsample_grad(&self, mode: UpsampleMode, result_diff: &SharedTensor<T>, x_diff: &mut SharedTensor<T>)
+                     -> Result<(), Error> {
+        let (in_plane, out_plane, taps) = upsample_taps::<T>(mode, x_diff.desc(), result_diff.desc())?;
+        let result_diff = read!(result_diff, T, self);
+        let x_diff = write_only!(x_diff, T, self);
+        for dx in x_diff.iter_mut() {
+            *dx = T::zero();
+        }
+        for (dx_plane, dr_plane) in x_diff.chunks_mut(in_plane).zip(result_diff.chunks(out_plane)) {
+            for (&dr, element_taps) in dr_plane.iter().zip(&taps) {
+                for &(i, w) in element_taps {
+                    dx_plane[i] = dx_plane[i] + w * dr;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Computes the input element every element of a padded Tensor is copied from.
+///
+/// Elements filled with a constant have no source.
+fn pad_sources<T>(mode: PadMode<T>, x: &TensorDesc, padding: &[(usize, usize)], result: &TensorDesc)
+                  -> Result<Vec<Option<usize>>, Error> {
+    if padding.len() != x.len() {
+        return Err(PluginError::Operation("Padding needs a pair of sizes for every dimension of the input").into());
+    }
+    let mut shape = x.clone();
+    for (dim, &(before, after)) in shape.iter_mut().zip(padding) {
+        *dim += before + after;
+    }
+    if *result != shape {
+        return Err(PluginError::Operation("Result is not of the shape of the input extended by the padding").into());
+    }
+
+    let mut sources = vec![Some(0)];
+    for (&input, &(before, after)) in x.iter().zip(padding) {
+        let dim_sources = (0..input + before + after).map(|o| {
+            let i = o as isize - before as isize;
+            match mode {
+                PadMode::Constant(_) if i < 0 || i >= input as isize => Ok(None),
+                PadMode::Constant(_) => Ok(Some(i as usize)),
+                PadMode::Reflect if before >= input || after >= input => {
+                    Err(PluginError::Operation("Reflect padding needs to be smaller than the padded dimension").into())
+                }
+                PadMode::Reflect if i < 0 => Ok(Some(-i as usize)),
+                PadMode::Reflect if i >= input as isize => Ok(Some(2 * (input - 1) - i as usize)),
+                PadMode::Reflect => Ok(Some(i as usize)),
+                PadMode::Replicate if input == 0 => {
+                    Err(PluginError::Operation("Replicate padding needs a non-empty dimension").into())
+                }
+                PadMode::Replicate => Ok(Some(i.max(0).min(input as isize - 1) as usize)),
+            }
+        }).collect::<Result<Vec<Option<usize>>, Error>>()?;
+        sources = sources.iter()
+            .flat_map(|&outer| dim_sources.iter().map(move |&inner| match (outer, inner) {
+                (Some(outer), Some(inner)) => Some(outer * input + inner),
+                _ => None,
+            }))
+            .collect();
+    }
+    Ok(sources)
+}
+
+impl<T> Pad<T> for Backend<Native>
+    where T: Float + Default
+{
+    fn pad(&self, mode: PadMode<T>, x: &SharedTensor<T>, padding: &[(usize, usize)], result: &mut SharedTensor<T>)
+           -> Result<(), Error> {
+        let sources = pad_sources(mode, x.desc(), padding, result.desc())?;
+        let value = match mode {
+            PadMode::Constant(value) => value,
+            _ => T::zero(),
+        };
+        let x = read!(x, T, self);
+        let result = write_only!(result, T, self);
+        for (r, source) in result.iter_mut().zip(sources) {
+            *r = source.map_or(value, |i| x[i]);
+        }
+        Ok(())
+    }
+
+    fn pad_grad(&self, mode: PadMode<T>, result_diff: &SharedTensor<T>, padding: &[(usize, usize)],
+                x_diff: &mut SharedTensor<T>)
+                -> Result<(), Error> {
+        let sources = pad_sources(mode, x_diff.desc(), padding, result_diff.desc())?;
+        let result_diff = read!(result_diff, T, self);
+        let x_diff = write_only!(x_diff, T, self);
+        for dx in x_diff.iter_mut() {
+            *dx = T::zero();
+        }
+        for (&dr, source) in result_diff.iter().zip(sources) {
+            if let Some(i) = source {
+                x_diff[i] = x_diff[i] + dr;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The layout of a softmax cross-entropy over an input of `[N, C, ...]`.
+struct CrossEntropyLayout {
+    classes: usize,
+    /// The number of samples per entry of the batch
+    inner: usize,
+    /// The class of every sample, `None` if it is ignored, or `None` for probability targets
+    target_classes: Option<Vec<Option<usize>>>,
+}
+
+impl CrossEntropyLayout {
+    fn new<T: Float>(x: &TensorDesc, target: &[T], target_desc: &TensorDesc, class_weights: Option<&TensorDesc>,
+                     ignore_index: Option<usize>, result: &TensorDesc)
+                     -> Result<CrossEntropyLayout, Error> {
+        if x.len() < 2 || x[1] == 0 {
+            return Err(PluginError::Operation("Softmax cross-entropy needs an input of [N, C] or [N, C, ...]").into());
+        }
+        let classes = x[1];
+        let mut shape = x.clone();
+        shape.remove(1);
+        if *result != shape {
+            return Err(PluginError::Operation("Result is not of the shape of the input without the class dimension").into());
+        }
+        if let Some(weights) = class_weights {
+            if *weights != vec![classes] {
+                return Err(PluginError::Operation("Class weights need to be of dimensions [C]").into());
+            }
+        }
+
+        let target_classes = if target_desc == x {
+            None
+        } else if target_desc.size() == result.size() {
+            let classes = target.iter()
+                .map(|&class| match class.to_usize() {
+                    Some(c) if T::from(c).unwrap() != class => Err(()),
+                    Some(c) if Some(c) == ignore_index => Ok(None),
+                    Some(c) if c < classes => Ok(Some(c)),
+                    _ => Err(()),
+                })
+                .collect::<Result<Vec<Option<usize>>, ()>>()
+                .map_err(|_| PluginError::Operation("Softmax cross-entropy target is not a class of the input"))?;
+            Some(classes)
+        } else {
+            return Err(PluginError::Operation("Target holds neither a class per sample nor a probability per class").into());
+        };
+
+        Ok(CrossEntropyLayout {
+            classes,
+            inner: x[2..].iter().product(),
+            target_classes,
+        })
+    }
+
+    /// The offset of the score of `class` for `sample` in the input.
+    fn index(&self, sample: usize, class: usize) -> usize {
+        (sample / self.inner * self.classes + class) * self.inner + sample % self.inner
+    }
+
+    /// Computes the logarithmic softmax over the classes of `sample`, subtracting the largest
+    /// score first for numerical stability.
+    fn log_softmax<T: Float>(&self, x: &[T], sample: usize) -> Vec<T> {
+        let scores: Vec<T> = (0..self.classes).map(|class| x[self.index(sample, class)]).collect();
+        let max = scores.iter().fold(T::neg_infinity(), |max, &score| max.max(score));
+        let sum = scores.iter().fold(T::zero(), |sum, &score| sum + (score - max).exp());
+        let log_sum = max + sum.ln();
+        scores.iter().map(|&score| score - log_sum).collect()
+    }
+}
+
+impl<T> SoftmaxCrossEntropy<T> for Backend<Native>
+    where T: Float + Default
+{
+    fn softmax_cross_entropy(&self, x: &SharedTensor<T>, target: &SharedTensor<T>,
+                             class_weights: Option<&SharedTensor<T>>, ignore_index: Option<usize>,
+                             result: &mut SharedTensor<T>)
+                             -> Result<(), Error> {
+        let layout = CrossEntropyLayout::new(x.desc(), read!(target, T, self), target.desc(),
+                                             class_weights.map(|weights| weights.desc()), ignore_index,
+                                             result.desc())?;
+        let weights = match class_weights {
+            Some(weights) => read!(weights, T, self).to_vec(),
+            None => vec![T::one(); layout.classes],
+        };
+        let x = read!(x, T, self);
+        let target = read!(target, T, self);
+        let result = write_only!(result, T, self);
+        for (sample, r) in result.iter_mut().enumerate() {
+            *r = match layout.target_classes {
+                Some(ref classes) => match classes[sample] {
+                    Some(class) => -weights[class] * layout.log_softmax(x, sample)[class],
+                    None => T::zero(),
+                },
+                None => layout.log_softmax(x, sample).iter().enumerate()
+                    .fold(T::zero(), |loss, (class, &log_p)| {
+                        loss - weights[class] * target[layout.index(sample, class)] * log_p
+                    }),
+            };
+        }
+        Ok(())
+    }
+
+    fn softmax_cross_entropy_grad(&self, x: &SharedTensor<T>, target: &SharedTensor<T>,
+                                  class_weights: Option<&SharedTensor<T>>, ignore_index: Option<usize>,
+                                  result_diff: &SharedTensor<T>, x_diff: &mut SharedTensor<T>)
+                                  -> Result<(), Error> {
+        let layout = CrossEntropyLayout::new(x.desc(), read!(target, T, self), target.desc(),
+                                             class_weights.map(|weights| weights.desc()), ignore_index,
+                                             result_diff.desc())?;
+        if x_diff.desc() != x.desc() {
+            return Err(PluginError::Operation("Input gradient is not of the shape of the input").into());
+        }
+        let weights = match class_weights {
+            Some(weights) => read!(weights, T, self).to_vec(),
+            None => vec![T::one(); layout.classes],
+        };
+        let x = read!(x, T, self);
+        let target = read!(target, T, self);
+        let result_diff = read!(result_diff, T, self);
+        let x_diff = write_only!(x_diff, T, self);
+        for (sample, &dr) in result_diff.iter().enumerate() {
+            // `d loss / d x_c = p_c * sum_k(w_k * t_k) - w_c * t_c`
+            let weighted_target: Vec<T> = match layout.target_classes {
+                Some(ref classes) => {
+                    let mut weighted_target = vec![T::zero(); layout.classes];
+                    if let Some(class) = classes[sample] {
+                        weighted_target[class] = weights[class];
+                    }
+                    weighted_target
+                }
+                None => (0..layout.classes)
+                    .map(|class| weights[class] * target[layout.index(sample, class)])
+                    .collect(),
+            };
+            let total = weighted_target.iter().fold(T::zero(), |sum, &t| sum + t);
+            for (class, &log_p) in layout.log_softmax(x, sample).iter().enumerate() {
+                x_diff[layout.index(sample, class)] = dr * (log_p.exp() * total - weighted_target[class]);
+            }
+        }
+        Ok(())
+    }
+}
+
 // convolution is not needed here, it is well implemented without the macro madness
 impl_ops_sigmoid_for!(f32, Backend<Native>);
 impl_ops_relu_for!(f32, Backend<Native>);
 impl_ops_tanh_for!(f32, Backend<Native>);
 impl_ops_softmax_for!(f32, Backend<Native>);
 impl_ops_log_softmax_for!(f32, Backend<Native>);
-// impl_ops_lrn_for!(f32, Backend<Native>);
 
 //impl NN<f64> for Backend<Native> {
 //type CC = helper::ConvolutionConfig;
@@ -972,4 +3471,3 @@ impl_ops_relu_for!(f64, Backend<Native>);
 impl_ops_tanh_for!(f64, Backend<Native>);
 impl_ops_softmax_for!(f64, Backend<Native>);
 impl_ops_log_softmax_for!(f64, Backend<Native>);
-// impl_ops_lrn_for!(f64, Backend<Native>);
@@ -86,17 +86,20 @@
 //! | Tanh                 | cuDNN v5 or later | -         | Rust      |
 //! | TanhPointwise        | cuDNN v5 or later | -         | Rust      |
 //! |                      |                   |           |           |
-//! | Normalization (LRN)  | cuDNN v5 or later | -         | -         |
+//! | Normalization (LRN)  | cuDNN v5 or later | -         | Rust      |
 //! |                      |                   |           |           |
 //! | Dropout              | cuDNN v5 or later | -         | Rust      |
 //! |                      |                   |           |           |
-//! | Convolution          | cuDNN v5 or later | -         | Rust(fwd) |
+//! | Convolution          | cuDNN v5 or later | -         | Rust      |
 //! |                      |                   |           |           |
 //! | Softmax              | cuDNN v5 or later | -         | Rust      |
 //! | LogSoftmax           | cuDNN v5 or later | -         | Rust      |
 //! |                      |                   |           |           |
-//! | Pooling Max          | cuDNN v5 or later | -         | Rust(fwd) |
-//! | Pooling Avg          | cuDNN v5 or later | -         | -         |
+//! | Pooling Max          | cuDNN v5 or later | -         | Rust      |
+//! | Pooling Avg          | cuDNN v5 or later | -         | Rust      |
+//! |                      |                   |           |           |
+//! | RNN (ReLU, Tanh)     | cuDNN v7 or later | -         | Rust      |
+//! | LSTM, GRU            | cuDNN v7 or later | -         | Rust      |
 //!
 //! [coaster]: https://github.com/spearow/coaster
 //! [coaster-docs]: https://spearow.github.io/coaster
@@ -123,10 +126,14 @@ extern crate rcudnn as cudnn;
 extern crate libc;
 extern crate log;
 
+#[cfg(feature = "native")]
+extern crate coaster_blas as coblas;
 #[cfg(feature = "native")]
 extern crate rand_hc;
 #[cfg(feature = "native")]
 extern crate rand;
+#[cfg(feature = "native")]
+extern crate rayon;
 
 mod plugin;
 pub mod frameworks;
@@ -25,9 +25,9 @@ pub enum ConvForwardAlgo {
     ///
     /// Needs a significant memory workspace.
     FFTTiling,
-    /// Compute the convolution without implicit or explicit matrix-multiplication. **Do not try to use this**.
+    /// Compute the convolution without implicit or explicit matrix-multiplication.
     ///
-    /// Listed in cuDNN docs but cuDNN does not provide a implementation.
+    /// Does not need any memory workspace. Listed in cuDNN docs but cuDNN does not provide a implementation.
     Direct,
     /// Winograd  Transform
     Winograd,
@@ -50,6 +50,12 @@ impl ConvForwardAlgo {
 pub enum ConvBackwardFilterAlgo {
     /// Attempt to automatically find the best algorithm of all the other available ones.
     Auto,
+    /// Compute the convolution as explicit matrix product.
+    ///
+    /// Needs a significant memory workspace. Not provided by cuDNN.
+    ///
+    /// The results are deterministic.
+    GEMM,
     /// Compute the convolution as matrix product without forming the matrix that holds the input data.
     ///
     /// Does not need any memory workspace.
@@ -74,6 +80,12 @@ pub enum ConvBackwardFilterAlgo {
     FFT,
     /// Winograd  Transform Non-Fused
     WinogradNonFused,
+    /// Compute the convolution without implicit or explicit matrix-multiplication.
+    ///
+    /// Does not need any memory workspace. Not provided by cuDNN.
+    ///
+    /// The results are deterministic.
+    Direct,
 }
 
 impl ConvBackwardFilterAlgo {
@@ -91,6 +103,12 @@ impl ConvBackwardFilterAlgo {
 pub enum ConvBackwardDataAlgo {
     /// Attempt to automatically find the best algorithm of all the other available ones.
     Auto,
+    /// Compute the convolution as explicit matrix product.
+    ///
+    /// Needs a significant memory workspace. Not provided by cuDNN.
+    ///
+    /// The results are deterministic.
+    GEMM,
     /// Compute the convolution as matrix product without forming the matrix that holds the input data.
     ///
     /// Does not need any memory workspace.
@@ -119,6 +137,12 @@ pub enum ConvBackwardDataAlgo {
     Winograd,
     /// Winograd  Transform Non-Fused
     WinogradNonFused,
+    /// Compute the convolution without implicit or explicit matrix-multiplication.
+    ///
+    /// Does not need any memory workspace. Not provided by cuDNN.
+    ///
+    /// The results are deterministic.
+    Direct,
 }
 
 impl ConvBackwardDataAlgo {
@@ -151,8 +175,8 @@ pub trait ConvolutionConfig<F> {
 ///
 /// Needs to be implemented for Operation specific configurations.
 pub trait RnnConfig<F> {
-    /// Workspace Size - Overwritten by each plugin method except native, which doesn't require
-    /// a workspace size.
+    /// Workspace Size - Overwritten by each plugin method. The workspace has to be kept
+    /// between the backward passes w.r.t. the data and the weights.
     fn workspace_size(&self) -> usize { 0 }
 }
 
@@ -170,6 +194,12 @@ pub trait NN<F> {
     type CDROP: NNOperationConfig<F>;
     /// The RNN Operation Config representation for this Plugin
     type CRNN: NNOperationConfig<F> + RnnConfig<F>;
+    /// The Batch Normalization Operation Config representation for this Plugin.
+    type CBN: NNOperationConfig<F>;
+    /// The Layer Normalization Operation Config representation for this Plugin.
+    type CLN: NNOperationConfig<F>;
+    /// The Group Normalization Operation Config representation for this Plugin.
+    type CGN: NNOperationConfig<F>;
 
     /// Initializes the Plugin.
     fn init_nn();
@@ -278,6 +308,552 @@ pub trait TanhPointwise<F> : NN<F> {
                            -> Result<(), crate::co::error::Error>;
 }
 
+/// Provides the functionality for a Backend to support LeakyReLU operations.
+///
+/// `y = max(0, x) + slope * min(0, x)`
+///
+/// `slope` is the factor applied to negative inputs.
+pub trait LeakyRelu<F> : NN<F> {
+    /// Computes the [Leaky Rectified linear units][leaky_relu] over the input Tensor `x`.
+    /// [leaky_relu]: https://en.wikipedia.org/wiki/Rectifier_(neural_networks)#Leaky_ReLU
+    ///
+    /// Saves the result to `result`.
+    fn leaky_relu(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>, slope: f64)
+                  -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of [LeakyReLU][leaky_relu] over the input Tensor `x`.
+    /// [leaky_relu]: https://en.wikipedia.org/wiki/Rectifier_(neural_networks)#Leaky_ReLU
+    ///
+    /// Saves the result to `result_diff`.
+    fn leaky_relu_grad(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>,
+                       result: &SharedTensor<F>, result_diff: &mut SharedTensor<F>, slope: f64)
+                       -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for pointwise LeakyReLU operations (overwrites the input
+/// with the result of the operation).
+pub trait LeakyReluPointwise<F> : NN<F> {
+    /// Computes the [Leaky Rectified linear units][leaky_relu] over the input Tensor `x`.
+    /// [leaky_relu]: https://en.wikipedia.org/wiki/Rectifier_(neural_networks)#Leaky_ReLU
+    ///
+    /// Saves the result back to `x`.
+    fn leaky_relu_pointwise(&self, x: &mut SharedTensor<F>, slope: f64)
+                            -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of [LeakyReLU][leaky_relu] over the input Tensor `x`.
+    /// [leaky_relu]: https://en.wikipedia.org/wiki/Rectifier_(neural_networks)#Leaky_ReLU
+    ///
+    /// Computing the gradient from the output requires a non-negative `slope`.
+    ///
+    /// Saves the result back to `x_diff`.
+    fn leaky_relu_pointwise_grad(&self, x: &SharedTensor<F>, x_diff: &mut SharedTensor<F>, slope: f64)
+                                 -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to support ELU operations.
+///
+/// `y = x` for positive `x`, `y = alpha * (exp(x) - 1)` otherwise
+///
+/// `alpha` is the value negative inputs saturate to, times -1.
+pub trait Elu<F> : NN<F> {
+    /// Computes the [Exponential linear units][elu] over the input Tensor `x`.
+    /// [elu]: https://arxiv.org/abs/1511.07289
+    ///
+    /// Saves the result to `result`.
+    fn elu(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>, alpha: f64)
+           -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of [ELU][elu] over the input Tensor `x`.
+    /// [elu]: https://arxiv.org/abs/1511.07289
+    ///
+    /// Saves the result to `result_diff`.
+    fn elu_grad(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>,
+                result: &SharedTensor<F>, result_diff: &mut SharedTensor<F>, alpha: f64)
+                -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for pointwise ELU operations (overwrites the input
+/// with the result of the operation).
+pub trait EluPointwise<F> : NN<F> {
+    /// Computes the [Exponential linear units][elu] over the input Tensor `x`.
+    /// [elu]: https://arxiv.org/abs/1511.07289
+    ///
+    /// Saves the result back to `x`.
+    fn elu_pointwise(&self, x: &mut SharedTensor<F>, alpha: f64) -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of [ELU][elu] over the input Tensor `x`.
+    /// [elu]: https://arxiv.org/abs/1511.07289
+    ///
+    /// Computing the gradient from the output requires a non-negative `alpha`.
+    ///
+    /// Saves the result back to `x_diff`.
+    fn elu_pointwise_grad(&self, x: &SharedTensor<F>, x_diff: &mut SharedTensor<F>, alpha: f64)
+                          -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to support SELU operations.
+///
+/// `y = scale * x` for positive `x`, `y = scale * alpha * (exp(x) - 1)` otherwise,
+/// with the fixed `alpha` and `scale` that make the activations self-normalizing
+pub trait Selu<F> : NN<F> {
+    /// Computes the [Scaled exponential linear units][selu] over the input Tensor `x`.
+    /// [selu]: https://arxiv.org/abs/1706.02515
+    ///
+    /// Saves the result to `result`.
+    fn selu(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>)
+            -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of [SELU][selu] over the input Tensor `x`.
+    /// [selu]: https://arxiv.org/abs/1706.02515
+    ///
+    /// Saves the result to `result_diff`.
+    fn selu_grad(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>,
+                 result: &SharedTensor<F>, result_diff: &mut SharedTensor<F>)
+                 -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for pointwise SELU operations (overwrites the input
+/// with the result of the operation).
+pub trait SeluPointwise<F> : NN<F> {
+    /// Computes the [Scaled exponential linear units][selu] over the input Tensor `x`.
+    /// [selu]: https://arxiv.org/abs/1706.02515
+    ///
+    /// Saves the result back to `x`.
+    fn selu_pointwise(&self, x: &mut SharedTensor<F>) -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of [SELU][selu] over the input Tensor `x`.
+    /// [selu]: https://arxiv.org/abs/1706.02515
+    ///
+    /// Saves the result back to `x_diff`.
+    fn selu_pointwise_grad(&self, x: &SharedTensor<F>, x_diff: &mut SharedTensor<F>)
+                           -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to support GELU operations.
+///
+/// `y = x * Φ(x)`, using the tanh approximation of the Gaussian CDF `Φ`
+pub trait Gelu<F> : NN<F> {
+    /// Computes the [Gaussian error linear units][gelu] over the input Tensor `x`.
+    /// [gelu]: https://arxiv.org/abs/1606.08415
+    ///
+    /// Saves the result to `result`.
+    fn gelu(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>)
+            -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of [GELU][gelu] over the input Tensor `x`.
+    /// [gelu]: https://arxiv.org/abs/1606.08415
+    ///
+    /// Saves the result to `result_diff`.
+    fn gelu_grad(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>,
+                 result: &SharedTensor<F>, result_diff: &mut SharedTensor<F>)
+                 -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for pointwise GELU operations (overwrites the input
+/// with the result of the operation).
+pub trait GeluPointwise<F> : NN<F> {
+    /// Computes the [Gaussian error linear units][gelu] over the input Tensor `x`.
+    /// [gelu]: https://arxiv.org/abs/1606.08415
+    ///
+    /// Saves the result back to `x`.
+    fn gelu_pointwise(&self, x: &mut SharedTensor<F>) -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of [GELU][gelu] over the input Tensor `x`.
+    /// [gelu]: https://arxiv.org/abs/1606.08415
+    ///
+    /// Unlike the other pointwise gradients, `x` has to be the input of
+    /// `gelu_pointwise`, since it can not be recovered from the output.
+    ///
+    /// Saves the result back to `x_diff`.
+    fn gelu_pointwise_grad(&self, x: &SharedTensor<F>, x_diff: &mut SharedTensor<F>)
+                           -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to support Swish operations.
+///
+/// `y = x * sigmoid(x)`, also known as SiLU
+pub trait Swish<F> : NN<F> {
+    /// Computes the [Swish][swish] over the input Tensor `x`.
+    /// [swish]: https://arxiv.org/abs/1710.05941
+    ///
+    /// Saves the result to `result`.
+    fn swish(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>)
+             -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of [Swish][swish] over the input Tensor `x`.
+    /// [swish]: https://arxiv.org/abs/1710.05941
+    ///
+    /// Saves the result to `result_diff`.
+    fn swish_grad(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>,
+                  result: &SharedTensor<F>, result_diff: &mut SharedTensor<F>)
+                  -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for pointwise Swish operations (overwrites the input
+/// with the result of the operation).
+pub trait SwishPointwise<F> : NN<F> {
+    /// Computes the [Swish][swish] over the input Tensor `x`.
+    /// [swish]: https://arxiv.org/abs/1710.05941
+    ///
+    /// Saves the result back to `x`.
+    fn swish_pointwise(&self, x: &mut SharedTensor<F>) -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of [Swish][swish] over the input Tensor `x`.
+    /// [swish]: https://arxiv.org/abs/1710.05941
+    ///
+    /// Unlike the other pointwise gradients, `x` has to be the input of
+    /// `swish_pointwise`, since it can not be recovered from the output.
+    ///
+    /// Saves the result back to `x_diff`.
+    fn swish_pointwise_grad(&self, x: &SharedTensor<F>, x_diff: &mut SharedTensor<F>)
+                            -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to support Softplus operations.
+///
+/// `y = ln(1 + exp(x))`, a smooth approximation of ReLU
+pub trait Softplus<F> : NN<F> {
+    /// Computes the [Softplus][softplus] over the input Tensor `x`.
+    /// [softplus]: https://en.wikipedia.org/wiki/Rectifier_(neural_networks)#Softplus
+    ///
+    /// Saves the result to `result`.
+    fn softplus(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>)
+                -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of [Softplus][softplus] over the input Tensor `x`.
+    /// [softplus]: https://en.wikipedia.org/wiki/Rectifier_(neural_networks)#Softplus
+    ///
+    /// Saves the result to `result_diff`.
+    fn softplus_grad(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>,
+                     result: &SharedTensor<F>, result_diff: &mut SharedTensor<F>)
+                     -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for pointwise Softplus operations (overwrites the input
+/// with the result of the operation).
+pub trait SoftplusPointwise<F> : NN<F> {
+    /// Computes the [Softplus][softplus] over the input Tensor `x`.
+    /// [softplus]: https://en.wikipedia.org/wiki/Rectifier_(neural_networks)#Softplus
+    ///
+    /// Saves the result back to `x`.
+    fn softplus_pointwise(&self, x: &mut SharedTensor<F>) -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of [Softplus][softplus] over the input Tensor `x`.
+    /// [softplus]: https://en.wikipedia.org/wiki/Rectifier_(neural_networks)#Softplus
+    ///
+    /// Saves the result back to `x_diff`.
+    fn softplus_pointwise_grad(&self, x: &SharedTensor<F>, x_diff: &mut SharedTensor<F>)
+                               -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to support HardTanh operations.
+///
+/// `y = min(max(x, min_value), max_value)`
+///
+/// `min_value` is the lower bound of the output.
+/// `max_value` is the upper bound of the output.
+pub trait HardTanh<F> : NN<F> {
+    /// Computes the [hard hyperbolic Tangent][hard_tanh] over the input Tensor `x`.
+    /// [hard_tanh]: https://pytorch.org/docs/stable/generated/torch.nn.Hardtanh.html
+    ///
+    /// Saves the result to `result`.
+    fn hard_tanh(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>,
+                 min_value: f64, max_value: f64)
+                 -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of [HardTanh][hard_tanh] over the input Tensor `x`.
+    /// [hard_tanh]: https://pytorch.org/docs/stable/generated/torch.nn.Hardtanh.html
+    ///
+    /// Saves the result to `result_diff`.
+    fn hard_tanh_grad(&self, x: &SharedTensor<F>, x_diff: &SharedTensor<F>,
+                      result: &SharedTensor<F>, result_diff: &mut SharedTensor<F>,
+                      min_value: f64, max_value: f64)
+                      -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for pointwise HardTanh operations (overwrites the input
+/// with the result of the operation).
+pub trait HardTanhPointwise<F> : NN<F> {
+    /// Computes the [hard hyperbolic Tangent][hard_tanh] over the input Tensor `x`.
+    /// [hard_tanh]: https://pytorch.org/docs/stable/generated/torch.nn.Hardtanh.html
+    ///
+    /// Saves the result back to `x`.
+    fn hard_tanh_pointwise(&self, x: &mut SharedTensor<F>, min_value: f64, max_value: f64)
+                           -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of [HardTanh][hard_tanh] over the input Tensor `x`.
+    /// [hard_tanh]: https://pytorch.org/docs/stable/generated/torch.nn.Hardtanh.html
+    ///
+    /// Saves the result back to `x_diff`.
+    fn hard_tanh_pointwise_grad(&self, x: &SharedTensor<F>, x_diff: &mut SharedTensor<F>,
+                                min_value: f64, max_value: f64)
+                                -> Result<(), crate::co::error::Error>;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Elementwise operations combining two Tensors `a` and `b`.
+pub enum BinaryOperation {
+    /// `a + b`
+    Add,
+    /// `a - b`
+    Sub,
+    /// `a * b`
+    Mul,
+    /// `a / b`
+    Div,
+    /// `a` to the power of `b`
+    Pow,
+    /// The smaller one of `a` and `b`
+    Min,
+    /// The larger one of `a` and `b`
+    Max,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// Elementwise operations on a single Tensor `x`.
+pub enum UnaryOperation {
+    /// `-x`
+    Neg,
+    /// `|x|`
+    Abs,
+    /// `e` to the power of `x`
+    Exp,
+    /// The natural logarithm of `x`
+    Log,
+    /// The square root of `x`
+    Sqrt,
+    /// `min(max(x, min), max)`
+    Clamp {
+        /// The lower bound of the result.
+        min: f64,
+        /// The upper bound of the result, must not be less than `min`.
+        max: f64,
+    },
+}
+
+/// Provides the functionality for a Backend to support elementwise arithmetic.
+///
+/// The operands of a binary operation are broadcast against each other like in NumPy:
+/// their dimensions are aligned at the end and each pair of dimensions has to be equal
+/// or one of them `1`, missing leading dimensions count as `1`. A Tensor of shape `[1]`
+/// therefore acts as a scalar.
+pub trait Arithmetic<F> : NN<F> {
+    /// Computes `op` for the elements of `a` and `b`, broadcast to a common shape.
+    ///
+    /// Saves the result to `result`, which has to be of the broadcast shape.
+    fn binary_operation(&self, op: BinaryOperation,
+                        a: &SharedTensor<F>, b: &SharedTensor<F>, result: &mut SharedTensor<F>)
+                        -> Result<(), crate::co::error::Error>;
+
+    /// Computes `op` for the elements of the input Tensor `x`.
+    ///
+    /// Saves the result to `result`.
+    fn unary_operation(&self, op: UnaryOperation, x: &SharedTensor<F>, result: &mut SharedTensor<F>)
+                       -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for pointwise elementwise arithmetic (overwrites the input
+/// with the result of the operation).
+pub trait ArithmeticPointwise<F> : NN<F> {
+    /// Computes `op` for the elements of `a` and `b`, with `b` broadcast to the shape of `a`.
+    ///
+    /// Saves the result back to `a`.
+    fn binary_operation_pointwise(&self, op: BinaryOperation, a: &mut SharedTensor<F>, b: &SharedTensor<F>)
+                                  -> Result<(), crate::co::error::Error>;
+
+    /// Computes `op` for the elements of the input Tensor `x`.
+    ///
+    /// Saves the result back to `x`.
+    fn unary_operation_pointwise(&self, op: UnaryOperation, x: &mut SharedTensor<F>)
+                                 -> Result<(), crate::co::error::Error>;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Operations combining all elements along the reduced dimensions into one.
+pub enum ReductionOperation {
+    /// The sum of the elements
+    Sum,
+    /// The arithmetic mean of the elements
+    Mean,
+    /// The largest element
+    Max,
+    /// The smallest element
+    Min,
+    /// The sum of the absolute values of the elements
+    Norm1,
+    /// The euclidean norm of the elements
+    Norm2,
+}
+
+/// Provides the functionality for a Backend to reduce Tensors along some of their dimensions.
+///
+/// With `keep_dims` the reduced dimensions stay in the shape of the result with a size of `1`,
+/// e.g. `[N, 1]` for reducing the second dimension of an input of `[N, C]`. Otherwise they are
+/// removed from it, e.g. `[N]`. If all dimensions are removed, the result is of shape `[1]`.
+pub trait Reduction<F> : NN<F> {
+    /// Computes `op` over the dimensions `axes` of the input Tensor `x`.
+    ///
+    /// Saves the result to `result`.
+    fn reduce(&self, op: ReductionOperation, x: &SharedTensor<F>, axes: &[usize], keep_dims: bool,
+              result: &mut SharedTensor<F>)
+              -> Result<(), crate::co::error::Error>;
+
+    /// Finds the index of the largest element along the dimension `axis` of the input Tensor `x`.
+    ///
+    /// If the largest value occurs more than once, the first index is taken.
+    /// Saves the indices to `result`.
+    fn argmax(&self, x: &SharedTensor<F>, axis: usize, keep_dims: bool, result: &mut SharedTensor<u32>)
+              -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to reorder the dimensions of Tensors.
+///
+/// `axes` is a permutation of the dimensions of the input, the `i`-th dimension of
+/// the result is the `axes[i]`-th dimension of the input. E.g. `[0, 2, 3, 1]` turns
+/// NCHW into NHWC.
+pub trait Permute<F> : NN<F> {
+    /// Reorders the dimensions of the input Tensor `x` according to `axes`.
+    ///
+    /// Saves the result to `result`.
+    fn permute(&self, x: &SharedTensor<F>, result: &mut SharedTensor<F>, axes: &[usize])
+               -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of a permutation by `axes`.
+    ///
+    /// Reorders the gradient w.r.t. the output `x_diff` back to the dimensions of the input.
+    /// Saves the result to `result_diff`.
+    fn permute_grad(&self, x_diff: &SharedTensor<F>, result_diff: &mut SharedTensor<F>, axes: &[usize])
+                    -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to join Tensors along an axis.
+///
+/// All inputs need to have the same dimensions, except for the dimension `axis`,
+/// which is the sum of the dimensions `axis` of the inputs in the result.
+pub trait Concat<F> : NN<F> {
+    /// Joins the input Tensors `inputs` along the dimension `axis`, in order.
+    ///
+    /// Saves the result to `result`.
+    fn concat(&self, inputs: &[&SharedTensor<F>], result: &mut SharedTensor<F>, axis: usize)
+              -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of a concatenation along the dimension `axis`.
+    ///
+    /// Splits the gradient w.r.t. the output `result_diff` into the gradients w.r.t.
+    /// every input, saved to `inputs_diff`.
+    fn concat_grad(&self, result_diff: &SharedTensor<F>, inputs_diff: &mut [&mut SharedTensor<F>], axis: usize)
+                   -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to split Tensors along an axis.
+///
+/// All results need to have the same dimensions as the input, except for the dimension
+/// `axis`, which has to sum up to the dimension `axis` of the input.
+pub trait Split<F> : NN<F> {
+    /// Splits the input Tensor `x` along the dimension `axis` into consecutive parts.
+    ///
+    /// Saves the parts to `results`, the dimension `axis` of each determines its size.
+    fn split(&self, x: &SharedTensor<F>, results: &mut [&mut SharedTensor<F>], axis: usize)
+             -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of a split along the dimension `axis`.
+    ///
+    /// Joins the gradients w.r.t. every output `results_diff` into the gradient w.r.t.
+    /// the input, saved to `x_diff`.
+    fn split_grad(&self, results_diff: &[&SharedTensor<F>], x_diff: &mut SharedTensor<F>, axis: usize)
+                  -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to look up rows of an embedding table.
+///
+/// The `weight` table is of dimensions `[vocab_size, embedding_dim]`. The `indices` are
+/// stored as integral values of `F`, every one of them selects a row of the table, so
+/// the result is of the dimensions of `indices` followed by `embedding_dim`.
+pub trait Embedding<F> : NN<F> {
+    /// Gathers the rows of `weight` selected by the Tensor `indices`.
+    ///
+    /// Saves the result to `result`.
+    fn embedding(&self, weight: &SharedTensor<F>, indices: &SharedTensor<F>, result: &mut SharedTensor<F>)
+                 -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of an embedding lookup w.r.t. the table.
+    ///
+    /// Scatters the gradient w.r.t. the output `result_diff` back onto the rows selected by
+    /// `indices`, adding up the rows that were selected more than once. Rows that were not
+    /// selected, as well as the row `padding_idx` if given, receive a gradient of zero.
+    /// Saves the result to `weight_diff`.
+    fn embedding_grad(&self, indices: &SharedTensor<F>, result_diff: &SharedTensor<F>,
+                      weight_diff: &mut SharedTensor<F>, padding_idx: Option<usize>)
+                      -> Result<(), crate::co::error::Error>;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// The interpolation used to compute the elements of an upsampled Tensor.
+pub enum UpsampleMode {
+    /// Every element takes the value of the nearest input element
+    Nearest,
+    /// Every element is interpolated linearly between the two nearest input elements along
+    /// each spatial dimension, which is bilinear for images
+    Bilinear,
+}
+
+/// Provides the functionality for a Backend to resize Tensors along their spatial dimensions.
+///
+/// The input is of dimensions `[N, C, ...]` followed by one to three spatial dimensions, the
+/// result has the same `N` and `C` and its spatial dimensions determine the scale factors.
+/// Output positions are mapped onto the input by their centers, so the corners of the
+/// result are not aligned with the corners of the input.
+pub trait Upsample<F> : NN<F> {
+    /// Resizes the spatial dimensions of the input Tensor `x` to those of `result`
+    /// according to `mode`.
+    ///
+    /// Saves the result to `result`.
+    fn upsample(&self, mode: UpsampleMode, x: &SharedTensor<F>, result: &mut SharedTensor<F>)
+                -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of an upsampling according to `mode`.
+    ///
+    /// Distributes the gradient w.r.t. the output `result_diff` back onto the input elements
+    /// it was computed from. Saves the result to `x_diff`.
+    fn upsample_grad(&self, mode: UpsampleMode, result_diff: &SharedTensor<F>, x_diff: &mut SharedTensor<F>)
+                     -> Result<(), crate::co::error::Error>;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// The values the border of a padded Tensor is filled with.
+pub enum PadMode<F> {
+    /// Every padded element takes the given value
+    Constant(F),
+    /// The input is mirrored at its border without repeating the border element,
+    /// e.g. `[a, b, c]` padded by two on both sides becomes `[c, b, a, b, c, b, a]`
+    Reflect,
+    /// The border element is repeated, e.g. `[a, b, c]` padded by two on both sides
+    /// becomes `[a, a, a, b, c, c, c]`
+    Replicate,
+}
+
+/// Provides the functionality for a Backend to pad Tensors along any of their dimensions.
+///
+/// `padding` holds a pair of the number of elements added before and after the input for
+/// every dimension of the input, so the result is of the dimensions of the input plus the
+/// sum of each pair.
+pub trait Pad<F> : NN<F> {
+    /// Pads the input Tensor `x` by `padding` according to `mode`.
+    ///
+    /// Saves the result to `result`.
+    fn pad(&self, mode: PadMode<F>, x: &SharedTensor<F>, padding: &[(usize, usize)], result: &mut SharedTensor<F>)
+           -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of a padding by `padding` according to `mode`.
+    ///
+    /// Crops the gradient w.r.t. the output `result_diff` to the input. With `Reflect` and
+    /// `Replicate` the gradients of the padded elements are added to the input elements
+    /// they were copied from. Saves the result to `x_diff`.
+    fn pad_grad(&self, mode: PadMode<F>, result_diff: &SharedTensor<F>, padding: &[(usize, usize)],
+                x_diff: &mut SharedTensor<F>)
+                -> Result<(), crate::co::error::Error>;
+}
+
 /// Provide the functionality for a Backend to support RNN operations
 pub trait Rnn<F>: NN<F> {
     /// Create a RnnConfig
@@ -330,6 +906,9 @@ pub trait Rnn<F>: NN<F> {
                          -> Result<(), crate::co::error::Error>;
 
     /// Calculates RNN Gradients for Weights
+    ///
+    /// Expects `rnn_backward_data` to be called beforehand with the same `rnn_config`
+    /// and `workspace`.
     fn rnn_backward_weights(&self,
                             src: &SharedTensor<F>,
                             output: &SharedTensor<F>,
@@ -510,6 +1089,9 @@ pub enum MathType {
 pub trait Convolution<F> : NN<F> {
     /// Creates a new ConvolutionConfig, which needs to be passed to further
     /// convolution Operations.
+    ///
+    /// The filters are not dilated and span all input channels, see
+    /// `new_grouped_convolution_config`.
     fn new_convolution_config(&self,
                               src: &SharedTensor<F>,
                               dest: &SharedTensor<F>,
@@ -519,7 +1101,32 @@ pub trait Convolution<F> : NN<F> {
                               algo_bwd_data: ConvBackwardDataAlgo,
                               stride: &[i32],
                               zero_padding: &[i32])
-                              -> Result<Self::CC, crate::co::error::Error>;
+                              -> Result<Self::CC, crate::co::error::Error> {
+        let dilation = vec![1; stride.len()];
+        self.new_grouped_convolution_config(src, dest, filter, algo_fwd, algo_bwd_filter, algo_bwd_data,
+                                            stride, zero_padding, &dilation, 1)
+    }
+
+    /// Creates a new ConvolutionConfig with dilated filters, which splits the channels
+    /// into `groups` independent convolutions.
+    ///
+    /// The filter element `f` is applied to the input at `f * dilation` within a window.
+    /// The input channels and the output channels are both split into `groups`
+    /// consecutive parts, the `g`-th part of the output only depends on the `g`-th part
+    /// of the input. The filter is of shape `[output channels, input channels / groups,
+    /// spatial..]`, so `groups` equal to the input channels is a depthwise convolution.
+    fn new_grouped_convolution_config(&self,
+                                      src: &SharedTensor<F>,
+                                      dest: &SharedTensor<F>,
+                                      filter: &SharedTensor<F>,
+                                      algo_fwd: ConvForwardAlgo,
+                                      algo_bwd_filter: ConvBackwardFilterAlgo,
+                                      algo_bwd_data: ConvBackwardDataAlgo,
+                                      stride: &[i32],
+                                      zero_padding: &[i32],
+                                      dilation: &[i32],
+                                      groups: usize)
+                                      -> Result<Self::CC, crate::co::error::Error>;
 
     /// Computes a [CNN convolution][convolution] over the input Tensor `x`.
     /// [convolution]: https://en.wikipedia.org/wiki/Convolutional_neural_network
@@ -586,6 +1193,10 @@ pub trait Convolution<F> : NN<F> {
 }
 
 /// Provides the functionality for a Backend to support Softmax operations.
+///
+/// The first dimension of 2D inputs and of inputs with four or more dimensions is
+/// the batch, every sample of it is normalized on its own. Inputs with one or three
+/// dimensions are normalized as a whole.
 pub trait Softmax<F> : NN<F> {
     /// Computes a [Softmax][softmax] over the input Tensor `x`.
     /// [softmax]: https://en.wikipedia.org/wiki/Softmax_function
@@ -604,6 +1215,8 @@ pub trait Softmax<F> : NN<F> {
 }
 
 /// Provides the functionality for a Backend to support LogSoftmax operations.
+///
+/// The input is split into samples like for [Softmax](trait.Softmax.html).
 pub trait LogSoftmax<F> : NN<F> {
     /// Computes a logarithmic softmax over the input Tensor `x`.
     ///
@@ -619,6 +1232,39 @@ pub trait LogSoftmax<F> : NN<F> {
                         -> Result<(), crate::co::error::Error>;
 }
 
+/// Provides the functionality for a Backend to compute the cross-entropy between the softmax
+/// of an input and a target in one step.
+///
+/// The input `x` holds unnormalized scores of dimensions `[N, C]` or `[N, C, ...]`, the classes
+/// are the second dimension, so every sample is a position of the other dimensions. The loss of
+/// every sample is saved into a result of the dimensions of `x` without the class dimension.
+///
+/// The `target` holds either the class index of every sample as integral values of `F`, e.g. of
+/// dimensions `[N]` or `[N, 1]` for an input of `[N, C]`, or a probability for every class, in
+/// which case it is of the dimensions of `x`.
+///
+/// Optional `class_weights` of dimensions `[C]` scale the loss of every class. Samples whose
+/// class index is `ignore_index` have a loss and a gradient of zero, which only applies to
+/// class index targets.
+pub trait SoftmaxCrossEntropy<F> : NN<F> {
+    /// Computes the cross-entropy between the softmax of the input Tensor `x` and `target`.
+    ///
+    /// Saves the loss of every sample to `result`.
+    fn softmax_cross_entropy(&self, x: &SharedTensor<F>, target: &SharedTensor<F>,
+                             class_weights: Option<&SharedTensor<F>>, ignore_index: Option<usize>,
+                             result: &mut SharedTensor<F>)
+                             -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of the cross-entropy between the softmax of `x` and `target`.
+    ///
+    /// Scales the gradient of every sample by its gradient w.r.t. the loss `result_diff`.
+    /// Saves the result to `x_diff`.
+    fn softmax_cross_entropy_grad(&self, x: &SharedTensor<F>, target: &SharedTensor<F>,
+                                  class_weights: Option<&SharedTensor<F>>, ignore_index: Option<usize>,
+                                  result_diff: &SharedTensor<F>, x_diff: &mut SharedTensor<F>)
+                                  -> Result<(), crate::co::error::Error>;
+}
+
 /// Provides the functionality for a Backend to support Local Response Normalization operations.
 pub trait LRN<F> : NN<F> {
     /// Creates a new (Local Response Normalization) LRNConfig, which needs to be
@@ -644,11 +1290,226 @@ pub trait LRN<F> : NN<F> {
                 -> Result<(), crate::co::error::Error>;
 }
 
+/// Provides the functionality for a Backend to support Batch Normalization operations.
+///
+/// The input `x` has the channels as second dimension, e.g. `[N, C]` or NCHW.
+/// Every channel is normalized over the batch and all spatial dimensions,
+/// so `scale`, `bias` and the statistics hold one value per channel.
+pub trait BatchNormalization<F> : NN<F> {
+    /// Creates a new BatchNormalizationConfig, which needs to be passed to further
+    /// batch normalization Operations.
+    ///
+    /// `epsilon` is added to the variance for numerical stability and `momentum`
+    /// is the weight of the current batch when updating the running statistics.
+    fn new_batch_normalization_config(&self, epsilon: f64, momentum: f64)
+                                      -> Result<Self::CBN, crate::co::error::Error>;
+
+    /// Computes a [Batch Normalization][bn] over the input Tensor `x` with the
+    /// statistics of the current batch, as done during training.
+    /// [bn]: https://arxiv.org/abs/1502.03167
+    ///
+    /// Saves the result to `result`, blends the batch mean and unbiased variance
+    /// into `running_mean` and `running_variance` and stores the batch mean and
+    /// inverse standard deviation in `saved_mean` and `saved_inv_variance` for
+    /// `batch_normalization_grad`.
+    fn batch_normalization_training(&self,
+                                    x: &SharedTensor<F>,
+                                    scale: &SharedTensor<F>,
+                                    bias: &SharedTensor<F>,
+                                    running_mean: &mut SharedTensor<F>,
+                                    running_variance: &mut SharedTensor<F>,
+                                    saved_mean: &mut SharedTensor<F>,
+                                    saved_inv_variance: &mut SharedTensor<F>,
+                                    result: &mut SharedTensor<F>,
+                                    config: &Self::CBN)
+                                    -> Result<(), crate::co::error::Error>;
+
+    /// Computes a [Batch Normalization][bn] over the input Tensor `x` with the
+    /// given `mean` and `variance`, as done during inference.
+    /// [bn]: https://arxiv.org/abs/1502.03167
+    ///
+    /// Saves the result to `result`.
+    fn batch_normalization_inference(&self,
+                                     x: &SharedTensor<F>,
+                                     scale: &SharedTensor<F>,
+                                     bias: &SharedTensor<F>,
+                                     mean: &SharedTensor<F>,
+                                     variance: &SharedTensor<F>,
+                                     result: &mut SharedTensor<F>,
+                                     config: &Self::CBN)
+                                     -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of a training mode [Batch Normalization][bn]
+    /// over the input Tensor `x`.
+    /// [bn]: https://arxiv.org/abs/1502.03167
+    ///
+    /// `saved_mean` and `saved_inv_variance` have to be the ones stored by
+    /// `batch_normalization_training`.
+    ///
+    /// Saves the gradient w.r.t. `x` to `x_diff` and the gradients w.r.t.
+    /// the scale and the bias to `scale_diff` and `bias_diff`.
+    fn batch_normalization_grad(&self,
+                                x: &SharedTensor<F>,
+                                result_diff: &SharedTensor<F>,
+                                scale: &SharedTensor<F>,
+                                saved_mean: &SharedTensor<F>,
+                                saved_inv_variance: &SharedTensor<F>,
+                                x_diff: &mut SharedTensor<F>,
+                                scale_diff: &mut SharedTensor<F>,
+                                bias_diff: &mut SharedTensor<F>,
+                                config: &Self::CBN)
+                                -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to support Layer Normalization operations.
+///
+/// The shape of `scale` and `bias` has to match the trailing dimensions of the
+/// input `x`. Every sample is normalized over those dimensions, so the saved
+/// statistics hold one value per element of the remaining leading dimensions,
+/// e.g. `[N]` for an input of `[N, D]` and a `scale` of `[D]`.
+pub trait LayerNorm<F> : NN<F> {
+    /// Creates a new LayerNormalizationConfig, which needs to be passed to further
+    /// layer normalization Operations.
+    ///
+    /// `epsilon` is added to the variance for numerical stability.
+    fn new_layer_normalization_config(&self, epsilon: f64)
+                                      -> Result<Self::CLN, crate::co::error::Error>;
+
+    /// Computes a [Layer Normalization][ln] over the input Tensor `x`.
+    /// [ln]: https://arxiv.org/abs/1607.06450
+    ///
+    /// Saves the result to `result` and the mean and inverse standard deviation
+    /// of every sample to `saved_mean` and `saved_inv_variance` for
+    /// `layer_normalization_grad`.
+    fn layer_normalization(&self,
+                           x: &SharedTensor<F>,
+                           scale: &SharedTensor<F>,
+                           bias: &SharedTensor<F>,
+                           saved_mean: &mut SharedTensor<F>,
+                           saved_inv_variance: &mut SharedTensor<F>,
+                           result: &mut SharedTensor<F>,
+                           config: &Self::CLN)
+                           -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of a [Layer Normalization][ln] over the input Tensor `x`.
+    /// [ln]: https://arxiv.org/abs/1607.06450
+    ///
+    /// `saved_mean` and `saved_inv_variance` have to be the ones stored by
+    /// `layer_normalization`.
+    ///
+    /// Saves the gradient w.r.t. `x` to `x_diff` and the gradients w.r.t.
+    /// the scale and the bias to `scale_diff` and `bias_diff`.
+    fn layer_normalization_grad(&self,
+                                x: &SharedTensor<F>,
+                                result_diff: &SharedTensor<F>,
+                                scale: &SharedTensor<F>,
+                                saved_mean: &SharedTensor<F>,
+                                saved_inv_variance: &SharedTensor<F>,
+                                x_diff: &mut SharedTensor<F>,
+                                scale_diff: &mut SharedTensor<F>,
+                                bias_diff: &mut SharedTensor<F>,
+                                config: &Self::CLN)
+                                -> Result<(), crate::co::error::Error>;
+}
+
+/// Provides the functionality for a Backend to support Group Normalization operations.
+///
+/// The input `x` has the channels as second dimension, e.g. `[N, C]` or NCHW.
+/// The channels are split into groups of consecutive channels and every group
+/// of every sample is normalized over its channels and all spatial dimensions.
+/// `scale` and `bias` hold one value per channel, the saved statistics are of
+/// shape `[N, groups]`.
+pub trait GroupNorm<F> : NN<F> {
+    /// Creates a new GroupNormalizationConfig, which needs to be passed to further
+    /// group normalization Operations.
+    ///
+    /// The number of channels has to be a multiple of `groups`, `epsilon` is
+    /// added to the variance for numerical stability.
+    fn new_group_normalization_config(&self, groups: usize, epsilon: f64)
+                                      -> Result<Self::CGN, crate::co::error::Error>;
+
+    /// Computes a [Group Normalization][gn] over the input Tensor `x`.
+    /// [gn]: https://arxiv.org/abs/1803.08494
+    ///
+    /// Saves the result to `result` and the mean and inverse standard deviation
+    /// of every group to `saved_mean` and `saved_inv_variance` for
+    /// `group_normalization_grad`.
+    fn group_normalization(&self,
+                           x: &SharedTensor<F>,
+                           scale: &SharedTensor<F>,
+                           bias: &SharedTensor<F>,
+                           saved_mean: &mut SharedTensor<F>,
+                           saved_inv_variance: &mut SharedTensor<F>,
+                           result: &mut SharedTensor<F>,
+                           config: &Self::CGN)
+                           -> Result<(), crate::co::error::Error>;
+
+    /// Computes the gradient of a [Group Normalization][gn] over the input Tensor `x`.
+    /// [gn]: https://arxiv.org/abs/1803.08494
+    ///
+    /// `saved_mean` and `saved_inv_variance` have to be the ones stored by
+    /// `group_normalization`.
+    ///
+    /// Saves the gradient w.r.t. `x` to `x_diff` and the gradients w.r.t.
+    /// the scale and the bias to `scale_diff` and `bias_diff`.
+    fn group_normalization_grad(&self,
+                                x: &SharedTensor<F>,
+                                result_diff: &SharedTensor<F>,
+                                scale: &SharedTensor<F>,
+                                saved_mean: &SharedTensor<F>,
+                                saved_inv_variance: &SharedTensor<F>,
+                                x_diff: &mut SharedTensor<F>,
+                                scale_diff: &mut SharedTensor<F>,
+                                bias_diff: &mut SharedTensor<F>,
+                                config: &Self::CGN)
+                                -> Result<(), crate::co::error::Error>;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Treatment of the zero padding by average pooling [cudnnPoolingMode_t][1]
+/// [1]: https://docs.nvidia.com/deeplearning/sdk/cudnn-api/index.html#cudnnPoolingMode_t
+pub enum PoolingAvgMode {
+    /// CUDNN_POOLING_AVERAGE_COUNT_INCLUDE_PADDING - Padded elements count towards the window size
+    IncludePadding,
+    /// CUDNN_POOLING_AVERAGE_COUNT_EXCLUDE_PADDING - Only elements within the input are averaged
+    ExcludePadding,
+}
+
 /// Provides the functionality for a Backend to support Pooling operations.
 pub trait Pooling<F> : NN<F> {
     /// Creates a new PoolingConfig, which needs to be passed to further pooling Operations.
+    ///
+    /// Average pooling excludes the padding, see `new_pooling_config_with_avg_mode`.
     fn new_pooling_config(&self, window: &[i32], stride: &[i32], padding: &[i32])
-                          -> Result<Self::CPOOL, crate::co::error::Error>;
+                          -> Result<Self::CPOOL, crate::co::error::Error> {
+        self.new_pooling_config_with_avg_mode(window, stride, padding, PoolingAvgMode::ExcludePadding)
+    }
+
+    /// Creates a new PoolingConfig, choosing how average pooling treats the padding.
+    fn new_pooling_config_with_avg_mode(&self, window: &[i32], stride: &[i32], padding: &[i32],
+                                        avg_mode: PoolingAvgMode)
+                                        -> Result<Self::CPOOL, crate::co::error::Error>;
+
+    /// Creates a new PoolingConfig for adaptive pooling, which splits every spatial
+    /// dimension of size `input` into `output` windows, one per element of the result.
+    ///
+    /// The `i`-th window spans the elements `floor(i * input / output)` up to, but excluding,
+    /// `ceil((i + 1) * input / output)`, so the windows differ in size and may overlap if
+    /// `output` does not divide `input`. Global pooling is adaptive pooling with an `output`
+    /// of `1` in every dimension.
+    ///
+    /// The default implementation only supports an `input` that is a multiple of the `output`,
+    /// for which the windows are all of the same size and do not overlap.
+    fn new_adaptive_pooling_config(&self, input: &[usize], output: &[usize])
+                                   -> Result<Self::CPOOL, crate::co::error::Error> {
+        if input.len() != output.len() || input.iter().zip(output).any(|(&i, &o)| o == 0 || i % o != 0) {
+            return Err(crate::co::plugin::Error::Operation(
+                "Adaptive pooling requires the input sizes to be multiples of the output sizes").into());
+        }
+        let window: Vec<i32> = input.iter().zip(output).map(|(&i, &o)| (i / o) as i32).collect();
+        let padding = vec![0; window.len()];
+        self.new_pooling_config(&window, &window, &padding)
+    }
 
     /// Computes non-linear down-sampling ([max Pooling][pooling]) over the input Tensor `x`.
     /// [pooling]: https://en.wikipedia.org/wiki/Convolutional_neural_network#Pooling_layer
@@ -8,6 +8,8 @@ use crate::co::prelude::*;
 use crate::co::plugin::numeric_helpers::Float;
 
 use crate::plugin::{Relu, ReluPointwise, Sigmoid, SigmoidPointwise, Tanh, TanhPointwise};
+use crate::plugin::{Elu, EluPointwise, Gelu, GeluPointwise, HardTanh, HardTanhPointwise, LeakyRelu,
+                    LeakyReluPointwise, Selu, SeluPointwise, Softplus, SoftplusPointwise, Swish, SwishPointwise};
 use crate::tests::{Epsilon, filled_tensor, tensor_assert_eq};
 
 const DIMS:   [usize; 4] = [3, 1, 2, 2];
@@ -60,6 +62,95 @@ const TANH_IN_GRAD: [f64; 12] =
      1.28159009724762284, 2.131040185040205402, -0.2658761943586825486,
      1.333889047346882493, 0.5833853608611378429, -0.2133261045550120892];
 
+const LEAKY_RELU_SLOPE: f64 = 0.1;
+const ELU_ALPHA: f64 = 1.0;
+const HARD_TANH_MIN: f64 = -0.5;
+const HARD_TANH_MAX: f64 = 1.0;
+
+const LEAKY_RELU_OUT: [f64; 12] =
+    [1.1216233780761824, 0.5628881195019448, -0.1339156477386188,
+     0.8759488434687464, 0.5710683496214726, 0.1198723930562686,
+     -0.03748904319909696, 0.209074213834396, -0.0662653952842352,
+     -0.09189827854195559, 1.4021598058049722, -0.1978255365302346];
+
+const LEAKY_RELU_IN_GRAD: [f64; 12] =
+    [-2.3327764044888655, 1.7058900318308823, -0.16393851569210413,
+     0.060623550278292644, -2.987575983561327, 2.29951399451255,
+     0.14703062361351654, 2.225557495134345, -0.040074621849388266,
+     0.28154670501056644, 2.709297453597424, -0.2895567849550242];
+
+const ELU_OUT: [f64; 12] =
+    [1.1216233780761824, 0.5628881195019448, -0.7379333654951226,
+     0.8759488434687464, 0.5710683496214726, 0.1198723930562686,
+     -0.3126354121654671, 0.209074213834396, -0.48451854597341265,
+     -0.6010753732874753, 1.4021598058049722, -0.8616896720606297];
+
+const ELU_IN_GRAD: [f64; 12] =
+    [-2.3327764044888655, 1.7058900318308823, -0.42962815073154753,
+     0.060623550278292644, -2.987575983561327, 2.29951399451255,
+     1.0106364399915913, 2.225557495134345, -0.20657724340488312,
+     1.123159141984815, 2.709297453597424, -0.400486938841991];
+
+const SELU_OUT: [f64; 12] =
+    [1.1784907907856341, 0.5914271029313631, -1.2973601634662613,
+     0.920360314705503, 0.6000220787947459, 0.12595004174088564,
+     -0.5496441120536556, 0.21967448290637073, -0.8518317363041861,
+     -1.0567502175763013, 1.473250692389453, -1.5149360444647852];
+
+const SELU_IN_GRAD: [f64; 12] =
+    [-2.4510504714760186, 1.79238034076458, -0.7553289686106112,
+     0.0636972241343967, -3.1390490357274072, 2.416101624472081,
+     1.7767992589855561, 2.338395457554046, -0.3631833154641931,
+     1.9746253471902084, 2.8466615095345023, -0.704095823196088];
+
+const GELU_OUT: [f64; 12] =
+    [0.9744851588792436, 0.4014504980740541, -0.12110523496620415,
+     0.7089461926636665, 0.408870557803442, 0.0656549873591295,
+     -0.13266873272478463, 0.12184885541031659, -0.16821190728069615,
+     -0.16467003427118154, 1.2891463368467355, -0.047299992144538054];
+
+const GELU_IN_GRAD: [f64; 12] =
+    [-2.582943731767925, 1.5433848758341242, 0.20923779837233097,
+     0.06348715850504512, -2.716895038683312, 1.3686372917667673,
+     0.31541012542433833, 1.4786575034912557, -0.016735040406549183,
+     -0.17156718049118141, 3.058628138079284, 0.25609228858029176];
+
+const SWISH_OUT: [f64; 12] =
+    [0.846028975091298, 0.3586275779801885, -0.27807424862450786,
+     0.6184041331383835, 0.36491818889319455, 0.06352424870074355,
+     -0.15271531068414965, 0.115425479768181, -0.22539756076859818,
+     -0.26206191357875697, 1.1252702519924853, -0.24036779919066112];
+
+const SWISH_IN_GRAD: [f64; 12] =
+    [-2.244522189748872, 1.30885967868584, 0.020793390989008405,
+     0.053821827624683544, -2.3026490868361247, 1.2872517552438807,
+     0.46587333407784753, 1.3437481795233932, -0.0767083113820031,
+     0.2754490498331699, 2.776318884819254, 0.25960788135568597];
+
+const SOFTPLUS_OUT: [f64; 12] =
+    [1.4036021291085312, 1.013684543084492, 0.23275056344213116,
+     1.224114353982079, 1.0189040652868853, 0.754878476530817,
+     0.5231678962986034, 0.8031383679658306, 0.4157331799130207,
+     0.3357038176862216, 1.622150341290153, 0.12954499443391237];
+
+const SOFTPLUS_IN_GRAD: [f64; 12] =
+    [-1.7595892428633282, 1.0868575640881726, -0.34041637658862234,
+     0.04279913643034155, -1.9090898976009558, 1.2185866583116205,
+     0.5989437299312914, 1.228683523024163, -0.13631129754574942,
+     0.8028732360114649, 2.1742827142173957, -0.3518257974228994];
+
+const HARD_TANH_OUT: [f64; 12] =
+    [1.0, 0.5628881195019448, -0.5,
+     0.8759488434687464, 0.5710683496214726, 0.1198723930562686,
+     -0.3748904319909696, 0.209074213834396, -0.5,
+     -0.5, 1.0, -0.5];
+
+const HARD_TANH_IN_GRAD: [f64; 12] =
+    [-0.0, 1.7058900318308823, -0.0,
+     0.060623550278292644, -2.987575983561327, 2.29951399451255,
+     1.4703062361351653, 2.225557495134345, -0.0,
+     0.0, 0.0, -0.0];
+
 
 //----------------------------------------------------------- relu
 
@@ -193,6 +284,316 @@ pub fn test_tanh_pointwise_grad<T, F: IFramework>(backend: Backend<F>)
     tensor_assert_eq(&dx, &TANH_IN_GRAD, 10.0);
 }
 
+//----------------------------------------------------------- leaky_relu
+
+pub fn test_leaky_relu<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: LeakyRelu<T> + IBackend {
+
+    let x  = filled_tensor(&backend, &DIMS, &IN);
+    let mut r = SharedTensor::<T>::new(&DIMS);
+
+    backend.leaky_relu(&x, &mut r, LEAKY_RELU_SLOPE).unwrap();
+    tensor_assert_eq(&r, &LEAKY_RELU_OUT, 3.0);
+}
+
+pub fn test_leaky_relu_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: LeakyRelu<T> + IBackend {
+
+    let x  = filled_tensor(&backend, &DIMS, &LEAKY_RELU_OUT);
+    let dx = filled_tensor(&backend, &DIMS, &OUT_GRAD);
+    let r  = filled_tensor(&backend, &DIMS, &IN);
+    let mut dr = SharedTensor::new(&DIMS);
+
+    backend.leaky_relu_grad(&x, &dx, &r, &mut dr, LEAKY_RELU_SLOPE).unwrap();
+    tensor_assert_eq(&dr, &LEAKY_RELU_IN_GRAD, 3.0);
+}
+
+pub fn test_leaky_relu_pointwise<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + fmt::Debug + Epsilon,
+          Backend<F>: LeakyReluPointwise<T> + IBackend {
+
+    let mut x = filled_tensor(&backend, &DIMS, &IN);
+    backend.leaky_relu_pointwise(&mut x, LEAKY_RELU_SLOPE).unwrap();
+    tensor_assert_eq(&x, &LEAKY_RELU_OUT, 3.0);
+}
+
+pub fn test_leaky_relu_pointwise_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + fmt::Debug + Epsilon,
+          Backend<F>: LeakyReluPointwise<T> + IBackend {
+    let      x = filled_tensor(&backend, &DIMS, &LEAKY_RELU_OUT);
+    let mut dx = filled_tensor(&backend, &DIMS, &OUT_GRAD);
+    backend.leaky_relu_pointwise_grad(&x, &mut dx, LEAKY_RELU_SLOPE).unwrap();
+    tensor_assert_eq(&dx, &LEAKY_RELU_IN_GRAD, 3.0);
+}
+
+//----------------------------------------------------------- elu
+
+pub fn test_elu<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Elu<T> + IBackend {
+
+    let x  = filled_tensor(&backend, &DIMS, &IN);
+    let mut r = SharedTensor::<T>::new(&DIMS);
+
+    backend.elu(&x, &mut r, ELU_ALPHA).unwrap();
+    tensor_assert_eq(&r, &ELU_OUT, 10.0);
+}
+
+pub fn test_elu_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Elu<T> + IBackend {
+
+    let x  = filled_tensor(&backend, &DIMS, &ELU_OUT);
+    let dx = filled_tensor(&backend, &DIMS, &OUT_GRAD);
+    let r  = filled_tensor(&backend, &DIMS, &IN);
+    let mut dr = SharedTensor::new(&DIMS);
+
+    backend.elu_grad(&x, &dx, &r, &mut dr, ELU_ALPHA).unwrap();
+    tensor_assert_eq(&dr, &ELU_IN_GRAD, 10.0);
+}
+
+pub fn test_elu_pointwise<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + fmt::Debug + Epsilon,
+          Backend<F>: EluPointwise<T> + IBackend {
+
+    let mut x = filled_tensor(&backend, &DIMS, &IN);
+    backend.elu_pointwise(&mut x, ELU_ALPHA).unwrap();
+    tensor_assert_eq(&x, &ELU_OUT, 10.0);
+}
+
+pub fn test_elu_pointwise_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + fmt::Debug + Epsilon,
+          Backend<F>: EluPointwise<T> + IBackend {
+    let      x = filled_tensor(&backend, &DIMS, &ELU_OUT);
+    let mut dx = filled_tensor(&backend, &DIMS, &OUT_GRAD);
+    backend.elu_pointwise_grad(&x, &mut dx, ELU_ALPHA).unwrap();
+    tensor_assert_eq(&dx, &ELU_IN_GRAD, 10.0);
+}
+
+//----------------------------------------------------------- selu
+
+pub fn test_selu<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Selu<T> + IBackend {
+
+    let x  = filled_tensor(&backend, &DIMS, &IN);
+    let mut r = SharedTensor::<T>::new(&DIMS);
+
+    backend.selu(&x, &mut r).unwrap();
+    tensor_assert_eq(&r, &SELU_OUT, 10.0);
+}
+
+pub fn test_selu_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Selu<T> + IBackend {
+
+    let x  = filled_tensor(&backend, &DIMS, &SELU_OUT);
+    let dx = filled_tensor(&backend, &DIMS, &OUT_GRAD);
+    let r  = filled_tensor(&backend, &DIMS, &IN);
+    let mut dr = SharedTensor::new(&DIMS);
+
+    backend.selu_grad(&x, &dx, &r, &mut dr).unwrap();
+    tensor_assert_eq(&dr, &SELU_IN_GRAD, 10.0);
+}
+
+pub fn test_selu_pointwise<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + fmt::Debug + Epsilon,
+          Backend<F>: SeluPointwise<T> + IBackend {
+
+    let mut x = filled_tensor(&backend, &DIMS, &IN);
+    backend.selu_pointwise(&mut x).unwrap();
+    tensor_assert_eq(&x, &SELU_OUT, 10.0);
+}
+
+pub fn test_selu_pointwise_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + fmt::Debug + Epsilon,
+          Backend<F>: SeluPointwise<T> + IBackend {
+    let      x = filled_tensor(&backend, &DIMS, &SELU_OUT);
+    let mut dx = filled_tensor(&backend, &DIMS, &OUT_GRAD);
+    backend.selu_pointwise_grad(&x, &mut dx).unwrap();
+    tensor_assert_eq(&dx, &SELU_IN_GRAD, 10.0);
+}
+
+//----------------------------------------------------------- gelu
+
+pub fn test_gelu<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Gelu<T> + IBackend {
+
+    let x  = filled_tensor(&backend, &DIMS, &IN);
+    let mut r = SharedTensor::<T>::new(&DIMS);
+
+    backend.gelu(&x, &mut r).unwrap();
+    tensor_assert_eq(&r, &GELU_OUT, 10.0);
+}
+
+pub fn test_gelu_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Gelu<T> + IBackend {
+
+    let x  = filled_tensor(&backend, &DIMS, &GELU_OUT);
+    let dx = filled_tensor(&backend, &DIMS, &OUT_GRAD);
+    let r  = filled_tensor(&backend, &DIMS, &IN);
+    let mut dr = SharedTensor::new(&DIMS);
+
+    backend.gelu_grad(&x, &dx, &r, &mut dr).unwrap();
+    tensor_assert_eq(&dr, &GELU_IN_GRAD, 30.0);
+}
+
+pub fn test_gelu_pointwise<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + fmt::Debug + Epsilon,
+          Backend<F>: GeluPointwise<T> + IBackend {
+
+    let mut x = filled_tensor(&backend, &DIMS, &IN);
+    backend.gelu_pointwise(&mut x).unwrap();
+    tensor_assert_eq(&x, &GELU_OUT, 10.0);
+}
+
+pub fn test_gelu_pointwise_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + fmt::Debug + Epsilon,
+          Backend<F>: GeluPointwise<T> + IBackend {
+    // the gradient needs the input, not the output
+    let      x = filled_tensor(&backend, &DIMS, &IN);
+    let mut dx = filled_tensor(&backend, &DIMS, &OUT_GRAD);
+    backend.gelu_pointwise_grad(&x, &mut dx).unwrap();
+    tensor_assert_eq(&dx, &GELU_IN_GRAD, 30.0);
+}
+
+//----------------------------------------------------------- swish
+
+pub fn test_swish<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Swish<T> + IBackend {
+
+    let x  = filled_tensor(&backend, &DIMS, &IN);
+    let mut r = SharedTensor::<T>::new(&DIMS);
+
+    backend.swish(&x, &mut r).unwrap();
+    tensor_assert_eq(&r, &SWISH_OUT, 10.0);
+}
+
+pub fn test_swish_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Swish<T> + IBackend {
+
+    let x  = filled_tensor(&backend, &DIMS, &SWISH_OUT);
+    let dx = filled_tensor(&backend, &DIMS, &OUT_GRAD);
+    let r  = filled_tensor(&backend, &DIMS, &IN);
+    let mut dr = SharedTensor::new(&DIMS);
+
+    backend.swish_grad(&x, &dx, &r, &mut dr).unwrap();
+    tensor_assert_eq(&dr, &SWISH_IN_GRAD, 30.0);
+}
+
+pub fn test_swish_pointwise<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + fmt::Debug + Epsilon,
+          Backend<F>: SwishPointwise<T> + IBackend {
+
+    let mut x = filled_tensor(&backend, &DIMS, &IN);
+    backend.swish_pointwise(&mut x).unwrap();
+    tensor_assert_eq(&x, &SWISH_OUT, 10.0);
+}
+
+pub fn test_swish_pointwise_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + fmt::Debug + Epsilon,
+          Backend<F>: SwishPointwise<T> + IBackend {
+    // the gradient needs the input, not the output
+    let      x = filled_tensor(&backend, &DIMS, &IN);
+    let mut dx = filled_tensor(&backend, &DIMS, &OUT_GRAD);
+    backend.swish_pointwise_grad(&x, &mut dx).unwrap();
+    tensor_assert_eq(&dx, &SWISH_IN_GRAD, 30.0);
+}
+
+//----------------------------------------------------------- softplus
+
+pub fn test_softplus<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Softplus<T> + IBackend {
+
+    let x  = filled_tensor(&backend, &DIMS, &IN);
+    let mut r = SharedTensor::<T>::new(&DIMS);
+
+    backend.softplus(&x, &mut r).unwrap();
+    tensor_assert_eq(&r, &SOFTPLUS_OUT, 10.0);
+}
+
+pub fn test_softplus_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Softplus<T> + IBackend {
+
+    let x  = filled_tensor(&backend, &DIMS, &SOFTPLUS_OUT);
+    let dx = filled_tensor(&backend, &DIMS, &OUT_GRAD);
+    let r  = filled_tensor(&backend, &DIMS, &IN);
+    let mut dr = SharedTensor::new(&DIMS);
+
+    backend.softplus_grad(&x, &dx, &r, &mut dr).unwrap();
+    tensor_assert_eq(&dr, &SOFTPLUS_IN_GRAD, 30.0);
+}
+
+pub fn test_softplus_pointwise<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + fmt::Debug + Epsilon,
+          Backend<F>: SoftplusPointwise<T> + IBackend {
+
+    let mut x = filled_tensor(&backend, &DIMS, &IN);
+    backend.softplus_pointwise(&mut x).unwrap();
+    tensor_assert_eq(&x, &SOFTPLUS_OUT, 10.0);
+}
+
+pub fn test_softplus_pointwise_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + fmt::Debug + Epsilon,
+          Backend<F>: SoftplusPointwise<T> + IBackend {
+    let      x = filled_tensor(&backend, &DIMS, &SOFTPLUS_OUT);
+    let mut dx = filled_tensor(&backend, &DIMS, &OUT_GRAD);
+    backend.softplus_pointwise_grad(&x, &mut dx).unwrap();
+    tensor_assert_eq(&dx, &SOFTPLUS_IN_GRAD, 30.0);
+}
+
+//----------------------------------------------------------- hard_tanh
+
+pub fn test_hard_tanh<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: HardTanh<T> + IBackend {
+
+    let x  = filled_tensor(&backend, &DIMS, &IN);
+    let mut r = SharedTensor::<T>::new(&DIMS);
+
+    backend.hard_tanh(&x, &mut r, HARD_TANH_MIN, HARD_TANH_MAX).unwrap();
+    tensor_assert_eq(&r, &HARD_TANH_OUT, 3.0);
+}
+
+pub fn test_hard_tanh_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: HardTanh<T> + IBackend {
+
+    let x  = filled_tensor(&backend, &DIMS, &HARD_TANH_OUT);
+    let dx = filled_tensor(&backend, &DIMS, &OUT_GRAD);
+    let r  = filled_tensor(&backend, &DIMS, &IN);
+    let mut dr = SharedTensor::new(&DIMS);
+
+    backend.hard_tanh_grad(&x, &dx, &r, &mut dr, HARD_TANH_MIN, HARD_TANH_MAX).unwrap();
+    tensor_assert_eq(&dr, &HARD_TANH_IN_GRAD, 3.0);
+}
+
+pub fn test_hard_tanh_pointwise<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + fmt::Debug + Epsilon,
+          Backend<F>: HardTanhPointwise<T> + IBackend {
+
+    let mut x = filled_tensor(&backend, &DIMS, &IN);
+    backend.hard_tanh_pointwise(&mut x, HARD_TANH_MIN, HARD_TANH_MAX).unwrap();
+    tensor_assert_eq(&x, &HARD_TANH_OUT, 3.0);
+}
+
+pub fn test_hard_tanh_pointwise_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + fmt::Debug + Epsilon,
+          Backend<F>: HardTanhPointwise<T> + IBackend {
+    let      x = filled_tensor(&backend, &DIMS, &HARD_TANH_OUT);
+    let mut dx = filled_tensor(&backend, &DIMS, &OUT_GRAD);
+    backend.hard_tanh_pointwise_grad(&x, &mut dx, HARD_TANH_MIN, HARD_TANH_MAX).unwrap();
+    tensor_assert_eq(&dx, &HARD_TANH_IN_GRAD, 3.0);
+}
+
 mod native {
     use super::*;
     test_native!(test_relu, relu_f32, relu_f64);
@@ -212,6 +613,48 @@ mod native {
     test_native!(test_tanh_pointwise, tanh_pointwise_f32, tanh_pointwise_f64);
     test_native!(test_tanh_pointwise_grad,
                tanh_pointwise_grad_f32, tanh_pointwise_grad_f64);
+
+    test_native!(test_leaky_relu, leaky_relu_f32, leaky_relu_f64);
+    test_native!(test_leaky_relu_grad, leaky_relu_grad_f32, leaky_relu_grad_f64);
+    test_native!(test_leaky_relu_pointwise, leaky_relu_pointwise_f32, leaky_relu_pointwise_f64);
+    test_native!(test_leaky_relu_pointwise_grad,
+               leaky_relu_pointwise_grad_f32, leaky_relu_pointwise_grad_f64);
+
+    test_native!(test_elu, elu_f32, elu_f64);
+    test_native!(test_elu_grad, elu_grad_f32, elu_grad_f64);
+    test_native!(test_elu_pointwise, elu_pointwise_f32, elu_pointwise_f64);
+    test_native!(test_elu_pointwise_grad,
+               elu_pointwise_grad_f32, elu_pointwise_grad_f64);
+
+    test_native!(test_selu, selu_f32, selu_f64);
+    test_native!(test_selu_grad, selu_grad_f32, selu_grad_f64);
+    test_native!(test_selu_pointwise, selu_pointwise_f32, selu_pointwise_f64);
+    test_native!(test_selu_pointwise_grad,
+               selu_pointwise_grad_f32, selu_pointwise_grad_f64);
+
+    test_native!(test_gelu, gelu_f32, gelu_f64);
+    test_native!(test_gelu_grad, gelu_grad_f32, gelu_grad_f64);
+    test_native!(test_gelu_pointwise, gelu_pointwise_f32, gelu_pointwise_f64);
+    test_native!(test_gelu_pointwise_grad,
+               gelu_pointwise_grad_f32, gelu_pointwise_grad_f64);
+
+    test_native!(test_swish, swish_f32, swish_f64);
+    test_native!(test_swish_grad, swish_grad_f32, swish_grad_f64);
+    test_native!(test_swish_pointwise, swish_pointwise_f32, swish_pointwise_f64);
+    test_native!(test_swish_pointwise_grad,
+               swish_pointwise_grad_f32, swish_pointwise_grad_f64);
+
+    test_native!(test_softplus, softplus_f32, softplus_f64);
+    test_native!(test_softplus_grad, softplus_grad_f32, softplus_grad_f64);
+    test_native!(test_softplus_pointwise, softplus_pointwise_f32, softplus_pointwise_f64);
+    test_native!(test_softplus_pointwise_grad,
+               softplus_pointwise_grad_f32, softplus_pointwise_grad_f64);
+
+    test_native!(test_hard_tanh, hard_tanh_f32, hard_tanh_f64);
+    test_native!(test_hard_tanh_grad, hard_tanh_grad_f32, hard_tanh_grad_f64);
+    test_native!(test_hard_tanh_pointwise, hard_tanh_pointwise_f32, hard_tanh_pointwise_f64);
+    test_native!(test_hard_tanh_pointwise_grad,
+               hard_tanh_pointwise_grad_f32, hard_tanh_pointwise_grad_f64);
 }
 
 mod cuda {
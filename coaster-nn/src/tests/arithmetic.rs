@@ -0,0 +1,209 @@
+use std::fmt;
+
+use crate::co::prelude::*;
+use crate::co::plugin::numeric_helpers::Float;
+
+use crate::plugin::{Arithmetic, ArithmeticPointwise, BinaryOperation, UnaryOperation};
+use crate::tests::{Epsilon, filled_tensor, tensor_assert_eq, tensor_assert_eq_tensor, uniformly_random_tensor};
+
+const A: [f64; 6] = [1.0, -2.0, 3.0, 4.0, 0.5, -6.0];
+const B: [f64; 6] = [2.0, 4.0, -1.0, 0.5, 2.0, 3.0];
+const ROW: [f64; 3] = [1.0, 2.0, -3.0];
+const COLUMN: [f64; 2] = [10.0, -1.0];
+
+const POSITIVE: [f64; 6] = [0.25, 1.0, 2.0, 4.0, 9.0, 16.0];
+
+pub fn test_binary_operation<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Arithmetic<T> + IBackend {
+
+    let a = filled_tensor(&backend, &[2, 3], &A);
+    let b = filled_tensor(&backend, &[2, 3], &B);
+    let mut r = SharedTensor::<T>::new(&[2, 3]);
+
+    backend.binary_operation(BinaryOperation::Add, &a, &b, &mut r).unwrap();
+    tensor_assert_eq(&r, &[3.0, 2.0, 2.0, 4.5, 2.5, -3.0], 3.0);
+    backend.binary_operation(BinaryOperation::Sub, &a, &b, &mut r).unwrap();
+    tensor_assert_eq(&r, &[-1.0, -6.0, 4.0, 3.5, -1.5, -9.0], 3.0);
+    backend.binary_operation(BinaryOperation::Mul, &a, &b, &mut r).unwrap();
+    tensor_assert_eq(&r, &[2.0, -8.0, -3.0, 2.0, 1.0, -18.0], 3.0);
+    backend.binary_operation(BinaryOperation::Min, &a, &b, &mut r).unwrap();
+    tensor_assert_eq(&r, &[1.0, -2.0, -1.0, 0.5, 0.5, -6.0], 3.0);
+    backend.binary_operation(BinaryOperation::Max, &a, &b, &mut r).unwrap();
+    tensor_assert_eq(&r, &[2.0, 4.0, 3.0, 4.0, 2.0, 3.0], 3.0);
+}
+
+pub fn test_binary_operation_div_pow<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Arithmetic<T> + IBackend {
+
+    let a = filled_tensor(&backend, &[2, 3], &A);
+    let b = filled_tensor(&backend, &[2, 3], &B);
+    let mut r = SharedTensor::<T>::new(&[2, 3]);
+
+    backend.binary_operation(BinaryOperation::Div, &a, &b, &mut r).unwrap();
+    tensor_assert_eq(&r, &[0.5, -0.5, -3.0, 8.0, 0.25, -2.0], 3.0);
+
+    let p = filled_tensor(&backend, &[6], &POSITIVE);
+    let exponent = filled_tensor(&backend, &[1], &[0.5]);
+    let mut r = SharedTensor::<T>::new(&[6]);
+
+    backend.binary_operation(BinaryOperation::Pow, &p, &exponent, &mut r).unwrap();
+    tensor_assert_eq(&r, &[0.5, 1.0, 2.0f64.sqrt(), 2.0, 3.0, 4.0], 3.0);
+}
+
+pub fn test_binary_operation_broadcast<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Arithmetic<T> + IBackend {
+
+    let a = filled_tensor(&backend, &[2, 3], &A);
+    let row = filled_tensor(&backend, &[3], &ROW);
+    let column = filled_tensor(&backend, &[2, 1], &COLUMN);
+    let mut r = SharedTensor::<T>::new(&[2, 3]);
+
+    backend.binary_operation(BinaryOperation::Mul, &a, &row, &mut r).unwrap();
+    tensor_assert_eq(&r, &[1.0, -4.0, -9.0, 4.0, 1.0, 18.0], 3.0);
+    backend.binary_operation(BinaryOperation::Sub, &column, &a, &mut r).unwrap();
+    tensor_assert_eq(&r, &[9.0, 12.0, 7.0, -5.0, -1.5, 5.0], 3.0);
+}
+
+pub fn test_binary_operation_broadcast_both<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Arithmetic<T> + IBackend {
+
+    let row = filled_tensor(&backend, &[3], &ROW);
+    let column = filled_tensor(&backend, &[2, 1], &COLUMN);
+    let mut r = SharedTensor::<T>::new(&[2, 3]);
+
+    backend.binary_operation(BinaryOperation::Add, &column, &row, &mut r).unwrap();
+    tensor_assert_eq(&r, &[11.0, 12.0, 7.0, 0.0, 1.0, -4.0], 3.0);
+}
+
+pub fn test_binary_operation_shape_mismatch<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Arithmetic<T> + IBackend {
+
+    let a = filled_tensor(&backend, &[2, 3], &A);
+    let column = filled_tensor(&backend, &[2], &COLUMN);
+    let row = filled_tensor(&backend, &[3], &ROW);
+    let mut r = SharedTensor::<T>::new(&[2, 3]);
+    let mut r_row = SharedTensor::<T>::new(&[3]);
+
+    assert!(backend.binary_operation(BinaryOperation::Add, &a, &column, &mut r).is_err());
+    assert!(backend.binary_operation(BinaryOperation::Add, &a, &row, &mut r_row).is_err());
+}
+
+pub fn test_binary_operation_pointwise<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: ArithmeticPointwise<T> + IBackend {
+
+    let mut a = filled_tensor(&backend, &[2, 3], &A);
+    let b = filled_tensor(&backend, &[2, 3], &B);
+    let column = filled_tensor(&backend, &[2, 1], &COLUMN);
+
+    backend.binary_operation_pointwise(BinaryOperation::Sub, &mut a, &b).unwrap();
+    tensor_assert_eq(&a, &[-1.0, -6.0, 4.0, 3.5, -1.5, -9.0], 3.0);
+    backend.binary_operation_pointwise(BinaryOperation::Div, &mut a, &column).unwrap();
+    tensor_assert_eq(&a, &[-0.1, -0.6, 0.4, -3.5, 1.5, 9.0], 3.0);
+
+    // the result can not take the broadcast shape
+    let mut row = filled_tensor(&backend, &[3], &ROW);
+    assert!(backend.binary_operation_pointwise(BinaryOperation::Add, &mut row, &a).is_err());
+}
+
+pub fn test_unary_operation<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Arithmetic<T> + IBackend {
+
+    let a = filled_tensor(&backend, &[2, 3], &A);
+    let p = filled_tensor(&backend, &[2, 3], &POSITIVE);
+    let mut r = SharedTensor::<T>::new(&[2, 3]);
+
+    backend.unary_operation(UnaryOperation::Neg, &a, &mut r).unwrap();
+    tensor_assert_eq(&r, &[-1.0, 2.0, -3.0, -4.0, -0.5, 6.0], 3.0);
+    backend.unary_operation(UnaryOperation::Abs, &a, &mut r).unwrap();
+    tensor_assert_eq(&r, &[1.0, 2.0, 3.0, 4.0, 0.5, 6.0], 3.0);
+    backend.unary_operation(UnaryOperation::Exp, &a, &mut r).unwrap();
+    tensor_assert_eq(&r, &A.iter().map(|x| x.exp()).collect::<Vec<_>>(), 3.0);
+    backend.unary_operation(UnaryOperation::Log, &p, &mut r).unwrap();
+    tensor_assert_eq(&r, &POSITIVE.iter().map(|x| x.ln()).collect::<Vec<_>>(), 3.0);
+    backend.unary_operation(UnaryOperation::Sqrt, &p, &mut r).unwrap();
+    tensor_assert_eq(&r, &[0.5, 1.0, 2.0f64.sqrt(), 2.0, 3.0, 4.0], 3.0);
+    backend.unary_operation(UnaryOperation::Clamp { min: -1.0, max: 2.0 }, &a, &mut r).unwrap();
+    tensor_assert_eq(&r, &[1.0, -1.0, 2.0, 2.0, 0.5, -1.0], 3.0);
+
+    let clamp = UnaryOperation::Clamp { min: 1.0, max: -1.0 };
+    assert!(backend.unary_operation(clamp, &a, &mut r).is_err());
+}
+
+pub fn test_unary_operation_pointwise<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: ArithmeticPointwise<T> + IBackend {
+
+    let mut p = filled_tensor(&backend, &[2, 3], &POSITIVE);
+
+    backend.unary_operation_pointwise(UnaryOperation::Sqrt, &mut p).unwrap();
+    tensor_assert_eq(&p, &[0.5, 1.0, 2.0f64.sqrt(), 2.0, 3.0, 4.0], 3.0);
+    backend.unary_operation_pointwise(UnaryOperation::Neg, &mut p).unwrap();
+    tensor_assert_eq(&p, &[-0.5, -1.0, -(2.0f64.sqrt()), -2.0, -3.0, -4.0], 3.0);
+}
+
+fn cross_test_arithmetic<F: IFramework, G: IFramework>(backend_a: Backend<F>, backend_b: Backend<G>)
+    where Backend<F>: Arithmetic<f32> + IBackend,
+          Backend<G>: Arithmetic<f32> + IBackend {
+
+    let a = uniformly_random_tensor(&backend_a, &[3, 1, 4, 5], 0.5f32, 2.0);
+    let b = uniformly_random_tensor(&backend_a, &[2, 1, 5], -2.0f32, 2.0);
+    let mut result_a = SharedTensor::<f32>::new(&[3, 2, 4, 5]);
+    let mut result_b = SharedTensor::<f32>::new(&[3, 2, 4, 5]);
+
+    // the math functions of the device may differ from the host ones by a few ulp
+    for &op in &[BinaryOperation::Add, BinaryOperation::Sub, BinaryOperation::Mul, BinaryOperation::Div,
+                 BinaryOperation::Pow, BinaryOperation::Min, BinaryOperation::Max] {
+        backend_a.binary_operation(op, &a, &b, &mut result_a).unwrap();
+        backend_b.binary_operation(op, &a, &b, &mut result_b).unwrap();
+        tensor_assert_eq_tensor(&result_a, &result_b, 8.0);
+    }
+
+    let mut result_a = SharedTensor::<f32>::new(&[3, 1, 4, 5]);
+    let mut result_b = SharedTensor::<f32>::new(&[3, 1, 4, 5]);
+    for &op in &[UnaryOperation::Neg, UnaryOperation::Abs, UnaryOperation::Exp, UnaryOperation::Log,
+                 UnaryOperation::Sqrt, UnaryOperation::Clamp { min: 0.75, max: 1.5 }] {
+        backend_a.unary_operation(op, &a, &mut result_a).unwrap();
+        backend_b.unary_operation(op, &a, &mut result_b).unwrap();
+        tensor_assert_eq_tensor(&result_a, &result_b, 8.0);
+    }
+}
+
+mod cuda {
+    use super::*;
+    test_cuda!(test_binary_operation, binary_operation_f32, binary_operation_f64);
+    test_cuda!(test_binary_operation_div_pow, binary_operation_div_pow_f32, binary_operation_div_pow_f64);
+    test_cuda!(test_binary_operation_broadcast, binary_operation_broadcast_f32, binary_operation_broadcast_f64);
+    test_cuda!(test_binary_operation_broadcast_both,
+               binary_operation_broadcast_both_f32, binary_operation_broadcast_both_f64);
+    test_cuda!(test_binary_operation_shape_mismatch,
+               binary_operation_shape_mismatch_f32, binary_operation_shape_mismatch_f64);
+    test_cuda!(test_binary_operation_pointwise, binary_operation_pointwise_f32, binary_operation_pointwise_f64);
+    test_cuda!(test_unary_operation, unary_operation_f32, unary_operation_f64);
+    test_cuda!(test_unary_operation_pointwise, unary_operation_pointwise_f32, unary_operation_pointwise_f64);
+}
+
+mod native {
+    use super::*;
+    test_native!(test_binary_operation, binary_operation_f32, binary_operation_f64);
+    test_native!(test_binary_operation_div_pow, binary_operation_div_pow_f32, binary_operation_div_pow_f64);
+    test_native!(test_binary_operation_broadcast, binary_operation_broadcast_f32, binary_operation_broadcast_f64);
+    test_native!(test_binary_operation_broadcast_both,
+                 binary_operation_broadcast_both_f32, binary_operation_broadcast_both_f64);
+    test_native!(test_binary_operation_shape_mismatch,
+                 binary_operation_shape_mismatch_f32, binary_operation_shape_mismatch_f64);
+    test_native!(test_binary_operation_pointwise, binary_operation_pointwise_f32, binary_operation_pointwise_f64);
+    test_native!(test_unary_operation, unary_operation_f32, unary_operation_f64);
+    test_native!(test_unary_operation_pointwise, unary_operation_pointwise_f32, unary_operation_pointwise_f64);
+}
+
+mod cross {
+    use super::*;
+    test_cross!(cross_test_arithmetic, cross_test_arithmetic_f32);
+}
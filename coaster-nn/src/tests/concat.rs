@@ -0,0 +1,110 @@
+use std::fmt;
+
+use crate::co::prelude::*;
+use crate::co::plugin::numeric_helpers::Float;
+
+use crate::plugin::{Concat, Split};
+use crate::tests::{Epsilon, filled_tensor, tensor_assert_eq};
+
+// `A` is laid out as `[2, 1, 2]` and `B` as `[2, 2, 2]`.
+const A: [f64; 4] = [1.0, 2.0, 3.0, 4.0];
+const B: [f64; 8] = [5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0];
+// `A` and `B` joined along the axis `1`, laid out as `[2, 3, 2]`.
+const JOINED: [f64; 12] = [1.0, 2.0, 5.0, 6.0, 7.0, 8.0,
+                           3.0, 4.0, 9.0, 10.0, 11.0, 12.0];
+
+pub fn test_concat<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Concat<T> + IBackend {
+
+    let a = filled_tensor(&backend, &[2, 1, 2], &A);
+    let b = filled_tensor(&backend, &[2, 2, 2], &B);
+    let mut r = SharedTensor::<T>::new(&[2, 3, 2]);
+    backend.concat(&[&a, &b], &mut r, 1).unwrap();
+    tensor_assert_eq(&r, &JOINED, 0.0);
+
+    // the first and the last axis
+    let a = filled_tensor(&backend, &[1, 2], &[1.0, 2.0]);
+    let b = filled_tensor(&backend, &[2, 2], &[3.0, 4.0, 5.0, 6.0]);
+    let mut r = SharedTensor::<T>::new(&[3, 2]);
+    backend.concat(&[&a, &b], &mut r, 0).unwrap();
+    tensor_assert_eq(&r, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 0.0);
+
+    let a = filled_tensor(&backend, &[2, 1], &[1.0, 2.0]);
+    let mut r = SharedTensor::<T>::new(&[2, 3]);
+    backend.concat(&[&a, &b], &mut r, 1).unwrap();
+    tensor_assert_eq(&r, &[1.0, 3.0, 4.0, 2.0, 5.0, 6.0], 0.0);
+}
+
+pub fn test_concat_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Concat<T> + IBackend {
+
+    let dr = filled_tensor(&backend, &[2, 3, 2], &JOINED);
+    let mut da = SharedTensor::<T>::new(&[2, 1, 2]);
+    let mut db = SharedTensor::<T>::new(&[2, 2, 2]);
+    backend.concat_grad(&dr, &mut [&mut da, &mut db], 1).unwrap();
+    tensor_assert_eq(&da, &A, 0.0);
+    tensor_assert_eq(&db, &B, 0.0);
+}
+
+pub fn test_split<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Split<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 3, 2], &JOINED);
+    let mut a = SharedTensor::<T>::new(&[2, 1, 2]);
+    let mut b = SharedTensor::<T>::new(&[2, 2, 2]);
+    backend.split(&x, &mut [&mut a, &mut b], 1).unwrap();
+    tensor_assert_eq(&a, &A, 0.0);
+    tensor_assert_eq(&b, &B, 0.0);
+}
+
+pub fn test_split_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Split<T> + IBackend {
+
+    let da = filled_tensor(&backend, &[2, 1, 2], &A);
+    let db = filled_tensor(&backend, &[2, 2, 2], &B);
+    let mut dx = SharedTensor::<T>::new(&[2, 3, 2]);
+    backend.split_grad(&[&da, &db], &mut dx, 1).unwrap();
+    tensor_assert_eq(&dx, &JOINED, 0.0);
+}
+
+pub fn test_concat_shape_mismatch<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Concat<T> + Split<T> + IBackend {
+
+    let a = filled_tensor(&backend, &[2, 1, 2], &A);
+    let b = filled_tensor(&backend, &[2, 2, 2], &B);
+    let mut r = SharedTensor::<T>::new(&[2, 3, 2]);
+    let mut r_short = SharedTensor::<T>::new(&[2, 2, 2]);
+
+    // the inputs differ in a dimension other than the axis
+    assert!(backend.concat(&[&a, &b], &mut r, 2).is_err());
+    // the inputs do not add up to the result
+    assert!(backend.concat(&[&a, &b], &mut r_short, 1).is_err());
+    assert!(backend.concat(&[], &mut r, 1).is_err());
+    assert!(backend.concat(&[&a, &b], &mut r, 3).is_err());
+
+    let mut a_out = SharedTensor::<T>::new(&[2, 1, 2]);
+    assert!(backend.split(&b, &mut [&mut a_out], 1).is_err());
+}
+
+mod cuda {
+    use super::*;
+    test_cuda!(test_concat, concat_f32, concat_f64);
+    test_cuda!(test_concat_grad, concat_grad_f32, concat_grad_f64);
+    test_cuda!(test_split, split_f32, split_f64);
+    test_cuda!(test_split_grad, split_grad_f32, split_grad_f64);
+    test_cuda!(test_concat_shape_mismatch, concat_shape_mismatch_f32, concat_shape_mismatch_f64);
+}
+
+mod native {
+    use super::*;
+    test_native!(test_concat, concat_f32, concat_f64);
+    test_native!(test_concat_grad, concat_grad_f32, concat_grad_f64);
+    test_native!(test_split, split_f32, split_f64);
+    test_native!(test_split_grad, split_grad_f32, split_grad_f64);
+    test_native!(test_concat_shape_mismatch, concat_shape_mismatch_f32, concat_shape_mismatch_f64);
+}
@@ -40,6 +40,34 @@ pub fn test_lrn_grad<T, F: IFramework>(backend: Backend<F>)
 }
 
 
+pub fn test_lrn_channels<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: LRN<T> + IBackend {
+
+    // window of 3 channels, clipped at the first and the last channel
+    let x  = filled_tensor(&backend,&[1, 3, 1], &[1.0, 2.0, 3.0]);
+    let dy = filled_tensor(&backend,&[1, 3, 1], &[0.5, -1.0, 2.0]);
+    let mut r = SharedTensor::<T>::new(&[1, 3, 1]);
+    let mut dr = SharedTensor::<T>::new(&[1, 3, 1]);
+    let conf = LRN::<T>::new_lrn_config(&backend, 3u32, 0.3f64, 0.75f64, 2f64)
+        .unwrap();
+
+    backend.lrn(&x, &mut r, &conf).unwrap();
+    backend.lrn_grad(&r, &dy, &x, &mut dr, &conf).unwrap();
+
+    let s = [2.0 + 0.1 * 5.0, 2.0 + 0.1 * 14.0, 2.0 + 0.1 * 13.0];
+    let r_test = [1.0 * s[0].powf(-0.75), 2.0 * s[1].powf(-0.75), 3.0 * s[2].powf(-0.75)];
+    tensor_assert_eq(&r, &r_test, 10.0);
+
+    // dx_i = dy_i * s_i^-beta - 2 * alpha * beta / n * x_i * sum_j dy_j * x_j * s_j^(-beta - 1)
+    let w = [0.5 * 1.0 * s[0].powf(-1.75), -1.0 * 2.0 * s[1].powf(-1.75), 2.0 * 3.0 * s[2].powf(-1.75)];
+    let dr_test = [0.5 * s[0].powf(-0.75) - 0.15 * 1.0 * (w[0] + w[1]),
+                   -1.0 * s[1].powf(-0.75) - 0.15 * 2.0 * (w[0] + w[1] + w[2]),
+                   2.0 * s[2].powf(-0.75) - 0.15 * 3.0 * (w[1] + w[2])];
+    tensor_assert_eq(&dr, &dr_test, 10.0);
+}
+
+
 pub fn test_convolution<T, F: IFramework>(backend: Backend<F>)
     where T: Float + Epsilon + fmt::Debug,
           Backend<F>: Convolution<T> + IBackend {
@@ -92,33 +120,342 @@ pub fn test_convolution<T, F: IFramework>(backend: Backend<F>)
 }
 
 
-// TODO
-// pub fn test_convolution_grad<T, F: IFramework>(backend: Backend<F>)
-//     where T: Float + Epsilon + fmt::Debug,
-//           Backend<F>: Convolution<T> + IBackend {
-//     let batch = 4;
-//     let w1 = 9;
-//     let h1 = 9;
-//     let d1 = 3;
-//     let k = 6;
-//     let f = 3;
-//     let w2 = (w1 - f + 0) / 1;
-//     let h2 = (h1 - f + 0) / 1;
-//
-//     let mut x_val  = vec![1.0; batch * d1 * h1 * w1];
-//     let mut dx_val = vec![1.0; batch * d1 * h1 * w1];
-//     let mut f_val  = vec![1.0; k * d1 * f * f];
-//     let mut r_val  = vec![1.0; batch * k * h2 * w2];
-//     x_val[0] = 2.0;
-//     f_val[0] = 2.0;
-//
-//     let x  = filled_tensor(&backend,&[batch, d1, h1, w1], &x_val);
-//     let dx = filled_tensor(&backend,&[batch,  k, h2, w2], &x_val);
-//     let f  = filled_tensor(&backend,&[k, d1, f,  f], &f_val);
-//     let r  = filled_tensor(&backend,&[batch,  k, h2, w2], &f_val);
-//     let mut dr  = SharedTensor::<T>::new(&[batch, k, h2, w2]);
-// }
+pub fn test_convolution_grad_filter<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Convolution<T> + IBackend {
+
+    let test = | x_dims: &[usize], x_val: &[f64],
+                 f_dims: &[usize],
+                 dy_dims: &[usize], dy_val: &[f64],
+                 stride: &[i32], padding: &[i32],
+                 expected: &[f64] |
+    {
+        let f_val = vec![0.0; f_dims.iter().product()];
+        let x  = filled_tensor(&backend, x_dims, x_val);
+        let f  = filled_tensor(&backend, f_dims, &f_val);
+        let dy = filled_tensor(&backend, dy_dims, dy_val);
+        let mut df = SharedTensor::<T>::new(&f_dims);
+
+        let conf = backend.new_convolution_config(
+            &x, &dy, &f,
+            ConvForwardAlgo::Auto,
+            ConvBackwardFilterAlgo::Auto,
+            ConvBackwardDataAlgo::Auto,
+            stride, padding).unwrap();
+
+        let mut ws = SharedTensor::<u8>::new(&[conf.workspace_size()]);
+
+        backend.convolution_grad_filter(&x, &dy, &mut df, &mut ws, &conf).unwrap();
+        tensor_assert_eq(&df, expected, 3.0);
+    };
+
+    let x_val : Vec<f64> = (1..10).map(|v| v as f64).collect();
+    test(&[1, 1, 3, 3], &x_val, &[1, 1, 2, 2], &[1, 1, 2, 2], &[1.0; 4],
+         &[1, 1], &[0, 0], &[12.0, 16.0, 24.0, 28.0]);
+    test(&[1, 1, 3, 3], &x_val, &[1, 1, 2, 2], &[1, 1, 2, 2], &[1.0, 2.0, 3.0, 4.0],
+         &[2, 2], &[1, 1], &[20.0, 36.0, 36.0, 64.0]);
+    // every filter element sees each output of each batch exactly once
+    test(&[2, 2, 3, 3], &[1.0; 36], &[3, 2, 2, 2], &[2, 3, 2, 2], &[1.0; 24],
+         &[1, 1], &[0, 0], &[8.0; 24]);
+}
+
+pub fn test_convolution_grad_data<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Convolution<T> + IBackend {
 
+    let test = | x_dims: &[usize],
+                 f_dims: &[usize], f_val: &[f64],
+                 dy_dims: &[usize], dy_val: &[f64],
+                 stride: &[i32], padding: &[i32],
+                 expected: &[f64] |
+    {
+        let x_val = vec![0.0; x_dims.iter().product()];
+        let x  = filled_tensor(&backend, x_dims, &x_val);
+        let f  = filled_tensor(&backend, f_dims, f_val);
+        let dy = filled_tensor(&backend, dy_dims, dy_val);
+        let mut dx = SharedTensor::<T>::new(&x_dims);
+
+        let conf = backend.new_convolution_config(
+            &x, &dy, &f,
+            ConvForwardAlgo::Auto,
+            ConvBackwardFilterAlgo::Auto,
+            ConvBackwardDataAlgo::Auto,
+            stride, padding).unwrap();
+
+        let mut ws = SharedTensor::<u8>::new(&[conf.workspace_size()]);
+
+        backend.convolution_grad_data(&f, &dy, &mut dx, &mut ws, &conf).unwrap();
+        tensor_assert_eq(&dx, expected, 3.0);
+    };
+
+    test(&[1, 1, 3, 3], &[1, 1, 2, 2], &[1.0, 2.0, 3.0, 4.0], &[1, 1, 2, 2], &[1.0; 4],
+         &[1, 1], &[0, 0],
+         &[1.0, 3.0, 2.0,
+           4.0, 10.0, 6.0,
+           3.0, 7.0, 4.0]);
+    test(&[1, 1, 3, 3], &[1, 1, 2, 2], &[1.0, 2.0, 3.0, 4.0], &[1, 1, 2, 2], &[1.0, 2.0, 3.0, 4.0],
+         &[2, 2], &[1, 1],
+         &[4.0, 6.0, 8.0,
+           6.0, 4.0, 8.0,
+           12.0, 12.0, 16.0]);
+    // each input element receives the gradient of every filter
+    let expected : Vec<f64> = [3.0, 6.0, 3.0, 6.0, 12.0, 6.0, 3.0, 6.0, 3.0]
+        .iter().cycle().take(36).cloned().collect();
+    test(&[2, 2, 3, 3], &[3, 2, 2, 2], &[1.0; 24], &[2, 3, 2, 2], &[1.0; 24],
+         &[1, 1], &[0, 0], &expected);
+}
+
+
+pub fn test_convolution_dilated<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Convolution<T> + IBackend {
+
+    // a 2x2 filter dilated by 2 covers a 3x3 window
+    let x_val: Vec<f64> = (1..26).map(|v| v as f64).collect();
+    let x = filled_tensor(&backend, &[1, 1, 5, 5], &x_val);
+    let f = filled_tensor(&backend, &[1, 1, 2, 2], &[1.0; 4]);
+    let dy = filled_tensor(&backend, &[1, 1, 3, 3], &[1.0; 9]);
+    let mut y = SharedTensor::<T>::new(&[1, 1, 3, 3]);
+    let mut df = SharedTensor::<T>::new(&[1, 1, 2, 2]);
+    let mut dx = SharedTensor::<T>::new(&[1, 1, 5, 5]);
+
+    let conf = backend.new_grouped_convolution_config(
+        &x, &y, &f,
+        ConvForwardAlgo::Auto,
+        ConvBackwardFilterAlgo::Auto,
+        ConvBackwardDataAlgo::Auto,
+        &[1, 1], &[0, 0], &[2, 2], 1).unwrap();
+    let mut ws = SharedTensor::<u8>::new(&[conf.workspace_size()]);
+
+    backend.convolution(&f, &x, &mut y, &mut ws, &conf).unwrap();
+    tensor_assert_eq(&y, &[28.0, 32.0, 36.0, 48.0, 52.0, 56.0, 68.0, 72.0, 76.0], 3.0);
+
+    backend.convolution_grad_filter(&x, &dy, &mut df, &mut ws, &conf).unwrap();
+    tensor_assert_eq(&df, &[63.0, 81.0, 153.0, 171.0], 3.0);
+
+    backend.convolution_grad_data(&f, &dy, &mut dx, &mut ws, &conf).unwrap();
+    tensor_assert_eq(&dx, &[1.0, 1.0, 2.0, 1.0, 1.0,
+                            1.0, 1.0, 2.0, 1.0, 1.0,
+                            2.0, 2.0, 4.0, 2.0, 2.0,
+                            1.0, 1.0, 2.0, 1.0, 1.0,
+                            1.0, 1.0, 2.0, 1.0, 1.0], 3.0);
+}
+
+pub fn test_convolution_1d<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Convolution<T> + IBackend {
+
+    // symmetric values, so the result does not depend on the filter being flipped
+    let x = filled_tensor(&backend, &[1, 1, 5], &[1.0, 2.0, 3.0, 2.0, 1.0]);
+    let f = filled_tensor(&backend, &[1, 1, 3], &[1.0, 2.0, 1.0]);
+    let dy = filled_tensor(&backend, &[1, 1, 5], &[1.0; 5]);
+    let mut y = SharedTensor::<T>::new(&[1, 1, 5]);
+    let mut df = SharedTensor::<T>::new(&[1, 1, 3]);
+    let mut dx = SharedTensor::<T>::new(&[1, 1, 5]);
+
+    let conf = backend.new_convolution_config(
+        &x, &y, &f,
+        ConvForwardAlgo::Auto,
+        ConvBackwardFilterAlgo::Auto,
+        ConvBackwardDataAlgo::Auto,
+        &[1], &[1]).unwrap();
+    let mut ws = SharedTensor::<u8>::new(&[conf.workspace_size()]);
+
+    backend.convolution(&f, &x, &mut y, &mut ws, &conf).unwrap();
+    tensor_assert_eq(&y, &[4.0, 8.0, 10.0, 8.0, 4.0], 3.0);
+
+    backend.convolution_grad_filter(&x, &dy, &mut df, &mut ws, &conf).unwrap();
+    tensor_assert_eq(&df, &[8.0, 9.0, 8.0], 3.0);
+
+    backend.convolution_grad_data(&f, &dy, &mut dx, &mut ws, &conf).unwrap();
+    tensor_assert_eq(&dx, &[3.0, 4.0, 4.0, 4.0, 3.0], 3.0);
+}
+
+pub fn test_convolution_3d<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Convolution<T> + IBackend {
+
+    let x_val: Vec<f64> = (1..28).map(|v| v as f64).collect();
+    let x = filled_tensor(&backend, &[1, 1, 3, 3, 3], &x_val);
+    let f = filled_tensor(&backend, &[1, 1, 2, 2, 2], &[1.0; 8]);
+    let dy = filled_tensor(&backend, &[1, 1, 2, 2, 2], &[1.0; 8]);
+    let mut y = SharedTensor::<T>::new(&[1, 1, 2, 2, 2]);
+    let mut df = SharedTensor::<T>::new(&[1, 1, 2, 2, 2]);
+    let mut dx = SharedTensor::<T>::new(&[1, 1, 3, 3, 3]);
+
+    let conf = backend.new_convolution_config(
+        &x, &y, &f,
+        ConvForwardAlgo::Auto,
+        ConvBackwardFilterAlgo::Auto,
+        ConvBackwardDataAlgo::Auto,
+        &[1, 1, 1], &[0, 0, 0]).unwrap();
+    let mut ws = SharedTensor::<u8>::new(&[conf.workspace_size()]);
+
+    // every window sums a 2x2x2 cube of the input
+    let sums = [60.0, 68.0, 84.0, 92.0, 132.0, 140.0, 156.0, 164.0];
+    backend.convolution(&f, &x, &mut y, &mut ws, &conf).unwrap();
+    tensor_assert_eq(&y, &sums, 3.0);
+
+    backend.convolution_grad_filter(&x, &dy, &mut df, &mut ws, &conf).unwrap();
+    tensor_assert_eq(&df, &sums, 3.0);
+
+    // the number of windows each input element is part of
+    backend.convolution_grad_data(&f, &dy, &mut dx, &mut ws, &conf).unwrap();
+    tensor_assert_eq(&dx, &[1.0, 2.0, 1.0, 2.0, 4.0, 2.0, 1.0, 2.0, 1.0,
+                            2.0, 4.0, 2.0, 4.0, 8.0, 4.0, 2.0, 4.0, 2.0,
+                            1.0, 2.0, 1.0, 2.0, 4.0, 2.0, 1.0, 2.0, 1.0], 3.0);
+}
+
+pub fn test_convolution_grouped<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Convolution<T> + IBackend {
+
+    let test = |x_dims: &[usize], x_val: &[f64], f_dims: &[usize], f_val: &[f64], groups: usize,
+                expected_y: &[f64], expected_df: &[f64], expected_dx: &[f64]| {
+        let y_dims = [x_dims[0], f_dims[0], x_dims[2], x_dims[3]];
+        let x = filled_tensor(&backend, x_dims, x_val);
+        let f = filled_tensor(&backend, f_dims, f_val);
+        let dy = filled_tensor(&backend, &y_dims, &vec![1.0; y_dims.iter().product()]);
+        let mut y = SharedTensor::<T>::new(&y_dims);
+        let mut df = SharedTensor::<T>::new(&f_dims);
+        let mut dx = SharedTensor::<T>::new(&x_dims);
+
+        let conf = backend.new_grouped_convolution_config(
+            &x, &y, &f,
+            ConvForwardAlgo::Auto,
+            ConvBackwardFilterAlgo::Auto,
+            ConvBackwardDataAlgo::Auto,
+            &[1, 1], &[0, 0], &[1, 1], groups).unwrap();
+        let mut ws = SharedTensor::<u8>::new(&[conf.workspace_size()]);
+
+        backend.convolution(&f, &x, &mut y, &mut ws, &conf).unwrap();
+        tensor_assert_eq(&y, expected_y, 3.0);
+        backend.convolution_grad_filter(&x, &dy, &mut df, &mut ws, &conf).unwrap();
+        tensor_assert_eq(&df, expected_df, 3.0);
+        backend.convolution_grad_data(&f, &dy, &mut dx, &mut ws, &conf).unwrap();
+        tensor_assert_eq(&dx, expected_dx, 3.0);
+    };
+
+    // depthwise, every channel has its own filter
+    test(&[1, 2, 2, 2], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0], &[2, 1, 1, 1], &[2.0, -1.0], 2,
+         &[2.0, 4.0, 6.0, 8.0, -5.0, -6.0, -7.0, -8.0],
+         &[10.0, 26.0],
+         &[2.0, 2.0, 2.0, 2.0, -1.0, -1.0, -1.0, -1.0]);
+    // two output channels per group
+    test(&[1, 2, 1, 1], &[1.0, 2.0], &[4, 1, 1, 1], &[1.0, 2.0, 3.0, 4.0], 2,
+         &[1.0, 2.0, 6.0, 8.0],
+         &[1.0, 1.0, 2.0, 2.0],
+         &[3.0, 7.0]);
+}
+
+pub fn test_convolution_grouped_invalid<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Convolution<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[1, 4, 2, 2], &[1.0; 16]);
+    let y = SharedTensor::<T>::new(&[1, 3, 2, 2]);
+    let f = filled_tensor(&backend, &[3, 2, 1, 1], &[1.0; 6]);
+
+    let conf = |groups| backend.new_grouped_convolution_config(
+        &x, &y, &f,
+        ConvForwardAlgo::Auto,
+        ConvBackwardFilterAlgo::Auto,
+        ConvBackwardDataAlgo::Auto,
+        &[1, 1], &[0, 0], &[1, 1], groups);
+
+    // the output channels can not be split into two groups
+    assert!(conf(2).is_err());
+    // the filter does not span a fourth of the input channels
+    assert!(conf(4).is_err());
+    assert!(conf(0).is_err());
+}
+
+pub fn test_convolution_algos<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Convolution<T> + IBackend {
+
+    // the explicit matrix product has to agree with the direct convolution
+    let test = |x_dims: &[usize], f_dims: &[usize], y_dims: &[usize], stride: &[i32], padding: &[i32],
+                dilation: &[i32], groups: usize| {
+        let x_val: Vec<f64> = (0..x_dims.iter().product()).map(|i| ((i * 7) % 11) as f64 - 5.0).collect();
+        let f_val: Vec<f64> = (0..f_dims.iter().product()).map(|i| ((i * 3) % 5) as f64 - 2.0).collect();
+        let dy_val: Vec<f64> = (0..y_dims.iter().product()).map(|i| ((i * 5) % 7) as f64 - 3.0).collect();
+        let x = filled_tensor(&backend, x_dims, &x_val);
+        let f = filled_tensor(&backend, f_dims, &f_val);
+        let dy = filled_tensor(&backend, y_dims, &dy_val);
+
+        let run = |algo_fwd, algo_bwd_filter, algo_bwd_data| {
+            let mut y = SharedTensor::<T>::new(&y_dims);
+            let mut df = SharedTensor::<T>::new(&f_dims);
+            let mut dx = SharedTensor::<T>::new(&x_dims);
+            let conf = backend.new_grouped_convolution_config(&x, &y, &f, algo_fwd, algo_bwd_filter, algo_bwd_data,
+                                                              stride, padding, dilation, groups).unwrap();
+            let mut ws = SharedTensor::<u8>::new(&[conf.workspace_size()]);
+            backend.convolution(&f, &x, &mut y, &mut ws, &conf).unwrap();
+            backend.convolution_grad_filter(&x, &dy, &mut df, &mut ws, &conf).unwrap();
+            backend.convolution_grad_data(&f, &dy, &mut dx, &mut ws, &conf).unwrap();
+            (y, df, dx)
+        };
+
+        let direct = run(ConvForwardAlgo::Direct,
+                         ConvBackwardFilterAlgo::Direct,
+                         ConvBackwardDataAlgo::Direct);
+        let gemm = run(ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::GEMM, ConvBackwardDataAlgo::GEMM);
+        tensor_assert_eq_tensor(&direct.0, &gemm.0, 3.0);
+        tensor_assert_eq_tensor(&direct.1, &gemm.1, 3.0);
+        tensor_assert_eq_tensor(&direct.2, &gemm.2, 3.0);
+    };
+
+    test(&[2, 3, 7, 6], &[4, 3, 3, 2], &[2, 4, 4, 4], &[2, 2], &[1, 1], &[1, 1], 1);
+    test(&[1, 2, 5, 5], &[3, 2, 1, 1], &[1, 3, 5, 5], &[1, 1], &[0, 0], &[1, 1], 1);
+    test(&[2, 2, 4, 4, 3], &[2, 2, 2, 3, 2], &[2, 2, 3, 2, 4], &[1, 2, 1], &[0, 0, 1], &[1, 1, 1], 1);
+    test(&[2, 4, 7, 6], &[6, 2, 3, 2], &[2, 6, 5, 6], &[1, 1], &[1, 1], &[2, 2], 2);
+    test(&[1, 3, 6, 6], &[3, 1, 3, 3], &[1, 3, 1, 6], &[2, 1], &[0, 1], &[2, 1], 3);
+}
+
+#[cfg(feature = "native")]
+pub fn test_convolution_threads<T, F: IFramework>(_backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<Native>: Convolution<T> {
+
+    fn run<T, F: IFramework>(backend: &Backend<F>, algo: ConvForwardAlgo, algo_bwd_filter: ConvBackwardFilterAlgo,
+                             algo_bwd_data: ConvBackwardDataAlgo)
+                             -> (SharedTensor<T>, SharedTensor<T>, SharedTensor<T>)
+        where T: Float + Epsilon + fmt::Debug,
+              Backend<F>: Convolution<T> + IBackend {
+        let (x_dims, f_dims, y_dims) = ([4, 3, 9, 9], [5, 3, 3, 3], [4, 5, 5, 5]);
+        let x_val: Vec<f64> = (0..x_dims.iter().product()).map(|i| ((i * 7) % 11) as f64 - 5.0).collect();
+        let f_val: Vec<f64> = (0..f_dims.iter().product()).map(|i| ((i * 3) % 5) as f64 - 2.0).collect();
+        let dy_val: Vec<f64> = (0..y_dims.iter().product()).map(|i| ((i * 5) % 7) as f64 - 3.0).collect();
+        let x = filled_tensor(backend, &x_dims, &x_val);
+        let f = filled_tensor(backend, &f_dims, &f_val);
+        let dy = filled_tensor(backend, &y_dims, &dy_val);
+
+        let mut y = SharedTensor::<T>::new(&y_dims);
+        let mut df = SharedTensor::<T>::new(&f_dims);
+        let mut dx = SharedTensor::<T>::new(&x_dims);
+        let conf = backend.new_convolution_config(&x, &y, &f, algo, algo_bwd_filter, algo_bwd_data,
+                                                  &[2, 2], &[1, 1]).unwrap();
+        let mut ws = SharedTensor::<u8>::new(&[conf.workspace_size()]);
+        backend.convolution(&f, &x, &mut y, &mut ws, &conf).unwrap();
+        backend.convolution_grad_filter(&x, &dy, &mut df, &mut ws, &conf).unwrap();
+        backend.convolution_grad_data(&f, &dy, &mut dx, &mut ws, &conf).unwrap();
+        (y, df, dx)
+    }
+
+    // splitting up the work across threads must not change the results
+    let single = crate::tests::get_native_backend_with_threads(1);
+    let multi = crate::tests::get_native_backend_with_threads(4);
+    let algos = [(ConvForwardAlgo::Direct, ConvBackwardFilterAlgo::Direct, ConvBackwardDataAlgo::Direct),
+                 (ConvForwardAlgo::GEMM, ConvBackwardFilterAlgo::GEMM, ConvBackwardDataAlgo::GEMM)];
+    for &(algo_fwd, algo_bwd_filter, algo_bwd_data) in algos.iter() {
+        let expected = run::<T, _>(&single, algo_fwd, algo_bwd_filter, algo_bwd_data);
+        let actual = run::<T, _>(&multi, algo_fwd, algo_bwd_filter, algo_bwd_data);
+        tensor_assert_eq_tensor(&expected.0, &actual.0, 0.0);
+        tensor_assert_eq_tensor(&expected.1, &actual.1, 0.0);
+        tensor_assert_eq_tensor(&expected.2, &actual.2, 0.0);
+    }
+}
 
 fn cross_test_convolution<F: IFramework, G: IFramework>(backend_a: Backend<F>, backend_b: Backend<G>)
     where Backend<F>: Convolution<f32> + IBackend,
@@ -175,14 +512,31 @@ mod cuda {
     use super::*;
     test_cuda!(test_lrn, lrn_f32, lrn_f64);
     test_cuda!(test_lrn_grad, lrn_grad_f32, lrn_grad_f64);
+    test_cuda!(test_lrn_channels, lrn_channels_f32, lrn_channels_f64);
     test_cuda!(test_convolution, convolution_f32, convolution_f64);
+    test_cuda!(test_convolution_grad_filter, convolution_grad_filter_f32, convolution_grad_filter_f64);
+    test_cuda!(test_convolution_grad_data, convolution_grad_data_f32, convolution_grad_data_f64);
+    test_cuda!(test_convolution_dilated, convolution_dilated_f32, convolution_dilated_f64);
+    test_cuda!(test_convolution_grouped, convolution_grouped_f32, convolution_grouped_f64);
+    test_cuda!(test_convolution_1d, convolution_1d_f32, convolution_1d_f64);
+    test_cuda!(test_convolution_3d, convolution_3d_f32, convolution_3d_f64);
 }
 
 mod native {
     use super::*;
-    //test_native!(test_lrn, lrn_f32, lrn_f64);
-    //test_native!(test_lrn_grad, lrn_grad_f32, lrn_grad_f64);
+    test_native!(test_lrn, lrn_f32, lrn_f64);
+    test_native!(test_lrn_grad, lrn_grad_f32, lrn_grad_f64);
+    test_native!(test_lrn_channels, lrn_channels_f32, lrn_channels_f64);
     test_native!(test_convolution, convolution_f32, convolution_f64);
+    test_native!(test_convolution_grad_filter, convolution_grad_filter_f32, convolution_grad_filter_f64);
+    test_native!(test_convolution_grad_data, convolution_grad_data_f32, convolution_grad_data_f64);
+    test_native!(test_convolution_dilated, convolution_dilated_f32, convolution_dilated_f64);
+    test_native!(test_convolution_grouped, convolution_grouped_f32, convolution_grouped_f64);
+    test_native!(test_convolution_1d, convolution_1d_f32, convolution_1d_f64);
+    test_native!(test_convolution_3d, convolution_3d_f32, convolution_3d_f64);
+    test_native!(test_convolution_grouped_invalid, convolution_grouped_invalid_f32, convolution_grouped_invalid_f64);
+    test_native!(test_convolution_algos, convolution_algos_f32, convolution_algos_f64);
+    test_native!(test_convolution_threads, convolution_threads_f32, convolution_threads_f64);
 }
 
 mod cross {
@@ -0,0 +1,105 @@
+use std::fmt;
+
+use crate::co::prelude::*;
+use crate::co::plugin::numeric_helpers::Float;
+
+use crate::plugin::SoftmaxCrossEntropy;
+use crate::tests::{Epsilon, filled_tensor, tensor_assert_eq};
+
+// the softmax of the first sample is `[1/8, 2/8, 5/8]`, of the second `[1/8, 3/8, 4/8]`,
+// which is shifted far beyond the range of `exp`
+fn scores() -> Vec<f64> {
+    vec![0.0, 2f64.ln(), 5f64.ln(), 100.0, 100.0 + 3f64.ln(), 100.0 + 4f64.ln()]
+}
+
+pub fn test_softmax_cross_entropy<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: SoftmaxCrossEntropy<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 3], &scores());
+    let target = filled_tensor(&backend, &[2, 1], &[2.0, 1.0]);
+    let weights = filled_tensor(&backend, &[3], &[1.0, 2.0, 3.0]);
+    let mut r = SharedTensor::<T>::new(&[2]);
+
+    backend.softmax_cross_entropy(&x, &target, None, None, &mut r).unwrap();
+    tensor_assert_eq(&r, &[-(5f64 / 8.0).ln(), -(3f64 / 8.0).ln()], 200.0);
+
+    backend.softmax_cross_entropy(&x, &target, Some(&weights), None, &mut r).unwrap();
+    tensor_assert_eq(&r, &[-3.0 * (5f64 / 8.0).ln(), -2.0 * (3f64 / 8.0).ln()], 200.0);
+
+    backend.softmax_cross_entropy(&x, &target, None, Some(2), &mut r).unwrap();
+    tensor_assert_eq(&r, &[0.0, -(3f64 / 8.0).ln()], 200.0);
+
+    // probability targets
+    let target = filled_tensor(&backend, &[2, 3], &[0.5, 0.0, 0.5, 0.0, 1.0, 0.0]);
+    backend.softmax_cross_entropy(&x, &target, None, None, &mut r).unwrap();
+    tensor_assert_eq(&r, &[-0.5 * (1f64 / 8.0).ln() - 0.5 * (5f64 / 8.0).ln(), -(3f64 / 8.0).ln()], 200.0);
+}
+
+pub fn test_softmax_cross_entropy_spatial<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: SoftmaxCrossEntropy<T> + IBackend {
+
+    // two classes at two positions, the softmax is `[1/4, 3/4]` at the first, `[3/4, 1/4]` at the second
+    let x = filled_tensor(&backend, &[1, 2, 2], &[0.0, 3f64.ln(), 3f64.ln(), 0.0]);
+    let target = filled_tensor(&backend, &[1, 2], &[1.0, 1.0]);
+    let mut r = SharedTensor::<T>::new(&[1, 2]);
+    backend.softmax_cross_entropy(&x, &target, None, None, &mut r).unwrap();
+    tensor_assert_eq(&r, &[-(3f64 / 4.0).ln(), -(1f64 / 4.0).ln()], 10.0);
+
+    let dr = filled_tensor(&backend, &[1, 2], &[1.0, 1.0]);
+    let mut dx = SharedTensor::<T>::new(&[1, 2, 2]);
+    backend.softmax_cross_entropy_grad(&x, &target, None, None, &dr, &mut dx).unwrap();
+    tensor_assert_eq(&dx, &[0.25, 0.75, -0.25, -0.75], 10.0);
+}
+
+pub fn test_softmax_cross_entropy_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: SoftmaxCrossEntropy<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 3], &scores());
+    let target = filled_tensor(&backend, &[2], &[2.0, 1.0]);
+    let weights = filled_tensor(&backend, &[3], &[1.0, 2.0, 3.0]);
+    let dr = filled_tensor(&backend, &[2], &[1.0, 0.5]);
+    let mut dx = SharedTensor::<T>::new(&[2, 3]);
+
+    backend.softmax_cross_entropy_grad(&x, &target, None, None, &dr, &mut dx).unwrap();
+    tensor_assert_eq(&dx, &[0.125, 0.25, -0.375, 0.0625, -0.3125, 0.25], 200.0);
+
+    backend.softmax_cross_entropy_grad(&x, &target, Some(&weights), Some(1), &dr, &mut dx).unwrap();
+    tensor_assert_eq(&dx, &[0.375, 0.75, -1.125, 0.0, 0.0, 0.0], 200.0);
+
+    let target = filled_tensor(&backend, &[2, 3], &[0.5, 0.0, 0.5, 0.0, 1.0, 0.0]);
+    backend.softmax_cross_entropy_grad(&x, &target, None, None, &dr, &mut dx).unwrap();
+    tensor_assert_eq(&dx, &[-0.375, 0.25, 0.125, 0.0625, -0.3125, 0.25], 200.0);
+}
+
+pub fn test_softmax_cross_entropy_invalid<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: SoftmaxCrossEntropy<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 3], &scores());
+    let mut r = SharedTensor::<T>::new(&[2]);
+    for &class in &[3.0, -1.0, 1.5] {
+        let target = filled_tensor(&backend, &[2], &[0.0, class]);
+        assert!(backend.softmax_cross_entropy(&x, &target, None, None, &mut r).is_err());
+    }
+
+    let target = filled_tensor(&backend, &[2], &[0.0, 1.0]);
+    // the weights do not match the classes
+    let weights = filled_tensor(&backend, &[2], &[1.0, 1.0]);
+    assert!(backend.softmax_cross_entropy(&x, &target, Some(&weights), None, &mut r).is_err());
+    // the result keeps the class dimension
+    let mut r = SharedTensor::<T>::new(&[2, 3]);
+    assert!(backend.softmax_cross_entropy(&x, &target, None, None, &mut r).is_err());
+}
+
+mod native {
+    use super::*;
+    test_native!(test_softmax_cross_entropy, softmax_cross_entropy_f32, softmax_cross_entropy_f64);
+    test_native!(test_softmax_cross_entropy_spatial, softmax_cross_entropy_spatial_f32,
+                 softmax_cross_entropy_spatial_f64);
+    test_native!(test_softmax_cross_entropy_grad, softmax_cross_entropy_grad_f32, softmax_cross_entropy_grad_f64);
+    test_native!(test_softmax_cross_entropy_invalid, softmax_cross_entropy_invalid_f32,
+                 softmax_cross_entropy_invalid_f64);
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+
+use crate::co::prelude::*;
+use crate::co::plugin::numeric_helpers::Float;
+
+use crate::plugin::Embedding;
+use crate::tests::{Epsilon, filled_tensor, tensor_assert_eq};
+
+// a table of `[4, 2]`, row `i` holds `[2 * i, 2 * i + 1]`
+const WEIGHT: [f64; 8] = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+// the indices are laid out as `[2, 2]`
+const INDICES: [f64; 4] = [3.0, 1.0, 1.0, 0.0];
+
+pub fn test_embedding<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Embedding<T> + IBackend {
+
+    let weight = filled_tensor(&backend, &[4, 2], &WEIGHT);
+    let indices = filled_tensor(&backend, &[2, 2], &INDICES);
+    let mut r = SharedTensor::<T>::new(&[2, 2, 2]);
+    backend.embedding(&weight, &indices, &mut r).unwrap();
+    tensor_assert_eq(&r, &[6.0, 7.0, 2.0, 3.0, 2.0, 3.0, 0.0, 1.0], 0.0);
+}
+
+pub fn test_embedding_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Embedding<T> + IBackend {
+
+    let indices = filled_tensor(&backend, &[2, 2], &INDICES);
+    let dr = filled_tensor(&backend, &[2, 2, 2], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+    let mut dw = filled_tensor(&backend, &[4, 2], &WEIGHT);
+    // the row `1` is selected twice, the row `2` never
+    backend.embedding_grad(&indices, &dr, &mut dw, None).unwrap();
+    tensor_assert_eq(&dw, &[7.0, 8.0, 8.0, 10.0, 0.0, 0.0, 1.0, 2.0], 0.0);
+
+    backend.embedding_grad(&indices, &dr, &mut dw, Some(1)).unwrap();
+    tensor_assert_eq(&dw, &[7.0, 8.0, 0.0, 0.0, 0.0, 0.0, 1.0, 2.0], 0.0);
+}
+
+pub fn test_embedding_invalid_indices<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Embedding<T> + IBackend {
+
+    let weight = filled_tensor(&backend, &[4, 2], &WEIGHT);
+    let mut r = SharedTensor::<T>::new(&[1, 2]);
+    for &index in &[4.0, -1.0, 1.5] {
+        let indices = filled_tensor(&backend, &[1], &[index]);
+        assert!(backend.embedding(&weight, &indices, &mut r).is_err());
+    }
+
+    // the result lacks the embedding dimension
+    let indices = filled_tensor(&backend, &[2], &[0.0, 1.0]);
+    let mut r = SharedTensor::<T>::new(&[2]);
+    assert!(backend.embedding(&weight, &indices, &mut r).is_err());
+}
+
+mod native {
+    use super::*;
+    test_native!(test_embedding, embedding_f32, embedding_f64);
+    test_native!(test_embedding_grad, embedding_grad_f32, embedding_grad_f64);
+    test_native!(test_embedding_invalid_indices, embedding_invalid_indices_f32, embedding_invalid_indices_f64);
+}
@@ -31,6 +31,12 @@ impl Epsilon for f64 {
 fn get_native_backend() -> Backend<Native> {
     Backend::<Native>::default().unwrap()
 }
+#[cfg(feature = "native")]
+fn get_native_backend_with_threads(threads: usize) -> Backend<Native> {
+    let framework = Native::new();
+    let hardwares = framework.hardwares().to_vec();
+    Backend::new(BackendConfig::new(framework, &hardwares).with_threads(threads)).unwrap()
+}
 #[cfg(feature = "cuda")]
 use crate::co::frameworks::cuda::get_cuda_backend;
 #[cfg(feature = "opencl")]
@@ -237,8 +243,18 @@ macro_rules! test_cross {
 }
 
 mod activation;
+mod arithmetic;
+mod concat;
 mod convolutional;
+mod cross_entropy;
+mod embedding;
+mod pad;
 mod softmax;
+mod permute;
 mod pooling;
 mod dropout;
+mod normalization;
+mod reduction;
+mod rnn;
+mod upsample;
 mod bench_all;
@@ -0,0 +1,209 @@
+use std::fmt;
+
+use crate::co::prelude::*;
+use crate::co::plugin::numeric_helpers::Float;
+
+use crate::plugin::{BatchNormalization, GroupNorm, LayerNorm};
+use crate::tests::{Epsilon, filled_tensor, tensor_assert_eq};
+
+// Two channels of four values each, laid out as `[N, C, L] = [2, 2, 2]`.
+const BN_IN: [f64; 8] = [1.0, 3.0, -2.0, 0.0, 5.0, -1.0, 2.0, 4.0];
+const BN_SCALE: [f64; 2] = [1.0, 2.0];
+const BN_BIAS: [f64; 2] = [0.5, -1.0];
+const BN_EPSILON: f64 = 1e-5;
+
+pub fn test_batch_normalization_training<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: BatchNormalization<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 2, 2], &BN_IN);
+    let scale = filled_tensor(&backend, &[2], &BN_SCALE);
+    let bias = filled_tensor(&backend, &[2], &BN_BIAS);
+    let mut running_mean = filled_tensor(&backend, &[2], &[0.0, 0.0]);
+    let mut running_variance = filled_tensor(&backend, &[2], &[1.0, 1.0]);
+    let mut saved_mean = SharedTensor::<T>::new(&[2]);
+    let mut saved_inv_variance = SharedTensor::<T>::new(&[2]);
+    let mut r = SharedTensor::<T>::new(&[2, 2, 2]);
+    let conf = backend.new_batch_normalization_config(BN_EPSILON, 0.1).unwrap();
+
+    backend.batch_normalization_training(&x, &scale, &bias,
+                                         &mut running_mean, &mut running_variance,
+                                         &mut saved_mean, &mut saved_inv_variance,
+                                         &mut r, &conf).unwrap();
+
+    let r_test = [0.05278685171296671, 0.9472131482870333, -3.6832788897221995, -1.8944262965740666,
+                  1.8416394448610998, -0.8416394448610998, -0.10557370342593342, 1.6832788897221995];
+    tensor_assert_eq(&r, &r_test, 10.0);
+    tensor_assert_eq(&saved_mean, &[2.0, 1.0], 3.0);
+    tensor_assert_eq(&saved_inv_variance, &[0.4472131482870333, 0.4472131482870333], 3.0);
+    // the running variance uses the unbiased batch variance
+    tensor_assert_eq(&running_mean, &[0.2, 0.1], 3.0);
+    tensor_assert_eq(&running_variance, &[1.5666666666666667, 1.5666666666666667], 3.0);
+}
+
+pub fn test_batch_normalization_inference<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: BatchNormalization<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 2, 2], &BN_IN);
+    let scale = filled_tensor(&backend, &[2], &BN_SCALE);
+    let bias = filled_tensor(&backend, &[2], &BN_BIAS);
+    let mean = filled_tensor(&backend, &[2], &[1.0, -0.5]);
+    let variance = filled_tensor(&backend, &[2], &[4.0, 0.25]);
+    let mut r = SharedTensor::<T>::new(&[2, 2, 2]);
+    let conf = backend.new_batch_normalization_config(BN_EPSILON, 0.1).unwrap();
+
+    backend.batch_normalization_inference(&x, &scale, &bias, &mean, &variance, &mut r, &conf)
+        .unwrap();
+
+    let r_test = [0.5, 1.4999987500023437, -6.99988000359988, 0.9999600011999599,
+                  2.4999975000046875, -0.49999875000234373, 8.999800005999798, 16.999640010799638];
+    tensor_assert_eq(&r, &r_test, 10.0);
+}
+
+pub fn test_batch_normalization_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: BatchNormalization<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 2, 2], &BN_IN);
+    let dr = filled_tensor(&backend, &[2, 2, 2], &[0.5, -1.0, 2.0, 1.0, -0.5, 0.0, 1.0, 3.0]);
+    let scale = filled_tensor(&backend, &[2], &BN_SCALE);
+    let saved_mean = filled_tensor(&backend, &[2], &[2.0, 1.0]);
+    let saved_inv_variance = filled_tensor(&backend, &[2], &[0.4472131482870333, 0.4472131482870333]);
+    let mut dx = SharedTensor::<T>::new(&[2, 2, 2]);
+    let mut dscale = SharedTensor::<T>::new(&[2]);
+    let mut dbias = SharedTensor::<T>::new(&[2]);
+    let conf = backend.new_batch_normalization_config(BN_EPSILON, 0.1).unwrap();
+
+    backend.batch_normalization_grad(&x, &dr, &scale, &saved_mean, &saved_inv_variance,
+                                     &mut dx, &mut dscale, &mut dbias, &conf).unwrap();
+
+    let dx_test = [0.2683280231358961, -0.2683280231358961, 0.6260976026197896, -0.5366560462717922,
+                   0.08944222716637817, -0.08944222716637817, -0.8049833985893077, 0.7155418422413102];
+    tensor_assert_eq(&dx, &dx_test, 30.0);
+    tensor_assert_eq(&dscale, &[-1.3416394448610998, 1.3416394448610998], 10.0);
+    tensor_assert_eq(&dbias, &[-1.0, 7.0], 3.0);
+}
+
+pub fn test_layer_normalization<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: LayerNorm<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 3], &BN_IN[..6]);
+    let scale = filled_tensor(&backend, &[3], &[1.0, 2.0, 0.5]);
+    let bias = filled_tensor(&backend, &[3], &[0.5, -1.0, 0.0]);
+    let mut saved_mean = SharedTensor::<T>::new(&[2]);
+    let mut saved_inv_variance = SharedTensor::<T>::new(&[2]);
+    let mut r = SharedTensor::<T>::new(&[2, 3]);
+    let conf = backend.new_layer_normalization_config(BN_EPSILON).unwrap();
+
+    backend.layer_normalization(&x, &scale, &bias, &mut saved_mean, &mut saved_inv_variance,
+                                &mut r, &conf).unwrap();
+
+    let r_test = [0.6622212290267893, 1.27109720637505, -0.6488849161071572,
+                  -0.008000139291117314, 1.7940007661011457, -0.4445001218797276];
+    tensor_assert_eq(&r, &r_test, 100.0);
+    tensor_assert_eq(&saved_mean, &[0.6666666666666666, 1.3333333333333333], 3.0);
+    tensor_assert_eq(&saved_inv_variance, &[0.4866636870803679, 0.381000104468338], 10.0);
+}
+
+pub fn test_layer_normalization_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: LayerNorm<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 3], &BN_IN[..6]);
+    let dr = filled_tensor(&backend, &[2, 3], &[0.5, -1.0, 2.0, 1.0, -0.5, 3.0]);
+    let scale = filled_tensor(&backend, &[3], &[1.0, 2.0, 0.5]);
+    let saved_mean = filled_tensor(&backend, &[2], &[0.6666666666666666, 1.3333333333333333]);
+    let saved_inv_variance = filled_tensor(&backend, &[2], &[0.4866636870803679, 0.381000104468338]);
+    let mut dx = SharedTensor::<T>::new(&[2, 3]);
+    let mut dscale = SharedTensor::<T>::new(&[3]);
+    let mut dbias = SharedTensor::<T>::new(&[3]);
+    let conf = backend.new_layer_normalization_config(BN_EPSILON).unwrap();
+
+    backend.layer_normalization_grad(&x, &dr, &scale, &saved_mean, &saved_inv_variance,
+                                     &mut dx, &mut dscale, &mut dbias, &conf).unwrap();
+
+    let dx_test = [0.4162253044646258, -0.24973683477001074, -0.16648846969461498,
+                   -0.018435185632800118, 0.0030717474316582223, 0.015363438201142093];
+    tensor_assert_eq(&dx, &dx_test, 1000.0);
+    tensor_assert_eq(&dscale, &[-0.4268895247777227, -1.8340487947128115, -5.262540395706994], 30.0);
+    tensor_assert_eq(&dbias, &[1.5, -1.5, 5.0], 3.0);
+}
+
+// Four channels of two values each, normalized in two groups of two channels.
+const GN_IN: [f64; 16] = [1.0, 3.0, -2.0, 0.0, 5.0, -1.0, 2.0, 4.0,
+                          0.5, 1.5, -3.0, 2.0, 1.0, 0.0, -1.0, 6.0];
+const GN_SCALE: [f64; 4] = [1.0, 2.0, 0.5, -1.0];
+const GN_MEAN: [f64; 4] = [0.5, 2.5, 0.25, 1.5];
+const GN_INV_VARIANCE: [f64; 4] = [0.5546993428422812, 0.4364353648194543,
+                                   0.5121468480640269, 0.3713904202228678];
+
+pub fn test_group_normalization<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: GroupNorm<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 4, 2], &GN_IN);
+    let scale = filled_tensor(&backend, &[4], &GN_SCALE);
+    let bias = filled_tensor(&backend, &[4], &[0.5, -1.0, 0.0, 2.0]);
+    let mut saved_mean = SharedTensor::<T>::new(&[2, 2]);
+    let mut saved_inv_variance = SharedTensor::<T>::new(&[2, 2]);
+    let mut r = SharedTensor::<T>::new(&[2, 4, 2]);
+    let conf = backend.new_group_normalization_config(2, BN_EPSILON).unwrap();
+
+    backend.group_normalization(&x, &scale, &bias, &mut saved_mean, &mut saved_inv_variance,
+                                &mut r, &conf).unwrap();
+
+    let r_test = [0.7773496714211405, 1.8867483571057029, -3.7734967142114058, -1.554699342842281,
+                  0.5455442060243179, -0.763761888434045, 2.218217682409727, 1.3453469527708186,
+                  0.6280367120160067, 1.1401835600800336, -4.328954512416175, 0.792513968224094,
+                  -0.09284760505571694, -0.27854281516715085, 2.9284760505571694, 0.3287431089970949];
+    tensor_assert_eq(&r, &r_test, 100.0);
+    tensor_assert_eq(&saved_mean, &GN_MEAN, 3.0);
+    tensor_assert_eq(&saved_inv_variance, &GN_INV_VARIANCE, 10.0);
+}
+
+pub fn test_group_normalization_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: GroupNorm<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 4, 2], &GN_IN);
+    let dr = filled_tensor(&backend, &[2, 4, 2], &[0.5, -1.0, 2.0, 1.0, -0.5, 0.0, 1.0, 3.0,
+                                                   1.0, 2.0, -1.0, 0.5, 0.0, -2.0, 1.5, 1.0]);
+    let scale = filled_tensor(&backend, &[4], &GN_SCALE);
+    let saved_mean = filled_tensor(&backend, &[2, 2], &GN_MEAN);
+    let saved_inv_variance = filled_tensor(&backend, &[2, 2], &GN_INV_VARIANCE);
+    let mut dx = SharedTensor::<T>::new(&[2, 4, 2]);
+    let mut dscale = SharedTensor::<T>::new(&[4]);
+    let mut dbias = SharedTensor::<T>::new(&[4]);
+    let conf = backend.new_group_normalization_config(2, BN_EPSILON).unwrap();
+
+    backend.group_normalization_grad(&x, &dr, &scale, &saved_mean, &saved_inv_variance,
+                                     &mut dx, &mut dscale, &mut dbias, &conf).unwrap();
+
+    let dx_test = [-0.20267947583067714, 0.09600130653117665, 0.04267352917939364, 0.06400464012010683,
+                   0.5949025098098021, 0.12729428886907657, -0.020782544877583187, -0.7014142538012954,
+                   0.16371931662140507, 0.30644973504299844, -0.07976372382215835, -0.390405327842245,
+                   0.3297690734014006, -0.03201643540868463, -0.20810673410733596, -0.08964590388537999];
+    tensor_assert_eq(&dx, &dx_test, 1000.0);
+    tensor_assert_eq(&dscale, &[0.1603303107809413, -0.9382406373684353, 0.5686270546442855, 2.024284274444968], 100.0);
+    tensor_assert_eq(&dbias, &[2.5, 2.5, -2.5, 6.5], 3.0);
+}
+
+mod cuda {
+    use super::*;
+    test_cuda!(test_batch_normalization_training, batch_normalization_training_f32, batch_normalization_training_f64);
+    test_cuda!(test_batch_normalization_inference, batch_normalization_inference_f32, batch_normalization_inference_f64);
+    test_cuda!(test_batch_normalization_grad, batch_normalization_grad_f32, batch_normalization_grad_f64);
+}
+
+mod native {
+    use super::*;
+    test_native!(test_batch_normalization_training, batch_normalization_training_f32, batch_normalization_training_f64);
+    test_native!(test_batch_normalization_inference, batch_normalization_inference_f32, batch_normalization_inference_f64);
+    test_native!(test_batch_normalization_grad, batch_normalization_grad_f32, batch_normalization_grad_f64);
+    test_native!(test_layer_normalization, layer_normalization_f32, layer_normalization_f64);
+    test_native!(test_layer_normalization_grad, layer_normalization_grad_f32, layer_normalization_grad_f64);
+    test_native!(test_group_normalization, group_normalization_f32, group_normalization_f64);
+    test_native!(test_group_normalization_grad, group_normalization_grad_f32, group_normalization_grad_f64);
+}
@@ -0,0 +1,92 @@
+use std::fmt;
+
+use crate::co::prelude::*;
+use crate::co::plugin::numeric_helpers::Float;
+
+use crate::plugin::{Pad, PadMode};
+use crate::tests::{Epsilon, filled_tensor, tensor_assert_eq};
+
+pub fn test_pad_constant<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Pad<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 2], &[1.0, 2.0, 3.0, 4.0]);
+    let mut r = SharedTensor::<T>::new(&[3, 3]);
+    let mode = PadMode::Constant(T::from(9.0).unwrap());
+    backend.pad(mode, &x, &[(1, 0), (0, 1)], &mut r).unwrap();
+    tensor_assert_eq(&r, &[9.0, 9.0, 9.0,
+                           1.0, 2.0, 9.0,
+                           3.0, 4.0, 9.0], 0.0);
+}
+
+pub fn test_pad_reflect<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Pad<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[3], &[1.0, 2.0, 3.0]);
+    let mut r = SharedTensor::<T>::new(&[7]);
+    backend.pad(PadMode::Reflect, &x, &[(2, 2)], &mut r).unwrap();
+    tensor_assert_eq(&r, &[3.0, 2.0, 1.0, 2.0, 3.0, 2.0, 1.0], 0.0);
+
+    let x = filled_tensor(&backend, &[2, 3], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let mut r = SharedTensor::<T>::new(&[4, 5]);
+    backend.pad(PadMode::Reflect, &x, &[(1, 1), (1, 1)], &mut r).unwrap();
+    tensor_assert_eq(&r, &[5.0, 4.0, 5.0, 6.0, 5.0,
+                           2.0, 1.0, 2.0, 3.0, 2.0,
+                           5.0, 4.0, 5.0, 6.0, 5.0,
+                           2.0, 1.0, 2.0, 3.0, 2.0], 0.0);
+}
+
+pub fn test_pad_replicate<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Pad<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[3], &[1.0, 2.0, 3.0]);
+    let mut r = SharedTensor::<T>::new(&[7]);
+    backend.pad(PadMode::Replicate, &x, &[(2, 2)], &mut r).unwrap();
+    tensor_assert_eq(&r, &[1.0, 1.0, 1.0, 2.0, 3.0, 3.0, 3.0], 0.0);
+}
+
+pub fn test_pad_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Pad<T> + IBackend {
+
+    // the constant padding is cropped away
+    let dr_data: Vec<f64> = (0..9).map(|i| i as f64).collect();
+    let dr = filled_tensor(&backend, &[3, 3], &dr_data);
+    let mut dx = SharedTensor::<T>::new(&[2, 2]);
+    backend.pad_grad(PadMode::Constant(T::zero()), &dr, &[(1, 0), (0, 1)], &mut dx).unwrap();
+    tensor_assert_eq(&dx, &[3.0, 4.0, 6.0, 7.0], 0.0);
+
+    // the copied elements add up onto their sources
+    let dr = filled_tensor(&backend, &[7], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]);
+    let mut dx = SharedTensor::<T>::new(&[3]);
+    backend.pad_grad(PadMode::Reflect, &dr, &[(2, 2)], &mut dx).unwrap();
+    tensor_assert_eq(&dx, &[10.0, 12.0, 6.0], 0.0);
+    backend.pad_grad(PadMode::Replicate, &dr, &[(2, 2)], &mut dx).unwrap();
+    tensor_assert_eq(&dx, &[6.0, 4.0, 18.0], 0.0);
+}
+
+pub fn test_pad_invalid<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Pad<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 2], &[1.0, 2.0, 3.0, 4.0]);
+    let mut r = SharedTensor::<T>::new(&[4, 2]);
+    // a pair for every dimension is needed
+    assert!(backend.pad(PadMode::Replicate, &x, &[(1, 1)], &mut r).is_err());
+    // the result does not match the padding
+    assert!(backend.pad(PadMode::Replicate, &x, &[(1, 0), (0, 0)], &mut r).is_err());
+    // reflecting can not go beyond the opposite border
+    let mut r = SharedTensor::<T>::new(&[6, 2]);
+    assert!(backend.pad(PadMode::Reflect, &x, &[(2, 2), (0, 0)], &mut r).is_err());
+}
+
+mod native {
+    use super::*;
+    test_native!(test_pad_constant, pad_constant_f32, pad_constant_f64);
+    test_native!(test_pad_reflect, pad_reflect_f32, pad_reflect_f64);
+    test_native!(test_pad_replicate, pad_replicate_f32, pad_replicate_f64);
+    test_native!(test_pad_grad, pad_grad_f32, pad_grad_f64);
+    test_native!(test_pad_invalid, pad_invalid_f32, pad_invalid_f64);
+}
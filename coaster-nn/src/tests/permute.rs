@@ -0,0 +1,77 @@
+use std::fmt;
+
+use crate::co::prelude::*;
+use crate::co::plugin::numeric_helpers::Float;
+
+use crate::plugin::Permute;
+use crate::tests::{Epsilon, filled_tensor, tensor_assert_eq};
+
+const DIMS: [usize; 3] = [2, 3, 4];
+const AXES: [usize; 3] = [2, 0, 1];
+
+fn input() -> Vec<f64> {
+    (0..24).map(|i| i as f64).collect()
+}
+
+// `y[k][i][j] = x[i][j][k]`
+fn permuted() -> Vec<f64> {
+    let mut out = Vec::new();
+    for k in 0..4 {
+        for i in 0..2 {
+            for j in 0..3 {
+                out.push((i * 12 + j * 4 + k) as f64);
+            }
+        }
+    }
+    out
+}
+
+pub fn test_permute<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Permute<T> + IBackend {
+
+    let x = filled_tensor(&backend, &DIMS, &input());
+    let mut r = SharedTensor::<T>::new(&[4, 2, 3]);
+
+    backend.permute(&x, &mut r, &AXES).unwrap();
+    tensor_assert_eq(&r, &permuted(), 0.0);
+}
+
+pub fn test_permute_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Permute<T> + IBackend {
+
+    let dx = filled_tensor(&backend, &[4, 2, 3], &permuted());
+    let mut dr = SharedTensor::<T>::new(&DIMS);
+
+    backend.permute_grad(&dx, &mut dr, &AXES).unwrap();
+    tensor_assert_eq(&dr, &input(), 0.0);
+}
+
+pub fn test_permute_invalid<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Permute<T> + IBackend {
+
+    let x = filled_tensor(&backend, &DIMS, &input());
+    let mut r = SharedTensor::<T>::new(&[4, 2, 3]);
+    let mut r_unpermuted = SharedTensor::<T>::new(&DIMS);
+
+    assert!(backend.permute(&x, &mut r, &[2, 0]).is_err());
+    assert!(backend.permute(&x, &mut r, &[2, 0, 0]).is_err());
+    assert!(backend.permute(&x, &mut r, &[2, 0, 3]).is_err());
+    assert!(backend.permute(&x, &mut r_unpermuted, &AXES).is_err());
+}
+
+mod cuda {
+    use super::*;
+    test_cuda!(test_permute, permute_f32, permute_f64);
+    test_cuda!(test_permute_grad, permute_grad_f32, permute_grad_f64);
+    test_cuda!(test_permute_invalid, permute_invalid_f32, permute_invalid_f64);
+}
+
+mod native {
+    use super::*;
+    test_native!(test_permute, permute_f32, permute_f64);
+    test_native!(test_permute_grad, permute_grad_f32, permute_grad_f64);
+    test_native!(test_permute_invalid, permute_invalid_f32, permute_invalid_f64);
+}
@@ -3,7 +3,7 @@ use std::fmt;
 use crate::co::prelude::*;
 use crate::co::plugin::numeric_helpers::Float;
 
-use crate::plugin::Pooling;
+use crate::plugin::{Pooling, PoolingAvgMode};
 use crate::tests::{Epsilon, filled_tensor, tensor_assert_eq, tensor_assert_eq_tensor, uniformly_random_tensor};
 
 
@@ -104,6 +104,85 @@ pub fn test_pooling_avg_grad<T, F: IFramework>(backend: Backend<F>)
     tensor_assert_eq(&dr, &dr_test, 1.0);
 }
 
+pub fn test_pooling_avg_padding<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Pooling<T> + IBackend {
+
+    let test = |mode: PoolingAvgMode, r_test: &[f64], dr_test: &[f64]| {
+        // every padded 2x2 window covers exactly one element of the input
+        let x  = filled_tensor(&backend, &[1, 1, 2, 2], &[1.0, 2.0, 3.0, 4.0]);
+        let dy = filled_tensor(&backend, &[1, 1, 2, 2], &[1.0, 1.0, 1.0, 1.0]);
+        let mut r = SharedTensor::<T>::new(&[1, 1, 2, 2]);
+        let mut dx = SharedTensor::<T>::new(&[1, 1, 2, 2]);
+        let conf = Pooling::<T>::new_pooling_config_with_avg_mode(&backend, &[2, 2], &[2, 2], &[1, 1], mode)
+            .unwrap();
+
+        backend.pooling_avg(&x, &mut r, &conf).unwrap();
+        tensor_assert_eq(&r, r_test, 3.0);
+
+        backend.pooling_avg_grad(&r, &dy, &x, &mut dx, &conf).unwrap();
+        tensor_assert_eq(&dx, dr_test, 3.0);
+    };
+
+    test(PoolingAvgMode::ExcludePadding, &[1.0, 2.0, 3.0, 4.0], &[1.0, 1.0, 1.0, 1.0]);
+    test(PoolingAvgMode::IncludePadding, &[0.25, 0.5, 0.75, 1.0], &[0.25, 0.25, 0.25, 0.25]);
+}
+
+pub fn test_pooling_adaptive<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Pooling<T> + IBackend {
+
+    let test = |out_dims: &[usize], max_test: &[f64], avg_test: &[f64]| {
+        let x_val: Vec<f64> = (1..17).map(|v| v as f64).collect();
+        let x = filled_tensor(&backend, &[1, 1, 4, 4], &x_val);
+        let mut r = SharedTensor::<T>::new(&out_dims);
+        let conf = Pooling::<T>::new_adaptive_pooling_config(&backend, &[4, 4], &out_dims[2..])
+            .unwrap();
+
+        backend.pooling_max(&x, &mut r, &conf).unwrap();
+        tensor_assert_eq(&r, max_test, 3.0);
+
+        backend.pooling_avg(&x, &mut r, &conf).unwrap();
+        tensor_assert_eq(&r, avg_test, 3.0);
+    };
+
+    test(&[1, 1, 2, 2], &[6.0, 8.0, 14.0, 16.0], &[3.5, 5.5, 11.5, 13.5]);
+    // global pooling
+    test(&[1, 1, 1, 1], &[16.0], &[8.5]);
+}
+
+pub fn test_pooling_adaptive_uneven<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Pooling<T> + IBackend {
+
+    // the windows are [1, 2], [2, 3, 4] and [4, 5]
+    let x  = filled_tensor(&backend, &[1, 1, 5], &[1.0, 2.0, 3.0, 4.0, 5.0]);
+    let dy = filled_tensor(&backend, &[1, 1, 3], &[1.0, 1.0, 1.0]);
+    let mut r = SharedTensor::<T>::new(&[1, 1, 3]);
+    let mut dx = SharedTensor::<T>::new(&[1, 1, 5]);
+    let conf = Pooling::<T>::new_adaptive_pooling_config(&backend, &[5], &[3])
+        .unwrap();
+
+    backend.pooling_max(&x, &mut r, &conf).unwrap();
+    tensor_assert_eq(&r, &[2.0, 4.0, 5.0], 3.0);
+    backend.pooling_max_grad(&r, &dy, &x, &mut dx, &conf).unwrap();
+    tensor_assert_eq(&dx, &[0.0, 1.0, 0.0, 1.0, 1.0], 3.0);
+
+    backend.pooling_avg(&x, &mut r, &conf).unwrap();
+    tensor_assert_eq(&r, &[1.5, 3.0, 4.5], 3.0);
+    backend.pooling_avg_grad(&r, &dy, &x, &mut dx, &conf).unwrap();
+    tensor_assert_eq(&dx, &[0.5, 0.5 + 1.0 / 3.0, 1.0 / 3.0, 0.5 + 1.0 / 3.0, 0.5], 3.0);
+}
+
+pub fn test_pooling_adaptive_uneven_unsupported<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Pooling<T> + IBackend {
+
+    // backends without their own adaptive pooling only support evenly sized windows
+    assert!(Pooling::<T>::new_adaptive_pooling_config(&backend, &[5], &[3]).is_err());
+    assert!(Pooling::<T>::new_adaptive_pooling_config(&backend, &[6], &[3]).is_ok());
+}
+
 pub fn cross_test_pooling_max<F: IFramework, G: IFramework>(backend_a: Backend<F>, backend_b: Backend<G>)
         where
           Backend<F>: Pooling<f32> + IBackend,
@@ -184,14 +263,21 @@ mod cuda {
     use super::*;
     test_cuda!(test_pooling_avg, pooling_avg_f32, pooling_avg_f64);
     test_cuda!(test_pooling_avg_grad, pooling_avg_grad_f32, pooling_avg_grad_f64);
+    test_cuda!(test_pooling_avg_padding, pooling_avg_padding_f32, pooling_avg_padding_f64);
     test_cuda!(test_pooling_max, pooling_max_f32, pooling_max_f64);
     test_cuda!(test_pooling_max_grad, pooling_max_grad_f32, pooling_max_grad_f64);
+    test_cuda!(test_pooling_adaptive, pooling_adaptive_f32, pooling_adaptive_f64);
+    test_cuda!(test_pooling_adaptive_uneven_unsupported, pooling_adaptive_uneven_unsupported_f32,
+               pooling_adaptive_uneven_unsupported_f64);
 }
 
 mod native {
     use super::*;
-    //test_native!(test_pooling_avg, pooling_avg_f32, pooling_avg_f64);
-    //test_native!(test_pooling_avg_grad, pooling_avg_grad_f32, pooling_avg_grad_f64);
+    test_native!(test_pooling_avg, pooling_avg_f32, pooling_avg_f64);
+    test_native!(test_pooling_avg_grad, pooling_avg_grad_f32, pooling_avg_grad_f64);
+    test_native!(test_pooling_avg_padding, pooling_avg_padding_f32, pooling_avg_padding_f64);
     test_native!(test_pooling_max, pooling_max_f32, pooling_max_f64);
-    //test_native!(test_pooling_max_grad, pooling_max_grad_f32, pooling_max_grad_f64);
+    test_native!(test_pooling_max_grad, pooling_max_grad_f32, pooling_max_grad_f64);
+    test_native!(test_pooling_adaptive, pooling_adaptive_f32, pooling_adaptive_f64);
+    test_native!(test_pooling_adaptive_uneven, pooling_adaptive_uneven_f32, pooling_adaptive_uneven_f64);
 }
@@ -0,0 +1,106 @@
+use std::fmt;
+
+use crate::co::prelude::*;
+use crate::co::plugin::numeric_helpers::Float;
+
+use crate::plugin::{Reduction, ReductionOperation};
+use crate::tests::{Epsilon, filled_tensor, get_native_backend, tensor_assert_eq};
+
+// Laid out as `[2, 3, 2]`.
+const IN: [f64; 12] = [1.0, -2.0, 3.0, 0.5, -4.0, 6.0,
+                       2.0, 2.0, -1.0, 5.0, 0.0, -3.0];
+
+fn read_indices(xs: &SharedTensor<u32>) -> Vec<u32> {
+    let native = get_native_backend();
+    let mem = xs.read(native.device()).unwrap();
+    mem.as_slice::<u32>().to_vec()
+}
+
+pub fn test_reduce_sum<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Reduction<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 3, 2], &IN);
+    let mut r = SharedTensor::<T>::new(&[3, 2]);
+    backend.reduce(ReductionOperation::Sum, &x, &[0], false, &mut r).unwrap();
+    tensor_assert_eq(&r, &[3.0, 0.0, 2.0, 5.5, -4.0, 3.0], 3.0);
+
+    let mut r = SharedTensor::<T>::new(&[2, 1, 2]);
+    backend.reduce(ReductionOperation::Sum, &x, &[1], true, &mut r).unwrap();
+    tensor_assert_eq(&r, &[0.0, 4.5, 1.0, 4.0], 3.0);
+
+    let mut r = SharedTensor::<T>::new(&[3]);
+    backend.reduce(ReductionOperation::Sum, &x, &[2, 0], false, &mut r).unwrap();
+    tensor_assert_eq(&r, &[3.0, 7.5, -1.0], 3.0);
+
+    let mut r = SharedTensor::<T>::new(&[1]);
+    backend.reduce(ReductionOperation::Sum, &x, &[0, 1, 2], false, &mut r).unwrap();
+    tensor_assert_eq(&r, &[9.5], 3.0);
+}
+
+pub fn test_reduce_operations<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Reduction<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 3, 2], &IN);
+    let mut r = SharedTensor::<T>::new(&[2, 3, 1]);
+
+    backend.reduce(ReductionOperation::Mean, &x, &[2], true, &mut r).unwrap();
+    tensor_assert_eq(&r, &[-0.5, 1.75, 1.0, 2.0, 2.0, -1.5], 3.0);
+    backend.reduce(ReductionOperation::Max, &x, &[2], true, &mut r).unwrap();
+    tensor_assert_eq(&r, &[1.0, 3.0, 6.0, 2.0, 5.0, 0.0], 3.0);
+    backend.reduce(ReductionOperation::Min, &x, &[2], true, &mut r).unwrap();
+    tensor_assert_eq(&r, &[-2.0, 0.5, -4.0, 2.0, -1.0, -3.0], 3.0);
+    backend.reduce(ReductionOperation::Norm1, &x, &[2], true, &mut r).unwrap();
+    tensor_assert_eq(&r, &[3.0, 3.5, 10.0, 4.0, 6.0, 3.0], 3.0);
+    backend.reduce(ReductionOperation::Norm2, &x, &[2], true, &mut r).unwrap();
+    tensor_assert_eq(&r, &[5.0f64.sqrt(), 9.25f64.sqrt(), 52.0f64.sqrt(),
+                           8.0f64.sqrt(), 26.0f64.sqrt(), 3.0], 3.0);
+}
+
+pub fn test_reduce_shape_mismatch<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Reduction<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 3, 2], &IN);
+    let mut r = SharedTensor::<T>::new(&[2, 1, 2]);
+    let mut r_removed = SharedTensor::<T>::new(&[2, 2]);
+
+    // the kept dimension does not match `keep_dims`
+    assert!(backend.reduce(ReductionOperation::Sum, &x, &[1], false, &mut r).is_err());
+    assert!(backend.reduce(ReductionOperation::Sum, &x, &[1], true, &mut r_removed).is_err());
+    // invalid and repeated axes
+    assert!(backend.reduce(ReductionOperation::Sum, &x, &[3], false, &mut r_removed).is_err());
+    assert!(backend.reduce(ReductionOperation::Sum, &x, &[1, 1], false, &mut r_removed).is_err());
+}
+
+pub fn test_argmax<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Reduction<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[2, 3, 2], &IN);
+
+    let mut r = SharedTensor::<u32>::new(&[2, 2]);
+    backend.argmax(&x, 1, false, &mut r).unwrap();
+    assert_eq!(read_indices(&r), vec![1, 2, 0, 1]);
+
+    // ties resolve to the first index
+    let mut r = SharedTensor::<u32>::new(&[2, 3, 1]);
+    backend.argmax(&x, 2, true, &mut r).unwrap();
+    assert_eq!(read_indices(&r), vec![0, 0, 1, 0, 1, 0]);
+}
+
+mod cuda {
+    use super::*;
+    test_cuda!(test_reduce_sum, reduce_sum_f32, reduce_sum_f64);
+    test_cuda!(test_reduce_operations, reduce_operations_f32, reduce_operations_f64);
+    test_cuda!(test_argmax, argmax_f32, argmax_f64);
+}
+
+mod native {
+    use super::*;
+    test_native!(test_reduce_sum, reduce_sum_f32, reduce_sum_f64);
+    test_native!(test_reduce_operations, reduce_operations_f32, reduce_operations_f64);
+    test_native!(test_reduce_shape_mismatch, reduce_shape_mismatch_f32, reduce_shape_mismatch_f64);
+    test_native!(test_argmax, argmax_f32, argmax_f64);
+}
@@ -0,0 +1,204 @@
+use std::fmt;
+
+use crate::co::prelude::*;
+use crate::co::plugin::numeric_helpers::{cast, Float, NumCast};
+
+use crate::plugin::{DirectionMode, NN, Rnn, RnnAlgorithm, RnnConfig, RnnInputMode, RnnNetworkMode};
+use crate::tests::{Epsilon, filled_tensor, tensor_assert_eq};
+
+fn new_rnn_config<T, F: IFramework>(backend: &Backend<F>,
+                                    x: &SharedTensor<T>,
+                                    sequence_length: usize,
+                                    network_mode: RnnNetworkMode,
+                                    input_mode: RnnInputMode,
+                                    direction_mode: DirectionMode,
+                                    hidden_size: usize,
+                                    num_layers: usize)
+                                    -> <Backend<F> as NN<T>>::CRNN
+    where Backend<F>: Rnn<T> + IBackend {
+    backend.new_rnn_config(x,
+                           Some(0.0),
+                           None,
+                           sequence_length as i32,
+                           network_mode,
+                           input_mode,
+                           direction_mode,
+                           RnnAlgorithm::Standard,
+                           hidden_size as i32,
+                           num_layers as i32,
+                           x.desc()[0] as i32)
+        .unwrap()
+}
+
+fn read_tensor<T: Copy + NumCast>(xs: &SharedTensor<T>) -> Vec<f64> {
+    let native = crate::tests::get_native_backend();
+    xs.read(native.device()).unwrap().as_slice::<T>().iter().map(|&x| cast(x).unwrap()).collect()
+}
+
+pub fn test_rnn_weight_description<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Rnn<T> + IBackend {
+
+    let test = |network_mode: RnnNetworkMode,
+                input_mode: RnnInputMode,
+                direction_mode: DirectionMode,
+                input_size: usize,
+                hidden_size: usize,
+                num_layers: usize,
+                expected: usize| {
+        let x = SharedTensor::<T>::new(&[1, 2, input_size]);
+        let config = new_rnn_config(&backend, &x, 2, network_mode, input_mode, direction_mode,
+                                    hidden_size, num_layers);
+        let desc = backend.generate_rnn_weight_description(&config, 1, input_size as i32).unwrap();
+        assert_eq!(desc, vec![expected, 1, 1]);
+    };
+
+    // 4 gates * 2 hidden * (3 input + 2 hidden + 2 biases)
+    test(RnnNetworkMode::LSTM, RnnInputMode::LinearInput, DirectionMode::UniDirectional, 3, 2, 1, 56);
+    // 3 gates * 2 hidden * (3 input + 2 hidden + 2 biases)
+    test(RnnNetworkMode::GRU, RnnInputMode::LinearInput, DirectionMode::UniDirectional, 3, 2, 1, 42);
+    // 2 directions * (2 * (3 + 2 + 2) + 2 * (4 + 2 + 2))
+    test(RnnNetworkMode::Tanh, RnnInputMode::LinearInput, DirectionMode::BiDirectional, 3, 2, 2, 60);
+    // 4 gates * 2 hidden * (2 hidden + 2 biases)
+    test(RnnNetworkMode::LSTM, RnnInputMode::SkipInput, DirectionMode::UniDirectional, 2, 2, 1, 32);
+}
+
+pub fn test_rnn_forward<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Rnn<T> + IBackend {
+
+    // single tanh unit over two time steps, weights are W, R, bW, bR
+    let x = filled_tensor(&backend, &[1, 2, 1], &[1.0, 2.0]);
+    let w = filled_tensor(&backend, &[4, 1, 1], &[0.5, 0.25, 0.1, 0.0]);
+    let mut y = SharedTensor::<T>::new(&[1, 2, 1]);
+    let mut workspace = SharedTensor::<u8>::new(&[1]);
+    let config = new_rnn_config(&backend, &x, 2, RnnNetworkMode::Tanh, RnnInputMode::LinearInput,
+                                DirectionMode::UniDirectional, 1, 1);
+
+    backend.rnn_forward(&x, &mut y, &config, &w, &mut workspace).unwrap();
+
+    let h1 = (0.5f64 + 0.1).tanh();
+    let h2 = (1.0 + 0.1 + 0.25 * h1).tanh();
+    tensor_assert_eq(&y, &[h1, h2], 10.0);
+}
+
+pub fn test_rnn_forward_batch<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Rnn<T> + IBackend {
+
+    // single tanh unit over two samples of two time steps each, `[batch, sequence, input]`
+    let x = filled_tensor(&backend, &[2, 2, 1], &[1.0, 2.0, -1.0, 0.5]);
+    let w = filled_tensor(&backend, &[4, 1, 1], &[0.5, 0.25, 0.1, 0.0]);
+    let mut y = SharedTensor::<T>::new(&[2, 2, 1]);
+    let mut workspace = SharedTensor::<u8>::new(&[1]);
+    let config = new_rnn_config(&backend, &x, 2, RnnNetworkMode::Tanh, RnnInputMode::LinearInput,
+                                DirectionMode::UniDirectional, 1, 1);
+
+    backend.rnn_forward(&x, &mut y, &config, &w, &mut workspace).unwrap();
+
+    let a1 = (0.5f64 + 0.1).tanh();
+    let a2 = (1.0 + 0.1 + 0.25 * a1).tanh();
+    let b1 = (-0.5f64 + 0.1).tanh();
+    let b2 = (0.25 + 0.1 + 0.25 * b1).tanh();
+    tensor_assert_eq(&y, &[a1, a2, b1, b2], 10.0);
+}
+
+pub fn test_rnn_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Rnn<T> + IBackend {
+
+    // compares the analytic gradients of `sum(coefficients * y)` with the
+    // central differences w.r.t. every input and weight
+    let test = |network_mode: RnnNetworkMode,
+                input_mode: RnnInputMode,
+                direction_mode: DirectionMode,
+                num_layers: usize| {
+        let (batch_size, sequence_length, input_size, hidden_size) = (2, 3, 2, 2);
+        let directions = match direction_mode {
+            DirectionMode::UniDirectional => 1,
+            DirectionMode::BiDirectional => 2,
+        };
+        let x_dims = [batch_size, sequence_length, input_size];
+        let y_dims = [batch_size, sequence_length, hidden_size * directions];
+        let x_size = batch_size * sequence_length * input_size;
+        let y_size = batch_size * sequence_length * hidden_size * directions;
+
+        let x_vals: Vec<f64> = (0..x_size).map(|i| (i as f64 * 1.3).sin() * 0.5).collect();
+        let coefficients: Vec<f64> = (0..y_size).map(|i| ((i * 5 % 7) as f64 - 3.0) * 0.25).collect();
+
+        let x = filled_tensor(&backend, &x_dims, &x_vals);
+        let config = new_rnn_config(&backend, &x, sequence_length, network_mode, input_mode,
+                                    direction_mode, hidden_size, num_layers);
+        let w_dims = backend.generate_rnn_weight_description(&config, batch_size as i32,
+                                                              input_size as i32).unwrap();
+        let w_size = w_dims.iter().product();
+        let w_vals: Vec<f64> = (0..w_size).map(|i| (i as f64 * 0.7).cos() * 0.4).collect();
+        let mut workspace = SharedTensor::<u8>::new(&[config.workspace_size()]);
+
+        let mut loss = |x_vals: &[f64], w_vals: &[f64]| {
+            let x = filled_tensor::<T, F>(&backend, &x_dims, x_vals);
+            let w = filled_tensor::<T, F>(&backend, &w_dims, w_vals);
+            let mut y = SharedTensor::<T>::new(&y_dims);
+            backend.rnn_forward(&x, &mut y, &config, &w, &mut workspace).unwrap();
+            read_tensor(&y).iter().zip(&coefficients).map(|(y, c)| y * c).sum::<f64>()
+        };
+
+        let w = filled_tensor(&backend, &w_dims, &w_vals);
+        let y = SharedTensor::<T>::new(&y_dims);
+        let dy = filled_tensor(&backend, &y_dims, &coefficients);
+        let mut dx = SharedTensor::<T>::new(&x_dims);
+        let mut dw = SharedTensor::<T>::new(&w_dims);
+        let mut workspace = SharedTensor::<u8>::new(&[config.workspace_size()]);
+        backend.rnn_backward_data(&x, &mut dx, &y, &dy, &config, &w, &mut workspace).unwrap();
+        backend.rnn_backward_weights(&x, &y, &mut dw, &config, &mut workspace).unwrap();
+
+        let h = 1e-2;
+        let tolerance = 1e-2;
+        for (i, &grad) in read_tensor(&dx).iter().enumerate() {
+            let (mut plus, mut minus) = (x_vals.clone(), x_vals.clone());
+            plus[i] += h;
+            minus[i] -= h;
+            let numeric = (loss(&plus, &w_vals) - loss(&minus, &w_vals)) / (2.0 * h);
+            assert!((grad - numeric).abs() < tolerance,
+                    "dx[{}]: {} != {} ({:?})", i, grad, numeric, network_mode);
+        }
+        for (i, &grad) in read_tensor(&dw).iter().enumerate() {
+            let (mut plus, mut minus) = (w_vals.clone(), w_vals.clone());
+            plus[i] += h;
+            minus[i] -= h;
+            let numeric = (loss(&x_vals, &plus) - loss(&x_vals, &minus)) / (2.0 * h);
+            assert!((grad - numeric).abs() < tolerance,
+                    "dw[{}]: {} != {} ({:?})", i, grad, numeric, network_mode);
+        }
+    };
+
+    for &mode in &[RnnNetworkMode::ReLU, RnnNetworkMode::Tanh, RnnNetworkMode::LSTM, RnnNetworkMode::GRU] {
+        test(mode, RnnInputMode::LinearInput, DirectionMode::UniDirectional, 1);
+        test(mode, RnnInputMode::LinearInput, DirectionMode::BiDirectional, 2);
+    }
+    test(RnnNetworkMode::LSTM, RnnInputMode::SkipInput, DirectionMode::UniDirectional, 1);
+}
+
+mod native {
+    use super::*;
+    test_native!(test_rnn_weight_description, rnn_weight_description_f32, rnn_weight_description_f64);
+    test_native!(test_rnn_forward, rnn_forward_f32, rnn_forward_f64);
+    test_native!(test_rnn_forward_batch, rnn_forward_batch_f32, rnn_forward_batch_f64);
+    test_native!(test_rnn_grad, rnn_grad_f32, rnn_grad_f64);
+
+    #[test]
+    fn rnn_dropout_between_layers_unsupported() {
+        let backend = crate::tests::get_native_backend();
+        let x = SharedTensor::<f32>::new(&[1, 2, 3]);
+        let config = |num_layers: i32, dropout_probability: Option<f32>| {
+            backend.new_rnn_config(&x, dropout_probability, None, 2, RnnNetworkMode::Tanh,
+                                   RnnInputMode::LinearInput, DirectionMode::UniDirectional,
+                                   RnnAlgorithm::Standard, 2, num_layers, 1)
+        };
+        assert!(config(2, Some(0.5)).is_err());
+        assert!(config(2, None).is_err());
+        assert!(config(2, Some(0.0)).is_ok());
+        // there is nothing to drop out of a single layer
+        assert!(config(1, Some(0.5)).is_ok());
+    }
+}
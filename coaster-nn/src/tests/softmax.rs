@@ -68,6 +68,23 @@ pub fn test_softmax_grad<T, F: IFramework>(backend: Backend<F>)
     tensor_assert_eq(&dr, &SOFTMAX_IN_GRAD, 10.0);
 }
 
+pub fn test_softmax_batched<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Softmax<T> + IBackend {
+
+    // every row of a 2D input is normalized on its own
+    let x  = filled_tensor(&backend, &[2, 3], &[1000.0, 1000.0, 1000.0, 0.0, 3f64.ln(), 4f64.ln()]);
+    let mut r = SharedTensor::<T>::new(&[2, 3]);
+    backend.softmax(&x, &mut r).unwrap();
+    let third = 1.0 / 3.0;
+    tensor_assert_eq(&r, &[third, third, third, 0.125, 0.375, 0.5], 3.0);
+
+    let dx = filled_tensor(&backend, &[2, 3], &[1.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+    let mut dr = SharedTensor::<T>::new(&[2, 3]);
+    backend.softmax_grad(&r, &dx, &mut dr).unwrap();
+    tensor_assert_eq(&dr, &[2.0 / 9.0, -1.0 / 9.0, -1.0 / 9.0, -0.0625, -0.1875, 0.25], 10.0);
+}
+
 pub fn test_log_softmax<T, F: IFramework>(backend: Backend<F>)
     where T: Float + Epsilon + fmt::Debug,
           Backend<F>: LogSoftmax<T> + IBackend {
@@ -118,6 +135,7 @@ mod native {
     use super::*;
     test_native!(test_softmax, softmax_f32, softmax_f64);
     test_native!(test_softmax_grad, softmax_grad_f32, softmax_grad_f64);
+    test_native!(test_softmax_batched, softmax_batched_f32, softmax_batched_f64);
     test_native!(test_log_softmax, log_softmax_f32, log_softmax_f64);
     test_native!(test_log_softmax_grad, log_softmax_grad_f32, log_softmax_grad_f64);
 }
@@ -126,6 +144,7 @@ mod cuda {
     use super::*;
     test_cuda!(test_softmax, softmax_f32, softmax_f64);
     test_cuda!(test_softmax_grad, softmax_grad_f32, softmax_grad_f64);
+    test_cuda!(test_softmax_batched, softmax_batched_f32, softmax_batched_f64);
     test_cuda!(test_log_softmax, log_softmax_f32, log_softmax_f64);
     test_cuda!(test_log_softmax_grad, log_softmax_grad_f32, log_softmax_grad_f64);
 }
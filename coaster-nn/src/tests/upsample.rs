@@ -0,0 +1,92 @@
+use std::fmt;
+
+use crate::co::prelude::*;
+use crate::co::plugin::numeric_helpers::Float;
+
+use crate::plugin::{Upsample, UpsampleMode};
+use crate::tests::{Epsilon, filled_tensor, tensor_assert_eq};
+
+pub fn test_upsample_nearest<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Upsample<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[1, 1, 2, 2], &[1.0, 2.0, 3.0, 4.0]);
+    let mut r = SharedTensor::<T>::new(&[1, 1, 4, 4]);
+    backend.upsample(UpsampleMode::Nearest, &x, &mut r).unwrap();
+    tensor_assert_eq(&r, &[1.0, 1.0, 2.0, 2.0,
+                           1.0, 1.0, 2.0, 2.0,
+                           3.0, 3.0, 4.0, 4.0,
+                           3.0, 3.0, 4.0, 4.0], 0.0);
+
+    // a single spatial dimension, two channels
+    let x = filled_tensor(&backend, &[1, 2, 2], &[1.0, 2.0, 3.0, 4.0]);
+    let mut r = SharedTensor::<T>::new(&[1, 2, 3]);
+    backend.upsample(UpsampleMode::Nearest, &x, &mut r).unwrap();
+    tensor_assert_eq(&r, &[1.0, 1.0, 2.0, 3.0, 3.0, 4.0], 0.0);
+
+    // shrinking picks every other element
+    let x = filled_tensor(&backend, &[1, 1, 4], &[1.0, 2.0, 3.0, 4.0]);
+    let mut r = SharedTensor::<T>::new(&[1, 1, 2]);
+    backend.upsample(UpsampleMode::Nearest, &x, &mut r).unwrap();
+    tensor_assert_eq(&r, &[1.0, 3.0], 0.0);
+}
+
+pub fn test_upsample_nearest_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Upsample<T> + IBackend {
+
+    let dr_data: Vec<f64> = (0..16).map(|i| i as f64).collect();
+    let dr = filled_tensor(&backend, &[1, 1, 4, 4], &dr_data);
+    let mut dx = SharedTensor::<T>::new(&[1, 1, 2, 2]);
+    backend.upsample_grad(UpsampleMode::Nearest, &dr, &mut dx).unwrap();
+    tensor_assert_eq(&dx, &[10.0, 18.0, 42.0, 50.0], 0.0);
+}
+
+pub fn test_upsample_bilinear<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Upsample<T> + IBackend {
+
+    // rows interpolate `[0, 8]` to `[0, 2, 6, 8]`, columns `[0, 4]` to `[0, 1, 3, 4]`
+    let x = filled_tensor(&backend, &[1, 1, 2, 2], &[0.0, 4.0, 8.0, 12.0]);
+    let mut r = SharedTensor::<T>::new(&[1, 1, 4, 4]);
+    backend.upsample(UpsampleMode::Bilinear, &x, &mut r).unwrap();
+    tensor_assert_eq(&r, &[0.0, 1.0, 3.0, 4.0,
+                           2.0, 3.0, 5.0, 6.0,
+                           6.0, 7.0, 9.0, 10.0,
+                           8.0, 9.0, 11.0, 12.0], 4.0);
+}
+
+pub fn test_upsample_bilinear_grad<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Upsample<T> + IBackend {
+
+    let dr = filled_tensor(&backend, &[1, 1, 4], &[1.0, 2.0, 3.0, 4.0]);
+    let mut dx = SharedTensor::<T>::new(&[1, 1, 2]);
+    backend.upsample_grad(UpsampleMode::Bilinear, &dr, &mut dx).unwrap();
+    tensor_assert_eq(&dx, &[3.25, 6.75], 4.0);
+}
+
+pub fn test_upsample_invalid<T, F: IFramework>(backend: Backend<F>)
+    where T: Float + Epsilon + fmt::Debug,
+          Backend<F>: Upsample<T> + IBackend {
+
+    let x = filled_tensor(&backend, &[1, 2, 2], &[1.0, 2.0, 3.0, 4.0]);
+    // no spatial dimension
+    let mut r = SharedTensor::<T>::new(&[1, 2]);
+    assert!(backend.upsample(UpsampleMode::Nearest, &x, &mut r).is_err());
+    // a different number of channels
+    let mut r = SharedTensor::<T>::new(&[1, 1, 4]);
+    assert!(backend.upsample(UpsampleMode::Nearest, &x, &mut r).is_err());
+    // a different number of spatial dimensions
+    let mut r = SharedTensor::<T>::new(&[1, 2, 4, 4]);
+    assert!(backend.upsample(UpsampleMode::Bilinear, &x, &mut r).is_err());
+}
+
+mod native {
+    use super::*;
+    test_native!(test_upsample_nearest, upsample_nearest_f32, upsample_nearest_f64);
+    test_native!(test_upsample_nearest_grad, upsample_nearest_grad_f32, upsample_nearest_grad_f64);
+    test_native!(test_upsample_bilinear, upsample_bilinear_f32, upsample_bilinear_f64);
+    test_native!(test_upsample_bilinear_grad, upsample_bilinear_grad_f32, upsample_bilinear_grad_f64);
+    test_native!(test_upsample_invalid, upsample_invalid_f32, upsample_invalid_f64);
+}
@@ -66,7 +66,10 @@ pub struct Backend<F: IFramework> {
 impl<F: IFramework + Clone> Backend<F> {
     /// Initialize a new native Backend from a BackendConfig.
     pub fn new(config: BackendConfig<F>) -> Result<Backend<F>, Error> {
-        let device = config.framework.new_device(config.hardwares)?;
+        let device = match config.threads {
+            Some(threads) => config.framework.new_device_with_threads(config.hardwares, threads)?,
+            None => config.framework.new_device(config.hardwares)?,
+        };
         Ok(
             Backend {
                 framework: Box::new(config.framework),
@@ -124,6 +127,8 @@ pub struct BackendConfig<'a, F: IFramework + 'a> {
     /// Framework - i.e. CUDA
     framework: F,
     hardwares: &'a [F::H],
+    /// Number of host threads kernels are executed on, one per compute unit if unset.
+    threads: Option<usize>,
 }
 
 impl<'a, F: IFramework + Clone> BackendConfig<'a, F> {
@@ -132,6 +137,15 @@ impl<'a, F: IFramework + Clone> BackendConfig<'a, F> {
         BackendConfig {
             framework,
             hardwares,
+            threads: None,
         }
     }
+
+    /// Sets the number of host threads the backend executes kernels on.
+    ///
+    /// Only the Native framework runs kernels on host threads, others ignore it.
+    pub fn with_threads(mut self, threads: usize) -> BackendConfig<'a, F> {
+        self.threads = Some(threads);
+        self
+    }
 }
@@ -60,6 +60,14 @@ pub trait IFramework {
 
     /// Initializes a new Device from the provided hardwares.
     fn new_device(&self, _: &[Self::H]) -> Result<Self::D, Error>;
+
+    /// Initializes a new Device from the provided hardwares, which executes its kernels on
+    /// `threads` host threads.
+    ///
+    /// Frameworks which do not execute kernels on the host ignore the thread count.
+    fn new_device_with_threads(&self, hardwares: &[Self::H], _threads: usize) -> Result<Self::D, Error> {
+        self.new_device(hardwares)
+    }
 }
 
 #[derive(Debug)]
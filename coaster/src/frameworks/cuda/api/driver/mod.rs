@@ -10,5 +10,6 @@ mod error;
 mod context;
 mod device;
 mod memory;
+mod module;
 pub mod ffi;
 mod utils;
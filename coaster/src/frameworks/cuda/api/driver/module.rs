@@ -0,0 +1,102 @@
+//! Provides the Cuda API with its module and kernel launch functionality.
+
+use super::{API, Error};
+use crate::frameworks::cuda::{Function, Module};
+use super::ffi::*;
+use std::ffi::CStr;
+use std::ptr;
+
+impl API {
+    /// Loads a module from a PTX or cubin image into the current context.
+    ///
+    /// The image has to be null-terminated if it is PTX.
+    pub fn load_module(image: &CStr) -> Result<Module, Error> {
+        Ok(Module::from_c(unsafe {API::ffi_load_module(image.as_ptr() as *const ::libc::c_void)}?))
+    }
+
+    /// Unloads a module from the current context.
+    pub fn unload_module(module: &Module) -> Result<(), Error> {
+        unsafe {API::ffi_unload_module(module.id_c())}
+    }
+
+    /// Returns the kernel of the module with the name `name`.
+    pub fn get_function(module: &Module, name: &CStr) -> Result<Function, Error> {
+        Ok(Function::from_c(unsafe {API::ffi_get_function(module.id_c(), name.as_ptr())}?))
+    }
+
+    /// Launches a kernel on a `grid` of blocks of `block` threads each.
+    ///
+    /// Every entry of `params` points to the value of the kernel parameter at its position.
+    /// The kernel is executed on the default stream of the current context.
+    ///
+    /// # Safety
+    /// The number and types of the values behind `params` have to match the signature of the
+    /// kernel, and device pointers passed have to be valid for the accesses of the kernel.
+    pub unsafe fn launch_kernel(
+        function: &Function,
+        grid: (u32, u32, u32),
+        block: (u32, u32, u32),
+        params: &mut [*mut ::libc::c_void],
+    ) -> Result<(), Error> {
+        API::ffi_launch_kernel(function.id_c(), grid, block, params.as_mut_ptr())
+    }
+
+    unsafe fn ffi_load_module(image: *const ::libc::c_void) -> Result<CUmodule, Error> {
+        let mut module: CUmodule = ptr::null_mut();
+        match cuModuleLoadData(&mut module, image) {
+            CUresult::CUDA_SUCCESS => Ok(module),
+            CUresult::CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized("CUDA got deinitialized.")),
+            CUresult::CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized("CUDA is not initialized.")),
+            CUresult::CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext("No valid context available.")),
+            CUresult::CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue("Invalid value provided.")),
+            CUresult::CUDA_ERROR_OUT_OF_MEMORY => Err(Error::OutOfMemory("Device is out of memory.")),
+            CUresult::CUDA_ERROR_INVALID_PTX => Err(Error::InvalidPtx("Invalid PTX provided.")),
+            CUresult::CUDA_ERROR_NO_BINARY_FOR_GPU => Err(Error::NoBinaryForGpu("No binary for the GPU provided.")),
+            _ => Err(Error::Unknown("Unable to load module.")),
+        }
+    }
+
+    unsafe fn ffi_unload_module(module: CUmodule) -> Result<(), Error> {
+        match cuModuleUnload(module) {
+            CUresult::CUDA_SUCCESS => Ok(()),
+            CUresult::CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized("CUDA got deinitialized.")),
+            CUresult::CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized("CUDA is not initialized.")),
+            CUresult::CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext("No valid context available.")),
+            CUresult::CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue("Invalid value provided.")),
+            _ => Err(Error::Unknown("Unable to unload module.")),
+        }
+    }
+
+    unsafe fn ffi_get_function(module: CUmodule, name: *const ::libc::c_char) -> Result<CUfunction, Error> {
+        let mut function: CUfunction = ptr::null_mut();
+        match cuModuleGetFunction(&mut function, module, name) {
+            CUresult::CUDA_SUCCESS => Ok(function),
+            CUresult::CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized("CUDA got deinitialized.")),
+            CUresult::CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized("CUDA is not initialized.")),
+            CUresult::CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext("No valid context available.")),
+            CUresult::CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue("Invalid value provided.")),
+            CUresult::CUDA_ERROR_NOT_FOUND => Err(Error::NotFound("No kernel of that name found in the module.")),
+            _ => Err(Error::Unknown("Unable to get function.")),
+        }
+    }
+
+    unsafe fn ffi_launch_kernel(
+        function: CUfunction,
+        grid: (u32, u32, u32),
+        block: (u32, u32, u32),
+        params: *mut *mut ::libc::c_void,
+    ) -> Result<(), Error> {
+        match cuLaunchKernel(function, grid.0, grid.1, grid.2, block.0, block.1, block.2,
+                             0, ptr::null_mut(), params, ptr::null_mut()) {
+            CUresult::CUDA_SUCCESS => Ok(()),
+            CUresult::CUDA_ERROR_DEINITIALIZED => Err(Error::Deinitialized("CUDA got deinitialized.")),
+            CUresult::CUDA_ERROR_NOT_INITIALIZED => Err(Error::NotInitialized("CUDA is not initialized.")),
+            CUresult::CUDA_ERROR_INVALID_CONTEXT => Err(Error::InvalidContext("No valid context available.")),
+            CUresult::CUDA_ERROR_INVALID_VALUE => Err(Error::InvalidValue("Invalid value provided.")),
+            CUresult::CUDA_ERROR_INVALID_HANDLE => Err(Error::InvalidHandle("Invalid kernel provided.")),
+            CUresult::CUDA_ERROR_LAUNCH_OUT_OF_RESOURCES => Err(Error::LaunchOutOfResources("Not enough resources to launch the kernel.")),
+            CUresult::CUDA_ERROR_LAUNCH_FAILED => Err(Error::LaunchFailed("Kernel launch failed.")),
+            _ => Err(Error::Unknown("Unable to launch kernel.")),
+        }
+    }
+}
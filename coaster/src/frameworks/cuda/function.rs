@@ -1,6 +1,7 @@
 //! Provides a Rust wrapper around Cuda's Function.
 
 use crate::operation::IOperation;
+use super::api::DriverFFI;
 
 #[derive(Debug, Copy, Clone)]
 /// Defines a Cuda Function.
@@ -12,22 +13,20 @@ pub struct Function {
 }
 
 impl Function {
-    /// Initializes a new OpenCL device.
+    /// Initializes a new Cuda Function.
     pub fn from_isize(id: isize) -> Function {
         Function { id }
     }
 
-    /*
-    /// Initializes a new OpenCL device from its C type.
-    //pub fn from_c(id: cl::kernel_id) -> Function {
+    /// Initializes a new Cuda Function from its C type.
+    pub fn from_c(id: DriverFFI::CUfunction) -> Function {
         Function { id: id as isize }
     }
 
     /// Returns the id as its C type.
-    pub fn id_c(&self) -> cl::kernel_id {
-        self.id as cl::kernel_id
+    pub fn id_c(&self) -> DriverFFI::CUfunction {
+        self.id as DriverFFI::CUfunction
     }
-    */
 }
 
 impl IOperation for Function {}
@@ -1,6 +1,7 @@
 //! Provides a Rust wrapper around Cuda's Module.
 
 use crate::binary::IBinary;
+use super::api::DriverFFI;
 
 #[derive(Debug, Copy, Clone)]
 /// Defines a Cuda Module.
@@ -19,20 +20,17 @@ impl Module {
         }
     }
 
-    // /// Initializes a new Cuda Module from its C type.
-    // pub fn from_c(id: cl::kernel_id) -> Module {
-    //     Module {
-    //         id: id as isize,
-    //         blas_dot: Function::from_isize(1),
-    //         blas_scale: Function::from_isize(1),
-    //         blas_axpy: Function::from_isize(1),
-    //     }
-    // }
-    //
-    // /// Returns the id as its C type.
-    // pub fn id_c(&self) -> cl::kernel_id {
-    //     self.id as cl::kernel_id
-    // }
+    /// Initializes a new Cuda Module from its C type.
+    pub fn from_c(id: DriverFFI::CUmodule) -> Module {
+        Module {
+            id: id as isize,
+        }
+    }
+
+    /// Returns the id as its C type.
+    pub fn id_c(&self) -> DriverFFI::CUmodule {
+        self.id as DriverFFI::CUmodule
+    }
 }
 
 impl IBinary for Module {}
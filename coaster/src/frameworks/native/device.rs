@@ -1,27 +1,74 @@
 //! Provides a hardware aka. the host CPU.
 use std::any::Any;
 use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use lazy_static::lazy_static;
+use rayon::{ThreadPool, ThreadPoolBuilder};
 
 use crate::device::{IDevice, MemorySync};
 use crate::device::Error as DeviceError;
+use crate::framework::Error as FrameworkError;
 use super::hardware::Hardware;
 use super::flatbox::FlatBox;
 use super::allocate_boxed_slice;
 
+lazy_static! {
+    /// The thread pool shared by all host CPU devices that did not ask for a thread count.
+    static ref SHARED_THREAD_POOL: Arc<ThreadPool> = match build_thread_pool(num_cpus::get()) {
+        Ok(thread_pool) => Arc::new(thread_pool),
+        Err(err) => panic!("{}", err),
+    };
+}
+
 #[derive(Debug, Clone)]
 /// Defines the host CPU Hardware.
 ///
 /// Can later be transformed into a [Coaster hardware][hardware].
 /// [hardware]: ../../hardware/index.html
 pub struct Cpu {
-    hardwares: Vec<Hardware>
+    hardwares: Vec<Hardware>,
+    thread_pool: Arc<ThreadPool>,
 }
 
 impl Cpu {
-    /// Initializes a new OpenCL hardware.
+    /// Initializes a new host CPU device, running kernels on the process-wide thread pool.
+    ///
+    /// The pool is created on first use with one thread per logical CPU and shared by every
+    /// device created this way. Panics if the threads can not be spawned.
     pub fn new(hardwares: Vec<Hardware>) -> Cpu {
-        Cpu { hardwares }
+        Cpu {
+            hardwares,
+            thread_pool: SHARED_THREAD_POOL.clone(),
+        }
+    }
+
+    /// Initializes a new host CPU device, running kernels on a dedicated pool of `threads`
+    /// threads.
+    pub fn with_threads(hardwares: Vec<Hardware>, threads: usize) -> Result<Cpu, FrameworkError> {
+        Ok(Cpu {
+            hardwares,
+            thread_pool: Arc::new(build_thread_pool(threads)?),
+        })
     }
+
+    /// Returns the thread pool kernels of this device are executed on.
+    pub fn thread_pool(&self) -> &ThreadPool {
+        &self.thread_pool
+    }
+
+    /// Returns the number of threads kernels of this device are executed on.
+    pub fn threads(&self) -> usize {
+        self.thread_pool.current_num_threads()
+    }
+}
+
+fn build_thread_pool(threads: usize) -> Result<ThreadPool, FrameworkError> {
+    ThreadPoolBuilder::new()
+        .num_threads(threads.max(1))
+        .thread_name(|i| format!("coaster-native-{}", i))
+        .build()
+        .map_err(|err| FrameworkError::Implementation(err.to_string()))
 }
 
 impl IDevice for Cpu {
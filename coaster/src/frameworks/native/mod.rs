@@ -71,7 +71,7 @@ impl IFramework for Native {
         let cpu = Hardware::new(1)
             .set_name(Some(String::from("Host CPU")))
             .set_hardware_type(Some(HardwareType::CPU))
-            .set_compute_units(Some(1))
+            .set_compute_units(Some(num_cpus::get() as isize))
             .build();
         Ok(vec!(cpu))
     }
@@ -87,6 +87,11 @@ impl IFramework for Native {
     fn new_device(&self, devices: &[Hardware]) -> Result<Self::D, crate::framework::Error> {
         Ok(Cpu::new(devices.to_vec()))
     }
+
+    fn new_device_with_threads(&self, devices: &[Hardware], threads: usize)
+                               -> Result<Self::D, crate::framework::Error> {
+        Cpu::with_threads(devices.to_vec(), threads)
+    }
 }
 
 impl IBackend for Backend<Native> {
@@ -155,6 +155,8 @@ extern crate lazy_static;
 extern crate regex;
 extern crate num;
 extern crate byteorder;
+extern crate rayon;
+extern crate num_cpus;
 
 pub mod backend;
 pub mod device;
@@ -22,6 +22,15 @@ mod backend_spec {
             use_ibackend(backend);
         }
 
+        #[test]
+        fn it_can_configure_threads() {
+            let framework = Native::new();
+            let hardwares = framework.hardwares().to_vec();
+            let backend_config = BackendConfig::new(framework, &hardwares).with_threads(3);
+            let backend = Backend::new(backend_config).unwrap();
+            assert_eq!(backend.device().threads(), 3);
+        }
+
         fn use_ibackend<B: IBackend>(backend: Rc<B>) {
             let backend: Rc<dyn IBackend<F=B::F>> = backend.clone();
             backend.device();
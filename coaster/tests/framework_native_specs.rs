@@ -10,4 +10,25 @@ mod framework_native_spec {
         let frm = Native::new();
         assert_eq!(frm.hardwares().len(), 1);
     }
+
+    #[test]
+    fn it_uses_all_cores_by_default() {
+        let frm = Native::new();
+        let cores = frm.hardwares()[0].compute_units().unwrap();
+        assert!(cores >= 1);
+        let device = frm.new_device(frm.hardwares()).unwrap();
+        assert_eq!(device.threads(), cores as usize);
+    }
+
+    #[test]
+    fn it_shares_the_thread_pool_between_default_devices() {
+        let frm = Native::new();
+        let first = frm.new_device(frm.hardwares()).unwrap();
+        let second = frm.new_device(frm.hardwares()).unwrap();
+        assert!(std::ptr::eq(first.thread_pool(), second.thread_pool()));
+
+        let dedicated = frm.new_device_with_threads(frm.hardwares(), 2).unwrap();
+        assert!(!std::ptr::eq(first.thread_pool(), dedicated.thread_pool()));
+        assert_eq!(dedicated.threads(), 2);
+    }
 }
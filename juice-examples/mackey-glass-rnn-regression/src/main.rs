@@ -12,6 +12,8 @@ use serde::Deserialize;
 
 #[cfg(all(feature = "cuda"))]
 use co::frameworks::cuda::get_cuda_backend;
+#[cfg(not(feature = "cuda"))]
+use co::frameworks::native::get_native_backend;
 use co::prelude::*;
 use conn::{DirectionMode, RnnInputMode, RnnNetworkMode};
 use juice::layer::*;
@@ -110,14 +112,13 @@ fn create_network(batch_size: usize, columns: usize) -> SequentialConfig {
     net_cfg
 }
 
-#[cfg(all(feature = "cuda"))]
-fn add_solver(
+fn add_solver<B: IBackend + SolverOps<f32> + 'static>(
     net_cfg: SequentialConfig,
-    backend: Rc<Backend<Cuda>>,
+    backend: Rc<B>,
     batch_size: usize,
     learning_rate: f32,
     momentum: f32,
-) -> Solver<Backend<Cuda>, Backend<Cuda>> {
+) -> Solver<B, B> {
     // Define an Objective Function
     let mut regressor_cfg = SequentialConfig::default();
 
@@ -127,7 +128,7 @@ fn add_solver(
 
     // Add a Layer expressing Mean Squared Error (MSE) Loss. This will be used with the solver to
     // train the model.
-    let mse_layer_cfg = LayerConfig::new("mse", LayerType::MeanSquaredError);
+    let mse_layer_cfg = LayerConfig::new("mse", MeanSquaredErrorConfig::default());
     regressor_cfg.add_layer(mse_layer_cfg);
 
     // Setup an Optimiser
@@ -143,7 +144,6 @@ fn add_solver(
     Solver::from_config(backend.clone(), backend, &solver_cfg)
 }
 
-#[cfg(all(feature = "cuda"))]
 #[allow(dead_code)]
 fn train(
     batch_size: Option<usize>,
@@ -151,8 +151,11 @@ fn train(
     momentum: Option<f32>,
     file: Option<String>,
 ) {
-    // Initialise a CUDA Backend, and the CUDNN and CUBLAS libraries.
+    // Initialise a CUDA Backend, and the CUDNN and CUBLAS libraries, or fall back to native.
+    #[cfg(all(feature = "cuda"))]
     let backend = Rc::new(get_cuda_backend());
+    #[cfg(not(feature = "cuda"))]
+    let backend = Rc::new(get_native_backend());
 
     let batch_size = batch_size.unwrap_or(10);
     let learning_rate = learning_rate.unwrap_or(0.1f32);
@@ -203,17 +206,19 @@ fn train(
     }
 }
 
-#[cfg(all(feature = "cuda"))]
 #[allow(dead_code)]
 fn test(batch_size: Option<usize>, file: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
-    // Initialise a CUDA Backend, and the CUDNN and CUBLAS libraries.
+    // Initialise a CUDA Backend, and the CUDNN and CUBLAS libraries, or fall back to native.
+    #[cfg(all(feature = "cuda"))]
     let backend = Rc::new(get_cuda_backend());
+    #[cfg(not(feature = "cuda"))]
+    let backend = Rc::new(get_native_backend());
 
     // Load in the Network, and some test data
     let batch_size = batch_size.unwrap_or(10);
 
     // Load in a pre-trained network
-    let mut network: Layer<Backend<Cuda>> = Layer::<Backend<Cuda>>::load(backend, file.unwrap())?;
+    let mut network = Layer::load(backend, file.unwrap())?;
 
     // Define Input & Labels
     let input = SharedTensor::<f32>::new(&[batch_size, 1, DATA_COLUMNS]);
@@ -261,23 +266,13 @@ fn main() {
         .unwrap_or_else(|e| e.exit());
 
     if args.cmd_train {
-        #[cfg(all(feature = "cuda"))]
         train(
             args.flag_batchSize,
             args.flag_learningRate,
             args.flag_momentum,
             args.flag_file,
         );
-        #[cfg(not(feature = "cuda"))]
-        panic!("Juice currently only supports RNNs via CUDA & CUDNN. If you'd like to check progress \
-                on native support, please look at the tracking issue https://github.com/spearow/juice/issues/41 \
-                or the 2020 road map https://github.com/spearow/juice/issues/30")
     } else if args.cmd_test {
-        #[cfg(all(feature = "cuda"))]
         test(args.flag_batchSize, args.flag_file);
-        #[cfg(not(feature = "cuda"))]
-        panic!("Juice currently only supports RNNs via CUDA & CUDNN. If you'd like to check progress \
-                    on native support, please look at the tracking issue https://github.com/spearow/juice/issues/41 \
-                    or the 2020 road map https://github.com/spearow/juice/issues/30")
     }
 }
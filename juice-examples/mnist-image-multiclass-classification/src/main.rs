@@ -102,21 +102,13 @@ fn main() {
             _ => println!("{}", "Failed to download MNIST dataset!".to_string()),
         }
     } else if args.cmd_mnist {
-        #[cfg(all(feature = "cuda"))]
-            run_mnist(
+        run_mnist(
             MnistType::Numbers,
             args.arg_model_name,
             args.arg_batch_size,
             args.arg_learning_rate,
             args.arg_momentum,
         );
-        #[cfg(not(feature = "cuda"))]
-            {
-                println!(
-                    "Right now, you really need cuda! Not all features are available for all backends and as such, this one -as of now - only works with cuda."
-                );
-                panic!()
-            }
     } else if args.cmd_fashion {
         run_mnist(
             MnistType::Fashion,
@@ -128,7 +120,6 @@ fn main() {
     }
 }
 
-#[cfg(all(feature = "cuda"))]
 fn add_conv_net(mut net_cfg: SequentialConfig, batch_size: usize, pixel_dim: usize) -> SequentialConfig {
     net_cfg.add_layer(LayerConfig::new(
         "reshape",
@@ -141,16 +132,13 @@ fn add_conv_net(mut net_cfg: SequentialConfig, batch_size: usize, pixel_dim: usi
             filter_shape: vec![5],
             padding: vec![0],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         },
     ));
     net_cfg.add_layer(LayerConfig::new(
         "pooling",
-        PoolingConfig {
-            mode: PoolingMode::Max,
-            filter_shape: vec![2],
-            padding: vec![0],
-            stride: vec![2],
-        },
+        PoolingConfig::of_window(PoolingMode::Max, &[2], &[2], &[0]),
     ));
     net_cfg.add_layer(LayerConfig::new(
         "linear1",
@@ -164,15 +152,6 @@ fn add_conv_net(mut net_cfg: SequentialConfig, batch_size: usize, pixel_dim: usi
     net_cfg
 }
 
-#[cfg(not(feature = "cuda"))]
-fn add_conv_net(_net_cfg: SequentialConfig, _batch_size: usize, _pixel_dim: usize) -> SequentialConfig {
-    println!(
-        "Currently Juice does not have a native pooling function to use with Conv Nets - you can either try
-        the CUDA implementation, or use a different type of layer"
-    );
-    panic!()
-}
-
 fn add_mlp(mut net_cfg: SequentialConfig, batch_size: usize, pixel_count: usize) -> SequentialConfig {
     net_cfg.add_layer(LayerConfig::new(
         "reshape",
@@ -251,7 +230,7 @@ fn run_mnist(
     classifier_cfg.add_input("network_out", &[batch_size, 10]);
     classifier_cfg.add_input("label", &[batch_size, 1]);
     // set up nll loss
-    let nll_layer_cfg = NegativeLogLikelihoodConfig { num_classes: 10 };
+    let nll_layer_cfg = NegativeLogLikelihoodConfig::new(10);
     let nll_cfg = LayerConfig::new("nll", LayerType::NegativeLogLikelihood(nll_layer_cfg));
     classifier_cfg.add_layer(nll_cfg);
 
@@ -127,6 +127,8 @@ mod benches {
             filter_shape: vec![11],
             padding: vec![2],
             stride: vec![4],
+            dilation: vec![1],
+            groups: 1,
         };
         let mut conv1_cfg = LayerConfig::new("conv1", LayerType::Convolution(conv1_layer_cfg));
         conv1_cfg.add_input("data");
@@ -143,6 +145,8 @@ mod benches {
             filter_shape: vec![3],
             stride: vec![2],
             padding: vec![0], // TODO: make optional
+            output_shape: vec![],
+            include_padding: false,
         };
         let mut pool1_cfg = LayerConfig::new("pool1", LayerType::Pooling(pool1_layer_cfg));
         pool1_cfg.add_input("conv1_out");
@@ -154,6 +158,8 @@ mod benches {
             filter_shape: vec![5],
             padding: vec![2],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         };
         let mut conv2_cfg = LayerConfig::new("conv2", LayerType::Convolution(conv2_layer_cfg));
         conv2_cfg.add_input("pool1_out");
@@ -170,6 +176,8 @@ mod benches {
             filter_shape: vec![3],
             stride: vec![2],
             padding: vec![0], // TODO: make optional
+            output_shape: vec![],
+            include_padding: false,
         };
         let mut pool2_cfg = LayerConfig::new("pool2", LayerType::Pooling(pool2_layer_cfg));
         pool2_cfg.add_input("conv2_out");
@@ -181,6 +189,8 @@ mod benches {
             filter_shape: vec![3],
             padding: vec![1],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         };
         let mut conv3_cfg = LayerConfig::new("conv3", LayerType::Convolution(conv3_layer_cfg));
         conv3_cfg.add_input("pool2_out");
@@ -197,6 +207,8 @@ mod benches {
             filter_shape: vec![3],
             padding: vec![1],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         };
         let mut conv4_cfg = LayerConfig::new("conv4", LayerType::Convolution(conv4_layer_cfg));
         conv4_cfg.add_input("conv3_out");
@@ -213,6 +225,8 @@ mod benches {
             filter_shape: vec![3],
             padding: vec![1],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         };
         let mut conv5_cfg = LayerConfig::new("conv5", LayerType::Convolution(conv5_layer_cfg));
         conv5_cfg.add_input("conv4_out");
@@ -229,6 +243,8 @@ mod benches {
             filter_shape: vec![3],
             stride: vec![2],
             padding: vec![0], // TODO: make optional
+            output_shape: vec![],
+            include_padding: false,
         };
         let mut pool3_cfg = LayerConfig::new("pool3", LayerType::Pooling(pool3_layer_cfg));
         pool3_cfg.add_input("conv5_out");
@@ -286,6 +302,8 @@ mod benches {
             filter_shape: vec![11],
             padding: vec![2],
             stride: vec![4],
+            dilation: vec![1],
+            groups: 1,
         };
         let mut conv1_cfg = LayerConfig::new("conv1", LayerType::Convolution(conv1_layer_cfg));
         conv1_cfg.add_input("data");
@@ -302,6 +320,8 @@ mod benches {
             filter_shape: vec![3],
             stride: vec![2],
             padding: vec![0], // TODO: make optional
+            output_shape: vec![],
+            include_padding: false,
         };
         let mut pool1_cfg = LayerConfig::new("pool1", LayerType::Pooling(pool1_layer_cfg));
         pool1_cfg.add_input("conv1_out");
@@ -313,6 +333,8 @@ mod benches {
             filter_shape: vec![5],
             padding: vec![2],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         };
         let mut conv2_cfg = LayerConfig::new("conv2", LayerType::Convolution(conv2_layer_cfg));
         conv2_cfg.add_input("pool1_out");
@@ -329,6 +351,8 @@ mod benches {
             filter_shape: vec![3],
             stride: vec![2],
             padding: vec![0], // TODO: make optional
+            output_shape: vec![],
+            include_padding: false,
         };
         let mut pool2_cfg = LayerConfig::new("pool2", LayerType::Pooling(pool2_layer_cfg));
         pool2_cfg.add_input("conv2_out");
@@ -340,6 +364,8 @@ mod benches {
             filter_shape: vec![3],
             padding: vec![1],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         };
         let mut conv3_cfg = LayerConfig::new("conv3", LayerType::Convolution(conv3_layer_cfg));
         conv3_cfg.add_input("pool2_out");
@@ -356,6 +382,8 @@ mod benches {
             filter_shape: vec![3],
             padding: vec![1],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         };
         let mut conv4_cfg = LayerConfig::new("conv4", LayerType::Convolution(conv4_layer_cfg));
         conv4_cfg.add_input("conv3_out");
@@ -372,6 +400,8 @@ mod benches {
             filter_shape: vec![3],
             padding: vec![1],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         };
         let mut conv5_cfg = LayerConfig::new("conv5", LayerType::Convolution(conv5_layer_cfg));
         conv5_cfg.add_input("conv4_out");
@@ -388,6 +418,8 @@ mod benches {
             filter_shape: vec![3],
             stride: vec![2],
             padding: vec![0], // TODO: make optional
+            output_shape: vec![],
+            include_padding: false,
         };
         let mut pool3_cfg = LayerConfig::new("pool3", LayerType::Pooling(pool3_layer_cfg));
         pool3_cfg.add_input("conv5_out");
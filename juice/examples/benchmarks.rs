@@ -125,6 +125,8 @@ fn bench_alexnet() {
         filter_shape: vec![11],
         padding: vec![2],
         stride: vec![4],
+        dilation: vec![1],
+        groups: 1,
     };
     cfg.add_layer(LayerConfig::new("conv1", conv1_layer_cfg));
     cfg.add_layer(LayerConfig::new("conv1/relu", LayerType::ReLU));
@@ -135,6 +137,7 @@ fn bench_alexnet() {
             filter_shape: vec![3],
             stride: vec![2],
             padding: vec![0],
+            output_shape: vec![],
         },
     ));
 
@@ -145,6 +148,8 @@ fn bench_alexnet() {
             filter_shape: vec![5],
             padding: vec![2],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         },
     ));
     cfg.add_layer(LayerConfig::new("conv2/relu", LayerType::ReLU));
@@ -155,6 +160,7 @@ fn bench_alexnet() {
             filter_shape: vec![3],
             stride: vec![2],
             padding: vec![0],
+            output_shape: vec![],
         },
     ));
 
@@ -165,6 +171,8 @@ fn bench_alexnet() {
             filter_shape: vec![3],
             padding: vec![1],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         },
     ));
     cfg.add_layer(LayerConfig::new("conv3/relu", LayerType::ReLU));
@@ -176,6 +184,8 @@ fn bench_alexnet() {
             filter_shape: vec![3],
             padding: vec![1],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         },
     ));
     cfg.add_layer(LayerConfig::new("conv4/relu", LayerType::ReLU));
@@ -187,6 +197,8 @@ fn bench_alexnet() {
             filter_shape: vec![3],
             padding: vec![1],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         },
     ));
     cfg.add_layer(LayerConfig::new("conv5/relu", LayerType::ReLU));
@@ -197,6 +209,7 @@ fn bench_alexnet() {
             filter_shape: vec![3],
             stride: vec![2],
             padding: vec![0],
+            output_shape: vec![],
         },
     ));
 
@@ -271,6 +284,8 @@ fn bench_overfeat() {
         filter_shape: vec![11],
         padding: vec![0],
         stride: vec![4],
+        dilation: vec![1],
+        groups: 1,
     };
     cfg.add_layer(LayerConfig::new("conv1", conv1_layer_cfg));
     cfg.add_layer(LayerConfig::new("conv1/relu", LayerType::ReLU));
@@ -279,6 +294,7 @@ fn bench_overfeat() {
         filter_shape: vec![2],
         stride: vec![2],
         padding: vec![0],
+        output_shape: vec![],
     };
     cfg.add_layer(LayerConfig::new("pool1", pool1_layer_cfg));
 
@@ -287,6 +303,8 @@ fn bench_overfeat() {
         filter_shape: vec![5],
         padding: vec![0],
         stride: vec![1],
+        dilation: vec![1],
+        groups: 1,
     };
     cfg.add_layer(LayerConfig::new("conv2", conv2_layer_cfg));
     cfg.add_layer(LayerConfig::new("conv2/relu", LayerType::ReLU));
@@ -295,6 +313,7 @@ fn bench_overfeat() {
         filter_shape: vec![2],
         stride: vec![2],
         padding: vec![0],
+        output_shape: vec![],
     };
     cfg.add_layer(LayerConfig::new("pool2", pool2_layer_cfg));
 
@@ -303,6 +322,8 @@ fn bench_overfeat() {
         filter_shape: vec![3],
         padding: vec![1],
         stride: vec![1],
+        dilation: vec![1],
+        groups: 1,
     };
     cfg.add_layer(LayerConfig::new("conv3", conv3_layer_cfg));
     cfg.add_layer(LayerConfig::new("conv3/relu", LayerType::ReLU));
@@ -312,6 +333,8 @@ fn bench_overfeat() {
         filter_shape: vec![3],
         padding: vec![1],
         stride: vec![1],
+        dilation: vec![1],
+        groups: 1,
     };
     cfg.add_layer(LayerConfig::new("conv4", conv4_layer_cfg));
     cfg.add_layer(LayerConfig::new("conv4/relu", LayerType::ReLU));
@@ -321,6 +344,8 @@ fn bench_overfeat() {
         filter_shape: vec![3],
         padding: vec![1],
         stride: vec![1],
+        dilation: vec![1],
+        groups: 1,
     };
     cfg.add_layer(LayerConfig::new("conv5", conv5_layer_cfg));
     cfg.add_layer(LayerConfig::new("conv5/relu", LayerType::ReLU));
@@ -329,6 +354,7 @@ fn bench_overfeat() {
         filter_shape: vec![2],
         stride: vec![2],
         padding: vec![0],
+        output_shape: vec![],
     };
     cfg.add_layer(LayerConfig::new("pool5", pool5_layer_cfg));
 
@@ -401,6 +427,8 @@ fn bench_vgg_a() {
         filter_shape: vec![3],
         padding: vec![1],
         stride: vec![1],
+        dilation: vec![1],
+        groups: 1,
     };
     cfg.add_layer(LayerConfig::new("conv1", conv1_layer_cfg));
     cfg.add_layer(LayerConfig::new("conv1/relu", LayerType::ReLU));
@@ -411,6 +439,7 @@ fn bench_vgg_a() {
             filter_shape: vec![2],
             stride: vec![2],
             padding: vec![0],
+            output_shape: vec![],
         },
     ));
 
@@ -421,6 +450,8 @@ fn bench_vgg_a() {
             filter_shape: vec![3],
             padding: vec![1],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         },
     ));
     cfg.add_layer(LayerConfig::new("conv2/relu", LayerType::ReLU));
@@ -429,6 +460,7 @@ fn bench_vgg_a() {
         filter_shape: vec![2],
         stride: vec![2],
         padding: vec![0],
+        output_shape: vec![],
     };
     cfg.add_layer(LayerConfig::new(
         "pool2",
@@ -437,6 +469,7 @@ fn bench_vgg_a() {
             filter_shape: vec![2],
             stride: vec![2],
             padding: vec![0],
+            output_shape: vec![],
         },
     ));
 
@@ -447,6 +480,8 @@ fn bench_vgg_a() {
             filter_shape: vec![3],
             padding: vec![1],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         },
     ));
     cfg.add_layer(LayerConfig::new("conv3/relu", LayerType::ReLU));
@@ -458,6 +493,8 @@ fn bench_vgg_a() {
             filter_shape: vec![3],
             padding: vec![1],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         },
     ));
     cfg.add_layer(LayerConfig::new("conv4/relu", LayerType::ReLU));
@@ -468,6 +505,7 @@ fn bench_vgg_a() {
             filter_shape: vec![2],
             stride: vec![2],
             padding: vec![0],
+            output_shape: vec![],
         },
     ));
 
@@ -478,6 +516,8 @@ fn bench_vgg_a() {
             filter_shape: vec![3],
             padding: vec![1],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         },
     ));
     cfg.add_layer(LayerConfig::new("conv5/relu", LayerType::ReLU));
@@ -489,6 +529,8 @@ fn bench_vgg_a() {
             filter_shape: vec![3],
             padding: vec![1],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         },
     ));
     cfg.add_layer(LayerConfig::new("conv6/relu", LayerType::ReLU));
@@ -499,6 +541,7 @@ fn bench_vgg_a() {
             filter_shape: vec![2],
             stride: vec![2],
             padding: vec![0],
+            output_shape: vec![],
         },
     ));
 
@@ -509,6 +552,8 @@ fn bench_vgg_a() {
             filter_shape: vec![3],
             padding: vec![1],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         },
     ));
     cfg.add_layer(LayerConfig::new("conv7/relu", LayerType::ReLU));
@@ -520,6 +565,8 @@ fn bench_vgg_a() {
             filter_shape: vec![3],
             padding: vec![1],
             stride: vec![1],
+            dilation: vec![1],
+            groups: 1,
         },
     ));
     cfg.add_layer(LayerConfig::new("conv8/relu", LayerType::ReLU));
@@ -530,6 +577,7 @@ fn bench_vgg_a() {
             filter_shape: vec![2],
             stride: vec![2],
             padding: vec![0],
+            output_shape: vec![],
         },
     ));
     cfg.add_layer(LayerConfig::new("fc1", LinearConfig { output_size: 4096 }));
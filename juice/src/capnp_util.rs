@@ -1,5 +1,8 @@
 //! Provides functionality for Cap'n Proto (de)serialization.
 
+use crate::juice_capnp::tensor as capnp_tensor;
+use num::traits::{cast, NumCast};
+
 pub trait CapnpWrite<'a> {
     /// The Builder that was autogenerated by capnp.
     type Builder;
@@ -15,3 +18,89 @@ pub trait CapnpRead<'a> {
     /// Read the struct from the Reader.
     fn read_capnp(reader: Self::Reader) -> Self;
 }
+
+/// Element types of tensors that can be written into a capnp `Tensor`.
+pub trait CapnpTensorData: NumCast + Copy {
+    /// Write the elements into the data of the Tensor in their own precision.
+    fn write_tensor_data(builder: &mut capnp_tensor::Builder, data: &[Self]);
+}
+
+impl CapnpTensorData for f32 {
+    fn write_tensor_data(builder: &mut capnp_tensor::Builder, data: &[f32]) {
+        let mut tensor_data = builder.reborrow().init_data().init_float32(data.len() as u32);
+        for (i, datum) in data.iter().enumerate() {
+            tensor_data.set(i as u32, *datum);
+        }
+    }
+}
+
+impl CapnpTensorData for f64 {
+    fn write_tensor_data(builder: &mut capnp_tensor::Builder, data: &[f64]) {
+        let mut tensor_data = builder.reborrow().init_data().init_float64(data.len() as u32);
+        for (i, datum) in data.iter().enumerate() {
+            tensor_data.set(i as u32, *datum);
+        }
+    }
+}
+
+/// Read the data of a capnp `Tensor` into `data`, casting from the precision it was written in.
+///
+/// Fails if the data does not hold exactly one value per element of `data` or a value can not
+/// be represented as `T`.
+pub fn read_tensor_data<T: NumCast>(reader: &capnp_tensor::Reader, data: &mut [T]) -> ::capnp::Result<()> {
+    // widening f32 to f64 is lossless, so both precisions are read as f64
+    let read_data: Vec<f64> = match reader.get_data().which()? {
+        capnp_tensor::data::Which::Float32(read_data) => {
+            let read_data = read_data?;
+            (0..read_data.len()).map(|k| read_data.get(k) as f64).collect()
+        }
+        capnp_tensor::data::Which::Float64(read_data) => {
+            let read_data = read_data?;
+            (0..read_data.len()).map(|k| read_data.get(k)).collect()
+        }
+    };
+    if read_data.len() != data.len() {
+        return Err(::capnp::Error::failed(format!(
+            "tensor holds {} values, but its shape has {} elements",
+            read_data.len(),
+            data.len()
+        )));
+    }
+    for (datum, read_datum) in data.iter_mut().zip(read_data) {
+        *datum = cast(read_datum)
+            .ok_or_else(|| ::capnp::Error::failed(format!("tensor value {} is out of range", read_datum)))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_tensor_data, CapnpTensorData};
+    use crate::juice_capnp::tensor as capnp_tensor;
+
+    fn tensor_message(data: &[f64]) -> ::capnp::message::Builder<::capnp::message::HeapAllocator> {
+        let mut message = ::capnp::message::Builder::new_default();
+        {
+            let mut tensor = message.init_root::<capnp_tensor::Builder>();
+            f64::write_tensor_data(&mut tensor, data);
+        }
+        message
+    }
+
+    #[test]
+    fn read_tensor_data_casts_precision() {
+        let message = tensor_message(&[0.5, -2.0]);
+        let reader = message.get_root_as_reader::<capnp_tensor::Reader>().unwrap();
+        let mut data = [0f32; 2];
+        read_tensor_data(&reader, &mut data).unwrap();
+        assert_eq!([0.5, -2.0], data);
+    }
+
+    #[test]
+    fn read_tensor_data_rejects_length_mismatch() {
+        let message = tensor_message(&[1.0, 2.0, 3.0]);
+        let reader = message.get_root_as_reader::<capnp_tensor::Reader>().unwrap();
+        assert!(read_tensor_data(&reader, &mut [0f32; 2]).is_err());
+        assert!(read_tensor_data(&reader, &mut [0f32; 4]).is_err());
+    }
+}
@@ -307,6 +307,7 @@ impl<B: IBackend> Layer<B> {
                 format!("{}-{}", self.name, weight_id)
             };
             self.weights_display_names.push(display_name.clone());
+            self.weights_display_names.push(format!("{}-bias", display_name));
             // create name for registry
             let registry_name = format!("SHARED_WEIGHT_{}", display_name);
 
@@ -728,6 +729,9 @@ impl<B: IBackend> Layer<B> {
     /// Read a Cap'n Proto file at the specified path and deserialize the Layer inside it.
     ///
     /// You can find the capnp schema [here](../../../../capnp/juice.capnp).
+    /// Returns an error of kind `InvalidData` if a weight of the layer is missing in the file.
+    /// A missing bias of a weight that is present keeps its initial value, as files written
+    /// by earlier versions do not contain it.
     ///
     /// ```
     /// # extern crate juice;
@@ -781,36 +785,57 @@ impl<B: IBackend> Layer<B> {
 
         let read_weights = read_layer.get_weights_data().unwrap();
 
-        let names = layer.learnable_weights_names();
-        let weights_data = layer.learnable_weights_data();
+        let mut names = layer.learnable_weights_names();
+        names.append(&mut layer.non_learnable_weights_names());
+        let mut weights_data = layer.learnable_weights_data();
+        weights_data.append(&mut layer.non_learnable_weights_data());
 
         let native_backend = Backend::<Native>::default().unwrap();
-        for (i, (name, weight)) in names.iter().zip(weights_data).enumerate() {
-            for j in 0..read_weights.len() {
-                let capnp_weight = read_weights.get(i as u32);
-                if capnp_weight.get_name().unwrap() != name {
+        let find_weight = |name: &str| {
+            (0..read_weights.len())
+                .map(|j| read_weights.get(j))
+                .find(|capnp_weight| capnp_weight.get_name().unwrap() == name)
+        };
+        for (name, weight) in names.iter().zip(weights_data) {
+            let capnp_weight = match find_weight(name) {
+                Some(capnp_weight) => capnp_weight,
+                // files written before the bias of auto weight blobs was saved only hold the weight
+                None if name.ends_with("-bias") && find_weight(name.trim_end_matches("-bias")).is_some() => {
+                    warn!(
+                        "weight {} is missing in {}, keeping its initial value",
+                        name,
+                        path.display()
+                    );
                     continue;
                 }
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("weight {} is missing in {}", name, path.display()),
+                    ))
+                }
+            };
 
-                let mut weight_lock = weight.write().unwrap();
+            let mut weight_lock = weight.write().unwrap();
 
-                let capnp_tensor = capnp_weight.get_tensor().unwrap();
-                let mut shape = Vec::new();
-                let capnp_shape = capnp_tensor.get_shape().unwrap();
-                for k in 0..capnp_shape.len() {
-                    shape.push(capnp_shape.get(k) as usize)
-                }
-                weight_lock.reshape(&shape).unwrap();
-
-                let native_slice = weight_lock
-                    .write_only(native_backend.device())
-                    .unwrap()
-                    .as_mut_slice::<f32>();
-                let data = capnp_tensor.get_data().unwrap();
-                for k in 0..data.len() {
-                    native_slice[k as usize] = data.get(k);
-                }
+            let capnp_tensor = capnp_weight.get_tensor().unwrap();
+            let mut shape = Vec::new();
+            let capnp_shape = capnp_tensor.get_shape().unwrap();
+            for k in 0..capnp_shape.len() {
+                shape.push(capnp_shape.get(k) as usize)
             }
+            weight_lock.reshape(&shape).unwrap();
+
+            let native_slice = weight_lock
+                .write_only(native_backend.device())
+                .unwrap()
+                .as_mut_slice::<f32>();
+            read_tensor_data(&capnp_tensor, native_slice).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("weight {} in {}: {}", name, path.display(), err),
+                )
+            })?;
         }
 
         Ok(layer)
@@ -887,6 +912,29 @@ impl<B: IBackend> Layer<B> {
         }
     }
 
+    /// Returns all the weights in the layer that are saved and loaded,
+    /// but not learnable.
+    ///
+    /// If the layer is a container layer it will return all the weights of the
+    /// layers inside it.
+    pub fn non_learnable_weights_data(&self) -> Vec<ArcLock<SharedTensor<f32>>> {
+        self.worker.non_learnable_weights().unwrap_or_default()
+    }
+
+    /// Returns the names of all the weights in the layer that are saved and loaded,
+    /// but not learnable.
+    ///
+    /// If the layer is a container layer it will return all the names of the
+    /// layers inside it.
+    pub fn non_learnable_weights_names(&self) -> Vec<String> {
+        let names = self.worker.non_learnable_weights_names().unwrap_or_default();
+        if self.worker.is_container() {
+            names
+        } else {
+            names.iter().map(|name| format!("{}-{}", self.name, name)).collect()
+        }
+    }
+
     /// Returns the learning rate for all the learnable weights in the layer.
     ///
     /// If the layer is a container layer it will return all learning rates of the
@@ -920,11 +968,11 @@ impl<'a, B: IBackend> CapnpWrite<'a> for Layer<B> {
         }
         {
             let native_backend = Backend::<Native>::default().unwrap();
-            let mut weights = builder
-                .reborrow()
-                .init_weights_data(self.learnable_weights_names().len() as u32);
-            let names = self.learnable_weights_names();
-            let weights_data = self.learnable_weights_data();
+            let mut names = self.learnable_weights_names();
+            names.append(&mut self.non_learnable_weights_names());
+            let mut weights_data = self.learnable_weights_data();
+            weights_data.append(&mut self.non_learnable_weights_data());
+            let mut weights = builder.reborrow().init_weights_data(names.len() as u32);
 
             for (i, (name, weight)) in names.iter().zip(weights_data).enumerate() {
                 let mut capnp_weight = weights.reborrow().get(i as u32);
@@ -941,10 +989,7 @@ impl<'a, B: IBackend> CapnpWrite<'a> for Layer<B> {
                 }
                 {
                     let native_slice = weight_lock.read(native_backend.device()).unwrap().as_slice::<f32>();
-                    let mut tensor_data = tensor.reborrow().init_data(native_slice.len() as u32);
-                    for (i, datum) in native_slice.iter().enumerate() {
-                        tensor_data.set(i as u32, *datum);
-                    }
+                    CapnpTensorData::write_tensor_data(&mut tensor, native_slice);
                 }
             }
         }
@@ -1000,21 +1045,49 @@ impl<B: IBackend + LayerOps<f32> + crate::coblas::plugin::Copy<f32> + 'static> L
     fn worker_from_config(backend: Rc<B>, config: &LayerConfig) -> Box<dyn ILayer<B>> {
         match config.layer_type.clone() {
             LayerType::Convolution(layer_config) => Box::new(Convolution::from_config(&layer_config)),
+            LayerType::ConvolutionTranspose(layer_config) => Box::new(ConvolutionTranspose::from_config(&layer_config)),
+            LayerType::Embedding(layer_config) => Box::new(Embedding::from_config(&layer_config)),
+            LayerType::MultiHeadAttention(layer_config) => Box::new(MultiHeadAttention::from_config(&layer_config)),
             LayerType::Rnn(layer_config) => Box::new(Rnn::from_config(&layer_config)),
             LayerType::Linear(layer_config) => Box::new(Linear::from_config(&layer_config)),
             LayerType::LogSoftmax => Box::new(LogSoftmax::default()),
             LayerType::Pooling(layer_config) => Box::new(Pooling::from_config(&layer_config)),
+            LayerType::Upsample(layer_config) => Box::new(Upsample::from_config(&layer_config)),
             LayerType::Sequential(layer_config) => Box::new(Sequential::from_config(backend, &layer_config)),
             LayerType::Softmax => Box::new(Softmax::default()),
             LayerType::ReLU => Box::new(ReLU),
             LayerType::TanH => Box::new(TanH),
             LayerType::Sigmoid => Box::new(Sigmoid),
+            LayerType::LeakyReLU(layer_config) => Box::new(LeakyReLU::from_config(&layer_config)),
+            LayerType::ELU(layer_config) => Box::new(ELU::from_config(&layer_config)),
+            LayerType::SELU => Box::new(SELU),
+            LayerType::GELU => Box::new(GELU),
+            LayerType::Swish => Box::new(Swish),
+            LayerType::Softplus => Box::new(Softplus),
+            LayerType::HardTanh(layer_config) => Box::new(HardTanh::from_config(&layer_config)),
             LayerType::NegativeLogLikelihood(layer_config) => {
                 Box::new(NegativeLogLikelihood::from_config(&layer_config))
             }
-            LayerType::MeanSquaredError => Box::new(MeanSquaredError),
+            LayerType::MeanSquaredError(layer_config) => Box::new(MeanSquaredError::from_config(&layer_config)),
+            LayerType::CrossEntropy(layer_config) => Box::new(CrossEntropy::from_config(&layer_config)),
+            LayerType::MeanAbsoluteError(layer_config) => Box::new(MeanAbsoluteError::from_config(&layer_config)),
+            LayerType::Huber(layer_config) => Box::new(Huber::from_config(&layer_config)),
+            LayerType::BinaryCrossEntropy(layer_config) => Box::new(BinaryCrossEntropy::from_config(&layer_config)),
+            LayerType::BinaryCrossEntropyWithLogits(layer_config) => {
+                Box::new(BinaryCrossEntropyWithLogits::from_config(&layer_config))
+            }
+            LayerType::KlDivergence(layer_config) => Box::new(KlDivergence::from_config(&layer_config)),
+            LayerType::MultiClassHinge(layer_config) => Box::new(MultiClassHinge::from_config(&layer_config)),
             LayerType::Reshape(layer_config) => Box::new(Reshape::from_config(&layer_config)),
+            LayerType::Permute(layer_config) => Box::new(Permute::from_config(&layer_config)),
+            LayerType::Pad(layer_config) => Box::new(Pad::from_config(&layer_config)),
+            LayerType::Concat(layer_config) => Box::new(Concat::from_config(&layer_config)),
+            LayerType::Split(layer_config) => Box::new(Split::from_config(&layer_config)),
             LayerType::Dropout(layer_config) => Box::new(Dropout::from_config(&layer_config)),
+            LayerType::LRN(layer_config) => Box::new(LRN::from_config(&layer_config)),
+            LayerType::BatchNorm(layer_config) => Box::new(BatchNorm::from_config(&layer_config)),
+            LayerType::LayerNorm(layer_config) => Box::new(LayerNorm::from_config(&layer_config)),
+            LayerType::GroupNorm(layer_config) => Box::new(GroupNorm::from_config(&layer_config)),
         }
     }
 }
@@ -1207,6 +1280,14 @@ pub trait ILayer<B: IBackend>:
     fn auto_weight_blobs(&self) -> bool {
         false
     }
+    /// Returns the minimum number of input blobs required by the layer,
+    /// or 0 if no minimum number is required.
+    ///
+    /// This method should be overridden to return a positive value if your
+    /// layer expects some minimum number of input blobs.
+    fn min_input_blobs(&self) -> usize {
+        0
+    }
     /// Returns the exact number of input blobs required by the layer,
     /// or `None` if no exact number is required.
     ///
@@ -1322,6 +1403,22 @@ pub trait ILayer<B: IBackend>:
     fn learnable_weights_lr(&self) -> Option<Vec<Option<f32>>> {
         None
     }
+
+    /// Return the weights inside the layer that are not learnable,
+    /// but part of its state, e.g. running statistics.
+    ///
+    /// They are saved and loaded with the layer, but not updated by the solver.
+    fn non_learnable_weights(&self) -> Option<Vec<ArcLock<SharedTensor<f32>>>> {
+        None
+    }
+
+    /// Return the names of the non-learnable weights inside the layer.
+    ///
+    /// Container layers return the names of the layers inside them unchanged,
+    /// all other names are prefixed with the name of the layer.
+    fn non_learnable_weights_names(&self) -> Option<Vec<String>> {
+        None
+    }
 }
 
 /// A Layer that can compute the output for a given input.
@@ -1399,6 +1496,12 @@ pub enum LayerType {
     // Common layers
     /// Convolution Layer
     Convolution(ConvolutionConfig),
+    /// Transposed Convolution Layer
+    ConvolutionTranspose(ConvolutionTransposeConfig),
+    /// Embedding Layer
+    Embedding(EmbeddingConfig),
+    /// Multi-Head Attention Layer
+    MultiHeadAttention(MultiHeadAttentionConfig),
     /// RNN Layer
     Rnn(RnnConfig),
     /// Linear Layer
@@ -1407,12 +1510,22 @@ pub enum LayerType {
     LogSoftmax,
     /// Pooling Layer
     Pooling(PoolingConfig),
+    /// Upsample Layer
+    Upsample(UpsampleConfig),
     /// Sequential Layer
     Sequential(SequentialConfig),
     /// Softmax Layer
     Softmax,
     /// Dropout
     Dropout(DropoutConfig),
+    /// Local Response Normalization Layer
+    LRN(LRNConfig),
+    /// Batch Normalization Layer
+    BatchNorm(BatchNormConfig),
+    /// Layer Normalization Layer
+    LayerNorm(LayerNormConfig),
+    /// Group Normalization Layer
+    GroupNorm(GroupNormConfig),
     // Activation layers
     /// ReLU Layer
     ReLU,
@@ -1420,14 +1533,50 @@ pub enum LayerType {
     TanH,
     /// Sigmoid Layer
     Sigmoid,
+    /// Leaky ReLU Layer
+    LeakyReLU(LeakyReLUConfig),
+    /// ELU Layer
+    ELU(ELUConfig),
+    /// SELU Layer
+    SELU,
+    /// GELU Layer
+    GELU,
+    /// Swish Layer
+    Swish,
+    /// Softplus Layer
+    Softplus,
+    /// HardTanh Layer
+    HardTanh(HardTanhConfig),
     // Loss layers
     /// NegativeLogLikelihood Layer
     NegativeLogLikelihood(NegativeLogLikelihoodConfig),
     /// MeanSquaredError Layer
-    MeanSquaredError,
+    MeanSquaredError(MeanSquaredErrorConfig),
+    /// CrossEntropy Layer
+    CrossEntropy(CrossEntropyConfig),
+    /// MeanAbsoluteError Layer
+    MeanAbsoluteError(MeanAbsoluteErrorConfig),
+    /// Huber Layer
+    Huber(HuberConfig),
+    /// BinaryCrossEntropy Layer
+    BinaryCrossEntropy(BinaryCrossEntropyConfig),
+    /// BinaryCrossEntropyWithLogits Layer
+    BinaryCrossEntropyWithLogits(BinaryCrossEntropyWithLogitsConfig),
+    /// KlDivergence Layer
+    KlDivergence(KlDivergenceConfig),
+    /// MultiClassHinge Layer
+    MultiClassHinge(MultiClassHingeConfig),
     // Utility layers
     /// Reshape Layer
     Reshape(ReshapeConfig),
+    /// Permute Layer
+    Permute(PermuteConfig),
+    /// Pad Layer
+    Pad(PadConfig),
+    /// Concat Layer
+    Concat(ConcatConfig),
+    /// Split Layer
+    Split(SplitConfig),
 }
 
 // TODO get rid of this, each implementation has to state if this
@@ -1443,13 +1592,39 @@ impl LayerType {
             LayerType::ReLU => true,
             LayerType::TanH => true,
             LayerType::Sigmoid => true,
+            LayerType::LeakyReLU(ref cfg) => cfg.slope >= 0.0,
+            LayerType::ELU(ref cfg) => cfg.alpha >= 0.0,
+            LayerType::SELU => true,
+            LayerType::GELU => false,
+            LayerType::Swish => false,
+            LayerType::Softplus => true,
+            LayerType::HardTanh(_) => true,
             LayerType::NegativeLogLikelihood(_) => false,
-            LayerType::MeanSquaredError => false,
+            LayerType::MeanSquaredError(_) => false,
+            LayerType::CrossEntropy(_) => false,
+            LayerType::MeanAbsoluteError(_) => false,
+            LayerType::Huber(_) => false,
+            LayerType::BinaryCrossEntropy(_) => false,
+            LayerType::BinaryCrossEntropyWithLogits(_) => false,
+            LayerType::KlDivergence(_) => false,
+            LayerType::MultiClassHinge(_) => false,
             LayerType::Reshape(_) => true,
+            LayerType::Permute(_) => false,
+            LayerType::Pad(_) => false,
+            LayerType::Concat(_) => false,
+            LayerType::Split(_) => false,
             LayerType::Convolution(_) => false,
+            LayerType::ConvolutionTranspose(_) => false,
+            LayerType::Embedding(_) => false,
+            LayerType::MultiHeadAttention(_) => false,
             LayerType::Rnn(_) => false,
             LayerType::Pooling(_) => false,
+            LayerType::Upsample(_) => false,
             LayerType::Dropout(_) => false,
+            LayerType::LRN(_) => false,
+            LayerType::BatchNorm(_) => false,
+            LayerType::LayerNorm(_) => false,
+            LayerType::GroupNorm(_) => false,
         }
     }
 }
@@ -1473,19 +1648,94 @@ impl<'a> CapnpWrite<'a> for LayerType {
             &LayerType::ReLU => builder.set_relu(()),
             &LayerType::TanH => builder.set_tanh(()),
             &LayerType::Sigmoid => builder.set_sigmoid(()),
+            &LayerType::LeakyReLU(ref cfg) => {
+                let ref mut config = builder.reborrow().init_leaky_relu();
+                cfg.write_capnp(config);
+            }
+            &LayerType::ELU(ref cfg) => {
+                let ref mut config = builder.reborrow().init_elu();
+                cfg.write_capnp(config);
+            }
+            &LayerType::SELU => builder.set_selu(()),
+            &LayerType::GELU => builder.set_gelu(()),
+            &LayerType::Swish => builder.set_swish(()),
+            &LayerType::Softplus => builder.set_softplus(()),
+            &LayerType::HardTanh(ref cfg) => {
+                let ref mut config = builder.reborrow().init_hard_tanh();
+                cfg.write_capnp(config);
+            }
             &LayerType::NegativeLogLikelihood(ref cfg) => {
                 let ref mut config = builder.reborrow().init_negative_log_likelihood();
                 cfg.write_capnp(config);
             }
-            &LayerType::MeanSquaredError => builder.set_mean_squared_error(()),
+            &LayerType::MeanSquaredError(ref cfg) => {
+                let ref mut config = builder.reborrow().init_mean_squared_error();
+                cfg.write_capnp(config);
+            }
+            &LayerType::CrossEntropy(ref cfg) => {
+                let ref mut config = builder.reborrow().init_cross_entropy();
+                cfg.write_capnp(config);
+            }
+            &LayerType::MeanAbsoluteError(ref cfg) => {
+                let ref mut config = builder.reborrow().init_mean_absolute_error();
+                cfg.write_capnp(config);
+            }
+            &LayerType::Huber(ref cfg) => {
+                let ref mut config = builder.reborrow().init_huber();
+                cfg.write_capnp(config);
+            }
+            &LayerType::BinaryCrossEntropy(ref cfg) => {
+                let ref mut config = builder.reborrow().init_binary_cross_entropy();
+                cfg.write_capnp(config);
+            }
+            &LayerType::BinaryCrossEntropyWithLogits(ref cfg) => {
+                let ref mut config = builder.reborrow().init_binary_cross_entropy_with_logits();
+                cfg.write_capnp(config);
+            }
+            &LayerType::KlDivergence(ref cfg) => {
+                let ref mut config = builder.reborrow().init_kl_divergence();
+                cfg.write_capnp(config);
+            }
+            &LayerType::MultiClassHinge(ref cfg) => {
+                let ref mut config = builder.reborrow().init_multi_class_hinge();
+                cfg.write_capnp(config);
+            }
             &LayerType::Reshape(ref cfg) => {
                 let ref mut config = builder.reborrow().init_reshape();
                 cfg.write_capnp(config);
             }
+            &LayerType::Permute(ref cfg) => {
+                let ref mut config = builder.reborrow().init_permute();
+                cfg.write_capnp(config);
+            }
+            &LayerType::Pad(ref cfg) => {
+                let ref mut config = builder.reborrow().init_pad();
+                cfg.write_capnp(config);
+            }
+            &LayerType::Concat(ref cfg) => {
+                let ref mut config = builder.reborrow().init_concat();
+                cfg.write_capnp(config);
+            }
+            &LayerType::Split(ref cfg) => {
+                let ref mut config = builder.reborrow().init_split();
+                cfg.write_capnp(config);
+            }
             &LayerType::Convolution(ref cfg) => {
                 let ref mut config = builder.reborrow().init_convolution();
                 cfg.write_capnp(config);
             }
+            &LayerType::ConvolutionTranspose(ref cfg) => {
+                let ref mut config = builder.reborrow().init_convolution_transpose();
+                cfg.write_capnp(config);
+            }
+            &LayerType::Embedding(ref cfg) => {
+                let ref mut config = builder.reborrow().init_embedding();
+                cfg.write_capnp(config);
+            }
+            &LayerType::MultiHeadAttention(ref cfg) => {
+                let ref mut config = builder.reborrow().init_multi_head_attention();
+                cfg.write_capnp(config);
+            }
             &LayerType::Rnn(ref cfg) => {
                 let ref mut config = builder.reborrow().init_rnn();
                 cfg.write_capnp(config);
@@ -1494,10 +1744,30 @@ impl<'a> CapnpWrite<'a> for LayerType {
                 let ref mut config = builder.reborrow().init_pooling();
                 cfg.write_capnp(config);
             }
+            &LayerType::Upsample(ref cfg) => {
+                let ref mut config = builder.reborrow().init_upsample();
+                cfg.write_capnp(config);
+            }
             &LayerType::Dropout(ref cfg) => {
                 let ref mut config = builder.reborrow().init_dropout();
                 cfg.write_capnp(config);
             }
+            &LayerType::LRN(ref cfg) => {
+                let ref mut config = builder.reborrow().init_lrn();
+                cfg.write_capnp(config);
+            }
+            &LayerType::BatchNorm(ref cfg) => {
+                let ref mut config = builder.reborrow().init_batch_norm();
+                cfg.write_capnp(config);
+            }
+            &LayerType::LayerNorm(ref cfg) => {
+                let ref mut config = builder.reborrow().init_layer_norm();
+                cfg.write_capnp(config);
+            }
+            &LayerType::GroupNorm(ref cfg) => {
+                let ref mut config = builder.reborrow().init_group_norm();
+                cfg.write_capnp(config);
+            }
         }
     }
 }
@@ -1520,23 +1790,105 @@ impl<'a> CapnpRead<'a> for LayerType {
             capnp_layer_type::Which::Relu(_) => LayerType::ReLU,
             capnp_layer_type::Which::Tanh(_) => LayerType::TanH,
             capnp_layer_type::Which::Sigmoid(_) => LayerType::Sigmoid,
+            capnp_layer_type::Which::LeakyRelu(read_config) => {
+                let config = LeakyReLUConfig::read_capnp(read_config.unwrap());
+                LayerType::LeakyReLU(config)
+            }
+            capnp_layer_type::Which::Elu(read_config) => {
+                let config = ELUConfig::read_capnp(read_config.unwrap());
+                LayerType::ELU(config)
+            }
+            capnp_layer_type::Which::Selu(_) => LayerType::SELU,
+            capnp_layer_type::Which::Gelu(_) => LayerType::GELU,
+            capnp_layer_type::Which::Swish(_) => LayerType::Swish,
+            capnp_layer_type::Which::Softplus(_) => LayerType::Softplus,
+            capnp_layer_type::Which::HardTanh(read_config) => {
+                let config = HardTanhConfig::read_capnp(read_config.unwrap());
+                LayerType::HardTanh(config)
+            }
             capnp_layer_type::Which::NegativeLogLikelihood(read_config) => {
                 let config = NegativeLogLikelihoodConfig::read_capnp(read_config.unwrap());
                 LayerType::NegativeLogLikelihood(config)
             }
-            capnp_layer_type::Which::MeanSquaredError(_) => LayerType::MeanSquaredError,
+            capnp_layer_type::Which::MeanSquaredErrorDefault(_) => {
+                LayerType::MeanSquaredError(MeanSquaredErrorConfig::default())
+            }
+            capnp_layer_type::Which::MeanSquaredError(read_config) => {
+                let config = MeanSquaredErrorConfig::read_capnp(read_config.unwrap());
+                LayerType::MeanSquaredError(config)
+            }
+            capnp_layer_type::Which::CrossEntropy(read_config) => {
+                let config = CrossEntropyConfig::read_capnp(read_config.unwrap());
+                LayerType::CrossEntropy(config)
+            }
+            capnp_layer_type::Which::MeanAbsoluteError(read_config) => {
+                let config = MeanAbsoluteErrorConfig::read_capnp(read_config.unwrap());
+                LayerType::MeanAbsoluteError(config)
+            }
+            capnp_layer_type::Which::Huber(read_config) => {
+                let config = HuberConfig::read_capnp(read_config.unwrap());
+                LayerType::Huber(config)
+            }
+            capnp_layer_type::Which::BinaryCrossEntropy(read_config) => {
+                let config = BinaryCrossEntropyConfig::read_capnp(read_config.unwrap());
+                LayerType::BinaryCrossEntropy(config)
+            }
+            capnp_layer_type::Which::BinaryCrossEntropyWithLogits(read_config) => {
+                let config = BinaryCrossEntropyWithLogitsConfig::read_capnp(read_config.unwrap());
+                LayerType::BinaryCrossEntropyWithLogits(config)
+            }
+            capnp_layer_type::Which::KlDivergence(read_config) => {
+                let config = KlDivergenceConfig::read_capnp(read_config.unwrap());
+                LayerType::KlDivergence(config)
+            }
+            capnp_layer_type::Which::MultiClassHinge(read_config) => {
+                let config = MultiClassHingeConfig::read_capnp(read_config.unwrap());
+                LayerType::MultiClassHinge(config)
+            }
             capnp_layer_type::Which::Reshape(read_config) => {
                 let config = ReshapeConfig::read_capnp(read_config.unwrap());
                 LayerType::Reshape(config)
             }
+            capnp_layer_type::Which::Permute(read_config) => {
+                let config = PermuteConfig::read_capnp(read_config.unwrap());
+                LayerType::Permute(config)
+            }
+            capnp_layer_type::Which::Pad(read_config) => {
+                let config = PadConfig::read_capnp(read_config.unwrap());
+                LayerType::Pad(config)
+            }
+            capnp_layer_type::Which::Concat(read_config) => {
+                let config = ConcatConfig::read_capnp(read_config.unwrap());
+                LayerType::Concat(config)
+            }
+            capnp_layer_type::Which::Split(read_config) => {
+                let config = SplitConfig::read_capnp(read_config.unwrap());
+                LayerType::Split(config)
+            }
             capnp_layer_type::Which::Pooling(read_config) => {
                 let config = PoolingConfig::read_capnp(read_config.unwrap());
                 LayerType::Pooling(config)
             }
+            capnp_layer_type::Which::Upsample(read_config) => {
+                let config = UpsampleConfig::read_capnp(read_config.unwrap());
+                LayerType::Upsample(config)
+            }
             capnp_layer_type::Which::Convolution(read_config) => {
                 let config = ConvolutionConfig::read_capnp(read_config.unwrap());
                 LayerType::Convolution(config)
             }
+            capnp_layer_type::Which::ConvolutionTranspose(read_config) => {
+                let config = ConvolutionTransposeConfig::read_capnp(read_config.unwrap());
+                LayerType::ConvolutionTranspose(config)
+            }
+            capnp_layer_type::Which::Embedding(read_config) => {
+                let config = EmbeddingConfig::read_capnp(read_config.unwrap());
+                LayerType::Embedding(config)
+            }
+            capnp_layer_type::Which::MultiHeadAttention(read_config) => {
+                let config = MultiHeadAttentionConfig::read_capnp(read_config.unwrap());
+                LayerType::MultiHeadAttention(config)
+            }
             capnp_layer_type::Which::Rnn(read_config) => {
                 let config = RnnConfig::read_capnp(read_config.unwrap());
                 LayerType::Rnn(config)
@@ -1545,6 +1897,22 @@ impl<'a> CapnpRead<'a> for LayerType {
                 let config = DropoutConfig::read_capnp(read_config.unwrap());
                 LayerType::Dropout(config)
             }
+            capnp_layer_type::Which::Lrn(read_config) => {
+                let config = LRNConfig::read_capnp(read_config.unwrap());
+                LayerType::LRN(config)
+            }
+            capnp_layer_type::Which::BatchNorm(read_config) => {
+                let config = BatchNormConfig::read_capnp(read_config.unwrap());
+                LayerType::BatchNorm(config)
+            }
+            capnp_layer_type::Which::LayerNorm(read_config) => {
+                let config = LayerNormConfig::read_capnp(read_config.unwrap());
+                LayerType::LayerNorm(config)
+            }
+            capnp_layer_type::Which::GroupNorm(read_config) => {
+                let config = GroupNormConfig::read_capnp(read_config.unwrap());
+                LayerType::GroupNorm(config)
+            }
         }
     }
 }
@@ -1698,3 +2066,85 @@ impl<'a> CapnpRead<'a> for LayerConfig {
         }
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "native")]
+mod tests {
+    use std::fs::File;
+    use std::io;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::util::{native_backend, write_to_memory};
+
+    #[test]
+    fn load_fails_on_missing_weights() {
+        let mut net_cfg = SequentialConfig::default();
+        net_cfg.add_input("data", &[1, 3]);
+        net_cfg.add_layer(LayerConfig::new("linear", LinearConfig { output_size: 2 }));
+        let layer = Layer::from_config(Rc::new(native_backend()), &LayerConfig::new("network", net_cfg));
+
+        let mut tmpfile = std::env::temp_dir();
+        tmpfile.push("tmpnet_missing_weights");
+        let mut message = ::capnp::message::Builder::new_default();
+        {
+            let mut builder = message.init_root::<capnp_layer::Builder>();
+            layer.write_capnp(&mut builder);
+            builder.init_weights_data(0);
+        }
+        ::capnp::serialize_packed::write_message(&mut File::create(&tmpfile).unwrap(), &message).unwrap();
+
+        let err = Layer::<Backend<Native>>::load(Rc::new(native_backend()), &tmpfile)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn load_keeps_initial_bias_missing_in_file() {
+        let mut net_cfg = SequentialConfig::default();
+        net_cfg.add_input("data", &[1, 3]);
+        net_cfg.add_layer(LayerConfig::new("linear", LinearConfig { output_size: 2 }));
+        let layer = Layer::from_config(Rc::new(native_backend()), &LayerConfig::new("network", net_cfg));
+        let native = native_backend();
+        let names = layer.learnable_weights_names();
+        let weights = layer.learnable_weights_data();
+        assert!(names[1].ends_with("-bias"));
+        write_to_memory(
+            weights[0].write().unwrap().write_only(native.device()).unwrap(),
+            &[1f32, 2.0, 3.0, 4.0, 5.0, 6.0],
+        );
+
+        // the format before the bias was saved, only the weight is in the file
+        let mut tmpfile = std::env::temp_dir();
+        tmpfile.push("tmpnet_without_bias");
+        let mut message = ::capnp::message::Builder::new_default();
+        {
+            let mut builder = message.init_root::<capnp_layer::Builder>();
+            layer.write_capnp(&mut builder);
+            let mut weights_data = builder.init_weights_data(1);
+            let mut capnp_weight = weights_data.reborrow().get(0);
+            capnp_weight.set_name(&names[0]);
+            let weight = weights[0].read().unwrap();
+            let mut tensor = capnp_weight.init_tensor();
+            {
+                let mut tensor_shape = tensor.reborrow().init_shape(weight.desc().len() as u32);
+                for (i, dim) in weight.desc().iter().enumerate() {
+                    tensor_shape.set(i as u32, *dim as u64);
+                }
+            }
+            CapnpTensorData::write_tensor_data(&mut tensor, weight.read(native.device()).unwrap().as_slice::<f32>());
+        }
+        ::capnp::serialize_packed::write_message(&mut File::create(&tmpfile).unwrap(), &message).unwrap();
+
+        let loaded = Layer::<Backend<Native>>::load(Rc::new(native_backend()), &tmpfile).unwrap();
+        let loaded_weights = loaded.learnable_weights_data();
+        let loaded_weight = loaded_weights[0].read().unwrap();
+        assert_eq!(
+            &[1f32, 2.0, 3.0, 4.0, 5.0, 6.0],
+            loaded_weight.read(native.device()).unwrap().as_slice::<f32>()
+        );
+        let loaded_bias = loaded_weights[1].read().unwrap();
+        assert_eq!(weights[1].read().unwrap().desc(), loaded_bias.desc());
+    }
+}
@@ -0,0 +1,139 @@
+//! Applies the nonlinear Exponential Linear Unit.
+//!
+//! Non-linearity activation function: y = x for x > 0, y = alpha * (e^x - 1) otherwise
+//!
+//! The negative saturation pushes the mean activation towards zero, see
+//! [Clevert et al.](https://arxiv.org/abs/1511.07289).
+
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::conn;
+use crate::juice_capnp::elu_config as capnp_config;
+use crate::layer::*;
+use crate::util::ArcLock;
+
+#[derive(Debug, Clone)]
+/// ELU Activation Layer
+pub struct ELU {
+    alpha: f64,
+}
+
+impl ELU {
+    /// Create a ELU layer from a ELUConfig.
+    pub fn from_config(config: &ELUConfig) -> ELU {
+        ELU { alpha: config.alpha }
+    }
+}
+
+//
+// ELU + ELUPointwise
+//
+impl<B: IBackend + conn::Elu<f32> + conn::EluPointwise<f32>> ILayer<B> for ELU {
+    impl_ilayer_activation!();
+
+    fn compute_in_place(&self) -> bool {
+        // the gradient can only be computed from the output for a non-negative alpha
+        self.alpha >= 0.0
+    }
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        if let Some(inp) = input_data.get(0) {
+            let read_inp = inp.read().unwrap();
+            let input_desc = read_inp.desc();
+            input_gradient[0].write().unwrap().resize(input_desc).unwrap();
+            output_data[0].write().unwrap().resize(input_desc).unwrap();
+            output_gradient[0].write().unwrap().resize(input_desc).unwrap();
+        }
+    }
+}
+
+impl<B: IBackend + conn::Elu<f32> + conn::EluPointwise<f32>> ComputeOutput<f32, B> for ELU {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        match input_data.get(0) {
+            Some(input) => backend.elu(input, output_data[0], self.alpha).unwrap(),
+            None => backend.elu_pointwise(output_data[0], self.alpha).unwrap(),
+        }
+    }
+}
+
+impl<B: IBackend + conn::Elu<f32> + conn::EluPointwise<f32>> ComputeInputGradient<f32, B> for ELU {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        match output_data.get(0) {
+            Some(_) => backend
+                .elu_grad(
+                    output_data[0],
+                    output_gradients[0],
+                    input_data[0],
+                    input_gradients[0],
+                    self.alpha,
+                )
+                .unwrap(),
+            None => backend
+                .elu_pointwise_grad(input_data[0], input_gradients[0], self.alpha)
+                .unwrap(),
+        }
+    }
+}
+
+impl<B: IBackend + conn::Elu<f32> + conn::EluPointwise<f32>> ComputeParametersGradient<f32, B> for ELU {}
+
+#[derive(Debug, Copy, Clone)]
+/// Specifies configuration parameters for a ELU Layer.
+pub struct ELUConfig {
+    /// The value negative inputs saturate to, times -1
+    pub alpha: f64,
+}
+
+impl Into<LayerType> for ELUConfig {
+    fn into(self) -> LayerType {
+        LayerType::ELU(self)
+    }
+}
+
+impl<'a> CapnpWrite<'a> for ELUConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the ELUConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.reborrow().set_alpha(self.alpha);
+    }
+}
+
+impl<'a> CapnpRead<'a> for ELUConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        ELUConfig {
+            alpha: reader.get_alpha(),
+        }
+    }
+}
+
+impl ::std::default::Default for ELUConfig {
+    fn default() -> ELUConfig {
+        ELUConfig { alpha: 1.0 }
+    }
+}
@@ -0,0 +1,73 @@
+//! Applies the nonlinear Gaussian Error Linear Unit.
+//!
+//! Non-linearity activation function: y = x * Φ(x), with the tanh approximation
+//! of the Gaussian CDF Φ of [Hendrycks and Gimpel](https://arxiv.org/abs/1606.08415).
+//!
+//! A popular choice in transformers.
+//! The gradient depends on the input, so it can not be computed in-place.
+
+use crate::co::{IBackend, SharedTensor};
+use crate::conn;
+use crate::layer::*;
+use crate::util::ArcLock;
+
+#[derive(Debug, Clone)]
+#[allow(missing_copy_implementations)]
+/// GELU Activation Layer
+pub struct GELU;
+
+//
+// GELU
+//
+impl<B: IBackend + conn::Gelu<f32>> ILayer<B> for GELU {
+    impl_ilayer_activation!();
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        if let Some(inp) = input_data.get(0) {
+            let read_inp = inp.read().unwrap();
+            let input_desc = read_inp.desc();
+            input_gradient[0].write().unwrap().resize(input_desc).unwrap();
+            output_data[0].write().unwrap().resize(input_desc).unwrap();
+            output_gradient[0].write().unwrap().resize(input_desc).unwrap();
+        }
+    }
+}
+
+impl<B: IBackend + conn::Gelu<f32>> ComputeOutput<f32, B> for GELU {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        backend.gelu(input_data[0], output_data[0]).unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Gelu<f32>> ComputeInputGradient<f32, B> for GELU {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        backend
+            .gelu_grad(output_data[0], output_gradients[0], input_data[0], input_gradients[0])
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Gelu<f32>> ComputeParametersGradient<f32, B> for GELU {}
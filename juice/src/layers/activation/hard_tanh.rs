@@ -0,0 +1,153 @@
+//! Applies the nonlinear HardTanh function.
+//!
+//! Non-linearity activation function: y = min(max(x, min_value), max_value)
+//!
+//! A cheap piecewise linear approximation of TanH.
+
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::conn;
+use crate::juice_capnp::hard_tanh_config as capnp_config;
+use crate::layer::*;
+use crate::util::ArcLock;
+
+#[derive(Debug, Clone)]
+/// HardTanh Activation Layer
+pub struct HardTanh {
+    min_value: f64,
+    max_value: f64,
+}
+
+impl HardTanh {
+    /// Create a HardTanh layer from a HardTanhConfig.
+    pub fn from_config(config: &HardTanhConfig) -> HardTanh {
+        HardTanh {
+            min_value: config.min_value,
+            max_value: config.max_value,
+        }
+    }
+}
+
+//
+// HardTanh + HardTanhPointwise
+//
+impl<B: IBackend + conn::HardTanh<f32> + conn::HardTanhPointwise<f32>> ILayer<B> for HardTanh {
+    impl_ilayer_activation!();
+
+    fn compute_in_place(&self) -> bool {
+        true
+    }
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        if let Some(inp) = input_data.get(0) {
+            let read_inp = inp.read().unwrap();
+            let input_desc = read_inp.desc();
+            input_gradient[0].write().unwrap().resize(input_desc).unwrap();
+            output_data[0].write().unwrap().resize(input_desc).unwrap();
+            output_gradient[0].write().unwrap().resize(input_desc).unwrap();
+        }
+    }
+}
+
+impl<B: IBackend + conn::HardTanh<f32> + conn::HardTanhPointwise<f32>> ComputeOutput<f32, B> for HardTanh {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        match input_data.get(0) {
+            Some(input) => backend
+                .hard_tanh(input, output_data[0], self.min_value, self.max_value)
+                .unwrap(),
+            None => backend
+                .hard_tanh_pointwise(output_data[0], self.min_value, self.max_value)
+                .unwrap(),
+        }
+    }
+}
+
+impl<B: IBackend + conn::HardTanh<f32> + conn::HardTanhPointwise<f32>> ComputeInputGradient<f32, B> for HardTanh {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        match output_data.get(0) {
+            Some(_) => backend
+                .hard_tanh_grad(
+                    output_data[0],
+                    output_gradients[0],
+                    input_data[0],
+                    input_gradients[0],
+                    self.min_value,
+                    self.max_value,
+                )
+                .unwrap(),
+            None => backend
+                .hard_tanh_pointwise_grad(input_data[0], input_gradients[0], self.min_value, self.max_value)
+                .unwrap(),
+        }
+    }
+}
+
+impl<B: IBackend + conn::HardTanh<f32> + conn::HardTanhPointwise<f32>> ComputeParametersGradient<f32, B> for HardTanh {}
+
+#[derive(Debug, Copy, Clone)]
+/// Specifies configuration parameters for a HardTanh Layer.
+pub struct HardTanhConfig {
+    /// The lower bound of the output
+    pub min_value: f64,
+    /// The upper bound of the output
+    pub max_value: f64,
+}
+
+impl Into<LayerType> for HardTanhConfig {
+    fn into(self) -> LayerType {
+        LayerType::HardTanh(self)
+    }
+}
+
+impl<'a> CapnpWrite<'a> for HardTanhConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the HardTanhConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.reborrow().set_min_value(self.min_value);
+        builder.reborrow().set_max_value(self.max_value);
+    }
+}
+
+impl<'a> CapnpRead<'a> for HardTanhConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        HardTanhConfig {
+            min_value: reader.get_min_value(),
+            max_value: reader.get_max_value(),
+        }
+    }
+}
+
+impl ::std::default::Default for HardTanhConfig {
+    fn default() -> HardTanhConfig {
+        HardTanhConfig {
+            min_value: -1.0,
+            max_value: 1.0,
+        }
+    }
+}
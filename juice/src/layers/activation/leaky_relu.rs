@@ -0,0 +1,141 @@
+//! Applies the nonlinear Leaky Rectified Linear Unit.
+//!
+//! Non-linearity activation function: y = max(0, x) + slope * min(0, x)
+//!
+//! Unlike ReLU it keeps a small gradient for negative inputs, so units can not die.
+
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::conn;
+use crate::juice_capnp::leaky_relu_config as capnp_config;
+use crate::layer::*;
+use crate::util::ArcLock;
+
+#[derive(Debug, Clone)]
+/// LeakyReLU Activation Layer
+pub struct LeakyReLU {
+    slope: f64,
+}
+
+impl LeakyReLU {
+    /// Create a LeakyReLU layer from a LeakyReLUConfig.
+    pub fn from_config(config: &LeakyReLUConfig) -> LeakyReLU {
+        LeakyReLU { slope: config.slope }
+    }
+}
+
+//
+// LeakyReLU + LeakyReLUPointwise
+//
+impl<B: IBackend + conn::LeakyRelu<f32> + conn::LeakyReluPointwise<f32>> ILayer<B> for LeakyReLU {
+    impl_ilayer_activation!();
+
+    fn compute_in_place(&self) -> bool {
+        // the gradient can only be computed from the output for a non-negative slope
+        self.slope >= 0.0
+    }
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        if let Some(inp) = input_data.get(0) {
+            let read_inp = inp.read().unwrap();
+            let input_desc = read_inp.desc();
+            input_gradient[0].write().unwrap().resize(input_desc).unwrap();
+            output_data[0].write().unwrap().resize(input_desc).unwrap();
+            output_gradient[0].write().unwrap().resize(input_desc).unwrap();
+        }
+    }
+}
+
+impl<B: IBackend + conn::LeakyRelu<f32> + conn::LeakyReluPointwise<f32>> ComputeOutput<f32, B> for LeakyReLU {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        match input_data.get(0) {
+            Some(input) => backend.leaky_relu(input, output_data[0], self.slope).unwrap(),
+            None => backend.leaky_relu_pointwise(output_data[0], self.slope).unwrap(),
+        }
+    }
+}
+
+impl<B: IBackend + conn::LeakyRelu<f32> + conn::LeakyReluPointwise<f32>> ComputeInputGradient<f32, B> for LeakyReLU {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        match output_data.get(0) {
+            Some(_) => backend
+                .leaky_relu_grad(
+                    output_data[0],
+                    output_gradients[0],
+                    input_data[0],
+                    input_gradients[0],
+                    self.slope,
+                )
+                .unwrap(),
+            None => backend
+                .leaky_relu_pointwise_grad(input_data[0], input_gradients[0], self.slope)
+                .unwrap(),
+        }
+    }
+}
+
+impl<B: IBackend + conn::LeakyRelu<f32> + conn::LeakyReluPointwise<f32>> ComputeParametersGradient<f32, B>
+    for LeakyReLU
+{
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Specifies configuration parameters for a LeakyReLU Layer.
+pub struct LeakyReLUConfig {
+    /// The factor applied to negative inputs
+    pub slope: f64,
+}
+
+impl Into<LayerType> for LeakyReLUConfig {
+    fn into(self) -> LayerType {
+        LayerType::LeakyReLU(self)
+    }
+}
+
+impl<'a> CapnpWrite<'a> for LeakyReLUConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the LeakyReLUConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.reborrow().set_slope(self.slope);
+    }
+}
+
+impl<'a> CapnpRead<'a> for LeakyReLUConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        LeakyReLUConfig {
+            slope: reader.get_slope(),
+        }
+    }
+}
+
+impl ::std::default::Default for LeakyReLUConfig {
+    fn default() -> LeakyReLUConfig {
+        LeakyReLUConfig { slope: 0.01 }
+    }
+}
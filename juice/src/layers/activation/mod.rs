@@ -36,10 +36,24 @@ macro_rules! impl_ilayer_activation {
     };
 }
 
+pub use self::elu::{ELUConfig, ELU};
+pub use self::gelu::GELU;
+pub use self::hard_tanh::{HardTanh, HardTanhConfig};
+pub use self::leaky_relu::{LeakyReLU, LeakyReLUConfig};
 pub use self::relu::ReLU;
+pub use self::selu::SELU;
 pub use self::sigmoid::Sigmoid;
+pub use self::softplus::Softplus;
+pub use self::swish::Swish;
 pub use self::tanh::TanH;
 
+pub mod elu;
+pub mod gelu;
+pub mod hard_tanh;
+pub mod leaky_relu;
 pub mod relu;
+pub mod selu;
 pub mod sigmoid;
+pub mod softplus;
+pub mod swish;
 pub mod tanh;
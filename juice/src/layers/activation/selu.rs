@@ -0,0 +1,82 @@
+//! Applies the nonlinear Scaled Exponential Linear Unit.
+//!
+//! Non-linearity activation function: y = scale * x for x > 0, y = scale * alpha * (e^x - 1) otherwise
+//!
+//! With the fixed `alpha` and `scale` of [Klambauer et al.](https://arxiv.org/abs/1706.02515)
+//! the activations of a network stay normalized.
+
+use crate::co::{IBackend, SharedTensor};
+use crate::conn;
+use crate::layer::*;
+use crate::util::ArcLock;
+
+#[derive(Debug, Clone)]
+#[allow(missing_copy_implementations)]
+/// SELU Activation Layer
+pub struct SELU;
+
+//
+// SELU + SELUPointwise
+//
+impl<B: IBackend + conn::Selu<f32> + conn::SeluPointwise<f32>> ILayer<B> for SELU {
+    impl_ilayer_activation!();
+
+    fn compute_in_place(&self) -> bool {
+        true
+    }
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        if let Some(inp) = input_data.get(0) {
+            let read_inp = inp.read().unwrap();
+            let input_desc = read_inp.desc();
+            input_gradient[0].write().unwrap().resize(input_desc).unwrap();
+            output_data[0].write().unwrap().resize(input_desc).unwrap();
+            output_gradient[0].write().unwrap().resize(input_desc).unwrap();
+        }
+    }
+}
+
+impl<B: IBackend + conn::Selu<f32> + conn::SeluPointwise<f32>> ComputeOutput<f32, B> for SELU {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        match input_data.get(0) {
+            Some(input) => backend.selu(input, output_data[0]).unwrap(),
+            None => backend.selu_pointwise(output_data[0]).unwrap(),
+        }
+    }
+}
+
+impl<B: IBackend + conn::Selu<f32> + conn::SeluPointwise<f32>> ComputeInputGradient<f32, B> for SELU {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        match output_data.get(0) {
+            Some(_) => backend
+                .selu_grad(output_data[0], output_gradients[0], input_data[0], input_gradients[0])
+                .unwrap(),
+            None => backend.selu_pointwise_grad(input_data[0], input_gradients[0]).unwrap(),
+        }
+    }
+}
+
+impl<B: IBackend + conn::Selu<f32> + conn::SeluPointwise<f32>> ComputeParametersGradient<f32, B> for SELU {}
@@ -0,0 +1,83 @@
+//! Applies the nonlinear Softplus function.
+//!
+//! Non-linearity activation function: y = ln(1 + e^x)
+//!
+//! A smooth approximation of ReLU.
+
+use crate::co::{IBackend, SharedTensor};
+use crate::conn;
+use crate::layer::*;
+use crate::util::ArcLock;
+
+#[derive(Debug, Clone)]
+#[allow(missing_copy_implementations)]
+/// Softplus Activation Layer
+pub struct Softplus;
+
+//
+// Softplus + SoftplusPointwise
+//
+impl<B: IBackend + conn::Softplus<f32> + conn::SoftplusPointwise<f32>> ILayer<B> for Softplus {
+    impl_ilayer_activation!();
+
+    fn compute_in_place(&self) -> bool {
+        true
+    }
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        if let Some(inp) = input_data.get(0) {
+            let read_inp = inp.read().unwrap();
+            let input_desc = read_inp.desc();
+            input_gradient[0].write().unwrap().resize(input_desc).unwrap();
+            output_data[0].write().unwrap().resize(input_desc).unwrap();
+            output_gradient[0].write().unwrap().resize(input_desc).unwrap();
+        }
+    }
+}
+
+impl<B: IBackend + conn::Softplus<f32> + conn::SoftplusPointwise<f32>> ComputeOutput<f32, B> for Softplus {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        match input_data.get(0) {
+            Some(input) => backend.softplus(input, output_data[0]).unwrap(),
+            None => backend.softplus_pointwise(output_data[0]).unwrap(),
+        }
+    }
+}
+
+impl<B: IBackend + conn::Softplus<f32> + conn::SoftplusPointwise<f32>> ComputeInputGradient<f32, B> for Softplus {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        match output_data.get(0) {
+            Some(_) => backend
+                .softplus_grad(output_data[0], output_gradients[0], input_data[0], input_gradients[0])
+                .unwrap(),
+            None => backend
+                .softplus_pointwise_grad(input_data[0], input_gradients[0])
+                .unwrap(),
+        }
+    }
+}
+
+impl<B: IBackend + conn::Softplus<f32> + conn::SoftplusPointwise<f32>> ComputeParametersGradient<f32, B> for Softplus {}
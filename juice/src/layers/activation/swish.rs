@@ -0,0 +1,72 @@
+//! Applies the nonlinear Swish function, also known as SiLU.
+//!
+//! Non-linearity activation function: y = x * (1 + e^(-x))^(-1)
+//!
+//! See [Ramachandran et al.](https://arxiv.org/abs/1710.05941).
+//! The gradient depends on the input, so it can not be computed in-place.
+
+use crate::co::{IBackend, SharedTensor};
+use crate::conn;
+use crate::layer::*;
+use crate::util::ArcLock;
+
+#[derive(Debug, Clone)]
+#[allow(missing_copy_implementations)]
+/// Swish Activation Layer
+pub struct Swish;
+
+//
+// Swish
+//
+impl<B: IBackend + conn::Swish<f32>> ILayer<B> for Swish {
+    impl_ilayer_activation!();
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        if let Some(inp) = input_data.get(0) {
+            let read_inp = inp.read().unwrap();
+            let input_desc = read_inp.desc();
+            input_gradient[0].write().unwrap().resize(input_desc).unwrap();
+            output_data[0].write().unwrap().resize(input_desc).unwrap();
+            output_gradient[0].write().unwrap().resize(input_desc).unwrap();
+        }
+    }
+}
+
+impl<B: IBackend + conn::Swish<f32>> ComputeOutput<f32, B> for Swish {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        backend.swish(input_data[0], output_data[0]).unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Swish<f32>> ComputeInputGradient<f32, B> for Swish {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        backend
+            .swish_grad(output_data[0], output_gradients[0], input_data[0], input_gradients[0])
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Swish<f32>> ComputeParametersGradient<f32, B> for Swish {}
@@ -0,0 +1,464 @@
+//! Applies Batch Normalization to the input data `x`.
+//!
+//! Every channel is normalized to zero mean and unit variance and then scaled
+//! and shifted by learnable weights, as introduced by [Ioffe and Szegedy][bn]:
+//!
+//! `y = scale * (x - mean) / sqrt(variance + epsilon) + shift`
+//!
+//! During training `mean` and `variance` are the statistics of the current batch,
+//! which are also blended into a running mean and variance.
+//! With `use_global_stats` the running statistics are used instead, as needed for inference
+//! or for fine-tuning with frozen statistics.
+//! They are not learnable, but are saved and loaded together with the layer.
+//!
+//! ## Input Data
+//!
+//! The layer expects the channels to be the second dimension of the input,
+//! e.g. `[N, C]` or NCHW. Statistics are computed over all other dimensions.
+//!
+//! [bn]: https://arxiv.org/abs/1502.03167
+
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::conn;
+use crate::juice_capnp::batch_norm_config as capnp_config;
+use crate::layer::*;
+use crate::util::{native_backend, write_to_memory, ArcLock};
+use crate::weight::FillerType;
+use std::rc::Rc;
+use std::sync::{Arc, RwLock};
+
+#[derive(Debug, Clone)]
+/// [BatchNorm](./index.html) Layer
+pub struct BatchNorm<T, B: conn::BatchNormalization<T>> {
+    epsilon: f64,
+    momentum: f64,
+    use_global_stats: bool,
+
+    running_mean: ArcLock<SharedTensor<T>>,
+    running_variance: ArcLock<SharedTensor<T>>,
+    saved_mean: ArcLock<SharedTensor<T>>,
+    saved_inv_variance: ArcLock<SharedTensor<T>>,
+    scale_diff: ArcLock<SharedTensor<T>>,
+    shift_diff: ArcLock<SharedTensor<T>>,
+
+    bn_config: Option<Rc<B::CBN>>,
+}
+
+impl<T, B: conn::BatchNormalization<T>> BatchNorm<T, B> {
+    /// Create a BatchNorm layer from a BatchNormConfig.
+    pub fn from_config(config: &BatchNormConfig) -> BatchNorm<T, B> {
+        let new_tensor = || Arc::new(RwLock::new(SharedTensor::new(&[1])));
+        BatchNorm {
+            epsilon: config.epsilon,
+            momentum: config.momentum,
+            use_global_stats: config.use_global_stats,
+
+            running_mean: new_tensor(),
+            running_variance: new_tensor(),
+            saved_mean: new_tensor(),
+            saved_inv_variance: new_tensor(),
+            scale_diff: new_tensor(),
+            shift_diff: new_tensor(),
+
+            bn_config: None,
+        }
+    }
+}
+
+impl<B: conn::BatchNormalization<f32>> BatchNorm<f32, B> {
+    /// Backpropagates through the normalization with the running statistics.
+    ///
+    /// Those are constants w.r.t. the input, so unlike with batch statistics every
+    /// element is a plain affine function of its input:
+    /// `dx = dy * scale / sqrt(running_variance + epsilon)`, while the gradients
+    /// of scale and shift are the sums of `dy * x_hat` and `dy` over each channel.
+    /// No backend provides this, so it is computed on the host.
+    fn global_stats_grad(
+        &self,
+        x: &SharedTensor<f32>,
+        dy: &SharedTensor<f32>,
+        scale: &SharedTensor<f32>,
+        dx: &mut SharedTensor<f32>,
+    ) {
+        let native = native_backend();
+        let channels = x.desc()[1];
+        let spatial = x.desc().iter().skip(2).product::<usize>();
+
+        let running_mean = self.running_mean.read().unwrap();
+        let running_mean = running_mean.read(native.device()).unwrap().as_slice::<f32>();
+        let running_variance = self.running_variance.read().unwrap();
+        let running_variance = running_variance.read(native.device()).unwrap().as_slice::<f32>();
+        let scale = scale.read(native.device()).unwrap().as_slice::<f32>();
+        let x = x.read(native.device()).unwrap().as_slice::<f32>();
+        let dy = dy.read(native.device()).unwrap().as_slice::<f32>();
+        let dx = dx.write_only(native.device()).unwrap().as_mut_slice::<f32>();
+
+        let inv_std: Vec<f32> = running_variance
+            .iter()
+            .map(|variance| 1f32 / (*variance + self.epsilon as f32).sqrt())
+            .collect();
+        let mut scale_diff = vec![0f32; channels];
+        let mut shift_diff = vec![0f32; channels];
+        for (i, ((dx, x), dy)) in dx.iter_mut().zip(x).zip(dy).enumerate() {
+            let c = (i / spatial) % channels;
+            *dx = dy * scale[c] * inv_std[c];
+            scale_diff[c] += dy * (x - running_mean[c]) * inv_std[c];
+            shift_diff[c] += dy;
+        }
+
+        let mut scale_diff_tensor = self.scale_diff.write().unwrap();
+        write_to_memory(scale_diff_tensor.write_only(native.device()).unwrap(), &scale_diff);
+        let mut shift_diff_tensor = self.shift_diff.write().unwrap();
+        write_to_memory(shift_diff_tensor.write_only(native.device()).unwrap(), &shift_diff);
+    }
+}
+
+impl<B: IBackend + conn::BatchNormalization<f32> + crate::coblas::plugin::Copy<f32>> ILayer<B> for BatchNorm<f32, B> {
+    impl_ilayer_common!();
+
+    fn auto_weight_blobs(&self) -> bool {
+        true
+    }
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let inp = input_data[0].read().unwrap();
+        let input_desc = inp.desc();
+        input_gradient[0].write().unwrap().resize(input_desc).unwrap();
+        output_data[0].write().unwrap().resize(input_desc).unwrap();
+        output_gradient[0].write().unwrap().resize(input_desc).unwrap();
+
+        let channels = input_desc[1];
+        // scale and shift start out as identity, but keep trained or loaded values
+        // if the number of channels did not change
+        for (weight_id, value) in [1f32, 0f32].iter().enumerate() {
+            if let Some(weight) = weights_data.get(weight_id) {
+                let mut weight = weight.write().unwrap();
+                if *weight.desc() != vec![channels] {
+                    weight.resize(&[channels]).unwrap();
+                    FillerType::fill_constant(&mut weight, *value);
+                }
+            }
+            if let Some(weight) = weights_gradient.get(weight_id) {
+                weight.write().unwrap().resize(&[channels]).unwrap();
+            }
+        }
+
+        // keep loaded statistics if the number of channels did not change
+        for (stat, value) in &[(&self.running_mean, 0f32), (&self.running_variance, 1f32)] {
+            let mut stat = stat.write().unwrap();
+            if *stat.desc() != vec![channels] {
+                stat.resize(&[channels]).unwrap();
+                FillerType::fill_constant(&mut stat, *value);
+            }
+        }
+        for tensor in &[
+            &self.saved_mean,
+            &self.saved_inv_variance,
+            &self.scale_diff,
+            &self.shift_diff,
+        ] {
+            tensor.write().unwrap().resize(&[channels]).unwrap();
+        }
+
+        let config = backend
+            .new_batch_normalization_config(self.epsilon, self.momentum)
+            .unwrap();
+        self.bn_config = Some(Rc::new(config));
+    }
+
+    fn non_learnable_weights(&self) -> Option<Vec<ArcLock<SharedTensor<f32>>>> {
+        Some(vec![self.running_mean.clone(), self.running_variance.clone()])
+    }
+
+    fn non_learnable_weights_names(&self) -> Option<Vec<String>> {
+        Some(vec!["running_mean".to_owned(), "running_variance".to_owned()])
+    }
+}
+
+impl<B: IBackend + conn::BatchNormalization<f32> + crate::coblas::plugin::Copy<f32>> ComputeOutput<f32, B>
+    for BatchNorm<f32, B>
+{
+    fn compute_output(
+        &self,
+        backend: &B,
+        weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        let config = self.bn_config.as_ref().unwrap();
+        if self.use_global_stats {
+            backend
+                .batch_normalization_inference(
+                    input_data[0],
+                    weights[0],
+                    weights[1],
+                    &self.running_mean.read().unwrap(),
+                    &self.running_variance.read().unwrap(),
+                    output_data[0],
+                    &*config,
+                )
+                .unwrap();
+        } else {
+            backend
+                .batch_normalization_training(
+                    input_data[0],
+                    weights[0],
+                    weights[1],
+                    &mut self.running_mean.write().unwrap(),
+                    &mut self.running_variance.write().unwrap(),
+                    &mut self.saved_mean.write().unwrap(),
+                    &mut self.saved_inv_variance.write().unwrap(),
+                    output_data[0],
+                    &*config,
+                )
+                .unwrap();
+        }
+    }
+}
+
+impl<B: IBackend + conn::BatchNormalization<f32> + crate::coblas::plugin::Copy<f32>> ComputeInputGradient<f32, B>
+    for BatchNorm<f32, B>
+{
+    /// Also computes the gradients w.r.t. scale and shift, since they need
+    /// the scale, which is not available in `compute_parameters_gradient`.
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        if self.use_global_stats {
+            self.global_stats_grad(input_data[0], output_gradients[0], weights_data[0], input_gradients[0]);
+            return;
+        }
+        let config = self.bn_config.as_ref().unwrap();
+        backend
+            .batch_normalization_grad(
+                input_data[0],
+                output_gradients[0],
+                weights_data[0],
+                &self.saved_mean.read().unwrap(),
+                &self.saved_inv_variance.read().unwrap(),
+                input_gradients[0],
+                &mut self.scale_diff.write().unwrap(),
+                &mut self.shift_diff.write().unwrap(),
+                &*config,
+            )
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::BatchNormalization<f32> + crate::coblas::plugin::Copy<f32>> ComputeParametersGradient<f32, B>
+    for BatchNorm<f32, B>
+{
+    fn compute_parameters_gradient(
+        &self,
+        backend: &B,
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        parameters_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        // computed together with the input gradient
+        backend
+            .copy(&self.scale_diff.read().unwrap(), parameters_gradients[0])
+            .unwrap();
+        backend
+            .copy(&self.shift_diff.read().unwrap(), parameters_gradients[1])
+            .unwrap();
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Specifies configuration parameters for a BatchNorm Layer.
+pub struct BatchNormConfig {
+    /// The value added to the variance for numerical stability
+    pub epsilon: f64,
+    /// The weight of the current batch when updating the running statistics
+    pub momentum: f64,
+    /// Normalize with the running statistics instead of the batch statistics
+    pub use_global_stats: bool,
+}
+
+impl Into<LayerType> for BatchNormConfig {
+    fn into(self) -> LayerType {
+        LayerType::BatchNorm(self)
+    }
+}
+
+impl<'a> CapnpWrite<'a> for BatchNormConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the BatchNormConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.reborrow().set_epsilon(self.epsilon);
+        builder.reborrow().set_momentum(self.momentum);
+        builder.reborrow().set_use_global_stats(self.use_global_stats);
+    }
+}
+
+impl<'a> CapnpRead<'a> for BatchNormConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        BatchNormConfig {
+            epsilon: reader.get_epsilon(),
+            momentum: reader.get_momentum(),
+            use_global_stats: reader.get_use_global_stats(),
+        }
+    }
+}
+
+impl ::std::default::Default for BatchNormConfig {
+    fn default() -> BatchNormConfig {
+        BatchNormConfig {
+            epsilon: 1e-5,
+            momentum: 0.1,
+            use_global_stats: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use std::sync::{Arc, RwLock};
+
+    use crate::co::*;
+    use crate::layer::{ILayer, Layer, LayerConfig};
+    use crate::layers::SequentialConfig;
+    use crate::util::{native_backend, write_to_memory, ArcLock};
+    use crate::weight::FillerType;
+
+    use super::{BatchNorm, BatchNormConfig};
+
+    fn new_tensors(count: usize, shape: &[usize]) -> Vec<ArcLock<SharedTensor<f32>>> {
+        (0..count)
+            .map(|_| Arc::new(RwLock::new(SharedTensor::new(&shape))))
+            .collect()
+    }
+
+    #[test]
+    fn batch_norm_reshape_keeps_weights() {
+        let backend = Rc::new(native_backend());
+        let mut layer = BatchNorm::<f32, Backend<Native>>::from_config(&BatchNormConfig::default());
+        let mut input_data = new_tensors(1, &[2, 3]);
+        let mut input_gradient = new_tensors(1, &[2, 3]);
+        let mut output_data = new_tensors(1, &[1]);
+        let mut output_gradient = new_tensors(1, &[1]);
+        let mut weights_data = new_tensors(2, &[1]);
+        let mut weights_gradient = new_tensors(2, &[1]);
+        let mut reshape = |layer: &mut BatchNorm<f32, Backend<Native>>, weights_data: &mut Vec<_>| {
+            layer.reshape(
+                backend.clone(),
+                &mut input_data,
+                &mut input_gradient,
+                weights_data,
+                &mut weights_gradient,
+                &mut output_data,
+                &mut output_gradient,
+            )
+        };
+
+        reshape(&mut layer, &mut weights_data);
+        let scale = |weights_data: &[ArcLock<SharedTensor<f32>>]| {
+            let scale = weights_data[0].read().unwrap();
+            scale
+                .read(native_backend().device())
+                .unwrap()
+                .as_slice::<f32>()
+                .to_vec()
+        };
+        assert_eq!(scale(&weights_data), vec![1.0, 1.0, 1.0]);
+
+        FillerType::fill_constant(&mut weights_data[0].write().unwrap(), 2.0);
+        reshape(&mut layer, &mut weights_data);
+        assert_eq!(scale(&weights_data), vec![2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn batch_norm_global_stats_gradients() {
+        let shape = [2, 3, 2];
+        let mut net_cfg = SequentialConfig::default();
+        net_cfg.add_input("data", &shape);
+        net_cfg.add_layer(LayerConfig::new(
+            "batch_norm",
+            BatchNormConfig {
+                use_global_stats: true,
+                ..BatchNormConfig::default()
+            },
+        ));
+        let mut network = Layer::from_config(Rc::new(native_backend()), &LayerConfig::new("network", net_cfg));
+        let native = native_backend();
+
+        let write = |tensor: &ArcLock<SharedTensor<f32>>, values: &[f32]| {
+            let mut tensor = tensor.write().unwrap();
+            write_to_memory(tensor.write_only(native.device()).unwrap(), values);
+        };
+        let read = |tensor: &ArcLock<SharedTensor<f32>>| -> Vec<f32> {
+            let tensor = tensor.read().unwrap();
+            tensor.read(native.device()).unwrap().as_slice::<f32>().to_vec()
+        };
+        let statistics = network.non_learnable_weights_data();
+        write(&statistics[0], &[0.5, -1.0, 0.0]);
+        write(&statistics[1], &[2.0, 0.5, 1.0]);
+        let weights = network.learnable_weights_data();
+        write(&weights[0], &[1.5, -0.5, 2.0]);
+        write(&weights[1], &[0.1, 0.2, -0.3]);
+
+        let mut input_data: Vec<f32> = (0..12).map(|i| (i as f32 * 0.7).sin()).collect();
+        let output_gradient: Vec<f32> = (0..12).map(|i| (i as f32 * 0.3).cos()).collect();
+
+        // the loss is the output weighted with the output gradient
+        let loss = |network: &mut Layer<Backend<Native>>, input_data: &[f32]| -> f32 {
+            let input = Arc::new(RwLock::new(SharedTensor::<f32>::new(&shape)));
+            write(&input, input_data);
+            let output = read(&network.forward(&[input])[0]);
+            output.iter().zip(&output_gradient).map(|(y, dy)| y * dy).sum()
+        };
+        loss(&mut network, &input_data);
+        let gradient = Arc::new(RwLock::new(SharedTensor::<f32>::new(&shape)));
+        write(&gradient, &output_gradient);
+        let input_gradient = read(&network.backward(&[gradient])[0]);
+        let weights_gradients: Vec<Vec<f32>> = network.learnable_weights_gradients().iter().map(read).collect();
+
+        let h = 1e-2;
+        for k in 0..input_data.len() {
+            input_data[k] += h;
+            let plus = loss(&mut network, &input_data);
+            input_data[k] -= 2.0 * h;
+            let minus = loss(&mut network, &input_data);
+            input_data[k] += h;
+            let numeric = (plus - minus) / (2.0 * h);
+            assert!((input_gradient[k] - numeric).abs() < 1e-2, "input[{}]: {}", k, numeric);
+        }
+        for (w, gradient) in weights_gradients.iter().enumerate() {
+            for (k, &analytic) in gradient.iter().enumerate() {
+                let mut perturbed = |delta: f32| {
+                    let mut values = read(&weights[w]);
+                    values[k] += delta;
+                    write(&weights[w], &values);
+                    loss(&mut network, &input_data)
+                };
+                let plus = perturbed(h);
+                let minus = perturbed(-2.0 * h);
+                perturbed(h);
+                let numeric = (plus - minus) / (2.0 * h);
+                assert!((analytic - numeric).abs() < 1e-2, "{}[{}]: {}", w, k, numeric);
+            }
+        }
+    }
+}
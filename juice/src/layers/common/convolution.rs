@@ -8,7 +8,16 @@
 //!
 //! ## Input Data
 //!
-//! The layer expects the input to be in 4D NCHW format (2 spatial dimensions).
+//! The layer expects the input to be in NCW, NCHW or NCDHW format, i.e. with
+//! 1 (time series, audio), 2 (images) or 3 (video, volumes) spatial dimensions.
+//!
+//! ## Dilation and Groups
+//!
+//! A `dilation` larger than one spreads the filter taps apart, growing the
+//! receptive field without adding weights (as in WaveNet). With `groups` larger
+//! than one the input and output feature maps are split into that many groups
+//! which are convolved independently; setting `groups` to the number of input
+//! feature maps gives a depthwise convolution (as in MobileNet).
 //!
 //! [cs231n_convnets]: https://cs231n.github.io/convolutional-networks
 
@@ -33,6 +42,8 @@ pub struct Convolution<B: conn::Convolution<f32>> {
     filter_shape: Vec<usize>,
     stride: Vec<usize>,
     padding: Vec<usize>,
+    dilation: Vec<usize>,
+    groups: usize,
 
     workspace: Option<ArcLock<SharedTensor<u8>>>,
     convolution_config: Option<Rc<B::CC>>,
@@ -47,6 +58,8 @@ impl<B: conn::Convolution<f32>> Convolution<B> {
             filter_shape: config.filter_shape.clone(),
             stride: config.stride.clone(),
             padding: config.padding.clone(),
+            dilation: config.dilation.clone(),
+            groups: config.groups,
 
             workspace: None,
             convolution_config: None,
@@ -56,12 +69,34 @@ impl<B: conn::Convolution<f32>> Convolution<B> {
     fn calculate_filter_shape(&self, input_shape: &[usize]) -> Vec<usize> {
         let num_spatial_dims = self.num_spatial_dims(input_shape);
         let spatial_dims = self.spatial_filter_dims(num_spatial_dims);
+        if self.groups == 0 || input_shape[1] % self.groups != 0 || self.num_output % self.groups != 0 {
+            panic!(
+                "Convolution groups ({}) must divide both the input ({}) and output ({}) feature maps",
+                self.groups, input_shape[1], self.num_output
+            );
+        }
         let filter_n = self.num_output; // number of output feature maps
-        let filter_c = input_shape[1]; // number of input feature maps
-        let filter_h = spatial_dims[0];
-        let filter_w = spatial_dims[1];
+        let filter_c = input_shape[1] / self.groups; // number of input feature maps per group
 
-        vec![filter_n, filter_c, filter_h, filter_w]
+        let mut filter_shape = vec![filter_n, filter_c];
+        filter_shape.extend(spatial_dims);
+        filter_shape
+    }
+
+    /// Retrieves the dilation for the convolution based on `self.dilation`
+    /// and the number of spatial dimensions.
+    ///
+    /// An empty dilation is treated as no dilation at all.
+    fn dilation_dims(&self, num_spatial_dims: usize) -> Vec<usize> {
+        match self.dilation.len() {
+            0 => vec![1; num_spatial_dims],
+            1 => vec![self.dilation[0]; num_spatial_dims],
+            n if n == num_spatial_dims => self.dilation.clone(),
+            n => panic!(
+                "Must either specify one dilation or one dilation per spatial dimension. Supplied {:?}",
+                n
+            ),
+        }
     }
 
     fn create_filter(&self, input_shape: &[usize]) -> SharedTensor<f32> {
@@ -75,15 +110,23 @@ impl<B: conn::Convolution<f32>> FilterLayer for Convolution<B> {
     /// Calculates the number of spatial dimensions for the convolution operation.
     fn num_spatial_dims(&self, input_shape: &[usize]) -> usize {
         match input_shape.len() {
-            // FIXME this is stupid
+            3 => 1,
             4 => 2,
-            _ => panic!("Only 2D convolutions supported at the moment"),
+            5 => 3,
+            _ => panic!("A convolution layer currently only supports 3D, 4D or 5D input."),
         }
     }
 
     fn calculate_output_shape(&self, input_shape: &[usize]) -> Vec<usize> {
         let num_spatial_dims = self.num_spatial_dims(input_shape);
-        let filter = self.spatial_filter_dims(num_spatial_dims);
+        let dilation = self.dilation_dims(num_spatial_dims);
+        // a dilated filter covers `(f - 1) * d + 1` input elements
+        let filter: Vec<usize> = self
+            .spatial_filter_dims(num_spatial_dims)
+            .iter()
+            .zip(&dilation)
+            .map(|(f, d)| (f - 1) * d + 1)
+            .collect();
         let padding = self.padding_dims(num_spatial_dims);
         let stride = self.stride_dims(num_spatial_dims);
         let mut output_shape = Vec::new();
@@ -142,9 +185,10 @@ impl<B: IBackend + conn::Convolution<f32>> ILayer<B> for Convolution<B> {
             let mut filter = self.create_filter(input_shape);
             let stride = cast_vec_usize_to_i32(self.stride_dims(num_spatial_dims));
             let padding = cast_vec_usize_to_i32(self.padding_dims(num_spatial_dims));
+            let dilation = cast_vec_usize_to_i32(self.dilation_dims(num_spatial_dims));
 
             let config = backend
-                .new_convolution_config(
+                .new_grouped_convolution_config(
                     &inp,
                     &output_data,
                     &mut filter,
@@ -153,6 +197,8 @@ impl<B: IBackend + conn::Convolution<f32>> ILayer<B> for Convolution<B> {
                     conn::ConvBackwardDataAlgo::Auto,
                     &stride,
                     &padding,
+                    &dilation,
+                    self.groups,
                 )
                 .unwrap();
 
@@ -289,6 +335,13 @@ pub struct ConvolutionConfig {
     pub stride: Vec<usize>,
     /// The padding size
     pub padding: Vec<usize>,
+    /// The spacing between filter taps, `1` for an undilated convolution
+    pub dilation: Vec<usize>,
+    /// The number of groups the feature maps are split into
+    ///
+    /// Has to divide both the number of input and output feature maps.
+    /// `1` is a regular convolution, the number of input feature maps a depthwise one.
+    pub groups: usize,
 }
 
 impl Into<LayerType> for ConvolutionConfig {
@@ -321,6 +374,13 @@ impl<'a> CapnpWrite<'a> for ConvolutionConfig {
                 padding.set(i as u32, *dim as u64);
             }
         }
+        {
+            let mut dilation = builder.reborrow().init_dilation(self.dilation.len() as u32);
+            for (i, dim) in self.dilation.iter().enumerate() {
+                dilation.set(i as u32, *dim as u64);
+            }
+        }
+        builder.reborrow().set_groups(self.groups as u64);
     }
 }
 
@@ -345,12 +405,20 @@ impl<'a> CapnpRead<'a> for ConvolutionConfig {
         for i in 0..read_padding.len() {
             padding.push(read_padding.get(i) as usize)
         }
+        let read_dilation = reader.get_dilation().unwrap();
+        let mut dilation = Vec::new();
+        for i in 0..read_dilation.len() {
+            dilation.push(read_dilation.get(i) as usize)
+        }
+        let groups = reader.get_groups() as usize;
 
         ConvolutionConfig {
             num_output: num_output,
             filter_shape: filter_shape,
             stride: stride,
             padding: padding,
+            dilation: dilation,
+            groups: groups,
         }
     }
 }
@@ -371,6 +439,8 @@ mod tests {
             filter_shape: vec![11],
             padding: vec![2],
             stride: vec![4],
+            dilation: vec![1],
+            groups: 1,
         };
         let layer = Convolution::<Backend<Cuda>>::from_config(&cfg);
         let num_spatial_dims = layer.num_spatial_dims(&[1, 3, 224, 224]);
@@ -381,4 +451,58 @@ mod tests {
         assert_eq!(vec![64, 3, 11, 11], layer.calculate_filter_shape(&[1, 3, 224, 224]));
         assert_eq!(vec![1, 64, 55, 55], layer.calculate_output_shape(&[1, 3, 224, 224]));
     }
+    #[test]
+    #[cfg(feature = "cuda")]
+    fn correct_shapes_dilated_grouped() {
+        let cfg = ConvolutionConfig {
+            num_output: 32,
+
+            filter_shape: vec![3],
+            padding: vec![2],
+            stride: vec![1],
+            dilation: vec![2],
+            groups: 16,
+        };
+        let layer = Convolution::<Backend<Cuda>>::from_config(&cfg);
+        assert_eq!(vec![2, 2], layer.dilation_dims(2));
+        assert_eq!(vec![32, 1, 3, 3], layer.calculate_filter_shape(&[1, 16, 28, 28]));
+        assert_eq!(vec![1, 32, 28, 28], layer.calculate_output_shape(&[1, 16, 28, 28]));
+    }
+    #[test]
+    #[cfg(feature = "native")]
+    fn correct_shapes_1d_3d() {
+        let cfg = ConvolutionConfig {
+            num_output: 8,
+
+            filter_shape: vec![5],
+            padding: vec![2],
+            stride: vec![2],
+            dilation: vec![1],
+            groups: 1,
+        };
+        let layer = Convolution::<Backend<Native>>::from_config(&cfg);
+        assert_eq!(1, layer.num_spatial_dims(&[1, 2, 100]));
+        assert_eq!(vec![8, 2, 5], layer.calculate_filter_shape(&[1, 2, 100]));
+        assert_eq!(vec![1, 8, 50], layer.calculate_output_shape(&[1, 2, 100]));
+
+        let cfg = ConvolutionConfig {
+            num_output: 4,
+
+            filter_shape: vec![3, 5, 5],
+            padding: vec![0, 2, 2],
+            stride: vec![1, 2, 2],
+            dilation: vec![1],
+            groups: 1,
+        };
+        let layer = Convolution::<Backend<Native>>::from_config(&cfg);
+        assert_eq!(3, layer.num_spatial_dims(&[1, 3, 16, 32, 32]));
+        assert_eq!(vec![3, 5, 5], layer.spatial_filter_dims(3));
+        assert_eq!(vec![0, 2, 2], layer.padding_dims(3));
+        assert_eq!(vec![1, 2, 2], layer.stride_dims(3));
+        assert_eq!(vec![4, 3, 3, 5, 5], layer.calculate_filter_shape(&[1, 3, 16, 32, 32]));
+        assert_eq!(
+            vec![1, 4, 14, 16, 16],
+            layer.calculate_output_shape(&[1, 3, 16, 32, 32])
+        );
+    }
 }
@@ -0,0 +1,581 @@
+//! Transposed convolution of the input tensor, sometimes called deconvolution.
+//!
+//! Each input element scatters a learnable filter, scaled by its value, into
+//! the output tensor. With a stride larger than one this upsamples the input,
+//! which makes it a learnable alternative to fixed upsampling in autoencoders
+//! and segmentation decoders.
+//!
+//! The layer is the adjoint of a [Convolution](../convolution/index.html) with the same
+//! filter, stride and padding: its forward pass is the gradient of that convolution
+//! with respect to the data and vice versa.
+//!
+//! ## Input Data
+//!
+//! The layer expects the input to be in NCW, NCHW or NCDHW format, i.e. with
+//! 1, 2 or 3 spatial dimensions.
+//!
+//! ## Output Shape
+//!
+//! Every spatial dimension of the output is `(input - 1) * stride - 2 * padding + filter + output_padding`.
+//! As several output sizes are mapped onto the same input size by a strided convolution,
+//! `output_padding` picks which one is produced.
+
+use std::rc::Rc;
+use std::sync::{Arc, RwLock};
+
+use crate::capnp_util::*;
+use crate::co::prelude::*;
+use crate::conn;
+use crate::conn::ConvolutionConfig as connConvolutionConfig;
+use crate::juice_capnp::convolution_transpose_config as capnp_config;
+use crate::layer::*;
+use crate::util::{cast_vec_usize_to_i32, ArcLock};
+use crate::weight::FillerType;
+
+use super::FilterLayer;
+
+#[derive(Debug, Clone)]
+/// Transposed Convolution Layer
+pub struct ConvolutionTranspose<B: conn::Convolution<f32>> {
+    num_output: usize,
+    filter_shape: Vec<usize>,
+    stride: Vec<usize>,
+    padding: Vec<usize>,
+    output_padding: Vec<usize>,
+
+    workspace: Option<ArcLock<SharedTensor<u8>>>,
+    convolution_config: Option<Rc<B::CC>>,
+}
+
+impl<B: conn::Convolution<f32>> ConvolutionTranspose<B> {
+    /// Create a ConvolutionTranspose layer from a ConvolutionTransposeConfig.
+    pub fn from_config(config: &ConvolutionTransposeConfig) -> ConvolutionTranspose<B> {
+        ConvolutionTranspose {
+            num_output: config.num_output,
+
+            filter_shape: config.filter_shape.clone(),
+            stride: config.stride.clone(),
+            padding: config.padding.clone(),
+            output_padding: config.output_padding.clone(),
+
+            workspace: None,
+            convolution_config: None,
+        }
+    }
+
+    /// The filter has the shape of a convolution from the output to the input,
+    /// i.e. `[input feature maps, output feature maps, spatial..]`.
+    fn calculate_filter_shape(&self, input_shape: &[usize]) -> Vec<usize> {
+        let num_spatial_dims = self.num_spatial_dims(input_shape);
+        let mut filter_shape = vec![input_shape[1], self.num_output];
+        filter_shape.extend(self.spatial_filter_dims(num_spatial_dims));
+        filter_shape
+    }
+
+    /// The bias is shared by all positions of an output feature map.
+    fn calculate_bias_shape(&self, input_shape: &[usize]) -> Vec<usize> {
+        let mut bias_shape = vec![1; input_shape.len()];
+        bias_shape[1] = self.num_output;
+        bias_shape
+    }
+
+    /// Retrieves the output padding based on `self.output_padding`
+    /// and the number of spatial dimensions.
+    ///
+    /// An empty output padding is treated as no output padding at all.
+    fn output_padding_dims(&self, num_spatial_dims: usize) -> Vec<usize> {
+        match self.output_padding.len() {
+            0 => vec![0; num_spatial_dims],
+            1 => vec![self.output_padding[0]; num_spatial_dims],
+            n if n == num_spatial_dims => self.output_padding.clone(),
+            n => panic!(
+                "Must either specify one output_padding or one output_padding per spatial dimension. Supplied {:?}",
+                n
+            ),
+        }
+    }
+}
+
+impl<B: conn::Convolution<f32>> FilterLayer for ConvolutionTranspose<B> {
+    /// Calculates the number of spatial dimensions for the convolution operation.
+    fn num_spatial_dims(&self, input_shape: &[usize]) -> usize {
+        match input_shape.len() {
+            3 => 1,
+            4 => 2,
+            5 => 3,
+            _ => panic!("A transposed convolution layer currently only supports 3D, 4D or 5D input."),
+        }
+    }
+
+    fn calculate_output_shape(&self, input_shape: &[usize]) -> Vec<usize> {
+        let num_spatial_dims = self.num_spatial_dims(input_shape);
+        let filter = self.spatial_filter_dims(num_spatial_dims);
+        let padding = self.padding_dims(num_spatial_dims);
+        let stride = self.stride_dims(num_spatial_dims);
+        let output_padding = self.output_padding_dims(num_spatial_dims);
+
+        let mut output_shape = vec![input_shape[0], self.num_output];
+        for i in 0..num_spatial_dims {
+            if output_padding[i] >= stride[i] {
+                panic!(
+                    "The output_padding ({}) has to be smaller than the stride ({})",
+                    output_padding[i], stride[i]
+                );
+            }
+            let full = (input_shape[2 + i] - 1) * stride[i] + filter[i] + output_padding[i];
+            if full <= 2 * padding[i] {
+                panic!(
+                    "The padding ({}) removes the whole output of spatial dimension {}",
+                    padding[i], i
+                );
+            }
+            output_shape.push(full - 2 * padding[i]);
+        }
+
+        output_shape
+    }
+
+    fn filter_shape(&self) -> &[usize] {
+        &self.filter_shape
+    }
+
+    fn stride(&self) -> &[usize] {
+        &self.stride
+    }
+
+    fn padding(&self) -> &[usize] {
+        &self.padding
+    }
+}
+
+impl<B: IBackend + conn::Convolution<f32> + conn::ArithmeticPointwise<f32> + conn::Reduction<f32>> ILayer<B>
+    for ConvolutionTranspose<B>
+{
+    impl_ilayer_common!();
+
+    fn auto_weight_blobs(&self) -> bool {
+        true
+    }
+
+    fn reshape(
+        &mut self,
+        backend: Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let inp = input_data[0].read().unwrap();
+        let mut output_data = output_data[0].write().unwrap();
+        let mut output_gradient = output_gradient[0].write().unwrap();
+        let input_shape = inp.desc();
+        let output_shape = self.calculate_output_shape(input_shape);
+        output_data.resize(&output_shape).unwrap();
+        output_gradient.resize(&output_shape).unwrap();
+
+        let num_spatial_dims = self.num_spatial_dims(input_shape);
+        let filter = SharedTensor::<f32>::new(&self.calculate_filter_shape(input_shape));
+        let stride = cast_vec_usize_to_i32(self.stride_dims(num_spatial_dims));
+        let padding = cast_vec_usize_to_i32(self.padding_dims(num_spatial_dims));
+
+        // the convolution this layer is the transpose of maps the output onto the input
+        let config = backend
+            .new_convolution_config(
+                &output_data,
+                &inp,
+                &filter,
+                conn::ConvForwardAlgo::Auto,
+                conn::ConvBackwardFilterAlgo::Auto,
+                conn::ConvBackwardDataAlgo::Auto,
+                &stride,
+                &padding,
+            )
+            .unwrap();
+
+        let bias_shape = self.calculate_bias_shape(input_shape);
+        weights_data[0].write().unwrap().resize(filter.desc()).unwrap();
+        weights_data[1].write().unwrap().resize(&bias_shape).unwrap();
+
+        let filler = FillerType::Glorot {
+            input_size: inp.desc().size(),
+            output_size: output_shape.size(),
+        };
+        filler.fill(&mut weights_data[0].write().unwrap());
+        FillerType::Constant { value: 0.0 }.fill(&mut weights_data[1].write().unwrap());
+
+        weights_gradient[0].write().unwrap().resize(filter.desc()).unwrap();
+        weights_gradient[1].write().unwrap().resize(&bias_shape).unwrap();
+        self.convolution_config = Some(Rc::new(config));
+    }
+
+    fn resize_shared_workspace(
+        &mut self,
+        backend: Rc<B>,
+        workspace: Option<ArcLock<SharedTensor<u8>>>,
+    ) -> Option<ArcLock<SharedTensor<u8>>> {
+        let required_size = self.convolution_config.as_ref().unwrap().workspace_size();
+        let new_workspace = if workspace.is_none() {
+            Arc::new(RwLock::new(SharedTensor::<u8>::new(&[required_size])))
+        } else {
+            let old_workspace = workspace.as_ref().unwrap().clone();
+            let old_workspace_size = old_workspace.read().unwrap().capacity();
+            if old_workspace_size < required_size {
+                Arc::new(RwLock::new(SharedTensor::<u8>::new(&[required_size])))
+            } else {
+                workspace.unwrap()
+            }
+        };
+
+        self.workspace = Some(new_workspace.clone());
+        Some(new_workspace)
+    }
+}
+
+impl<B: IBackend + conn::Convolution<f32> + conn::ArithmeticPointwise<f32> + conn::Reduction<f32>> ComputeOutput<f32, B>
+    for ConvolutionTranspose<B>
+{
+    fn compute_output(
+        &self,
+        backend: &B,
+        weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        let conv_config = self.convolution_config.as_ref().unwrap();
+        let mut workspace = self.workspace.as_ref().unwrap().write().unwrap();
+        backend
+            .convolution_grad_data(weights[0], input_data[0], output_data[0], &mut workspace, conv_config)
+            .unwrap();
+        backend
+            .binary_operation_pointwise(conn::BinaryOperation::Add, output_data[0], weights[1])
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Convolution<f32> + conn::ArithmeticPointwise<f32> + conn::Reduction<f32>>
+    ComputeInputGradient<f32, B> for ConvolutionTranspose<B>
+{
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        _output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        let conv_config = self.convolution_config.as_ref().unwrap();
+        let mut workspace = self.workspace.as_ref().unwrap().write().unwrap();
+        // compute gradient w.r.t. input
+        backend
+            .convolution(
+                weights_data[0],
+                output_gradients[0],
+                input_gradients[0],
+                &mut workspace,
+                conv_config,
+            )
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Convolution<f32> + conn::ArithmeticPointwise<f32> + conn::Reduction<f32>>
+    ComputeParametersGradient<f32, B> for ConvolutionTranspose<B>
+{
+    fn compute_parameters_gradient(
+        &self,
+        backend: &B,
+        _output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        parameters_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        let conv_config = self.convolution_config.as_ref().unwrap();
+        let mut workspace = self.workspace.as_ref().unwrap().write().unwrap();
+        // compute gradient w.r.t. filter, the roles of input and output are swapped
+        backend
+            .convolution_grad_filter(
+                output_gradients[0],
+                input_data[0],
+                parameters_gradients[0],
+                &mut workspace,
+                conv_config,
+            )
+            .unwrap();
+
+        // compute gradient w.r.t. bias by summing over everything but the feature maps
+        let axes: Vec<usize> = (0..output_gradients[0].desc().len())
+            .filter(|&axis| axis != 1)
+            .collect();
+        backend
+            .reduce(
+                conn::ReductionOperation::Sum,
+                output_gradients[0],
+                &axes,
+                true,
+                parameters_gradients[1],
+            )
+            .unwrap();
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Specifies configuration parameters for a ConvolutionTranspose Layer.
+pub struct ConvolutionTransposeConfig {
+    /// The number of output feature maps
+    pub num_output: usize,
+    /// The size of the kernel
+    pub filter_shape: Vec<usize>,
+    /// The stride size, i.e. the upsampling factor
+    pub stride: Vec<usize>,
+    /// The padding size, removed from both sides of the output
+    pub padding: Vec<usize>,
+    /// The size added to one side of the output, has to be smaller than the stride
+    pub output_padding: Vec<usize>,
+}
+
+impl Into<LayerType> for ConvolutionTransposeConfig {
+    fn into(self) -> LayerType {
+        LayerType::ConvolutionTranspose(self)
+    }
+}
+
+impl<'a> CapnpWrite<'a> for ConvolutionTransposeConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the ConvolutionTransposeConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.reborrow().set_num_output(self.num_output as u64);
+        {
+            let mut filter_shape = builder.reborrow().init_filter_shape(self.filter_shape.len() as u32);
+            for (i, dim) in self.filter_shape.iter().enumerate() {
+                filter_shape.set(i as u32, *dim as u64);
+            }
+        }
+        {
+            let mut stride = builder.reborrow().init_stride(self.stride.len() as u32);
+            for (i, dim) in self.stride.iter().enumerate() {
+                stride.set(i as u32, *dim as u64);
+            }
+        }
+        {
+            let mut padding = builder.reborrow().init_padding(self.padding.len() as u32);
+            for (i, dim) in self.padding.iter().enumerate() {
+                padding.set(i as u32, *dim as u64);
+            }
+        }
+        {
+            let mut output_padding = builder.reborrow().init_output_padding(self.output_padding.len() as u32);
+            for (i, dim) in self.output_padding.iter().enumerate() {
+                output_padding.set(i as u32, *dim as u64);
+            }
+        }
+    }
+}
+
+impl<'a> CapnpRead<'a> for ConvolutionTransposeConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        let num_output = reader.get_num_output() as usize;
+
+        let read_filter_shape = reader.get_filter_shape().unwrap();
+        let mut filter_shape = Vec::new();
+        for i in 0..read_filter_shape.len() {
+            filter_shape.push(read_filter_shape.get(i) as usize)
+        }
+        let read_stride = reader.get_stride().unwrap();
+        let mut stride = Vec::new();
+        for i in 0..read_stride.len() {
+            stride.push(read_stride.get(i) as usize)
+        }
+        let read_padding = reader.get_padding().unwrap();
+        let mut padding = Vec::new();
+        for i in 0..read_padding.len() {
+            padding.push(read_padding.get(i) as usize)
+        }
+        let read_output_padding = reader.get_output_padding().unwrap();
+        let mut output_padding = Vec::new();
+        for i in 0..read_output_padding.len() {
+            output_padding.push(read_output_padding.get(i) as usize)
+        }
+
+        ConvolutionTransposeConfig {
+            num_output: num_output,
+            filter_shape: filter_shape,
+            stride: stride,
+            padding: padding,
+            output_padding: output_padding,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use std::sync::{Arc, RwLock};
+
+    use crate::co::*;
+    use crate::layer::{Layer, LayerConfig};
+    use crate::layers::SequentialConfig;
+    use crate::util::{native_backend, write_to_memory, ArcLock};
+
+    use super::super::FilterLayer;
+    use super::{ConvolutionTranspose, ConvolutionTransposeConfig};
+
+    /// A network of a single 3x3 transposed convolution with stride 2 and padding 1,
+    /// which maps a 2x2 input onto a 4x4 output.
+    #[cfg(feature = "native")]
+    fn strided_network() -> Layer<Backend<Native>> {
+        let mut net_cfg = SequentialConfig::default();
+        net_cfg.add_input("data", &[1, 1, 2, 2]);
+        net_cfg.add_layer(LayerConfig::new(
+            "conv_transpose",
+            ConvolutionTransposeConfig {
+                num_output: 1,
+                filter_shape: vec![3],
+                padding: vec![1],
+                stride: vec![2],
+                output_padding: vec![1],
+            },
+        ));
+        let network = Layer::from_config(Rc::new(native_backend()), &LayerConfig::new("network", net_cfg));
+        let weights = network.learnable_weights_data();
+        write(&weights[0], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+        write(&weights[1], &[0.5]);
+        network
+    }
+
+    #[cfg(feature = "native")]
+    fn write(tensor: &ArcLock<SharedTensor<f32>>, values: &[f32]) {
+        let mut tensor = tensor.write().unwrap();
+        write_to_memory(tensor.write_only(native_backend().device()).unwrap(), values);
+    }
+
+    #[cfg(feature = "native")]
+    fn read(tensor: &ArcLock<SharedTensor<f32>>) -> Vec<f32> {
+        let tensor = tensor.read().unwrap();
+        tensor
+            .read(native_backend().device())
+            .unwrap()
+            .as_slice::<f32>()
+            .to_vec()
+    }
+
+    #[cfg(feature = "native")]
+    fn forward(network: &mut Layer<Backend<Native>>, input: &[f32]) -> Vec<f32> {
+        let input_tensor = Arc::new(RwLock::new(SharedTensor::new(&[1, 1, 2, 2])));
+        write(&input_tensor, input);
+        read(&network.forward(&[input_tensor])[0])
+    }
+
+    #[test]
+    #[cfg(feature = "native")]
+    fn strided_padded_output() {
+        let mut network = strided_network();
+        // every input value scatters the filter onto the output with a step of 2,
+        // then the border of 1 is cropped
+        let expected = [
+            [5.5, 14.5, 10.5, 12.5],
+            [14.5, 36.5, 24.5, 30.5],
+            [15.5, 34.5, 20.5, 24.5],
+            [24.5, 55.5, 32.5, 36.5],
+        ];
+        let output = forward(&mut network, &[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(expected.concat(), output);
+    }
+
+    #[test]
+    #[cfg(feature = "native")]
+    fn strided_padded_gradients() {
+        let mut network = strided_network();
+        let mut input = vec![1.0, -2.0, 0.5, 3.0];
+        let output_gradient: Vec<f32> = (0..16).map(|i| (i as f32 * 0.5).sin()).collect();
+
+        // the loss is the output weighted with the output gradient
+        let loss = |network: &mut Layer<Backend<Native>>, input: &[f32]| -> f32 {
+            let output = forward(network, input);
+            output.iter().zip(&output_gradient).map(|(y, dy)| y * dy).sum()
+        };
+        loss(&mut network, &input);
+        let gradient = Arc::new(RwLock::new(SharedTensor::new(&[1, 1, 4, 4])));
+        write(&gradient, &output_gradient);
+        let input_gradient = read(&network.backward(&[gradient])[0]);
+        let weights = network.learnable_weights_data();
+        let weights_gradients: Vec<Vec<f32>> = network.learnable_weights_gradients().iter().map(read).collect();
+        // the bias is added to every output
+        assert!((weights_gradients[1][0] - output_gradient.iter().sum::<f32>()).abs() < 1e-4);
+
+        let h = 1e-2;
+        for k in 0..input.len() {
+            input[k] += h;
+            let plus = loss(&mut network, &input);
+            input[k] -= 2.0 * h;
+            let minus = loss(&mut network, &input);
+            input[k] += h;
+            let numeric = (plus - minus) / (2.0 * h);
+            assert!((input_gradient[k] - numeric).abs() < 1e-2, "input[{}]: {}", k, numeric);
+        }
+        for (w, gradient) in weights_gradients.iter().enumerate() {
+            for (k, &analytic) in gradient.iter().enumerate() {
+                let mut perturbed = |delta: f32| {
+                    let mut values = read(&weights[w]);
+                    values[k] += delta;
+                    write(&weights[w], &values);
+                    loss(&mut network, &input)
+                };
+                let plus = perturbed(h);
+                let minus = perturbed(-2.0 * h);
+                perturbed(h);
+                let numeric = (plus - minus) / (2.0 * h);
+                assert!((analytic - numeric).abs() < 1e-2, "{}[{}]: {}", w, k, numeric);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "native")]
+    fn correct_shapes() {
+        let cfg = ConvolutionTransposeConfig {
+            num_output: 16,
+
+            filter_shape: vec![3],
+            padding: vec![1],
+            stride: vec![2],
+            output_padding: vec![1],
+        };
+        let layer = ConvolutionTranspose::<Backend<Native>>::from_config(&cfg);
+        assert_eq!(vec![32, 16, 3, 3], layer.calculate_filter_shape(&[1, 32, 14, 14]));
+        assert_eq!(vec![1, 16, 1, 1], layer.calculate_bias_shape(&[1, 32, 14, 14]));
+        assert_eq!(vec![1, 16, 28, 28], layer.calculate_output_shape(&[1, 32, 14, 14]));
+
+        let cfg = ConvolutionTransposeConfig {
+            num_output: 1,
+
+            filter_shape: vec![4],
+            padding: vec![0],
+            stride: vec![4],
+            output_padding: vec![],
+        };
+        let layer = ConvolutionTranspose::<Backend<Native>>::from_config(&cfg);
+        assert_eq!(vec![2, 1, 40], layer.calculate_output_shape(&[2, 8, 10]));
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "native")]
+    fn output_padding_not_below_stride() {
+        let cfg = ConvolutionTransposeConfig {
+            num_output: 16,
+
+            filter_shape: vec![3],
+            padding: vec![1],
+            stride: vec![2],
+            output_padding: vec![2],
+        };
+        let layer = ConvolutionTranspose::<Backend<Native>>::from_config(&cfg);
+        layer.calculate_output_shape(&[1, 32, 14, 14]);
+    }
+}
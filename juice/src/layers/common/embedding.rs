@@ -0,0 +1,277 @@
+//! Looks up a learnable vector for every index of the input.
+//!
+//! The layer holds a table of `vocab_size` vectors of `embedding_dim` values each and
+//! replaces every index `i` of the input with the `i`-th vector of the table, which turns
+//! token indices into dense features e.g. for the RNN layer.
+//!
+//! The gradient w.r.t. the table is the gradient w.r.t. the output scattered back onto
+//! the selected rows, a row that was selected several times adds up its gradients.
+//! The indices themselves are not differentiable, so no gradient w.r.t. the input is computed.
+//!
+//! ## Input Data
+//!
+//! The input holds the indices as integral `f32` values in any shape, e.g. `[N, T]` for
+//! a batch of `N` sequences of `T` tokens each.
+//!
+//! ## Output Shape
+//!
+//! The output is of the shape of the input followed by `embedding_dim`, e.g. `[N, T, D]`.
+//!
+//! ## Padding
+//!
+//! If a `padding_idx` is given, its row of the table starts out as zeros and receives no
+//! gradient, so padded positions of a sequence always map to a vector of zeros.
+
+use crate::capnp_util::*;
+use crate::co::prelude::*;
+use crate::conn;
+use crate::juice_capnp::embedding_config as capnp_config;
+use crate::layer::*;
+use crate::util::{native_backend, ArcLock};
+use crate::weight::FillerType;
+
+#[derive(Debug, Clone)]
+/// [Embedding](./index.html) Layer
+pub struct Embedding {
+    vocab_size: usize,
+    embedding_dim: usize,
+    padding_idx: Option<usize>,
+    init: EmbeddingInit,
+}
+
+impl Embedding {
+    /// Create an Embedding layer from an EmbeddingConfig.
+    pub fn from_config(config: &EmbeddingConfig) -> Embedding {
+        Embedding {
+            vocab_size: config.vocab_size,
+            embedding_dim: config.embedding_dim,
+            padding_idx: config.padding_idx,
+            init: config.init,
+        }
+    }
+
+    fn calculate_output_shape(&self, input_shape: &[usize]) -> Vec<usize> {
+        let mut output_shape = input_shape.to_vec();
+        output_shape.push(self.embedding_dim);
+        output_shape
+    }
+
+    fn calculate_weight_shape(&self) -> Vec<usize> {
+        if let Some(padding_idx) = self.padding_idx {
+            if padding_idx >= self.vocab_size {
+                panic!(
+                    "The padding_idx ({}) has to be smaller than the vocab_size ({})",
+                    padding_idx, self.vocab_size
+                );
+            }
+        }
+        vec![self.vocab_size, self.embedding_dim]
+    }
+}
+
+impl<B: IBackend + conn::Embedding<f32>> ILayer<B> for Embedding {
+    impl_ilayer_common!();
+
+    fn auto_weight_blobs(&self) -> bool {
+        true
+    }
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let inp = input_data[0].read().unwrap();
+        let input_shape = inp.desc();
+        input_gradient[0].write().unwrap().resize(input_shape).unwrap();
+        let output_shape = self.calculate_output_shape(input_shape);
+        output_data[0].write().unwrap().resize(&output_shape).unwrap();
+        output_gradient[0].write().unwrap().resize(&output_shape).unwrap();
+
+        let weight_shape = self.calculate_weight_shape();
+        if let Some(weight) = weights_data.get(0) {
+            let mut table = weight.write().unwrap();
+            table.resize(&weight_shape).unwrap();
+            let filler = match self.init {
+                EmbeddingInit::Glorot => FillerType::Glorot {
+                    input_size: self.vocab_size,
+                    output_size: self.embedding_dim,
+                },
+                EmbeddingInit::Normal { std } => FillerType::Gaussian { mean: 0.0, std },
+            };
+            filler.fill(&mut table);
+            if let Some(padding_idx) = self.padding_idx {
+                let native = native_backend();
+                let values = table.read_write(native.device()).unwrap().as_mut_slice::<f32>();
+                let row = padding_idx * self.embedding_dim;
+                for value in &mut values[row..row + self.embedding_dim] {
+                    *value = 0.0;
+                }
+            }
+        }
+        if let Some(weight) = weights_gradient.get(0) {
+            weight.write().unwrap().resize(&weight_shape).unwrap();
+        }
+        // the second weight is not used by the layer
+        for weight in weights_data.iter().chain(weights_gradient.iter()).skip(1) {
+            weight.write().unwrap().resize(&[1]).unwrap();
+        }
+        if let Some(bias) = weights_data.get(1) {
+            FillerType::fill_constant(&mut bias.write().unwrap(), 0.0);
+        }
+    }
+}
+
+impl<B: IBackend + conn::Embedding<f32>> ComputeOutput<f32, B> for Embedding {
+    fn compute_output(
+        &self,
+        backend: &B,
+        weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        backend.embedding(weights[0], input_data[0], output_data[0]).unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Embedding<f32>> ComputeInputGradient<f32, B> for Embedding {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        // the indices are not differentiable
+    }
+}
+
+impl<B: IBackend + conn::Embedding<f32>> ComputeParametersGradient<f32, B> for Embedding {
+    fn compute_parameters_gradient(
+        &self,
+        backend: &B,
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        parameters_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        backend
+            .embedding_grad(
+                input_data[0],
+                output_gradients[0],
+                parameters_gradients[0],
+                self.padding_idx,
+            )
+            .unwrap();
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// The initialization of the table of an Embedding Layer.
+pub enum EmbeddingInit {
+    /// Uniform values scaled by the vocabulary size and the embedding dimension, see
+    /// [FillerType::Glorot](../../../weight/enum.FillerType.html#variant.Glorot).
+    Glorot,
+    /// Values drawn from a normal distribution with zero mean.
+    Normal {
+        /// The standard deviation of the distribution.
+        std: f32,
+    },
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Specifies configuration parameters for an Embedding Layer.
+pub struct EmbeddingConfig {
+    /// The number of rows of the table, every index has to be smaller
+    pub vocab_size: usize,
+    /// The size of every vector of the table
+    pub embedding_dim: usize,
+    /// The index whose vector stays at zero
+    pub padding_idx: Option<usize>,
+    /// The initialization of the table
+    pub init: EmbeddingInit,
+}
+
+impl Into<LayerType> for EmbeddingConfig {
+    fn into(self) -> LayerType {
+        LayerType::Embedding(self)
+    }
+}
+
+impl<'a> CapnpWrite<'a> for EmbeddingConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the EmbeddingConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.reborrow().set_vocab_size(self.vocab_size as u64);
+        builder.reborrow().set_embedding_dim(self.embedding_dim as u64);
+        match self.padding_idx {
+            Some(index) => builder.reborrow().init_padding_idx().set_index(index as u64),
+            None => builder.reborrow().init_padding_idx().set_none(()),
+        }
+        match self.init {
+            EmbeddingInit::Glorot => builder.reborrow().init_weight_init().set_glorot(()),
+            EmbeddingInit::Normal { std } => builder.reborrow().init_weight_init().set_normal(std),
+        }
+    }
+}
+
+impl<'a> CapnpRead<'a> for EmbeddingConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        let padding_idx = match reader.get_padding_idx().which().unwrap() {
+            capnp_config::padding_idx::Which::Index(index) => Some(index as usize),
+            capnp_config::padding_idx::Which::None(()) => None,
+        };
+        let init = match reader.get_weight_init().which().unwrap() {
+            capnp_config::weight_init::Which::Glorot(()) => EmbeddingInit::Glorot,
+            capnp_config::weight_init::Which::Normal(std) => EmbeddingInit::Normal { std },
+        };
+
+        EmbeddingConfig {
+            vocab_size: reader.get_vocab_size() as usize,
+            embedding_dim: reader.get_embedding_dim() as usize,
+            padding_idx,
+            init,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Embedding, EmbeddingConfig, EmbeddingInit};
+
+    #[test]
+    fn correct_shapes() {
+        let cfg = EmbeddingConfig {
+            vocab_size: 100,
+            embedding_dim: 8,
+            padding_idx: Some(0),
+            init: EmbeddingInit::Glorot,
+        };
+        let layer = Embedding::from_config(&cfg);
+        assert_eq!(vec![100, 8], layer.calculate_weight_shape());
+        assert_eq!(vec![4, 12, 8], layer.calculate_output_shape(&[4, 12]));
+        assert_eq!(vec![5, 8], layer.calculate_output_shape(&[5]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn padding_idx_within_vocab() {
+        let cfg = EmbeddingConfig {
+            vocab_size: 100,
+            embedding_dim: 8,
+            padding_idx: Some(100),
+            init: EmbeddingInit::Normal { std: 1.0 },
+        };
+        Embedding::from_config(&cfg).calculate_weight_shape();
+    }
+}
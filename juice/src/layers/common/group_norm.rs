@@ -0,0 +1,234 @@
+//! Applies Group Normalization to the input data `x`.
+//!
+//! The channels are split into groups, and every group of every sample is
+//! normalized to zero mean and unit variance before the channels are scaled
+//! and shifted by learnable weights, as introduced by [Wu and He][gn]:
+//!
+//! `y = scale * (x - mean) / sqrt(variance + epsilon) + shift`
+//!
+//! Like [LayerNorm](../layer_norm/index.html) the statistics do not depend on
+//! the other samples of the batch, which makes it suitable for small batches.
+//!
+//! ## Input Data
+//!
+//! The layer expects the channels to be the second dimension of the input,
+//! e.g. `[N, C]` or NCHW, with `C` being a multiple of `groups`.
+//!
+//! [gn]: https://arxiv.org/abs/1803.08494
+
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::conn;
+use crate::juice_capnp::group_norm_config as capnp_config;
+use crate::layer::*;
+use crate::util::ArcLock;
+use crate::weight::FillerType;
+use std::rc::Rc;
+use std::sync::{Arc, RwLock};
+
+#[derive(Debug, Clone)]
+/// [GroupNorm](./index.html) Layer
+pub struct GroupNorm<T, B: conn::GroupNorm<T>> {
+    groups: usize,
+    epsilon: f64,
+
+    saved_mean: ArcLock<SharedTensor<T>>,
+    saved_inv_variance: ArcLock<SharedTensor<T>>,
+    scale_diff: ArcLock<SharedTensor<T>>,
+    shift_diff: ArcLock<SharedTensor<T>>,
+
+    gn_config: Option<Rc<B::CGN>>,
+}
+
+impl<T, B: conn::GroupNorm<T>> GroupNorm<T, B> {
+    /// Create a GroupNorm layer from a GroupNormConfig.
+    pub fn from_config(config: &GroupNormConfig) -> GroupNorm<T, B> {
+        let new_tensor = || Arc::new(RwLock::new(SharedTensor::new(&[1])));
+        GroupNorm {
+            groups: config.groups,
+            epsilon: config.epsilon,
+
+            saved_mean: new_tensor(),
+            saved_inv_variance: new_tensor(),
+            scale_diff: new_tensor(),
+            shift_diff: new_tensor(),
+
+            gn_config: None,
+        }
+    }
+}
+
+impl<B: IBackend + conn::GroupNorm<f32> + crate::coblas::plugin::Copy<f32>> ILayer<B> for GroupNorm<f32, B> {
+    impl_ilayer_common!();
+
+    fn auto_weight_blobs(&self) -> bool {
+        true
+    }
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let inp = input_data[0].read().unwrap();
+        let input_desc = inp.desc();
+        input_gradient[0].write().unwrap().resize(input_desc).unwrap();
+        output_data[0].write().unwrap().resize(input_desc).unwrap();
+        output_gradient[0].write().unwrap().resize(input_desc).unwrap();
+
+        if self.groups == 0 || input_desc.len() < 2 || input_desc[1] % self.groups != 0 {
+            panic!(
+                "GroupNorm can not split the channels of an input of shape {:?} into {} groups",
+                input_desc, self.groups
+            );
+        }
+        let param_shape = vec![input_desc[1]];
+        let stats_shape = vec![input_desc[0], self.groups];
+        // scale and shift start out as identity
+        for (weight_id, value) in [1f32, 0f32].iter().enumerate() {
+            if let Some(weight) = weights_data.get(weight_id) {
+                weight.write().unwrap().resize(&param_shape).unwrap();
+                FillerType::fill_constant(&mut weight.write().unwrap(), *value);
+            }
+            if let Some(weight) = weights_gradient.get(weight_id) {
+                weight.write().unwrap().resize(&param_shape).unwrap();
+            }
+        }
+        self.saved_mean.write().unwrap().resize(&stats_shape).unwrap();
+        self.saved_inv_variance.write().unwrap().resize(&stats_shape).unwrap();
+        self.scale_diff.write().unwrap().resize(&param_shape).unwrap();
+        self.shift_diff.write().unwrap().resize(&param_shape).unwrap();
+
+        let config = backend
+            .new_group_normalization_config(self.groups, self.epsilon)
+            .unwrap();
+        self.gn_config = Some(Rc::new(config));
+    }
+}
+
+impl<B: IBackend + conn::GroupNorm<f32> + crate::coblas::plugin::Copy<f32>> ComputeOutput<f32, B>
+    for GroupNorm<f32, B>
+{
+    fn compute_output(
+        &self,
+        backend: &B,
+        weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        let config = self.gn_config.as_ref().unwrap();
+        backend
+            .group_normalization(
+                input_data[0],
+                weights[0],
+                weights[1],
+                &mut self.saved_mean.write().unwrap(),
+                &mut self.saved_inv_variance.write().unwrap(),
+                output_data[0],
+                &*config,
+            )
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::GroupNorm<f32> + crate::coblas::plugin::Copy<f32>> ComputeInputGradient<f32, B>
+    for GroupNorm<f32, B>
+{
+    /// Also computes the gradients w.r.t. scale and shift, since they need
+    /// the scale, which is not available in `compute_parameters_gradient`.
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        let config = self.gn_config.as_ref().unwrap();
+        backend
+            .group_normalization_grad(
+                input_data[0],
+                output_gradients[0],
+                weights_data[0],
+                &self.saved_mean.read().unwrap(),
+                &self.saved_inv_variance.read().unwrap(),
+                input_gradients[0],
+                &mut self.scale_diff.write().unwrap(),
+                &mut self.shift_diff.write().unwrap(),
+                &*config,
+            )
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::GroupNorm<f32> + crate::coblas::plugin::Copy<f32>> ComputeParametersGradient<f32, B>
+    for GroupNorm<f32, B>
+{
+    fn compute_parameters_gradient(
+        &self,
+        backend: &B,
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        parameters_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        // computed together with the input gradient
+        backend
+            .copy(&self.scale_diff.read().unwrap(), parameters_gradients[0])
+            .unwrap();
+        backend
+            .copy(&self.shift_diff.read().unwrap(), parameters_gradients[1])
+            .unwrap();
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Specifies configuration parameters for a GroupNorm Layer.
+pub struct GroupNormConfig {
+    /// The number of groups the channels are split into
+    pub groups: usize,
+    /// The value added to the variance for numerical stability
+    pub epsilon: f64,
+}
+
+impl Into<LayerType> for GroupNormConfig {
+    fn into(self) -> LayerType {
+        LayerType::GroupNorm(self)
+    }
+}
+
+impl<'a> CapnpWrite<'a> for GroupNormConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the GroupNormConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.reborrow().set_groups(self.groups as u64);
+        builder.reborrow().set_epsilon(self.epsilon);
+    }
+}
+
+impl<'a> CapnpRead<'a> for GroupNormConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        GroupNormConfig {
+            groups: reader.get_groups() as usize,
+            epsilon: reader.get_epsilon(),
+        }
+    }
+}
+
+impl ::std::default::Default for GroupNormConfig {
+    fn default() -> GroupNormConfig {
+        GroupNormConfig {
+            groups: 32,
+            epsilon: 1e-5,
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Applies Layer Normalization to the input data `x`.
+//!
+//! Every sample is normalized to zero mean and unit variance over its trailing
+//! dimensions and then scaled and shifted by learnable weights, as introduced by
+//! [Ba, Kiros and Hinton][ln]:
+//!
+//! `y = scale * (x - mean) / sqrt(variance + epsilon) + shift`
+//!
+//! Unlike [BatchNorm](../batch_norm/index.html) the statistics do not depend on
+//! the other samples of the batch, so training and inference behave the same.
+//!
+//! ## Input Data
+//!
+//! The last `normalized_dims` dimensions of the input are normalized, e.g. the
+//! features of an input `[N, D]` or the features of every step of `[N, T, D]`.
+//! The scale and shift have the shape of those dimensions.
+//!
+//! [ln]: https://arxiv.org/abs/1607.06450
+
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::conn;
+use crate::juice_capnp::layer_norm_config as capnp_config;
+use crate::layer::*;
+use crate::util::ArcLock;
+use crate::weight::FillerType;
+use std::rc::Rc;
+use std::sync::{Arc, RwLock};
+
+#[derive(Debug, Clone)]
+/// [LayerNorm](./index.html) Layer
+pub struct LayerNorm<T, B: conn::LayerNorm<T>> {
+    normalized_dims: usize,
+    epsilon: f64,
+
+    saved_mean: ArcLock<SharedTensor<T>>,
+    saved_inv_variance: ArcLock<SharedTensor<T>>,
+    scale_diff: ArcLock<SharedTensor<T>>,
+    shift_diff: ArcLock<SharedTensor<T>>,
+
+    ln_config: Option<Rc<B::CLN>>,
+}
+
+impl<T, B: conn::LayerNorm<T>> LayerNorm<T, B> {
+    /// Create a LayerNorm layer from a LayerNormConfig.
+    pub fn from_config(config: &LayerNormConfig) -> LayerNorm<T, B> {
+        let new_tensor = || Arc::new(RwLock::new(SharedTensor::new(&[1])));
+        LayerNorm {
+            normalized_dims: config.normalized_dims,
+            epsilon: config.epsilon,
+
+            saved_mean: new_tensor(),
+            saved_inv_variance: new_tensor(),
+            scale_diff: new_tensor(),
+            shift_diff: new_tensor(),
+
+            ln_config: None,
+        }
+    }
+}
+
+impl<B: IBackend + conn::LayerNorm<f32> + crate::coblas::plugin::Copy<f32>> ILayer<B> for LayerNorm<f32, B> {
+    impl_ilayer_common!();
+
+    fn auto_weight_blobs(&self) -> bool {
+        true
+    }
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let inp = input_data[0].read().unwrap();
+        let input_desc = inp.desc();
+        input_gradient[0].write().unwrap().resize(input_desc).unwrap();
+        output_data[0].write().unwrap().resize(input_desc).unwrap();
+        output_gradient[0].write().unwrap().resize(input_desc).unwrap();
+
+        if self.normalized_dims == 0 || self.normalized_dims > input_desc.len() {
+            panic!(
+                "LayerNorm can not normalize {} dimensions of an input of shape {:?}",
+                self.normalized_dims, input_desc
+            );
+        }
+        let param_shape = input_desc[input_desc.len() - self.normalized_dims..].to_vec();
+        let samples = input_desc[..input_desc.len() - self.normalized_dims]
+            .iter()
+            .product::<usize>();
+        // scale and shift start out as identity
+        for (weight_id, value) in [1f32, 0f32].iter().enumerate() {
+            if let Some(weight) = weights_data.get(weight_id) {
+                weight.write().unwrap().resize(&param_shape).unwrap();
+                FillerType::fill_constant(&mut weight.write().unwrap(), *value);
+            }
+            if let Some(weight) = weights_gradient.get(weight_id) {
+                weight.write().unwrap().resize(&param_shape).unwrap();
+            }
+        }
+        self.saved_mean.write().unwrap().resize(&[samples]).unwrap();
+        self.saved_inv_variance.write().unwrap().resize(&[samples]).unwrap();
+        self.scale_diff.write().unwrap().resize(&param_shape).unwrap();
+        self.shift_diff.write().unwrap().resize(&param_shape).unwrap();
+
+        let config = backend.new_layer_normalization_config(self.epsilon).unwrap();
+        self.ln_config = Some(Rc::new(config));
+    }
+}
+
+impl<B: IBackend + conn::LayerNorm<f32> + crate::coblas::plugin::Copy<f32>> ComputeOutput<f32, B>
+    for LayerNorm<f32, B>
+{
+    fn compute_output(
+        &self,
+        backend: &B,
+        weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        let config = self.ln_config.as_ref().unwrap();
+        backend
+            .layer_normalization(
+                input_data[0],
+                weights[0],
+                weights[1],
+                &mut self.saved_mean.write().unwrap(),
+                &mut self.saved_inv_variance.write().unwrap(),
+                output_data[0],
+                &*config,
+            )
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::LayerNorm<f32> + crate::coblas::plugin::Copy<f32>> ComputeInputGradient<f32, B>
+    for LayerNorm<f32, B>
+{
+    /// Also computes the gradients w.r.t. scale and shift, since they need
+    /// the scale, which is not available in `compute_parameters_gradient`.
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        let config = self.ln_config.as_ref().unwrap();
+        backend
+            .layer_normalization_grad(
+                input_data[0],
+                output_gradients[0],
+                weights_data[0],
+                &self.saved_mean.read().unwrap(),
+                &self.saved_inv_variance.read().unwrap(),
+                input_gradients[0],
+                &mut self.scale_diff.write().unwrap(),
+                &mut self.shift_diff.write().unwrap(),
+                &*config,
+            )
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::LayerNorm<f32> + crate::coblas::plugin::Copy<f32>> ComputeParametersGradient<f32, B>
+    for LayerNorm<f32, B>
+{
+    fn compute_parameters_gradient(
+        &self,
+        backend: &B,
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        parameters_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        // computed together with the input gradient
+        backend
+            .copy(&self.scale_diff.read().unwrap(), parameters_gradients[0])
+            .unwrap();
+        backend
+            .copy(&self.shift_diff.read().unwrap(), parameters_gradients[1])
+            .unwrap();
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Specifies configuration parameters for a LayerNorm Layer.
+pub struct LayerNormConfig {
+    /// The number of trailing dimensions of the input that are normalized
+    pub normalized_dims: usize,
+    /// The value added to the variance for numerical stability
+    pub epsilon: f64,
+}
+
+impl Into<LayerType> for LayerNormConfig {
+    fn into(self) -> LayerType {
+        LayerType::LayerNorm(self)
+    }
+}
+
+impl<'a> CapnpWrite<'a> for LayerNormConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the LayerNormConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.reborrow().set_normalized_dims(self.normalized_dims as u64);
+        builder.reborrow().set_epsilon(self.epsilon);
+    }
+}
+
+impl<'a> CapnpRead<'a> for LayerNormConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        LayerNormConfig {
+            normalized_dims: reader.get_normalized_dims() as usize,
+            epsilon: reader.get_epsilon(),
+        }
+    }
+}
+
+impl ::std::default::Default for LayerNormConfig {
+    fn default() -> LayerNormConfig {
+        LayerNormConfig {
+            normalized_dims: 1,
+            epsilon: 1e-5,
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Applies Local Response Normalization across the channels of the input.
+//!
+//! Every value is divided by the activity of the neighbouring channels at the
+//! same position, as introduced by AlexNet:
+//!
+//! `y = x / (k + alpha / n * sum(x_j^2))^beta`
+//!
+//! where the sum runs over the `n` channels centered around the channel of `x`.
+//!
+//! ## Input Data
+//!
+//! The layer expects the channels to be the second dimension of the input,
+//! e.g. 4D NCHW.
+
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::conn;
+use crate::juice_capnp::lrn_config as capnp_config;
+use crate::layer::*;
+use crate::util::ArcLock;
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+/// [LRN](./index.html) Layer
+pub struct LRN<T, B: conn::LRN<T>> {
+    n: u32,
+    alpha: f64,
+    beta: f64,
+    k: f64,
+
+    lrn_config: Option<Rc<B::CLRN>>,
+}
+
+impl<T, B: conn::LRN<T>> LRN<T, B> {
+    /// Create a LRN layer from a LRNConfig.
+    pub fn from_config(config: &LRNConfig) -> LRN<T, B> {
+        LRN {
+            n: config.n,
+            alpha: config.alpha,
+            beta: config.beta,
+            k: config.k,
+
+            lrn_config: None,
+        }
+    }
+}
+
+impl<B: IBackend + conn::LRN<f32>> ILayer<B> for LRN<f32, B> {
+    impl_ilayer_common!();
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let inp = input_data[0].read().unwrap();
+        let input_desc = inp.desc();
+        input_gradient[0].write().unwrap().resize(input_desc).unwrap();
+        output_data[0].write().unwrap().resize(input_desc).unwrap();
+        output_gradient[0].write().unwrap().resize(input_desc).unwrap();
+
+        let config = backend.new_lrn_config(self.n, self.alpha, self.beta, self.k).unwrap();
+        self.lrn_config = Some(Rc::new(config));
+    }
+}
+
+impl<B: IBackend + conn::LRN<f32>> ComputeOutput<f32, B> for LRN<f32, B> {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        let config = self.lrn_config.as_ref().unwrap();
+        backend.lrn(input_data[0], output_data[0], &*config).unwrap();
+    }
+}
+
+impl<B: IBackend + conn::LRN<f32>> ComputeInputGradient<f32, B> for LRN<f32, B> {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        let config = self.lrn_config.as_ref().unwrap();
+        backend
+            .lrn_grad(
+                output_data[0],
+                output_gradients[0],
+                input_data[0],
+                input_gradients[0],
+                &*config,
+            )
+            .unwrap()
+    }
+}
+
+impl<B: IBackend + conn::LRN<f32>> ComputeParametersGradient<f32, B> for LRN<f32, B> {}
+
+#[derive(Debug, Copy, Clone)]
+/// Specifies configuration parameters for a LRN Layer.
+pub struct LRNConfig {
+    /// The number of channels to normalize over
+    pub n: u32,
+    /// The scaling parameter
+    pub alpha: f64,
+    /// The exponent
+    pub beta: f64,
+    /// The additive constant
+    pub k: f64,
+}
+
+impl Into<LayerType> for LRNConfig {
+    fn into(self) -> LayerType {
+        LayerType::LRN(self)
+    }
+}
+
+impl<'a> CapnpWrite<'a> for LRNConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the LRNConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.reborrow().set_n(self.n);
+        builder.reborrow().set_alpha(self.alpha);
+        builder.reborrow().set_beta(self.beta);
+        builder.reborrow().set_k(self.k);
+    }
+}
+
+impl<'a> CapnpRead<'a> for LRNConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        LRNConfig {
+            n: reader.get_n(),
+            alpha: reader.get_alpha(),
+            beta: reader.get_beta(),
+            k: reader.get_k(),
+        }
+    }
+}
+
+impl ::std::default::Default for LRNConfig {
+    /// The parameters used by AlexNet.
+    fn default() -> LRNConfig {
+        LRNConfig {
+            n: 5,
+            alpha: 1e-4,
+            beta: 0.75,
+            k: 2.0,
+        }
+    }
+}
@@ -17,25 +17,41 @@ macro_rules! impl_ilayer_common {
     };
 }
 
+pub use self::batch_norm::{BatchNorm, BatchNormConfig};
 pub use self::convolution::{Convolution, ConvolutionConfig};
+pub use self::convolution_transpose::{ConvolutionTranspose, ConvolutionTransposeConfig};
 pub use self::dropout::{Dropout, DropoutConfig};
+pub use self::embedding::{Embedding, EmbeddingConfig, EmbeddingInit};
+pub use self::group_norm::{GroupNorm, GroupNormConfig};
+pub use self::layer_norm::{LayerNorm, LayerNormConfig};
 pub use self::linear::{Linear, LinearConfig};
 pub use self::log_softmax::LogSoftmax;
+pub use self::lrn::{LRNConfig, LRN};
+pub use self::multi_head_attention::{MultiHeadAttention, MultiHeadAttentionConfig};
 pub use self::pooling::{Pooling, PoolingConfig, PoolingMode};
 pub use self::rnn::{Rnn, RnnConfig};
 pub use self::softmax::Softmax;
+pub use self::upsample::{Upsample, UpsampleConfig, UpsampleMode, UpsampleSize};
 
+pub mod batch_norm;
 pub mod convolution;
+pub mod convolution_transpose;
 pub mod dropout;
+pub mod embedding;
+pub mod group_norm;
+pub mod layer_norm;
 pub mod linear;
 pub mod log_softmax;
+pub mod lrn;
+pub mod multi_head_attention;
 pub mod pooling;
 pub mod rnn;
 pub mod softmax;
+pub mod upsample;
 
 /// Provides common utilities for Layers that utilize a filter with stride and padding.
 ///
-/// This is used by the Convolution and Pooling layers.
+/// This is used by the Convolution, ConvolutionTranspose and Pooling layers.
 pub trait FilterLayer {
     /// Computes the shape of the spatial dimensions.
     fn calculate_spatial_output_dims(
@@ -70,7 +86,7 @@ pub trait FilterLayer {
                 spatial_dims.push(filter_shape[0]);
             }
         } else if filter_shape.len() == num_spatial_dims {
-            panic!("unimplemented: You can not yet specify one filter dimension per spatial dimension");
+            spatial_dims.extend_from_slice(filter_shape);
         } else {
             panic!(
                 "Must either specify one filter_shape or one filter_shape per spatial dimension. Supplied {:?}",
@@ -91,7 +107,7 @@ pub trait FilterLayer {
                 stride_dims.push(stride[0]);
             }
         } else if stride.len() == num_spatial_dims {
-            panic!("unimplemented: You can not yet specify one stride per spatial dimension");
+            stride_dims.extend_from_slice(stride);
         } else {
             panic!(
                 "Must either specify one stride or one stride per spatial dimension. Supplied {:?}",
@@ -112,7 +128,7 @@ pub trait FilterLayer {
                 padding_dims.push(padding[0]);
             }
         } else if padding.len() == num_spatial_dims {
-            panic!("unimplemented: You can not yet specify one padding per spatial dimension");
+            padding_dims.extend_from_slice(padding);
         } else {
             panic!(
                 "Must either specify one padding or one padding per spatial dimension. Supplied {:?}",
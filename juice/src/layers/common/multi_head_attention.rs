@@ -0,0 +1,804 @@
+//! Applies multi-head scaled dot-product self-attention to a sequence.
+//!
+//! Every position of the input is projected to a query, a key and a value, which are
+//! split into `num_heads` heads of `D = E / num_heads` features each. Every head then
+//! attends from each query to all keys, as introduced by [Vaswani et al.][attention]:
+//!
+//! `attention(Q, K, V) = softmax(Q * K^T / sqrt(D)) * V`
+//!
+//! The results of all heads are joined and passed through an output projection.
+//!
+//! ## Input Data
+//!
+//! The first input is the sequence in the shape `[N, T, E]`, i.e. `N` sequences of `T`
+//! steps with `E` features each. `E` has to be divisible by `num_heads`.
+//!
+//! An optional second input of shape `[N, T]` marks the padding of the sequences: a
+//! step with the value `0` is not attended to by any query. It receives no gradient.
+//!
+//! ## Masking
+//!
+//! With `causal` set, each step only attends to itself and the steps before it, as
+//! needed for autoregressive models.
+//!
+//! ## Weights
+//!
+//! The weights of the query, key, value and output projections are stacked into a
+//! single weight of shape `[4 * E, E]`, their biases into a second one of shape `[4, E]`.
+//!
+//! [attention]: https://arxiv.org/abs/1706.03762
+
+use crate::capnp_util::*;
+use crate::co::prelude::*;
+use crate::coblas::transpose::Transpose;
+use crate::conn;
+use crate::juice_capnp::multi_head_attention_config as capnp_config;
+use crate::layer::*;
+use crate::util::{native_backend, native_scalar, write_to_memory, ArcLock, LayerOps};
+use crate::weight::FillerType;
+use std::sync::{Arc, RwLock};
+
+/// The score of masked pairs of a query and a key, their attention vanishes after the softmax.
+const MASKED_SCORE: f32 = -1e9;
+
+#[derive(Debug)]
+/// [MultiHeadAttention](./index.html) Layer
+pub struct MultiHeadAttention {
+    num_heads: usize,
+    causal: bool,
+
+    one: SharedTensor<f32>,
+    zero: SharedTensor<f32>,
+    scale: SharedTensor<f32>,
+
+    state: ArcLock<Option<AttentionState>>,
+}
+
+/// The intermediate results of the forward pass, which are needed by the backward pass.
+#[derive(Debug)]
+struct AttentionState {
+    batch_size: usize,
+    seq_len: usize,
+    embed_dim: usize,
+
+    /// The input as `[N * T, E]`
+    input: SharedTensor<f32>,
+    /// The query, key, value and output weights, `[E, E]` each
+    weights: Vec<SharedTensor<f32>>,
+    /// The query, key, value and output biases, `[1, E]` each
+    biases: Vec<SharedTensor<f32>>,
+    /// The queries, keys and values, `[N * T, E]` each
+    projections: Vec<SharedTensor<f32>>,
+    /// The queries, keys and values of every sample and head, `[T, D]` each
+    heads: Vec<Vec<SharedTensor<f32>>>,
+    /// The scores of every sample and head, `[T, T]` each
+    scores: Vec<SharedTensor<f32>>,
+    /// The scores of all samples and heads, `[N * H * T, T]`
+    logits: SharedTensor<f32>,
+    /// The additive mask of the scores, `[N * H * T, T]`
+    mask: Option<SharedTensor<f32>>,
+    /// The softmax of the scores, `[N * H * T, T]`
+    attention: SharedTensor<f32>,
+    /// The attention of every sample and head, `[T, T]` each
+    attention_heads: Vec<SharedTensor<f32>>,
+    /// The attended values of every sample and head, `[T, D]` each
+    context_heads: Vec<SharedTensor<f32>>,
+    /// The attended values of all heads joined, `[N * T, E]`
+    context: SharedTensor<f32>,
+    /// The heads joined or about to be split, `[N * H * T, D]`
+    head_buffer: SharedTensor<f32>,
+
+    /// The gradient w.r.t. the output as `[N * T, E]`
+    output_diff: SharedTensor<f32>,
+    /// The gradient w.r.t. the attended values, `[N * T, E]`
+    context_diff: SharedTensor<f32>,
+    /// The gradient w.r.t. the softmax of the scores, `[N * H * T, T]`
+    attention_diff: SharedTensor<f32>,
+    /// The gradients w.r.t. the queries, keys and values of every sample and head, `[T, D]` each
+    head_diffs: Vec<Vec<SharedTensor<f32>>>,
+    /// The gradients w.r.t. the queries, keys and values, `[N * T, E]` each
+    projection_diffs: Vec<SharedTensor<f32>>,
+    /// The gradients w.r.t. the query, key, value and output weights, `[E, E]` each
+    weight_diffs: Vec<SharedTensor<f32>>,
+    /// The gradients w.r.t. the query, key, value and output biases, `[1, E]` each
+    bias_diffs: Vec<SharedTensor<f32>>,
+    /// The gradient w.r.t. the stacked weights, `[4 * E, E]`
+    weight_diff: SharedTensor<f32>,
+    /// The gradient w.r.t. the stacked biases, `[4, E]`
+    bias_diff: SharedTensor<f32>,
+}
+
+impl AttentionState {
+    fn new(batch_size: usize, seq_len: usize, embed_dim: usize, num_heads: usize) -> AttentionState {
+        let head_dim = embed_dim / num_heads;
+        let samples = batch_size * num_heads;
+        let tensors = |count: usize, shape: &[usize]| -> Vec<SharedTensor<f32>> {
+            (0..count).map(|_| SharedTensor::new(&shape)).collect()
+        };
+        let flat = [batch_size * seq_len, embed_dim];
+        let all_scores = [samples * seq_len, seq_len];
+        AttentionState {
+            batch_size,
+            seq_len,
+            embed_dim,
+
+            input: SharedTensor::new(&flat),
+            weights: tensors(4, &[embed_dim, embed_dim]),
+            biases: tensors(4, &[1, embed_dim]),
+            projections: tensors(3, &flat),
+            heads: (0..3).map(|_| tensors(samples, &[seq_len, head_dim])).collect(),
+            scores: tensors(samples, &[seq_len, seq_len]),
+            logits: SharedTensor::new(&all_scores),
+            mask: None,
+            attention: SharedTensor::new(&all_scores),
+            attention_heads: tensors(samples, &[seq_len, seq_len]),
+            context_heads: tensors(samples, &[seq_len, head_dim]),
+            context: SharedTensor::new(&flat),
+            head_buffer: SharedTensor::new(&[samples * seq_len, head_dim]),
+
+            output_diff: SharedTensor::new(&flat),
+            context_diff: SharedTensor::new(&flat),
+            attention_diff: SharedTensor::new(&all_scores),
+            head_diffs: (0..3).map(|_| tensors(samples, &[seq_len, head_dim])).collect(),
+            projection_diffs: tensors(3, &flat),
+            weight_diffs: tensors(4, &[embed_dim, embed_dim]),
+            bias_diffs: tensors(4, &[1, embed_dim]),
+            weight_diff: SharedTensor::new(&[4 * embed_dim, embed_dim]),
+            bias_diff: SharedTensor::new(&[4, embed_dim]),
+        }
+    }
+
+    /// The shape `[N, T, H, D]` of a tensor of `[N * T, E]` with separate heads.
+    fn split_shape(&self, num_heads: usize) -> Vec<usize> {
+        vec![self.batch_size, self.seq_len, num_heads, self.embed_dim / num_heads]
+    }
+
+    /// The shape `[N, H, T, D]` of a tensor of `[N * H * T, D]` with separate samples and heads.
+    fn heads_shape(&self, num_heads: usize) -> Vec<usize> {
+        vec![self.batch_size, num_heads, self.seq_len, self.embed_dim / num_heads]
+    }
+}
+
+/// Splits `x` of `[N * T, E]` into the `parts` of `[T, D]` of every sample and head.
+fn split_heads<B: IBackend + LayerOps<f32>>(
+    backend: &B,
+    x: &mut SharedTensor<f32>,
+    buffer: &mut SharedTensor<f32>,
+    parts: &mut [SharedTensor<f32>],
+    split_shape: &[usize],
+    heads_shape: &[usize],
+) {
+    let flat_shape = x.desc().clone();
+    let buffer_shape = buffer.desc().clone();
+    x.reshape(&split_shape).unwrap();
+    buffer.reshape(&heads_shape).unwrap();
+    backend.permute(x, buffer, &[0, 2, 1, 3]).unwrap();
+    x.reshape(&flat_shape).unwrap();
+    buffer.reshape(&buffer_shape).unwrap();
+    let mut parts: Vec<&mut SharedTensor<f32>> = parts.iter_mut().collect();
+    backend.split(buffer, &mut parts, 0).unwrap();
+}
+
+/// Joins the `parts` of `[T, D]` of every sample and head into `x` of `[N * T, E]`.
+fn merge_heads<B: IBackend + LayerOps<f32>>(
+    backend: &B,
+    parts: &[SharedTensor<f32>],
+    buffer: &mut SharedTensor<f32>,
+    x: &mut SharedTensor<f32>,
+    split_shape: &[usize],
+    heads_shape: &[usize],
+) {
+    let parts: Vec<&SharedTensor<f32>> = parts.iter().collect();
+    backend.concat(&parts, buffer, 0).unwrap();
+    let flat_shape = x.desc().clone();
+    let buffer_shape = buffer.desc().clone();
+    x.reshape(&split_shape).unwrap();
+    buffer.reshape(&heads_shape).unwrap();
+    backend.permute(buffer, x, &[0, 2, 1, 3]).unwrap();
+    x.reshape(&flat_shape).unwrap();
+    buffer.reshape(&buffer_shape).unwrap();
+}
+
+impl MultiHeadAttention {
+    /// Create a MultiHeadAttention layer from a MultiHeadAttentionConfig.
+    pub fn from_config(config: &MultiHeadAttentionConfig) -> MultiHeadAttention {
+        MultiHeadAttention {
+            num_heads: config.num_heads,
+            causal: config.causal,
+
+            one: native_scalar(1f32),
+            zero: native_scalar(0f32),
+            scale: native_scalar(1f32),
+
+            state: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Calculates the embedding dimension of an input and checks that it can be split into the heads.
+    fn calculate_embed_dim(&self, input_shape: &[usize]) -> usize {
+        if input_shape.len() != 3 {
+            panic!(
+                "MultiHeadAttention expects an input of shape [N, T, E], got {:?}",
+                input_shape
+            );
+        }
+        let embed_dim = input_shape[2];
+        if self.num_heads == 0 || embed_dim % self.num_heads != 0 {
+            panic!(
+                "The embedding dimension ({}) has to be divisible by the number of heads ({})",
+                embed_dim, self.num_heads
+            );
+        }
+        embed_dim
+    }
+
+    /// Calculates the additive mask of the scores, or `None` if nothing is masked.
+    fn calculate_mask(&self, batch_size: usize, seq_len: usize, padding: Option<&[f32]>) -> Option<Vec<f32>> {
+        if !self.causal && padding.is_none() {
+            return None;
+        }
+        let mut mask = Vec::with_capacity(batch_size * self.num_heads * seq_len * seq_len);
+        for n in 0..batch_size {
+            for _ in 0..self.num_heads {
+                for query in 0..seq_len {
+                    for key in 0..seq_len {
+                        let is_future = self.causal && key > query;
+                        let is_padding = padding.map_or(false, |padding| padding[n * seq_len + key] == 0f32);
+                        mask.push(if is_future || is_padding { MASKED_SCORE } else { 0f32 });
+                    }
+                }
+            }
+        }
+        Some(mask)
+    }
+}
+
+impl<B: IBackend + LayerOps<f32>> ILayer<B> for MultiHeadAttention {
+    fn exact_num_output_blobs(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    fn auto_weight_blobs(&self) -> bool {
+        true
+    }
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let input_shape = input_data[0].read().unwrap().desc().clone();
+        let embed_dim = self.calculate_embed_dim(&input_shape);
+        let (batch_size, seq_len) = (input_shape[0], input_shape[1]);
+        for (input, gradient) in input_data.iter().zip(input_gradient.iter()) {
+            let desc = input.read().unwrap().desc().clone();
+            gradient.write().unwrap().resize(&desc).unwrap();
+        }
+        if let Some(padding) = input_data.get(1) {
+            let padding_shape = padding.read().unwrap().desc().clone();
+            if padding_shape != vec![batch_size, seq_len] {
+                panic!(
+                    "The padding mask of MultiHeadAttention has to be of shape [N, T] = {:?}, got {:?}",
+                    [batch_size, seq_len],
+                    padding_shape
+                );
+            }
+        }
+        output_data[0].write().unwrap().resize(&input_shape).unwrap();
+        output_gradient[0].write().unwrap().resize(&input_shape).unwrap();
+
+        let weight_shape = vec![4 * embed_dim, embed_dim];
+        let bias_shape = vec![4, embed_dim];
+        if let Some(weight) = weights_data.get(0) {
+            weight.write().unwrap().resize(&weight_shape).unwrap();
+            let filler = FillerType::Glorot {
+                input_size: embed_dim,
+                output_size: embed_dim,
+            };
+            filler.fill(&mut weight.write().unwrap());
+        }
+        if let Some(weight) = weights_gradient.get(0) {
+            weight.write().unwrap().resize(&weight_shape).unwrap();
+        }
+        if let Some(weight) = weights_data.get(1) {
+            weight.write().unwrap().resize(&bias_shape).unwrap();
+            FillerType::fill_constant(&mut weight.write().unwrap(), 0f32);
+        }
+        if let Some(weight) = weights_gradient.get(1) {
+            weight.write().unwrap().resize(&bias_shape).unwrap();
+        }
+
+        let head_dim = embed_dim / self.num_heads;
+        self.scale = native_scalar(1f32 / (head_dim as f32).sqrt());
+        *self.state.write().unwrap() = Some(AttentionState::new(batch_size, seq_len, embed_dim, self.num_heads));
+    }
+}
+
+impl<B: IBackend + LayerOps<f32>> ComputeOutput<f32, B> for MultiHeadAttention {
+    fn compute_output(
+        &self,
+        backend: &B,
+        weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        let mut guard = self.state.write().unwrap();
+        let st = guard.as_mut().unwrap();
+        let split_shape = st.split_shape(self.num_heads);
+        let heads_shape = st.heads_shape(self.num_heads);
+
+        backend.copy(input_data[0], &mut st.input).unwrap();
+        {
+            let mut weights_parts: Vec<&mut SharedTensor<f32>> = st.weights.iter_mut().collect();
+            backend.split(weights[0], &mut weights_parts, 0).unwrap();
+            let mut biases_parts: Vec<&mut SharedTensor<f32>> = st.biases.iter_mut().collect();
+            backend.split(weights[1], &mut biases_parts, 0).unwrap();
+        }
+
+        // project the input to queries, keys and values and split them into heads
+        for i in 0..3 {
+            backend
+                .gemm(
+                    &self.one,
+                    Transpose::NoTrans,
+                    &st.input,
+                    Transpose::Trans,
+                    &st.weights[i],
+                    &self.zero,
+                    &mut st.projections[i],
+                )
+                .unwrap();
+            backend
+                .binary_operation_pointwise(conn::BinaryOperation::Add, &mut st.projections[i], &st.biases[i])
+                .unwrap();
+            split_heads(
+                backend,
+                &mut st.projections[i],
+                &mut st.head_buffer,
+                &mut st.heads[i],
+                &split_shape,
+                &heads_shape,
+            );
+        }
+
+        // scaled dot-product attention of every sample and head
+        for (b, scores) in st.scores.iter_mut().enumerate() {
+            backend
+                .gemm(
+                    &self.scale,
+                    Transpose::NoTrans,
+                    &st.heads[0][b],
+                    Transpose::Trans,
+                    &st.heads[1][b],
+                    &self.zero,
+                    scores,
+                )
+                .unwrap();
+        }
+        {
+            let scores: Vec<&SharedTensor<f32>> = st.scores.iter().collect();
+            backend.concat(&scores, &mut st.logits, 0).unwrap();
+        }
+        let padding = input_data
+            .get(1)
+            .map(|padding| padding.read(native_backend().device()).unwrap().as_slice::<f32>());
+        st.mask = self.calculate_mask(st.batch_size, st.seq_len, padding).map(|values| {
+            let mut mask = SharedTensor::<f32>::new(st.logits.desc());
+            write_to_memory(mask.write_only(native_backend().device()).unwrap(), &values);
+            mask
+        });
+        if let Some(ref mask) = st.mask {
+            backend
+                .binary_operation_pointwise(conn::BinaryOperation::Add, &mut st.logits, mask)
+                .unwrap();
+        }
+        backend.softmax(&st.logits, &mut st.attention).unwrap();
+        {
+            let mut attention_parts: Vec<&mut SharedTensor<f32>> = st.attention_heads.iter_mut().collect();
+            backend.split(&st.attention, &mut attention_parts, 0).unwrap();
+        }
+        for (b, context) in st.context_heads.iter_mut().enumerate() {
+            backend
+                .gemm(
+                    &self.one,
+                    Transpose::NoTrans,
+                    &st.attention_heads[b],
+                    Transpose::NoTrans,
+                    &st.heads[2][b],
+                    &self.zero,
+                    context,
+                )
+                .unwrap();
+        }
+
+        // join the heads and project them to the output
+        merge_heads(
+            backend,
+            &st.context_heads,
+            &mut st.head_buffer,
+            &mut st.context,
+            &split_shape,
+            &heads_shape,
+        );
+        let output_shape = output_data[0].desc().clone();
+        output_data[0].reshape(st.context.desc()).unwrap();
+        backend
+            .gemm(
+                &self.one,
+                Transpose::NoTrans,
+                &st.context,
+                Transpose::Trans,
+                &st.weights[3],
+                &self.zero,
+                output_data[0],
+            )
+            .unwrap();
+        backend
+            .binary_operation_pointwise(conn::BinaryOperation::Add, output_data[0], &st.biases[3])
+            .unwrap();
+        output_data[0].reshape(&output_shape).unwrap();
+    }
+}
+
+impl<B: IBackend + LayerOps<f32>> ComputeInputGradient<f32, B> for MultiHeadAttention {
+    /// Also computes the gradients w.r.t. the weights, since they need most of
+    /// the intermediate gradients w.r.t. the input.
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        let mut guard = self.state.write().unwrap();
+        let st = guard.as_mut().unwrap();
+        let split_shape = st.split_shape(self.num_heads);
+        let heads_shape = st.heads_shape(self.num_heads);
+
+        // output projection
+        backend.copy(output_gradients[0], &mut st.output_diff).unwrap();
+        backend
+            .gemm(
+                &self.one,
+                Transpose::Trans,
+                &st.output_diff,
+                Transpose::NoTrans,
+                &st.context,
+                &self.zero,
+                &mut st.weight_diffs[3],
+            )
+            .unwrap();
+        backend
+            .reduce(
+                conn::ReductionOperation::Sum,
+                &st.output_diff,
+                &[0],
+                true,
+                &mut st.bias_diffs[3],
+            )
+            .unwrap();
+        backend
+            .gemm(
+                &self.one,
+                Transpose::NoTrans,
+                &st.output_diff,
+                Transpose::NoTrans,
+                &st.weights[3],
+                &self.zero,
+                &mut st.context_diff,
+            )
+            .unwrap();
+
+        // attention of every sample and head, the attended values are not needed anymore
+        // so their buffers take the gradients w.r.t. them
+        split_heads(
+            backend,
+            &mut st.context_diff,
+            &mut st.head_buffer,
+            &mut st.context_heads,
+            &split_shape,
+            &heads_shape,
+        );
+        for b in 0..st.scores.len() {
+            backend
+                .gemm(
+                    &self.one,
+                    Transpose::NoTrans,
+                    &st.context_heads[b],
+                    Transpose::Trans,
+                    &st.heads[2][b],
+                    &self.zero,
+                    &mut st.scores[b],
+                )
+                .unwrap();
+            backend
+                .gemm(
+                    &self.one,
+                    Transpose::Trans,
+                    &st.attention_heads[b],
+                    Transpose::NoTrans,
+                    &st.context_heads[b],
+                    &self.zero,
+                    &mut st.head_diffs[2][b],
+                )
+                .unwrap();
+        }
+        {
+            let scores: Vec<&SharedTensor<f32>> = st.scores.iter().collect();
+            backend.concat(&scores, &mut st.attention_diff, 0).unwrap();
+        }
+        // masked scores have an attention of zero and therefore receive no gradient
+        backend
+            .softmax_grad(&st.attention, &st.attention_diff, &mut st.logits)
+            .unwrap();
+        {
+            let mut scores: Vec<&mut SharedTensor<f32>> = st.scores.iter_mut().collect();
+            backend.split(&st.logits, &mut scores, 0).unwrap();
+        }
+        for b in 0..st.scores.len() {
+            backend
+                .gemm(
+                    &self.scale,
+                    Transpose::NoTrans,
+                    &st.scores[b],
+                    Transpose::NoTrans,
+                    &st.heads[1][b],
+                    &self.zero,
+                    &mut st.head_diffs[0][b],
+                )
+                .unwrap();
+            backend
+                .gemm(
+                    &self.scale,
+                    Transpose::Trans,
+                    &st.scores[b],
+                    Transpose::NoTrans,
+                    &st.heads[0][b],
+                    &self.zero,
+                    &mut st.head_diffs[1][b],
+                )
+                .unwrap();
+        }
+
+        // query, key and value projections
+        let input_shape = input_gradients[0].desc().clone();
+        input_gradients[0].reshape(st.input.desc()).unwrap();
+        for i in 0..3 {
+            merge_heads(
+                backend,
+                &st.head_diffs[i],
+                &mut st.head_buffer,
+                &mut st.projection_diffs[i],
+                &split_shape,
+                &heads_shape,
+            );
+            backend
+                .gemm(
+                    &self.one,
+                    Transpose::Trans,
+                    &st.projection_diffs[i],
+                    Transpose::NoTrans,
+                    &st.input,
+                    &self.zero,
+                    &mut st.weight_diffs[i],
+                )
+                .unwrap();
+            backend
+                .reduce(
+                    conn::ReductionOperation::Sum,
+                    &st.projection_diffs[i],
+                    &[0],
+                    true,
+                    &mut st.bias_diffs[i],
+                )
+                .unwrap();
+            // the gradients of the three projections add up
+            let beta = if i == 0 { &self.zero } else { &self.one };
+            backend
+                .gemm(
+                    &self.one,
+                    Transpose::NoTrans,
+                    &st.projection_diffs[i],
+                    Transpose::NoTrans,
+                    &st.weights[i],
+                    beta,
+                    input_gradients[0],
+                )
+                .unwrap();
+        }
+        input_gradients[0].reshape(&input_shape).unwrap();
+
+        let weight_diffs: Vec<&SharedTensor<f32>> = st.weight_diffs.iter().collect();
+        backend.concat(&weight_diffs, &mut st.weight_diff, 0).unwrap();
+        let bias_diffs: Vec<&SharedTensor<f32>> = st.bias_diffs.iter().collect();
+        backend.concat(&bias_diffs, &mut st.bias_diff, 0).unwrap();
+    }
+}
+
+impl<B: IBackend + LayerOps<f32>> ComputeParametersGradient<f32, B> for MultiHeadAttention {
+    fn compute_parameters_gradient(
+        &self,
+        backend: &B,
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        parameters_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        // computed together with the input gradient
+        let guard = self.state.read().unwrap();
+        let st = guard.as_ref().unwrap();
+        backend.copy(&st.weight_diff, parameters_gradients[0]).unwrap();
+        backend.copy(&st.bias_diff, parameters_gradients[1]).unwrap();
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Specifies configuration parameters for a MultiHeadAttention Layer.
+pub struct MultiHeadAttentionConfig {
+    /// The number of heads the embedding dimension is split into
+    pub num_heads: usize,
+    /// Whether every step only attends to itself and the steps before it
+    pub causal: bool,
+}
+
+impl Into<LayerType> for MultiHeadAttentionConfig {
+    fn into(self) -> LayerType {
+        LayerType::MultiHeadAttention(self)
+    }
+}
+
+impl<'a> CapnpWrite<'a> for MultiHeadAttentionConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the MultiHeadAttentionConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.reborrow().set_num_heads(self.num_heads as u64);
+        builder.reborrow().set_causal(self.causal);
+    }
+}
+
+impl<'a> CapnpRead<'a> for MultiHeadAttentionConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        MultiHeadAttentionConfig {
+            num_heads: reader.get_num_heads() as usize,
+            causal: reader.get_causal(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use std::sync::{Arc, RwLock};
+
+    use crate::co::*;
+    use crate::layer::{Layer, LayerConfig};
+    use crate::layers::SequentialConfig;
+    use crate::util::{native_backend, write_to_memory, ArcLock};
+
+    use super::{MultiHeadAttention, MultiHeadAttentionConfig, MASKED_SCORE};
+
+    #[test]
+    fn causal_and_padding_mask() {
+        let cfg = MultiHeadAttentionConfig {
+            num_heads: 1,
+            causal: true,
+        };
+        let layer = MultiHeadAttention::from_config(&cfg);
+        assert_eq!(8, layer.calculate_embed_dim(&[2, 3, 8]));
+        let m = MASKED_SCORE;
+        assert_eq!(
+            Some(vec![0.0, m, m, 0.0, 0.0, m, 0.0, 0.0, m]),
+            layer.calculate_mask(1, 3, Some(&[1.0, 1.0, 0.0][..]))
+        );
+
+        let cfg = MultiHeadAttentionConfig {
+            num_heads: 2,
+            causal: false,
+        };
+        let layer = MultiHeadAttention::from_config(&cfg);
+        assert_eq!(None, layer.calculate_mask(1, 2, None));
+        assert_eq!(
+            Some(vec![m, 0.0, m, 0.0, m, 0.0, m, 0.0]),
+            layer.calculate_mask(1, 2, Some(&[0.0, 1.0][..]))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn embed_dim_divisible_by_heads() {
+        let cfg = MultiHeadAttentionConfig {
+            num_heads: 3,
+            causal: false,
+        };
+        MultiHeadAttention::from_config(&cfg).calculate_embed_dim(&[2, 3, 8]);
+    }
+
+    #[test]
+    fn stacked_gradients() {
+        // both layers keep the parameter gradients from the backward pass of their input
+        // until all input gradients of the network are computed
+        let shape = [2, 3, 4];
+        let mut net_cfg = SequentialConfig::default();
+        net_cfg.add_input("data", &shape);
+        for (name, causal) in &[("attention1", true), ("attention2", false)] {
+            net_cfg.add_layer(LayerConfig::new(
+                name,
+                MultiHeadAttentionConfig {
+                    num_heads: 2,
+                    causal: *causal,
+                },
+            ));
+        }
+        let mut network = Layer::from_config(Rc::new(native_backend()), &LayerConfig::new("network", net_cfg));
+        let native = native_backend();
+
+        let write = |tensor: &ArcLock<SharedTensor<f32>>, values: &[f32]| {
+            let mut tensor = tensor.write().unwrap();
+            write_to_memory(tensor.write_only(native.device()).unwrap(), values);
+        };
+        let read = |tensor: &ArcLock<SharedTensor<f32>>| -> Vec<f32> {
+            let tensor = tensor.read().unwrap();
+            tensor.read(native.device()).unwrap().as_slice::<f32>().to_vec()
+        };
+        // the biases start out as zero, which would hide some of their gradients
+        let weights = network.learnable_weights_data();
+        for (w, weight) in weights.iter().enumerate() {
+            let values: Vec<f32> = (0..weight.read().unwrap().desc().size())
+                .map(|i| 0.5 * ((i + 7 * w) as f32 * 1.3).sin())
+                .collect();
+            write(weight, &values);
+        }
+
+        let mut input_data: Vec<f32> = (0..24).map(|i| (i as f32 * 0.7).sin()).collect();
+        let output_gradient: Vec<f32> = (0..24).map(|i| (i as f32 * 0.3).cos()).collect();
+
+        // the loss is the output weighted with the output gradient
+        let loss = |network: &mut Layer<Backend<Native>>, input_data: &[f32]| -> f32 {
+            let input = Arc::new(RwLock::new(SharedTensor::<f32>::new(&shape)));
+            write(&input, input_data);
+            let output = read(&network.forward(&[input])[0]);
+            output.iter().zip(&output_gradient).map(|(y, dy)| y * dy).sum()
+        };
+        loss(&mut network, &input_data);
+        let gradient = Arc::new(RwLock::new(SharedTensor::<f32>::new(&shape)));
+        write(&gradient, &output_gradient);
+        let input_gradient = read(&network.backward(&[gradient])[0]);
+        let weights_gradients: Vec<Vec<f32>> = network.learnable_weights_gradients().iter().map(read).collect();
+
+        let h = 1e-2;
+        for k in 0..input_data.len() {
+            input_data[k] += h;
+            let plus = loss(&mut network, &input_data);
+            input_data[k] -= 2.0 * h;
+            let minus = loss(&mut network, &input_data);
+            input_data[k] += h;
+            let numeric = (plus - minus) / (2.0 * h);
+            assert!((input_gradient[k] - numeric).abs() < 1e-2, "input[{}]: {}", k, numeric);
+        }
+        for (w, gradient) in weights_gradients.iter().enumerate() {
+            for (k, &analytic) in gradient.iter().enumerate() {
+                let mut perturbed = |delta: f32| {
+                    let mut values = read(&weights[w]);
+                    values[k] += delta;
+                    write(&weights[w], &values);
+                    loss(&mut network, &input_data)
+                };
+                let plus = perturbed(h);
+                let minus = perturbed(-2.0 * h);
+                perturbed(h);
+                let numeric = (plus - minus) / (2.0 * h);
+                assert!((analytic - numeric).abs() < 1e-2, "{}[{}]: {}", w, k, numeric);
+            }
+        }
+    }
+}
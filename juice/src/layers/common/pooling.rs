@@ -8,8 +8,25 @@
 //!
 //! ## Input Data
 //!
-//! The layer expects the input to be in either 4D NCHW (2 spatial dimensions)
-//! or 5D NCDHW (3 spatial dimensions) format.
+//! The layer expects the input to be in either 3D NCW (1 spatial dimension),
+//! 4D NCHW (2 spatial dimensions) or 5D NCDHW (3 spatial dimensions) format.
+//!
+//! ## Global and Adaptive Pooling
+//!
+//! The global and adaptive modes ignore `filter_shape`, `stride` and `padding`
+//! and derive the windows from the input shape when the layer is reshaped.
+//! Global pooling reduces every feature map to a single value, adaptive pooling
+//! to the `output_shape` of the [PoolingConfig][pooling_config].
+//!
+//! Backends other than the native one may only support input sizes that are
+//! multiples of the output sizes, the layer refuses to be built otherwise.
+//!
+//! [pooling_config]: ./struct.PoolingConfig.html
+//!
+//! ## Average Pooling and Padding
+//!
+//! Average pooling divides by the number of input elements inside the window by default.
+//! With `include_padding` the padded elements count towards the window size as well.
 
 use super::FilterLayer;
 use crate::capnp_util::*;
@@ -29,8 +46,10 @@ pub struct Pooling<T, B: conn::Pooling<T>> {
     filter_shape: Vec<usize>,
     stride: Vec<usize>,
     padding: Vec<usize>,
+    output_shape: Vec<usize>,
+    include_padding: bool,
 
-    pooling_configs: Vec<Rc<B::CPOOL>>,
+    pooling_config: Option<Rc<B::CPOOL>>,
 }
 
 impl<T, B: conn::Pooling<T>> Pooling<T, B> {
@@ -42,8 +61,27 @@ impl<T, B: conn::Pooling<T>> Pooling<T, B> {
             filter_shape: config.filter_shape.clone(),
             stride: config.stride.clone(),
             padding: config.padding.clone(),
+            output_shape: config.output_shape.clone(),
+            include_padding: config.include_padding,
+
+            pooling_config: None,
+        }
+    }
 
-            pooling_configs: vec![],
+    /// The spatial output dimensions of the global and adaptive modes,
+    /// which do not depend on the input.
+    fn adaptive_output_dims(&self, num_spatial_dims: usize) -> Option<Vec<usize>> {
+        match self.mode {
+            PoolingMode::Max | PoolingMode::Average => None,
+            PoolingMode::GlobalMax | PoolingMode::GlobalAverage => Some(vec![1; num_spatial_dims]),
+            PoolingMode::AdaptiveMax | PoolingMode::AdaptiveAverage => match self.output_shape.len() {
+                1 => Some(vec![self.output_shape[0]; num_spatial_dims]),
+                n if n == num_spatial_dims => Some(self.output_shape.clone()),
+                n => panic!(
+                    "Must either specify one output_shape or one output_shape per spatial dimension. Supplied {:?}",
+                    n
+                ),
+            },
         }
     }
 }
@@ -52,14 +90,20 @@ impl<T, B: conn::Pooling<T>> FilterLayer for Pooling<T, B> {
     /// Calculates the number of spatial dimensions for the pooling operation.
     fn num_spatial_dims(&self, input_shape: &[usize]) -> usize {
         match input_shape.len() {
+            3 => 1,
             4 => 2,
             5 => 3,
-            _ => panic!("A pooling layer currently only supports 4D or 5D input."),
+            _ => panic!("A pooling layer currently only supports 3D, 4D or 5D input."),
         }
     }
 
     fn calculate_output_shape(&self, input_shape: &[usize]) -> Vec<usize> {
         let num_spatial_dims = self.num_spatial_dims(input_shape);
+        if let Some(output_dims) = self.adaptive_output_dims(num_spatial_dims) {
+            let mut output_shape = input_shape[0..2].to_vec();
+            output_shape.extend(output_dims);
+            return output_shape;
+        }
         let filter = self.spatial_filter_dims(num_spatial_dims);
         let padding = self.padding_dims(num_spatial_dims);
         let stride = self.stride_dims(num_spatial_dims);
@@ -108,12 +152,32 @@ impl<B: IBackend + conn::Pooling<f32>> ILayer<B> for Pooling<f32, B> {
             output_gradient[0].write().unwrap().resize(&output_shape).unwrap();
 
             let num_spatial_dims = self.num_spatial_dims(inp.desc());
-            let filter = cast_vec_usize_to_i32(self.spatial_filter_dims(num_spatial_dims));
-            let stride = cast_vec_usize_to_i32(self.stride_dims(num_spatial_dims));
-            let padding = cast_vec_usize_to_i32(self.padding_dims(num_spatial_dims));
-
-            let config = backend.new_pooling_config(&filter, &stride, &padding).unwrap();
-            self.pooling_configs.push(Rc::new(config));
+            let config = match self.adaptive_output_dims(num_spatial_dims) {
+                Some(output_dims) => backend
+                    .new_adaptive_pooling_config(&input_shape[2..], &output_dims)
+                    .unwrap_or_else(|err| {
+                        panic!(
+                            "Adaptive pooling from {:?} to {:?} is not supported by the backend: {}",
+                            &input_shape[2..],
+                            output_dims,
+                            err
+                        )
+                    }),
+                None => {
+                    let filter = cast_vec_usize_to_i32(self.spatial_filter_dims(num_spatial_dims));
+                    let stride = cast_vec_usize_to_i32(self.stride_dims(num_spatial_dims));
+                    let padding = cast_vec_usize_to_i32(self.padding_dims(num_spatial_dims));
+                    let avg_mode = if self.include_padding {
+                        conn::PoolingAvgMode::IncludePadding
+                    } else {
+                        conn::PoolingAvgMode::ExcludePadding
+                    };
+                    backend
+                        .new_pooling_config_with_avg_mode(&filter, &stride, &padding, avg_mode)
+                        .unwrap()
+                }
+            };
+            self.pooling_config = Some(Rc::new(config));
         }
     }
 }
@@ -126,10 +190,14 @@ impl<B: IBackend + conn::Pooling<f32>> ComputeOutput<f32, B> for Pooling<f32, B>
         input_data: &[&SharedTensor<f32>],
         output_data: &mut [&mut SharedTensor<f32>],
     ) {
-        let config = &self.pooling_configs[0];
+        let config = self.pooling_config.as_ref().unwrap();
         match self.mode {
-            PoolingMode::Max => backend.pooling_max(input_data[0], output_data[0], &*config).unwrap(),
-            PoolingMode::Average => backend.pooling_avg(input_data[0], output_data[0], &*config).unwrap(),
+            PoolingMode::Max | PoolingMode::GlobalMax | PoolingMode::AdaptiveMax => {
+                backend.pooling_max(input_data[0], output_data[0], &*config).unwrap()
+            }
+            PoolingMode::Average | PoolingMode::GlobalAverage | PoolingMode::AdaptiveAverage => {
+                backend.pooling_avg(input_data[0], output_data[0], &*config).unwrap()
+            }
         }
     }
 }
@@ -144,9 +212,9 @@ impl<B: IBackend + conn::Pooling<f32>> ComputeInputGradient<f32, B> for Pooling<
         input_data: &[&SharedTensor<f32>],
         input_gradients: &mut [&mut SharedTensor<f32>],
     ) {
-        let config = &self.pooling_configs[0];
+        let config = self.pooling_config.as_ref().unwrap();
         match self.mode {
-            PoolingMode::Max => backend
+            PoolingMode::Max | PoolingMode::GlobalMax | PoolingMode::AdaptiveMax => backend
                 .pooling_max_grad(
                     output_data[0],
                     output_gradients[0],
@@ -155,7 +223,7 @@ impl<B: IBackend + conn::Pooling<f32>> ComputeInputGradient<f32, B> for Pooling<
                     config,
                 )
                 .unwrap(),
-            PoolingMode::Average => backend
+            PoolingMode::Average | PoolingMode::GlobalAverage | PoolingMode::AdaptiveAverage => backend
                 .pooling_avg_grad(
                     output_data[0],
                     output_gradients[0],
@@ -181,6 +249,39 @@ pub struct PoolingConfig {
     pub stride: Vec<usize>,
     /// The padding size
     pub padding: Vec<usize>,
+    /// The size of the spatial output dimensions of the adaptive modes
+    ///
+    /// Either one size for all spatial dimensions or one size per spatial dimension.
+    pub output_shape: Vec<usize>,
+    /// Whether the padded elements count towards the window size of average pooling
+    pub include_padding: bool,
+}
+
+impl PoolingConfig {
+    /// Create a PoolingConfig that slides a window of `filter_shape` over the input.
+    pub fn of_window(mode: PoolingMode, filter_shape: &[usize], stride: &[usize], padding: &[usize]) -> PoolingConfig {
+        PoolingConfig {
+            mode,
+            filter_shape: filter_shape.to_owned(),
+            stride: stride.to_owned(),
+            padding: padding.to_owned(),
+            output_shape: vec![],
+            include_padding: false,
+        }
+    }
+
+    /// Create a PoolingConfig for the global and adaptive modes, which pool the input
+    /// down to `output_shape`.
+    pub fn of_output_shape(mode: PoolingMode, output_shape: &[usize]) -> PoolingConfig {
+        PoolingConfig {
+            mode,
+            filter_shape: vec![],
+            stride: vec![],
+            padding: vec![],
+            output_shape: output_shape.to_owned(),
+            include_padding: false,
+        }
+    }
 }
 
 impl Into<LayerType> for PoolingConfig {
@@ -213,6 +314,13 @@ impl<'a> CapnpWrite<'a> for PoolingConfig {
                 padding.set(i as u32, *dim as u64);
             }
         }
+        {
+            let mut output_shape = builder.reborrow().init_output_shape(self.output_shape.len() as u32);
+            for (i, dim) in self.output_shape.iter().enumerate() {
+                output_shape.set(i as u32, *dim as u64);
+            }
+        }
+        builder.reborrow().set_include_padding(self.include_padding);
     }
 }
 
@@ -237,12 +345,19 @@ impl<'a> CapnpRead<'a> for PoolingConfig {
         for i in 0..read_padding.len() {
             padding.push(read_padding.get(i) as usize)
         }
+        let read_output_shape = reader.get_output_shape().unwrap();
+        let mut output_shape = Vec::new();
+        for i in 0..read_output_shape.len() {
+            output_shape.push(read_output_shape.get(i) as usize)
+        }
 
         PoolingConfig {
             mode: mode,
             filter_shape: filter_shape,
             stride: stride,
             padding: padding,
+            output_shape: output_shape,
+            include_padding: reader.get_include_padding(),
         }
     }
 }
@@ -254,6 +369,16 @@ pub enum PoolingMode {
     Max,
     /// The average of all values inside the pooling window will be used as result.
     Average,
+    /// The maximum value of every feature map will be used as result.
+    GlobalMax,
+    /// The average of every feature map will be used as result.
+    GlobalAverage,
+    /// The feature maps are split into `output_shape` windows, the maximum value
+    /// of each window will be used as result.
+    AdaptiveMax,
+    /// The feature maps are split into `output_shape` windows, the average
+    /// of each window will be used as result.
+    AdaptiveAverage,
 }
 
 impl PoolingMode {
@@ -262,6 +387,10 @@ impl PoolingMode {
         match *self {
             PoolingMode::Max => CapnpPoolingMode::Max,
             PoolingMode::Average => CapnpPoolingMode::Average,
+            PoolingMode::GlobalMax => CapnpPoolingMode::GlobalMax,
+            PoolingMode::GlobalAverage => CapnpPoolingMode::GlobalAverage,
+            PoolingMode::AdaptiveMax => CapnpPoolingMode::AdaptiveMax,
+            PoolingMode::AdaptiveAverage => CapnpPoolingMode::AdaptiveAverage,
         }
     }
 
@@ -270,6 +399,10 @@ impl PoolingMode {
         match value {
             CapnpPoolingMode::Max => PoolingMode::Max,
             CapnpPoolingMode::Average => PoolingMode::Average,
+            CapnpPoolingMode::GlobalMax => PoolingMode::GlobalMax,
+            CapnpPoolingMode::GlobalAverage => PoolingMode::GlobalAverage,
+            CapnpPoolingMode::AdaptiveMax => PoolingMode::AdaptiveMax,
+            CapnpPoolingMode::AdaptiveAverage => PoolingMode::AdaptiveAverage,
         }
     }
 }
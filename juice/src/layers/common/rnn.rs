@@ -5,7 +5,10 @@
 //! one element of data flowing into the next. This type of understanding is suitable for tasks such
 //! as translating a sentence, mimicking the patterns in a musical piece, or time series forecasting.
 //!
-//! Currently this is implemented in CUDA, but not in native or opencl.
+//! Currently this is implemented in CUDA and native, but not in opencl. Both share the cuDNN
+//! weight layout, so weights trained on one backend can be used with the other.
+//! The native backend does not support dropout between the layers, stacked layers on native
+//! require a dropout probability of zero.
 //!
 //! ## CUDA Specific Notes - Using Juice
 //! CUDA currently supports GRU, LSTM, ReLU, and tanh for LSTM operations.
@@ -102,15 +105,19 @@ impl<B: IBackend + conn::Rnn<f32>> ILayer<B> for Rnn<B> {
         let mut output_data = output_data[0].write().unwrap();
         let mut output_gradient = output_gradient[0].write().unwrap();
 
-        // Input Shape is Batch, Number of Inputs, Sequence Length
+        // Input Shape is Batch, Sequence Length, Number of Inputs
         let input_shape = input.desc();
         let batch_size = input_shape[0];
         let sequence_length = input_shape[1];
         let input_size = input_shape[2];
 
         let hidden_size = self.hidden_size;
+        let num_directions = match self.direction_mode {
+            DirectionMode::UniDirectional => 1,
+            DirectionMode::BiDirectional => 2,
+        };
 
-        let output_shape = &[batch_size, hidden_size, self.num_layers];
+        let output_shape = &[batch_size, sequence_length, hidden_size * num_directions];
         input_gradient[0].write().unwrap().resize(input_shape).unwrap();
         output_data.resize(output_shape).unwrap();
         output_gradient.resize(output_shape).unwrap();
@@ -169,21 +176,17 @@ impl<B: IBackend + conn::Rnn<f32>> ILayer<B> for Rnn<B> {
         backend: Rc<B>,
         workspace: Option<ArcLock<SharedTensor<u8>>>,
     ) -> Option<ArcLock<SharedTensor<u8>>> {
+        // The workspace holds intermediate results from the backward pass w.r.t. the input until
+        // the one w.r.t. the parameters, other layers run in between so it can not be shared.
         let required_size = self.rnn_config.as_ref().unwrap().workspace_size();
-        let new_workspace = if workspace.is_none() {
-            Arc::new(RwLock::new(SharedTensor::<u8>::new(&[required_size])))
-        } else {
-            let old_workspace = workspace.as_ref().unwrap().clone();
-            let old_workspace_size = old_workspace.read().unwrap().capacity();
-            if old_workspace_size < required_size {
-                Arc::new(RwLock::new(SharedTensor::<u8>::new(&[required_size])))
-            } else {
-                workspace.unwrap()
-            }
+        let too_small = match self.workspace {
+            Some(ref own_workspace) => own_workspace.read().unwrap().capacity() < required_size,
+            None => true,
         };
-
-        self.workspace = Some(new_workspace.clone());
-        Some(new_workspace)
+        if too_small {
+            self.workspace = Some(Arc::new(RwLock::new(SharedTensor::<u8>::new(&[required_size]))));
+        }
+        workspace
     }
 }
 
@@ -358,6 +361,7 @@ impl<'a> CapnpRead<'a> for RnnConfig {
 #[cfg(test)]
 mod tests {
     use std::rc::Rc;
+    use std::sync::{Arc, RwLock};
 
     use conn::{DirectionMode, RnnAlgorithm, RnnInputMode, RnnNetworkMode};
     use conn::Rnn as coRnn;
@@ -365,8 +369,9 @@ mod tests {
     use crate::co::*;
     #[cfg(feature = "cuda")]
     use crate::co::frameworks::cuda::get_cuda_backend as cuda_backend;
-    use crate::layer::ILayer;
-    use crate::util::native_backend;
+    use crate::layer::{ILayer, Layer, LayerConfig};
+    use crate::layers::SequentialConfig;
+    use crate::util::{native_backend, write_to_memory};
     use crate::weight::FillerType;
 
     use super::{Rnn, RnnConfig};
@@ -542,4 +547,136 @@ mod tests {
             Err(e) => panic!("Couldn't complete RNN Forward"),
         };
     }
+
+    #[test]
+    fn rnn_output_shape() {
+        let test = |direction_mode: DirectionMode, expected: &[usize]| {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[2, 3, 4]);
+            net_cfg.add_layer(LayerConfig::new(
+                "rnn",
+                RnnConfig {
+                    hidden_size: 5,
+                    num_layers: 2,
+                    dropout_probability: 0.0,
+                    dropout_seed: 0,
+                    rnn_type: RnnNetworkMode::GRU,
+                    input_mode: RnnInputMode::LinearInput,
+                    direction_mode,
+                },
+            ));
+            let mut network = Layer::from_config(Rc::new(native_backend()), &LayerConfig::new("network", net_cfg));
+
+            let mut input = SharedTensor::<f32>::new(&[2, 3, 4]);
+            FillerType::fill_constant(&mut input, 0.5);
+            let output = network.forward(&[Arc::new(RwLock::new(input))])[0].clone();
+            assert_eq!(output.read().unwrap().desc(), &expected.to_vec());
+        };
+
+        // Batch, Sequence Length, Hidden Size * Number of Directions
+        test(DirectionMode::UniDirectional, &[2, 3, 5]);
+        test(DirectionMode::BiDirectional, &[2, 3, 10]);
+    }
+
+    #[test]
+    fn rnn_batch_major_output() {
+        // single tanh unit, the input and output are `[batch, sequence, size]`
+        let mut net_cfg = SequentialConfig::default();
+        net_cfg.add_input("data", &[2, 2, 1]);
+        net_cfg.add_layer(LayerConfig::new(
+            "rnn",
+            RnnConfig {
+                hidden_size: 1,
+                num_layers: 1,
+                dropout_probability: 0.0,
+                dropout_seed: 0,
+                rnn_type: RnnNetworkMode::Tanh,
+                input_mode: RnnInputMode::LinearInput,
+                direction_mode: DirectionMode::UniDirectional,
+            },
+        ));
+        let mut network = Layer::from_config(Rc::new(native_backend()), &LayerConfig::new("network", net_cfg));
+        let native = native_backend();
+        {
+            // W, R, bW, bR
+            let weights = network.learnable_weights_data();
+            let mut weight = weights[0].write().unwrap();
+            write_to_memory(weight.write_only(native.device()).unwrap(), &[0.5, 0.25, 0.1, 0.0]);
+        }
+
+        let mut input = SharedTensor::<f32>::new(&[2, 2, 1]);
+        write_to_memory(input.write_only(native.device()).unwrap(), &[1.0, 2.0, -1.0, 0.5]);
+        let output = network.forward(&[Arc::new(RwLock::new(input))])[0].clone();
+        let output = output.read().unwrap();
+        let output = output.read(native.device()).unwrap().as_slice::<f32>().to_vec();
+
+        let a1 = (0.5f32 + 0.1).tanh();
+        let a2 = (1.0 + 0.1 + 0.25 * a1).tanh();
+        let b1 = (-0.5f32 + 0.1).tanh();
+        let b2 = (0.25 + 0.1 + 0.25 * b1).tanh();
+        for (actual, expected) in output.iter().zip(&[a1, a2, b1, b2]) {
+            assert!((actual - expected).abs() < 1e-6, "{:?}", output);
+        }
+    }
+
+    #[test]
+    fn rnn_stacked_weight_gradients() {
+        // both layers keep intermediate results in their workspace between the backward passes
+        let mut net_cfg = SequentialConfig::default();
+        net_cfg.add_input("data", &[2, 3, 2]);
+        for (name, hidden_size) in &[("rnn1", 3), ("rnn2", 2)] {
+            net_cfg.add_layer(LayerConfig::new(
+                name,
+                RnnConfig {
+                    hidden_size: *hidden_size,
+                    num_layers: 1,
+                    dropout_probability: 0.0,
+                    dropout_seed: 0,
+                    rnn_type: RnnNetworkMode::Tanh,
+                    input_mode: RnnInputMode::LinearInput,
+                    direction_mode: DirectionMode::UniDirectional,
+                },
+            ));
+        }
+        let mut network = Layer::from_config(Rc::new(native_backend()), &LayerConfig::new("network", net_cfg));
+        let native = native_backend();
+        let input_data: Vec<f32> = (0..12).map(|i| (i as f32 * 0.7).sin()).collect();
+
+        // the loss is the sum of the outputs
+        let mut loss = |network: &mut Layer<Backend<Native>>| -> f32 {
+            let mut input = SharedTensor::<f32>::new(&[2, 3, 2]);
+            write_to_memory(input.write_only(native.device()).unwrap(), &input_data);
+            let output = network.forward(&[Arc::new(RwLock::new(input))])[0].clone();
+            let output = output.read().unwrap();
+            output.read(native.device()).unwrap().as_slice::<f32>().iter().sum()
+        };
+        loss(&mut network);
+        let mut output_gradient = SharedTensor::<f32>::new(&[2, 3, 2]);
+        FillerType::fill_constant(&mut output_gradient, 1.0);
+        network.backward(&[Arc::new(RwLock::new(output_gradient))]);
+
+        // the first weight tensor of every layer holds all parameters
+        let weights = network.learnable_weights_data();
+        let gradients = network.learnable_weights_gradients();
+        for &w in &[0, 2] {
+            let gradient = gradients[w].read().unwrap();
+            let gradient = gradient.read(native.device()).unwrap().as_slice::<f32>().to_vec();
+            for (k, &analytic) in gradient.iter().enumerate() {
+                let h = 1e-2;
+                let mut perturbed = |delta: f32| {
+                    {
+                        let mut weight = weights[w].write().unwrap();
+                        let weight = weight.read_write(native.device()).unwrap().as_mut_slice::<f32>();
+                        weight[k] += delta;
+                    }
+                    loss(&mut network)
+                };
+                let plus = perturbed(h);
+                let minus = perturbed(-2.0 * h);
+                perturbed(h);
+                let numeric = (plus - minus) / (2.0 * h);
+                assert!((analytic - numeric).abs() < 1e-2, "{}[{}]: {}", w, k, numeric);
+            }
+        }
+    }
 }
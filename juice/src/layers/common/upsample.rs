@@ -0,0 +1,298 @@
+//! Resizes the spatial dimensions of the input without any learnable parameters.
+//!
+//! Used to bring coarse feature maps back to a finer resolution, e.g. in feature
+//! pyramids or the decoder of a segmentation network.
+//! *See [UpsampleMode][upsample_mode]*
+//!
+//! [upsample_mode]: ./enum.UpsampleMode.html
+//!
+//! ## Input Data
+//!
+//! The layer expects the input to be in either 3D NCW (1 spatial dimension),
+//! 4D NCHW (2 spatial dimensions) or 5D NCDHW (3 spatial dimensions) format.
+//!
+//! ## Output Shape
+//!
+//! The output keeps `N` and `C` of the input, its spatial dimensions are either the input
+//! dimensions multiplied by the scale factors and rounded down, or a fixed target size.
+//! *See [UpsampleSize][upsample_size]*
+//!
+//! [upsample_size]: ./enum.UpsampleSize.html
+
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::conn;
+use crate::juice_capnp::upsample_config as capnp_config;
+use crate::juice_capnp::UpsampleMode as CapnpUpsampleMode;
+use crate::layer::*;
+use crate::util::ArcLock;
+
+#[derive(Debug, Clone)]
+/// [Upsample](./index.html) Layer
+pub struct Upsample {
+    mode: UpsampleMode,
+    size: UpsampleSize,
+}
+
+impl Upsample {
+    /// Create an Upsample layer from an UpsampleConfig.
+    pub fn from_config(config: &UpsampleConfig) -> Upsample {
+        Upsample {
+            mode: config.mode,
+            size: config.size.clone(),
+        }
+    }
+
+    fn calculate_output_shape(&self, input_shape: &[usize]) -> Vec<usize> {
+        let num_spatial_dims = match input_shape.len() {
+            3 => 1,
+            4 => 2,
+            5 => 3,
+            _ => panic!("An upsample layer currently only supports 3D, 4D or 5D input."),
+        };
+        let spatial_dims: Vec<usize> = match self.size {
+            UpsampleSize::Scale(ref scale) => {
+                let scale = Self::spatial_values(scale, num_spatial_dims);
+                input_shape[2..]
+                    .iter()
+                    .zip(scale)
+                    .map(|(&dim, factor)| (dim as f32 * factor).floor() as usize)
+                    .collect()
+            }
+            UpsampleSize::Shape(ref shape) => Self::spatial_values(shape, num_spatial_dims),
+        };
+        if spatial_dims.iter().any(|&dim| dim == 0) {
+            panic!(
+                "Upsampling an input of shape {:?} by {:?} leaves no elements",
+                input_shape, self.size
+            );
+        }
+        let mut output_shape = input_shape[0..2].to_vec();
+        output_shape.extend(spatial_dims);
+        output_shape
+    }
+
+    /// Expands a single value to all spatial dimensions.
+    fn spatial_values<V: Copy + ::std::fmt::Debug>(values: &[V], num_spatial_dims: usize) -> Vec<V> {
+        match values.len() {
+            1 => vec![values[0]; num_spatial_dims],
+            n if n == num_spatial_dims => values.to_vec(),
+            _ => panic!(
+                "Must either specify one value or one value per spatial dimension. Supplied {:?}",
+                values
+            ),
+        }
+    }
+}
+
+impl<B: IBackend + conn::Upsample<f32>> ILayer<B> for Upsample {
+    impl_ilayer_common!();
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let inp = input_data[0].read().unwrap();
+        let input_shape = inp.desc();
+        input_gradient[0].write().unwrap().resize(input_shape).unwrap();
+        let output_shape = self.calculate_output_shape(input_shape);
+        output_data[0].write().unwrap().resize(&output_shape).unwrap();
+        output_gradient[0].write().unwrap().resize(&output_shape).unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Upsample<f32>> ComputeOutput<f32, B> for Upsample {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        backend
+            .upsample(self.mode.to_conn(), input_data[0], output_data[0])
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Upsample<f32>> ComputeInputGradient<f32, B> for Upsample {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        backend
+            .upsample_grad(self.mode.to_conn(), output_gradients[0], input_gradients[0])
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Upsample<f32>> ComputeParametersGradient<f32, B> for Upsample {}
+
+#[derive(Debug, Clone)]
+/// Specifies configuration parameters for an Upsample Layer.
+pub struct UpsampleConfig {
+    /// The UpsampleMode to use
+    pub mode: UpsampleMode,
+    /// The scale factors or the target size of the spatial dimensions
+    pub size: UpsampleSize,
+}
+
+impl Into<LayerType> for UpsampleConfig {
+    fn into(self) -> LayerType {
+        LayerType::Upsample(self)
+    }
+}
+
+impl<'a> CapnpWrite<'a> for UpsampleConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the UpsampleConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.reborrow().set_mode(self.mode.to_capnp());
+        match self.size {
+            UpsampleSize::Scale(ref scale) => {
+                let mut factors = builder.reborrow().init_size().init_scale(scale.len() as u32);
+                for (i, factor) in scale.iter().enumerate() {
+                    factors.set(i as u32, *factor);
+                }
+            }
+            UpsampleSize::Shape(ref shape) => {
+                let mut dims = builder.reborrow().init_size().init_shape(shape.len() as u32);
+                for (i, dim) in shape.iter().enumerate() {
+                    dims.set(i as u32, *dim as u64);
+                }
+            }
+        }
+    }
+}
+
+impl<'a> CapnpRead<'a> for UpsampleConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        let mode = UpsampleMode::from_capnp(reader.get_mode().unwrap());
+        let size = match reader.get_size().which().unwrap() {
+            capnp_config::size::Which::Scale(read_scale) => {
+                let read_scale = read_scale.unwrap();
+                let mut scale = Vec::new();
+                for i in 0..read_scale.len() {
+                    scale.push(read_scale.get(i))
+                }
+                UpsampleSize::Scale(scale)
+            }
+            capnp_config::size::Which::Shape(read_shape) => {
+                let read_shape = read_shape.unwrap();
+                let mut shape = Vec::new();
+                for i in 0..read_shape.len() {
+                    shape.push(read_shape.get(i) as usize)
+                }
+                UpsampleSize::Shape(shape)
+            }
+        };
+
+        UpsampleConfig { mode, size }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// The different interpolations an Upsample Layer can use.
+pub enum UpsampleMode {
+    /// Every output value is the value of the nearest input value.
+    Nearest,
+    /// Every output value is interpolated linearly from the nearest input values
+    /// along each spatial dimension.
+    Bilinear,
+}
+
+impl UpsampleMode {
+    /// Return the corresponding coaster-nn value.
+    fn to_conn(&self) -> conn::UpsampleMode {
+        match *self {
+            UpsampleMode::Nearest => conn::UpsampleMode::Nearest,
+            UpsampleMode::Bilinear => conn::UpsampleMode::Bilinear,
+        }
+    }
+
+    /// Return the corresponding Cap'n Proto value.
+    fn to_capnp(&self) -> CapnpUpsampleMode {
+        match *self {
+            UpsampleMode::Nearest => CapnpUpsampleMode::Nearest,
+            UpsampleMode::Bilinear => CapnpUpsampleMode::Bilinear,
+        }
+    }
+
+    /// Return the enum value for a Cap'n Proto value.
+    fn from_capnp(value: CapnpUpsampleMode) -> Self {
+        match value {
+            CapnpUpsampleMode::Nearest => UpsampleMode::Nearest,
+            CapnpUpsampleMode::Bilinear => UpsampleMode::Bilinear,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// How the spatial dimensions of the output of an Upsample Layer are determined.
+///
+/// Both variants take either a single value for all spatial dimensions or one value
+/// per spatial dimension.
+pub enum UpsampleSize {
+    /// Multiply the spatial dimensions of the input by these factors.
+    Scale(Vec<f32>),
+    /// Resize the spatial dimensions to this shape, independent of the input.
+    Shape(Vec<usize>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Upsample, UpsampleConfig, UpsampleMode, UpsampleSize};
+
+    fn layer(size: UpsampleSize) -> Upsample {
+        Upsample::from_config(&UpsampleConfig {
+            mode: UpsampleMode::Nearest,
+            size,
+        })
+    }
+
+    #[test]
+    fn correct_shapes() {
+        assert_eq!(
+            vec![2, 3, 8, 8],
+            layer(UpsampleSize::Scale(vec![2.0])).calculate_output_shape(&[2, 3, 4, 4])
+        );
+        assert_eq!(
+            vec![2, 3, 6, 4],
+            layer(UpsampleSize::Scale(vec![1.5, 1.0])).calculate_output_shape(&[2, 3, 4, 4])
+        );
+        assert_eq!(
+            vec![1, 2, 7, 5, 3],
+            layer(UpsampleSize::Shape(vec![7, 5, 3])).calculate_output_shape(&[1, 2, 2, 2, 2])
+        );
+        assert_eq!(
+            vec![1, 2, 16],
+            layer(UpsampleSize::Shape(vec![16])).calculate_output_shape(&[1, 2, 5])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn one_value_per_spatial_dim() {
+        layer(UpsampleSize::Shape(vec![4, 4])).calculate_output_shape(&[1, 1, 2, 2, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn output_not_empty() {
+        layer(UpsampleSize::Scale(vec![0.25])).calculate_output_shape(&[1, 1, 2, 2]);
+    }
+}
@@ -267,6 +267,24 @@ impl<B: IBackend + LayerOps<f32> + 'static> ILayer<B> for Sequential<B> {
         Some(names)
     }
 
+    fn non_learnable_weights(&self) -> Option<Vec<ArcLock<SharedTensor<f32>>>> {
+        let weights = self
+            .layers
+            .iter()
+            .flat_map(|layer| layer.borrow().non_learnable_weights_data())
+            .collect();
+        Some(weights)
+    }
+
+    fn non_learnable_weights_names(&self) -> Option<Vec<String>> {
+        let names = self
+            .layers
+            .iter()
+            .flat_map(|layer| layer.borrow().non_learnable_weights_names())
+            .collect();
+        Some(names)
+    }
+
     fn resize_shared_workspace(
         &mut self,
         backend: Rc<B>,
@@ -292,9 +310,29 @@ impl<B: IBackend + LayerOps<f32> + 'static> ILayer<B> for Sequential<B> {
         weights_data: &[ArcLock<SharedTensor<f32>>],
         output_data: &mut [ArcLock<SharedTensor<f32>>],
     ) {
+        // In-place layers compute on the registered input blob, which they share with the
+        // following layers, so inputs they consume are copied into it instead of replacing it.
+        let mut in_place_inputs = Vec::with_capacity(input_data.len());
+        for (input, input_name) in input_data.iter().zip(self.input_tensor_names.iter()) {
+            let in_place = self.layers.iter().any(|layer| {
+                let layer = layer.borrow();
+                layer.is_using_in_place() && &layer.input_blob_names[0] == input_name
+            });
+            if in_place {
+                let blob = self.registry[input_name].0.clone();
+                if !Arc::ptr_eq(&blob, input) {
+                    let input = input.read().unwrap();
+                    let mut blob = blob.write().unwrap();
+                    blob.resize(input.desc()).unwrap();
+                    backend.copy(&input, &mut blob).unwrap();
+                }
+            }
+            in_place_inputs.push(in_place);
+        }
+
         for layer in &self.layers {
             for (i, (input, input_name)) in input_data.iter().zip(self.input_tensor_names.iter()).enumerate() {
-                if &layer.borrow().input_blob_names[i] == input_name {
+                if !in_place_inputs[i] && &layer.borrow().input_blob_names[i] == input_name {
                     layer.borrow_mut().input_blobs_data[i] = input.clone();
                 }
             }
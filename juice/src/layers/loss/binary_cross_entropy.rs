@@ -0,0 +1,198 @@
+//! Provides Loss & Gradient for Binary Cross-Entropy
+//!
+//! Calculation of the [cross-entropy][1] between predicted probabilities and targets for
+//! binary and multi-label classification, where every element is a class of its own.
+//! The predictions are usually the output of a `Sigmoid` layer, prefer
+//! [BinaryCrossEntropyWithLogits][logits] which is numerically more stable.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Cross_entropy
+//! [logits]: ../binary_cross_entropy_with_logits/index.html
+//!
+//! The predictions may have any dimensions, the targets have the dimensions of the predictions
+//! and are in the range `[0, 1]`. The loss of every element is combined as configured by the
+//! [LossReduction][reduction].
+//!
+//! [reduction]: ../enum.LossReduction.html
+
+use super::{batch_size, sample_weights, LossReduction};
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::juice_capnp::binary_cross_entropy_config as capnp_config;
+use crate::layer::*;
+use crate::util::{native_backend, write_to_memory, ArcLock};
+
+/// The smallest logarithm of a probability, which keeps the loss finite for probabilities of `0`.
+const MIN_LOG: f32 = -100f32;
+/// The smallest value the gradient of a prediction is divided by.
+const EPSILON: f32 = 1e-12;
+
+#[derive(Debug, Clone)]
+#[allow(missing_copy_implementations)]
+/// Binary Cross-Entropy Loss Layer
+pub struct BinaryCrossEntropy {
+    reduction: LossReduction,
+}
+
+impl BinaryCrossEntropy {
+    /// Create a BinaryCrossEntropy layer from a BinaryCrossEntropyConfig.
+    pub fn from_config(config: &BinaryCrossEntropyConfig) -> BinaryCrossEntropy {
+        BinaryCrossEntropy {
+            reduction: config.reduction,
+        }
+    }
+}
+
+impl<B: IBackend> ILayer<B> for BinaryCrossEntropy {
+    impl_ilayer_loss!();
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let input_desc = input_data[0].read().unwrap().desc().clone();
+        let output_shape = self.reduction.output_shape(&input_desc);
+        input_gradient[0].write().unwrap().resize(&input_desc).unwrap();
+        output_data[0].write().unwrap().resize(&output_shape).unwrap();
+        output_gradient[0].write().unwrap().resize(&output_shape).unwrap();
+    }
+}
+
+impl<B: IBackend> ComputeOutput<f32, B> for BinaryCrossEntropy {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        let native = native_backend();
+        let predictions = input_data[0].read(native.device()).unwrap().as_slice::<f32>();
+        let targets = input_data[1].read(native.device()).unwrap().as_slice::<f32>();
+
+        let losses: Vec<f32> = predictions
+            .iter()
+            .zip(targets)
+            .map(|(&prediction, &target)| {
+                -target * prediction.ln().max(MIN_LOG) - (1f32 - target) * (1f32 - prediction).ln().max(MIN_LOG)
+            })
+            .collect();
+        let weights = sample_weights(input_data, losses.len());
+        let normalizer = self
+            .reduction
+            .normalizer(losses.len() as f32, batch_size(input_data[0].desc()));
+
+        write_to_memory(
+            output_data[0].write_only(native.device()).unwrap(),
+            &self.reduction.reduce(&losses, weights.as_deref(), normalizer),
+        );
+    }
+}
+
+impl<B: IBackend> ComputeInputGradient<f32, B> for BinaryCrossEntropy {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        let native = native_backend();
+        let predictions = input_data[0].read(native.device()).unwrap().as_slice::<f32>();
+        let targets = input_data[1].read(native.device()).unwrap().as_slice::<f32>();
+        let count = predictions.len();
+        let weights = sample_weights(input_data, count);
+        let losses_gradient = self
+            .reduction
+            .losses_gradient(
+                count,
+                weights.as_deref(),
+                self.reduction
+                    .normalizer(count as f32, batch_size(input_data[0].desc())),
+                output_gradients.first().copied(),
+            )
+            .unwrap();
+
+        // Gradient is calculated as (Predictions - Targets) / (Predictions * (1 - Predictions))
+        let gradient: Vec<f32> = predictions
+            .iter()
+            .zip(targets)
+            .zip(losses_gradient)
+            .map(|((&prediction, &target), loss_gradient)| {
+                (prediction - target) / (prediction * (1f32 - prediction)).max(EPSILON) * loss_gradient
+            })
+            .collect();
+
+        write_to_memory(input_gradients[0].write_only(native.device()).unwrap(), &gradient);
+    }
+}
+
+impl<B: IBackend> ComputeParametersGradient<f32, B> for BinaryCrossEntropy {}
+
+#[derive(Debug, Copy, Clone, Default)]
+/// Specifies configuration parameters for a BinaryCrossEntropy Layer.
+pub struct BinaryCrossEntropyConfig {
+    /// How the losses of all elements are combined.
+    pub reduction: LossReduction,
+}
+
+impl<'a> CapnpWrite<'a> for BinaryCrossEntropyConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the BinaryCrossEntropyConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.set_reduction(self.reduction.to_capnp());
+    }
+}
+
+impl<'a> CapnpRead<'a> for BinaryCrossEntropyConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        BinaryCrossEntropyConfig {
+            reduction: LossReduction::from_capnp(reader.get_reduction().unwrap()),
+        }
+    }
+}
+
+impl Into<LayerType> for BinaryCrossEntropyConfig {
+    fn into(self) -> LayerType {
+        LayerType::BinaryCrossEntropy(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::testing::{assert_gradient, gradient, loss};
+    use super::{BinaryCrossEntropy, BinaryCrossEntropyConfig};
+
+    #[test]
+    fn loss_and_gradient() {
+        let layer = BinaryCrossEntropy::from_config(&BinaryCrossEntropyConfig::default());
+        let predictions = [0.8, 0.3, 0.6, 0.1];
+        let targets = [1.0, 0.0, 0.5, 0.0];
+        let expected = -(0.8f32.ln() + 0.7f32.ln() + 0.5 * 0.6f32.ln() + 0.5 * 0.4f32.ln() + 0.9f32.ln()) / 4.0;
+        assert!((loss(&layer, &[2, 2], &predictions, &[2, 2], &targets) - expected).abs() < 1e-6);
+        assert_gradient(&layer, &[2, 2], &predictions, &[2, 2], &targets);
+    }
+
+    #[test]
+    fn finite_for_saturated_predictions() {
+        let layer = BinaryCrossEntropy::from_config(&BinaryCrossEntropyConfig::default());
+        let predictions = [0.0, 1.0, 0.0, 1.0];
+        let targets = [0.0, 1.0, 1.0, 0.0];
+        // the logarithm is clamped to -100
+        assert_eq!(50.0, loss(&layer, &[4], &predictions, &[4], &targets));
+        let gradient = gradient(&layer, &[4], &predictions, &[4], &targets);
+        assert!(gradient.iter().all(|value| value.is_finite()));
+        assert_eq!([0.0, 0.0], gradient[..2]);
+        assert!(gradient[2] < 0.0 && gradient[3] > 0.0);
+    }
+}
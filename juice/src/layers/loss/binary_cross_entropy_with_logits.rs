@@ -0,0 +1,214 @@
+//! Provides Loss & Gradient for Binary Cross-Entropy of a Sigmoid
+//!
+//! Combines a sigmoid of the input with the [Binary Cross-Entropy][bce] to a target,
+//! so a network for multi-label classification ends in unnormalized scores (logits)
+//! instead of a `Sigmoid` layer. Computing both in one step stays numerically stable for large scores.
+//!
+//! [bce]: ../binary_cross_entropy/index.html
+//!
+//! The scores may have any dimensions, the targets have the dimensions of the scores and are
+//! in the range `[0, 1]`. The loss of every element is combined as configured by the
+//! [LossReduction][reduction].
+//!
+//! [reduction]: ../enum.LossReduction.html
+
+use super::{batch_size, sample_weights, LossReduction};
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::juice_capnp::binary_cross_entropy_with_logits_config as capnp_config;
+use crate::layer::*;
+use crate::util::{native_backend, write_to_memory, ArcLock};
+
+#[derive(Debug, Clone)]
+#[allow(missing_copy_implementations)]
+/// Binary Cross-Entropy with Logits Loss Layer
+pub struct BinaryCrossEntropyWithLogits {
+    pos_weight: f32,
+    reduction: LossReduction,
+}
+
+impl BinaryCrossEntropyWithLogits {
+    /// Create a BinaryCrossEntropyWithLogits layer from a BinaryCrossEntropyWithLogitsConfig.
+    pub fn from_config(config: &BinaryCrossEntropyWithLogitsConfig) -> BinaryCrossEntropyWithLogits {
+        BinaryCrossEntropyWithLogits {
+            pos_weight: config.pos_weight,
+            reduction: config.reduction,
+        }
+    }
+
+    /// The loss of a single score.
+    ///
+    /// `ln(1 + exp(-x))` is computed as `ln(1 + exp(-|x|)) + max(-x, 0)` to not overflow.
+    fn loss(&self, score: f32, target: f32) -> f32 {
+        let log_weight = 1f32 + (self.pos_weight - 1f32) * target;
+        (1f32 - target) * score + log_weight * ((-score.abs()).exp().ln_1p() + (-score).max(0f32))
+    }
+
+    /// The derivative of the loss of a single score.
+    fn loss_grad(&self, score: f32, target: f32) -> f32 {
+        let log_weight = 1f32 + (self.pos_weight - 1f32) * target;
+        let sigmoid = 1f32 / (1f32 + (-score).exp());
+        (1f32 - target) - log_weight * (1f32 - sigmoid)
+    }
+}
+
+impl<B: IBackend> ILayer<B> for BinaryCrossEntropyWithLogits {
+    impl_ilayer_loss!();
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let input_desc = input_data[0].read().unwrap().desc().clone();
+        let output_shape = self.reduction.output_shape(&input_desc);
+        input_gradient[0].write().unwrap().resize(&input_desc).unwrap();
+        output_data[0].write().unwrap().resize(&output_shape).unwrap();
+        output_gradient[0].write().unwrap().resize(&output_shape).unwrap();
+    }
+}
+
+impl<B: IBackend> ComputeOutput<f32, B> for BinaryCrossEntropyWithLogits {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        let native = native_backend();
+        let scores = input_data[0].read(native.device()).unwrap().as_slice::<f32>();
+        let targets = input_data[1].read(native.device()).unwrap().as_slice::<f32>();
+
+        let losses: Vec<f32> = scores
+            .iter()
+            .zip(targets)
+            .map(|(&score, &target)| self.loss(score, target))
+            .collect();
+        let weights = sample_weights(input_data, losses.len());
+        let normalizer = self
+            .reduction
+            .normalizer(losses.len() as f32, batch_size(input_data[0].desc()));
+
+        write_to_memory(
+            output_data[0].write_only(native.device()).unwrap(),
+            &self.reduction.reduce(&losses, weights.as_deref(), normalizer),
+        );
+    }
+}
+
+impl<B: IBackend> ComputeInputGradient<f32, B> for BinaryCrossEntropyWithLogits {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        let native = native_backend();
+        let scores = input_data[0].read(native.device()).unwrap().as_slice::<f32>();
+        let targets = input_data[1].read(native.device()).unwrap().as_slice::<f32>();
+        let count = scores.len();
+        let weights = sample_weights(input_data, count);
+        let losses_gradient = self
+            .reduction
+            .losses_gradient(
+                count,
+                weights.as_deref(),
+                self.reduction
+                    .normalizer(count as f32, batch_size(input_data[0].desc())),
+                output_gradients.first().copied(),
+            )
+            .unwrap();
+
+        let gradient: Vec<f32> = scores
+            .iter()
+            .zip(targets)
+            .zip(losses_gradient)
+            .map(|((&score, &target), loss_gradient)| self.loss_grad(score, target) * loss_gradient)
+            .collect();
+
+        write_to_memory(input_gradients[0].write_only(native.device()).unwrap(), &gradient);
+    }
+}
+
+impl<B: IBackend> ComputeParametersGradient<f32, B> for BinaryCrossEntropyWithLogits {}
+
+#[derive(Debug, Copy, Clone)]
+/// Specifies configuration parameters for a BinaryCrossEntropyWithLogits Layer.
+pub struct BinaryCrossEntropyWithLogitsConfig {
+    /// The weight of the loss of positive targets relative to negative ones,
+    /// values above `1` trade precision for recall on imbalanced labels.
+    ///
+    /// Defaults to `1.0`
+    pub pos_weight: f32,
+    /// How the losses of all elements are combined.
+    pub reduction: LossReduction,
+}
+
+impl Default for BinaryCrossEntropyWithLogitsConfig {
+    fn default() -> BinaryCrossEntropyWithLogitsConfig {
+        BinaryCrossEntropyWithLogitsConfig {
+            pos_weight: 1.0,
+            reduction: LossReduction::default(),
+        }
+    }
+}
+
+impl<'a> CapnpWrite<'a> for BinaryCrossEntropyWithLogitsConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the BinaryCrossEntropyWithLogitsConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.set_pos_weight(self.pos_weight);
+        builder.set_reduction(self.reduction.to_capnp());
+    }
+}
+
+impl<'a> CapnpRead<'a> for BinaryCrossEntropyWithLogitsConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        BinaryCrossEntropyWithLogitsConfig {
+            pos_weight: reader.get_pos_weight(),
+            reduction: LossReduction::from_capnp(reader.get_reduction().unwrap()),
+        }
+    }
+}
+
+impl Into<LayerType> for BinaryCrossEntropyWithLogitsConfig {
+    fn into(self) -> LayerType {
+        LayerType::BinaryCrossEntropyWithLogits(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BinaryCrossEntropyWithLogits, BinaryCrossEntropyWithLogitsConfig};
+
+    #[test]
+    fn stable_for_large_scores() {
+        let layer = BinaryCrossEntropyWithLogits::from_config(&BinaryCrossEntropyWithLogitsConfig::default());
+        assert_eq!(0.0, layer.loss(200.0, 1.0));
+        assert_eq!(200.0, layer.loss(-200.0, 1.0));
+        assert_eq!(-1.0, layer.loss_grad(-200.0, 1.0));
+    }
+
+    #[test]
+    fn weighs_positive_targets() {
+        let layer = BinaryCrossEntropyWithLogits::from_config(&BinaryCrossEntropyWithLogitsConfig {
+            pos_weight: 3.0,
+            ..BinaryCrossEntropyWithLogitsConfig::default()
+        });
+        assert!((layer.loss(0.0, 1.0) - 3.0 * 2f32.ln()).abs() < 1e-6);
+        assert!((layer.loss(0.0, 0.0) - 2f32.ln()).abs() < 1e-6);
+        assert_eq!(-1.5, layer.loss_grad(0.0, 1.0));
+    }
+}
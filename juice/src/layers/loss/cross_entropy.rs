@@ -0,0 +1,320 @@
+//! Provides Loss & Gradient for the Cross-Entropy of a Softmax
+//!
+//! Combines a softmax over the class scores of the input with the [cross-entropy][1]
+//! to a target, so the network ends in unnormalized scores instead of a `LogSoftmax` layer.
+//! Computing both in one step stays numerically stable for large scores.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Cross_entropy
+//!
+//! ## Input Data
+//!
+//! The first input holds the scores of dimensions `[N, C]` or `[N, C, ...]`, the classes are
+//! the second dimension, so e.g. every pixel of an `[N, C, H, W]` input is a sample.
+//!
+//! The second input holds the target, either the class index of every sample, e.g. of
+//! dimensions `[N]` or `[N, 1]`, or a probability for every class in the dimensions of the scores.
+//!
+//! ## Output
+//!
+//! The loss of every sample, combined as configured by the [LossReduction][reduction].
+//! With class index targets the mean is the sum of the losses divided by the sum of the weights
+//! of the target classes, samples whose class is the `ignore_index` are left out.
+//! With probability targets it is divided by the number of samples.
+//!
+//! [reduction]: ../enum.LossReduction.html
+
+use super::{batch_size, sample_weights, LossReduction};
+use crate::capnp_util::*;
+use crate::co::{IBackend, ITensorDesc, SharedTensor};
+use crate::conn;
+use crate::juice_capnp::cross_entropy_config as capnp_config;
+use crate::layer::*;
+use crate::util::{native_backend, write_to_memory, ArcLock};
+
+#[derive(Debug)]
+/// CrossEntropy Loss Layer
+pub struct CrossEntropy {
+    class_weights: Option<Vec<f32>>,
+    ignore_index: Option<usize>,
+    reduction: LossReduction,
+
+    class_weights_tensor: Option<SharedTensor<f32>>,
+}
+
+impl CrossEntropy {
+    /// Create a CrossEntropy layer from a CrossEntropyConfig.
+    pub fn from_config(config: &CrossEntropyConfig) -> CrossEntropy {
+        let class_weights_tensor = config.class_weights.as_ref().map(|weights| {
+            let native = native_backend();
+            let mut tensor = SharedTensor::<f32>::new(&[weights.len()]);
+            write_to_memory(tensor.write_only(native.device()).unwrap(), weights);
+            tensor
+        });
+
+        CrossEntropy {
+            class_weights: config.class_weights.clone(),
+            ignore_index: config.ignore_index,
+            reduction: config.reduction,
+
+            class_weights_tensor,
+        }
+    }
+
+    /// The dimensions of the losses of every sample, which are those of the scores
+    /// without the class dimension.
+    fn sample_shape(input_shape: &[usize]) -> Vec<usize> {
+        if input_shape.len() < 2 {
+            panic!("CrossEntropy layer needs inputs of [N, C] or [N, C, ...]");
+        }
+        let mut shape = input_shape.to_vec();
+        shape.remove(1);
+        shape
+    }
+
+    /// The value the sum of the losses of all samples is divided by.
+    fn normalizer(&self, input_shape: &[usize], target: &SharedTensor<f32>) -> f32 {
+        if target.desc() == input_shape {
+            return (input_shape.iter().product::<usize>() / input_shape[1]) as f32;
+        }
+        let native = native_backend();
+        let classes = target.read(native.device()).unwrap().as_slice::<f32>();
+        classes
+            .iter()
+            .map(|&class| class as usize)
+            .filter(|&class| Some(class) != self.ignore_index)
+            .map(|class| self.class_weights.as_ref().map_or(1f32, |weights| weights[class]))
+            .sum()
+    }
+}
+
+impl<B: IBackend + conn::SoftmaxCrossEntropy<f32>> ILayer<B> for CrossEntropy {
+    fn exact_num_output_blobs(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    fn min_input_blobs(&self) -> usize {
+        // the sample weights are an optional third input
+        2
+    }
+
+    fn auto_output_blobs(&self) -> bool {
+        true
+    }
+
+    fn loss_weight(&self, output_id: usize) -> Option<f32> {
+        if output_id == 0 {
+            Some(1f32)
+        } else {
+            None
+        }
+    }
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let data = input_data[0].read().unwrap();
+        let output_shape = self.reduction.output_shape(&Self::sample_shape(data.desc()));
+
+        input_gradient[0].write().unwrap().resize(data.desc()).unwrap();
+        output_data[0].write().unwrap().resize(&output_shape).unwrap();
+        output_gradient[0].write().unwrap().resize(&output_shape).unwrap();
+    }
+}
+
+impl<B: IBackend + conn::SoftmaxCrossEntropy<f32>> ComputeOutput<f32, B> for CrossEntropy {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        let scores = input_data[0];
+        let target = input_data[1];
+
+        let mut losses = SharedTensor::<f32>::new(&Self::sample_shape(scores.desc()));
+        backend
+            .softmax_cross_entropy(
+                scores,
+                target,
+                self.class_weights_tensor.as_ref(),
+                self.ignore_index,
+                &mut losses,
+            )
+            .unwrap();
+
+        let native = native_backend();
+        let losses = losses.read(native.device()).unwrap().as_slice::<f32>();
+        let weights = sample_weights(input_data, losses.len());
+        let normalizer = self
+            .reduction
+            .normalizer(self.normalizer(scores.desc(), target), batch_size(scores.desc()));
+
+        write_to_memory(
+            output_data[0].write_only(native.device()).unwrap(),
+            &self.reduction.reduce(losses, weights.as_deref(), normalizer),
+        );
+    }
+}
+
+impl<B: IBackend + conn::SoftmaxCrossEntropy<f32>> ComputeInputGradient<f32, B> for CrossEntropy {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        let scores = input_data[0];
+        let target = input_data[1];
+
+        let sample_shape = Self::sample_shape(scores.desc());
+        let weights = sample_weights(input_data, sample_shape.size());
+        let normalizer = self
+            .reduction
+            .normalizer(self.normalizer(scores.desc(), target), batch_size(scores.desc()));
+        let mut losses_diff = SharedTensor::<f32>::new(&sample_shape);
+        let native = native_backend();
+        write_to_memory(
+            losses_diff.write_only(native.device()).unwrap(),
+            &self
+                .reduction
+                .losses_gradient(
+                    sample_shape.size(),
+                    weights.as_deref(),
+                    normalizer,
+                    output_gradients.first().copied(),
+                )
+                .unwrap(),
+        );
+
+        backend
+            .softmax_cross_entropy_grad(
+                scores,
+                target,
+                self.class_weights_tensor.as_ref(),
+                self.ignore_index,
+                &losses_diff,
+                input_gradients[0],
+            )
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::SoftmaxCrossEntropy<f32>> ComputeParametersGradient<f32, B> for CrossEntropy {}
+
+#[derive(Debug, Clone, Default)]
+/// Specifies configuration parameters for a CrossEntropy Layer.
+pub struct CrossEntropyConfig {
+    /// The weight of every class, all classes weigh the same if `None`.
+    pub class_weights: Option<Vec<f32>>,
+    /// The class index of samples that do not contribute to the loss.
+    ///
+    /// Only applies to class index targets.
+    pub ignore_index: Option<usize>,
+    /// How the losses of all samples are combined.
+    pub reduction: LossReduction,
+}
+
+impl<'a> CapnpWrite<'a> for CrossEntropyConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the CrossEntropyConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        match self.class_weights {
+            Some(ref class_weights) => {
+                let mut weights = builder
+                    .reborrow()
+                    .init_class_weights()
+                    .init_weights(class_weights.len() as u32);
+                for (i, weight) in class_weights.iter().enumerate() {
+                    weights.set(i as u32, *weight);
+                }
+            }
+            None => builder.reborrow().init_class_weights().set_none(()),
+        }
+        match self.ignore_index {
+            Some(index) => builder.reborrow().init_ignore_index().set_index(index as u64),
+            None => builder.reborrow().init_ignore_index().set_none(()),
+        }
+        builder.set_reduction(self.reduction.to_capnp());
+    }
+}
+
+impl<'a> CapnpRead<'a> for CrossEntropyConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        let class_weights = match reader.get_class_weights().which().unwrap() {
+            capnp_config::class_weights::Which::Weights(read_weights) => {
+                let read_weights = read_weights.unwrap();
+                let mut weights = Vec::new();
+                for i in 0..read_weights.len() {
+                    weights.push(read_weights.get(i))
+                }
+                Some(weights)
+            }
+            capnp_config::class_weights::Which::None(()) => None,
+        };
+        let ignore_index = match reader.get_ignore_index().which().unwrap() {
+            capnp_config::ignore_index::Which::Index(index) => Some(index as usize),
+            capnp_config::ignore_index::Which::None(()) => None,
+        };
+
+        let reduction = LossReduction::from_capnp(reader.get_reduction().unwrap());
+
+        CrossEntropyConfig {
+            class_weights,
+            ignore_index,
+            reduction,
+        }
+    }
+}
+
+impl Into<LayerType> for CrossEntropyConfig {
+    fn into(self) -> LayerType {
+        LayerType::CrossEntropy(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CrossEntropy, CrossEntropyConfig};
+    use crate::co::SharedTensor;
+    use crate::util::{native_backend, write_to_memory};
+
+    fn target(dims: &[usize], data: &[f32]) -> SharedTensor<f32> {
+        let mut tensor = SharedTensor::<f32>::new(&dims);
+        write_to_memory(tensor.write_only(native_backend().device()).unwrap(), data);
+        tensor
+    }
+
+    #[test]
+    fn sample_shape_drops_classes() {
+        assert_eq!(vec![4], CrossEntropy::sample_shape(&[4, 10]));
+        assert_eq!(vec![4, 8, 8], CrossEntropy::sample_shape(&[4, 3, 8, 8]));
+    }
+
+    #[test]
+    fn normalizer_weighs_target_classes() {
+        let layer = CrossEntropy::from_config(&CrossEntropyConfig {
+            class_weights: Some(vec![1.0, 2.0, 3.0]),
+            ignore_index: Some(0),
+            ..CrossEntropyConfig::default()
+        });
+        let classes = target(&[4, 1], &[0.0, 1.0, 2.0, 2.0]);
+        assert_eq!(8.0, layer.normalizer(&[4, 3], &classes));
+
+        let probabilities = target(&[2, 3, 2], &[0.5; 12]);
+        assert_eq!(4.0, layer.normalizer(&[2, 3, 2], &probabilities));
+    }
+}
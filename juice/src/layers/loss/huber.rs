@@ -0,0 +1,217 @@
+//! Provides Loss & Gradient for the Huber Loss
+//!
+//! The [Huber loss][1] is quadratic for errors up to `delta` and linear beyond, so it is
+//! smooth around zero like the [Mean Squared Error][mse] and robust against outliers like the
+//! [Mean Absolute Error][mae]. With a `delta` of `1` it equals the Smooth L1 loss.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Huber_loss
+//! [mse]: ../mean_squared_error/index.html
+//! [mae]: ../mean_absolute_error/index.html
+//!
+//! The predictions and labels may have any dimensions, the loss of every element is
+//! combined as configured by the [LossReduction][reduction].
+//!
+//! [reduction]: ../enum.LossReduction.html
+
+use super::{batch_size, sample_weights, LossReduction};
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::juice_capnp::huber_config as capnp_config;
+use crate::layer::*;
+use crate::util::{native_backend, write_to_memory, ArcLock};
+
+#[derive(Debug, Clone)]
+#[allow(missing_copy_implementations)]
+/// Huber Loss Layer
+pub struct Huber {
+    delta: f32,
+    reduction: LossReduction,
+}
+
+impl Huber {
+    /// Create a Huber layer from a HuberConfig.
+    pub fn from_config(config: &HuberConfig) -> Huber {
+        Huber {
+            delta: config.delta,
+            reduction: config.reduction,
+        }
+    }
+
+    /// The loss of a single error.
+    fn loss(&self, error: f32) -> f32 {
+        let abs_error = error.abs();
+        if abs_error <= self.delta {
+            0.5 * error * error
+        } else {
+            self.delta * (abs_error - 0.5 * self.delta)
+        }
+    }
+
+    /// The derivative of the loss of a single error.
+    fn loss_grad(&self, error: f32) -> f32 {
+        error.max(-self.delta).min(self.delta)
+    }
+}
+
+impl<B: IBackend> ILayer<B> for Huber {
+    impl_ilayer_loss!();
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let input_desc = input_data[0].read().unwrap().desc().clone();
+        let output_shape = self.reduction.output_shape(&input_desc);
+        input_gradient[0].write().unwrap().resize(&input_desc).unwrap();
+        output_data[0].write().unwrap().resize(&output_shape).unwrap();
+        output_gradient[0].write().unwrap().resize(&output_shape).unwrap();
+    }
+}
+
+impl<B: IBackend> ComputeOutput<f32, B> for Huber {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        let native = native_backend();
+        let predictions = input_data[0].read(native.device()).unwrap().as_slice::<f32>();
+        let labels = input_data[1].read(native.device()).unwrap().as_slice::<f32>();
+
+        let losses: Vec<f32> = predictions
+            .iter()
+            .zip(labels)
+            .map(|(prediction, label)| self.loss(prediction - label))
+            .collect();
+        let weights = sample_weights(input_data, losses.len());
+        let normalizer = self
+            .reduction
+            .normalizer(losses.len() as f32, batch_size(input_data[0].desc()));
+
+        write_to_memory(
+            output_data[0].write_only(native.device()).unwrap(),
+            &self.reduction.reduce(&losses, weights.as_deref(), normalizer),
+        );
+    }
+}
+
+impl<B: IBackend> ComputeInputGradient<f32, B> for Huber {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        let native = native_backend();
+        let predictions = input_data[0].read(native.device()).unwrap().as_slice::<f32>();
+        let labels = input_data[1].read(native.device()).unwrap().as_slice::<f32>();
+        let count = predictions.len();
+        let weights = sample_weights(input_data, count);
+        let losses_gradient = self
+            .reduction
+            .losses_gradient(
+                count,
+                weights.as_deref(),
+                self.reduction
+                    .normalizer(count as f32, batch_size(input_data[0].desc())),
+                output_gradients.first().copied(),
+            )
+            .unwrap();
+
+        let gradient: Vec<f32> = predictions
+            .iter()
+            .zip(labels)
+            .zip(losses_gradient)
+            .map(|((prediction, label), loss_gradient)| self.loss_grad(prediction - label) * loss_gradient)
+            .collect();
+
+        write_to_memory(input_gradients[0].write_only(native.device()).unwrap(), &gradient);
+    }
+}
+
+impl<B: IBackend> ComputeParametersGradient<f32, B> for Huber {}
+
+#[derive(Debug, Copy, Clone)]
+/// Specifies configuration parameters for a Huber Layer.
+pub struct HuberConfig {
+    /// The error at which the loss changes from quadratic to linear.
+    ///
+    /// Defaults to `1.0`
+    pub delta: f32,
+    /// How the losses of all elements are combined.
+    pub reduction: LossReduction,
+}
+
+impl Default for HuberConfig {
+    fn default() -> HuberConfig {
+        HuberConfig {
+            delta: 1.0,
+            reduction: LossReduction::default(),
+        }
+    }
+}
+
+impl<'a> CapnpWrite<'a> for HuberConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the HuberConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.set_delta(self.delta);
+        builder.set_reduction(self.reduction.to_capnp());
+    }
+}
+
+impl<'a> CapnpRead<'a> for HuberConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        HuberConfig {
+            delta: reader.get_delta(),
+            reduction: LossReduction::from_capnp(reader.get_reduction().unwrap()),
+        }
+    }
+}
+
+impl Into<LayerType> for HuberConfig {
+    fn into(self) -> LayerType {
+        LayerType::Huber(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Huber, HuberConfig};
+
+    #[test]
+    fn quadratic_within_delta() {
+        let layer = Huber::from_config(&HuberConfig {
+            delta: 2.0,
+            ..HuberConfig::default()
+        });
+        assert_eq!(0.5, layer.loss(-1.0));
+        assert_eq!(2.0, layer.loss(2.0));
+        assert_eq!(-1.0, layer.loss_grad(-1.0));
+    }
+
+    #[test]
+    fn linear_beyond_delta() {
+        let layer = Huber::from_config(&HuberConfig {
+            delta: 2.0,
+            ..HuberConfig::default()
+        });
+        assert_eq!(6.0, layer.loss(-4.0));
+        assert_eq!(-2.0, layer.loss_grad(-4.0));
+        assert_eq!(2.0, layer.loss_grad(10.0));
+    }
+}
@@ -0,0 +1,224 @@
+//! Provides Loss & Gradient for the Kullback-Leibler Divergence
+//!
+//! Calculation of the [Kullback-Leibler divergence][1] of a target distribution from the
+//! predicted distribution, e.g. to train against soft labels or to distill a model.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Kullback%E2%80%93Leibler_divergence
+//!
+//! ## Input Data
+//!
+//! The predictions are log-probabilities, usually the output of a `LogSoftmax` layer, of
+//! dimensions `[C]`, `[N, C]` or `[N, C, ...]`, the classes are the second dimension.
+//! The targets have the same dimensions and are probabilities, or log-probabilities if
+//! `log_target` is set.
+//!
+//! ## Output
+//!
+//! The divergence of every sample, combined as configured by the [LossReduction][reduction].
+//! The mean is the sum of the divergences divided by the number of samples.
+//!
+//! [reduction]: ../enum.LossReduction.html
+
+use super::{batch_size, class_layout, class_sample_shape, sample_weights, LossReduction};
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::juice_capnp::kl_divergence_config as capnp_config;
+use crate::layer::*;
+use crate::util::{native_backend, write_to_memory, ArcLock};
+
+#[derive(Debug, Clone)]
+#[allow(missing_copy_implementations)]
+/// Kullback-Leibler Divergence Loss Layer
+pub struct KlDivergence {
+    log_target: bool,
+    reduction: LossReduction,
+}
+
+impl KlDivergence {
+    /// Create a KlDivergence layer from a KlDivergenceConfig.
+    pub fn from_config(config: &KlDivergenceConfig) -> KlDivergence {
+        KlDivergence {
+            log_target: config.log_target,
+            reduction: config.reduction,
+        }
+    }
+
+    /// The target probability and its logarithm.
+    fn target(&self, target: f32) -> (f32, f32) {
+        if self.log_target {
+            (target.exp(), target)
+        } else {
+            (target, target.ln())
+        }
+    }
+}
+
+impl<B: IBackend> ILayer<B> for KlDivergence {
+    impl_ilayer_loss!();
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let input_desc = input_data[0].read().unwrap().desc().clone();
+        let output_shape = self.reduction.output_shape(&class_sample_shape(&input_desc));
+        input_gradient[0].write().unwrap().resize(&input_desc).unwrap();
+        output_data[0].write().unwrap().resize(&output_shape).unwrap();
+        output_gradient[0].write().unwrap().resize(&output_shape).unwrap();
+    }
+}
+
+impl<B: IBackend> ComputeOutput<f32, B> for KlDivergence {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        let (outer_num, num_classes, inner_num) = class_layout(input_data[0].desc());
+        let native = native_backend();
+        let log_probabilities = input_data[0].read(native.device()).unwrap().as_slice::<f32>();
+        let targets = input_data[1].read(native.device()).unwrap().as_slice::<f32>();
+
+        let mut losses = vec![0f32; outer_num * inner_num];
+        for (i, (&log_probability, &target)) in log_probabilities.iter().zip(targets).enumerate() {
+            // targets with a probability of 0 do not contribute, even though their logarithm is -inf
+            let (probability, log_target) = self.target(target);
+            if probability > 0f32 {
+                let sample = i / (num_classes * inner_num) * inner_num + i % inner_num;
+                losses[sample] += probability * (log_target - log_probability);
+            }
+        }
+        let weights = sample_weights(input_data, losses.len());
+        let normalizer = self
+            .reduction
+            .normalizer(losses.len() as f32, batch_size(input_data[0].desc()));
+
+        write_to_memory(
+            output_data[0].write_only(native.device()).unwrap(),
+            &self.reduction.reduce(&losses, weights.as_deref(), normalizer),
+        );
+    }
+}
+
+impl<B: IBackend> ComputeInputGradient<f32, B> for KlDivergence {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        let (outer_num, num_classes, inner_num) = class_layout(input_data[0].desc());
+        let native = native_backend();
+        let targets = input_data[1].read(native.device()).unwrap().as_slice::<f32>();
+        let count = outer_num * inner_num;
+        let weights = sample_weights(input_data, count);
+        let losses_gradient = self
+            .reduction
+            .losses_gradient(
+                count,
+                weights.as_deref(),
+                self.reduction
+                    .normalizer(count as f32, batch_size(input_data[0].desc())),
+                output_gradients.first().copied(),
+            )
+            .unwrap();
+
+        // Gradient is calculated as -Targets
+        let gradient: Vec<f32> = targets
+            .iter()
+            .enumerate()
+            .map(|(i, &target)| {
+                let sample = i / (num_classes * inner_num) * inner_num + i % inner_num;
+                -self.target(target).0 * losses_gradient[sample]
+            })
+            .collect();
+
+        write_to_memory(input_gradients[0].write_only(native.device()).unwrap(), &gradient);
+    }
+}
+
+impl<B: IBackend> ComputeParametersGradient<f32, B> for KlDivergence {}
+
+#[derive(Debug, Copy, Clone, Default)]
+/// Specifies configuration parameters for a KlDivergence Layer.
+pub struct KlDivergenceConfig {
+    /// Whether the targets are log-probabilities instead of probabilities.
+    pub log_target: bool,
+    /// How the divergences of all samples are combined.
+    pub reduction: LossReduction,
+}
+
+impl<'a> CapnpWrite<'a> for KlDivergenceConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the KlDivergenceConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.set_log_target(self.log_target);
+        builder.set_reduction(self.reduction.to_capnp());
+    }
+}
+
+impl<'a> CapnpRead<'a> for KlDivergenceConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        KlDivergenceConfig {
+            log_target: reader.get_log_target(),
+            reduction: LossReduction::from_capnp(reader.get_reduction().unwrap()),
+        }
+    }
+}
+
+impl Into<LayerType> for KlDivergenceConfig {
+    fn into(self) -> LayerType {
+        LayerType::KlDivergence(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::testing::{assert_gradient, gradient, loss};
+    use super::{KlDivergence, KlDivergenceConfig};
+
+    fn log_probabilities() -> Vec<f32> {
+        [0.25f32, 0.25, 0.5, 0.2, 0.3, 0.5].iter().map(|p| p.ln()).collect()
+    }
+
+    #[test]
+    fn loss_and_gradient() {
+        let layer = KlDivergence::from_config(&KlDivergenceConfig::default());
+        let targets = [0.5, 0.5, 0.0, 0.2, 0.3, 0.5];
+        let expected = 2f32.ln() / 2.0;
+        assert!((loss(&layer, &[2, 3], &log_probabilities(), &[2, 3], &targets) - expected).abs() < 1e-6);
+        assert_eq!(
+            vec![-0.25, -0.25, 0.0, -0.1, -0.15, -0.25],
+            gradient(&layer, &[2, 3], &log_probabilities(), &[2, 3], &targets)
+        );
+        assert_gradient(&layer, &[2, 3], &log_probabilities(), &[2, 3], &targets);
+    }
+
+    #[test]
+    fn zero_targets_in_log_space() {
+        let layer = KlDivergence::from_config(&KlDivergenceConfig {
+            log_target: true,
+            ..KlDivergenceConfig::default()
+        });
+        let targets: Vec<f32> = [0.5f32, 0.5, 0.0, 0.2, 0.3, 0.5].iter().map(|p| p.ln()).collect();
+        let expected = 2f32.ln() / 2.0;
+        assert!((loss(&layer, &[2, 3], &log_probabilities(), &[2, 3], &targets) - expected).abs() < 1e-6);
+        let gradient = gradient(&layer, &[2, 3], &log_probabilities(), &[2, 3], &targets);
+        assert_eq!(0.0, gradient[2]);
+        assert!(gradient.iter().all(|value| value.is_finite()));
+    }
+}
@@ -0,0 +1,192 @@
+//! Provides Loss & Gradient for Mean Absolute Error
+//!
+//! Calculation of [Mean Absolute Error][1], also known as L1 loss, for regression problems.
+//! Large errors weigh less than with the [Mean Squared Error][mse], which makes it
+//! more robust against outliers in the labels.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Mean_absolute_error
+//! [mse]: ../mean_squared_error/index.html
+//!
+//! The predictions and labels may have any dimensions, the loss of every element is
+//! combined as configured by the [LossReduction][reduction].
+//!
+//! [reduction]: ../enum.LossReduction.html
+
+use super::{batch_size, sample_weights, LossReduction};
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::juice_capnp::mean_absolute_error_config as capnp_config;
+use crate::layer::*;
+use crate::util::{native_backend, write_to_memory, ArcLock};
+
+#[derive(Debug, Clone)]
+#[allow(missing_copy_implementations)]
+/// Mean Absolute Error Layer
+pub struct MeanAbsoluteError {
+    reduction: LossReduction,
+}
+
+impl MeanAbsoluteError {
+    /// Create a MeanAbsoluteError layer from a MeanAbsoluteErrorConfig.
+    pub fn from_config(config: &MeanAbsoluteErrorConfig) -> MeanAbsoluteError {
+        MeanAbsoluteError {
+            reduction: config.reduction,
+        }
+    }
+}
+
+impl<B: IBackend> ILayer<B> for MeanAbsoluteError {
+    impl_ilayer_loss!();
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let input_desc = input_data[0].read().unwrap().desc().clone();
+        let output_shape = self.reduction.output_shape(&input_desc);
+        input_gradient[0].write().unwrap().resize(&input_desc).unwrap();
+        output_data[0].write().unwrap().resize(&output_shape).unwrap();
+        output_gradient[0].write().unwrap().resize(&output_shape).unwrap();
+    }
+}
+
+// Calculate the absolute errors of all elements as Output
+impl<B: IBackend> ComputeOutput<f32, B> for MeanAbsoluteError {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        let native = native_backend();
+        let predictions = input_data[0].read(native.device()).unwrap().as_slice::<f32>();
+        let labels = input_data[1].read(native.device()).unwrap().as_slice::<f32>();
+
+        let losses: Vec<f32> = predictions
+            .iter()
+            .zip(labels)
+            .map(|(prediction, label)| (prediction - label).abs())
+            .collect();
+        let weights = sample_weights(input_data, losses.len());
+        let normalizer = self
+            .reduction
+            .normalizer(losses.len() as f32, batch_size(input_data[0].desc()));
+
+        write_to_memory(
+            output_data[0].write_only(native.device()).unwrap(),
+            &self.reduction.reduce(&losses, weights.as_deref(), normalizer),
+        );
+    }
+}
+
+// Calculate a Gradient for Mean Absolute Error
+impl<B: IBackend> ComputeInputGradient<f32, B> for MeanAbsoluteError {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        let native = native_backend();
+        let predictions = input_data[0].read(native.device()).unwrap().as_slice::<f32>();
+        let labels = input_data[1].read(native.device()).unwrap().as_slice::<f32>();
+        let count = predictions.len();
+        let weights = sample_weights(input_data, count);
+        let losses_gradient = self
+            .reduction
+            .losses_gradient(
+                count,
+                weights.as_deref(),
+                self.reduction
+                    .normalizer(count as f32, batch_size(input_data[0].desc())),
+                output_gradients.first().copied(),
+            )
+            .unwrap();
+
+        // Gradient is the sign of (Predictions - Labels), which is 0 for exact predictions
+        let gradient: Vec<f32> = predictions
+            .iter()
+            .zip(labels)
+            .zip(losses_gradient)
+            .map(|((prediction, label), loss_gradient)| {
+                let error = prediction - label;
+                if error > 0f32 {
+                    loss_gradient
+                } else if error < 0f32 {
+                    -loss_gradient
+                } else {
+                    0f32
+                }
+            })
+            .collect();
+
+        write_to_memory(input_gradients[0].write_only(native.device()).unwrap(), &gradient);
+    }
+}
+
+impl<B: IBackend> ComputeParametersGradient<f32, B> for MeanAbsoluteError {}
+
+#[derive(Debug, Copy, Clone, Default)]
+/// Specifies configuration parameters for a MeanAbsoluteError Layer.
+pub struct MeanAbsoluteErrorConfig {
+    /// How the losses of all elements are combined.
+    pub reduction: LossReduction,
+}
+
+impl<'a> CapnpWrite<'a> for MeanAbsoluteErrorConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the MeanAbsoluteErrorConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.set_reduction(self.reduction.to_capnp());
+    }
+}
+
+impl<'a> CapnpRead<'a> for MeanAbsoluteErrorConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        MeanAbsoluteErrorConfig {
+            reduction: LossReduction::from_capnp(reader.get_reduction().unwrap()),
+        }
+    }
+}
+
+impl Into<LayerType> for MeanAbsoluteErrorConfig {
+    fn into(self) -> LayerType {
+        LayerType::MeanAbsoluteError(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::testing::{assert_gradient, gradient, loss};
+    use super::{MeanAbsoluteError, MeanAbsoluteErrorConfig};
+
+    #[test]
+    fn loss_and_gradient() {
+        let layer = MeanAbsoluteError::from_config(&MeanAbsoluteErrorConfig::default());
+        let labels = [0.0, 2.0, 5.0, 3.5];
+        assert_eq!(0.875, loss(&layer, &[4], &[1.0, 2.0, 3.0, 4.0], &[4], &labels));
+        assert_gradient(&layer, &[2, 2], &[1.0, 2.5, 3.0, 4.0], &[2, 2], &labels);
+    }
+
+    #[test]
+    fn no_gradient_for_exact_predictions() {
+        let layer = MeanAbsoluteError::from_config(&MeanAbsoluteErrorConfig::default());
+        assert_eq!(
+            vec![0.25, 0.0, -0.25, 0.25],
+            gradient(&layer, &[4], &[1.0, 2.0, 3.0, 4.0], &[4], &[0.0, 2.0, 5.0, 3.5])
+        );
+    }
+}
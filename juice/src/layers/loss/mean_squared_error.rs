@@ -3,27 +3,38 @@
 //! Calculation of [Mean Squared Error][1] for regression problems.
 //!
 //! [1]: https://en.wikipedia.org/wiki/Mean_squared_error
+//!
+//! The predictions and labels may have any dimensions, the loss of every element is
+//! combined as configured by the [LossReduction][reduction].
+//!
+//! [reduction]: ../enum.LossReduction.html
 
-use crate::co::prelude::*;
+use super::{batch_size, sample_weights, LossReduction};
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::juice_capnp::mean_squared_error_config as capnp_config;
 use crate::layer::*;
-use crate::util::*;
+use crate::util::{native_backend, write_to_memory, ArcLock};
 
 #[derive(Debug, Clone)]
 #[allow(missing_copy_implementations)]
 /// Mean Squared Error Layer
-pub struct MeanSquaredError;
+pub struct MeanSquaredError {
+    reduction: LossReduction,
+}
 
 impl MeanSquaredError {
-    fn batch_size(input_shape: &[usize]) -> usize {
-        match input_shape.len() {
-            1 => 1,
-            2 => input_shape[0],
-            _ => panic!("MSE layer only supports 1D/2D inputs"),
+    /// Create a MeanSquaredError layer from a MeanSquaredErrorConfig.
+    pub fn from_config(config: &MeanSquaredErrorConfig) -> MeanSquaredError {
+        MeanSquaredError {
+            reduction: config.reduction,
         }
     }
 }
 
-impl<B: IBackend + LayerOps<f32> + Axpby<f32>> ILayer<B> for MeanSquaredError {
+impl<B: IBackend> ILayer<B> for MeanSquaredError {
+    impl_ilayer_loss!();
+
     fn reshape(
         &mut self,
         backend: ::std::rc::Rc<B>,
@@ -35,9 +46,10 @@ impl<B: IBackend + LayerOps<f32> + Axpby<f32>> ILayer<B> for MeanSquaredError {
         output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
     ) {
         let input_desc = input_data[0].read().unwrap().desc().clone();
+        let output_shape = self.reduction.output_shape(&input_desc);
         input_gradient[0].write().unwrap().resize(&input_desc).unwrap();
-        output_data[0].write().unwrap().resize(&input_desc).unwrap();
-        output_gradient[0].write().unwrap().resize(&input_desc).unwrap();
+        output_data[0].write().unwrap().resize(&output_shape).unwrap();
+        output_gradient[0].write().unwrap().resize(&output_shape).unwrap();
     }
 }
 
@@ -50,27 +62,29 @@ impl<B: IBackend> ComputeOutput<f32, B> for MeanSquaredError {
         input_data: &[&SharedTensor<f32>],
         output_data: &mut [&mut SharedTensor<f32>],
     ) {
-        let predictions = input_data[0];
-        let labels = input_data[1];
-        let batch_size = Self::batch_size(labels.desc());
         let native = native_backend();
-        let native_labels = labels.read(native.device()).unwrap().as_slice::<f32>();
-        let native_predictions = predictions.read(native.device()).unwrap().as_slice::<f32>();
+        let predictions = input_data[0].read(native.device()).unwrap().as_slice::<f32>();
+        let labels = input_data[1].read(native.device()).unwrap().as_slice::<f32>();
 
-        let writable_loss = native_labels
+        let losses: Vec<f32> = predictions
             .iter()
-            .zip(native_predictions)
-            .fold(0f32, |acc, (label, prediction)| acc + (prediction - label).powi(2));
+            .zip(labels)
+            .map(|(prediction, label)| (prediction - label).powi(2))
+            .collect();
+        let weights = sample_weights(input_data, losses.len());
+        let normalizer = self
+            .reduction
+            .normalizer(losses.len() as f32, batch_size(input_data[0].desc()));
 
         write_to_memory(
             output_data[0].write_only(native.device()).unwrap(),
-            &vec![writable_loss / batch_size as f32],
+            &self.reduction.reduce(&losses, weights.as_deref(), normalizer),
         );
     }
 }
 
 // Calculate a Gradient for Mean Squared Error
-impl<B: IBackend + LayerOps<f32>> ComputeInputGradient<f32, B> for MeanSquaredError {
+impl<B: IBackend> ComputeInputGradient<f32, B> for MeanSquaredError {
     fn compute_input_gradient(
         &self,
         backend: &B,
@@ -80,30 +94,31 @@ impl<B: IBackend + LayerOps<f32>> ComputeInputGradient<f32, B> for MeanSquaredEr
         input_data: &[&SharedTensor<f32>],
         input_gradients: &mut [&mut SharedTensor<f32>],
     ) {
-        let labels = input_data[1];
-        let predictions = input_data[0];
-        let batch_size = Self::batch_size(labels.desc());
         let native = native_backend();
-        let native_labels = labels.read(native.device()).unwrap().as_slice::<f32>();
-        let native_predictions = predictions.read(native.device()).unwrap().as_slice::<f32>();
-
-        let mut writable_input: SharedTensor<f32> = SharedTensor::new(labels.desc());
-        write_to_memory(writable_input.write_only(native.device()).unwrap(), native_labels);
+        let predictions = input_data[0].read(native.device()).unwrap().as_slice::<f32>();
+        let labels = input_data[1].read(native.device()).unwrap().as_slice::<f32>();
+        let count = predictions.len();
+        let weights = sample_weights(input_data, count);
+        let losses_gradient = self
+            .reduction
+            .losses_gradient(
+                count,
+                weights.as_deref(),
+                self.reduction
+                    .normalizer(count as f32, batch_size(input_data[0].desc())),
+                output_gradients.first().copied(),
+            )
+            .unwrap();
 
         // Gradient is calculated as 2 * (Predictions - Labels)
-        Axpby::axpby(
-            backend,
-            &native_scalar(2f32),
-            &predictions,
-            &native_scalar(-2f32),
-            &mut writable_input,
-        )
-            .unwrap();
+        let gradient: Vec<f32> = predictions
+            .iter()
+            .zip(labels)
+            .zip(losses_gradient)
+            .map(|((prediction, label), loss_gradient)| 2f32 * (prediction - label) * loss_gradient)
+            .collect();
 
-        write_to_memory(
-            input_gradients[0].write_only(native.device()).unwrap(),
-            &writable_input.read(native.device()).unwrap().as_slice::<f32>(),
-        );
+        write_to_memory(input_gradients[0].write_only(native.device()).unwrap(), &gradient);
     }
 }
 
@@ -111,6 +126,49 @@ impl<B: IBackend> ComputeParametersGradient<f32, B> for MeanSquaredError {}
 
 impl ::std::default::Default for MeanSquaredError {
     fn default() -> MeanSquaredError {
-        MeanSquaredError
+        MeanSquaredError::from_config(&MeanSquaredErrorConfig::default())
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// Specifies configuration parameters for a MeanSquaredError Layer.
+pub struct MeanSquaredErrorConfig {
+    /// How the losses of all elements are combined.
+    ///
+    /// Defaults to the sum divided by the batch size, the loss the layer returned before
+    /// the reduction was configurable.
+    pub reduction: LossReduction,
+}
+
+impl ::std::default::Default for MeanSquaredErrorConfig {
+    fn default() -> MeanSquaredErrorConfig {
+        MeanSquaredErrorConfig {
+            reduction: LossReduction::BatchMean,
+        }
+    }
+}
+
+impl<'a> CapnpWrite<'a> for MeanSquaredErrorConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the MeanSquaredErrorConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.set_reduction(self.reduction.to_capnp());
+    }
+}
+
+impl<'a> CapnpRead<'a> for MeanSquaredErrorConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        MeanSquaredErrorConfig {
+            reduction: LossReduction::from_capnp(reader.get_reduction().unwrap()),
+        }
+    }
+}
+
+impl Into<LayerType> for MeanSquaredErrorConfig {
+    fn into(self) -> LayerType {
+        LayerType::MeanSquaredError(self)
     }
 }
@@ -9,8 +9,9 @@ macro_rules! impl_ilayer_loss {
         fn exact_num_output_blobs(&self) -> Option<usize> {
             Some(1)
         }
-        fn exact_num_input_blobs(&self) -> Option<usize> {
-            Some(1)
+        fn min_input_blobs(&self) -> usize {
+            // the sample weights are an optional third input
+            2
         }
         fn auto_output_blobs(&self) -> bool {
             true
@@ -26,7 +27,400 @@ macro_rules! impl_ilayer_loss {
     };
 }
 
-pub use self::mean_squared_error::MeanSquaredError;
+use crate::co::SharedTensor;
+use crate::juice_capnp::LossReduction as CapnpLossReduction;
+use crate::util::native_backend;
+
+pub use self::binary_cross_entropy::{BinaryCrossEntropy, BinaryCrossEntropyConfig};
+pub use self::binary_cross_entropy_with_logits::{BinaryCrossEntropyWithLogits, BinaryCrossEntropyWithLogitsConfig};
+pub use self::cross_entropy::{CrossEntropy, CrossEntropyConfig};
+pub use self::huber::{Huber, HuberConfig};
+pub use self::kl_divergence::{KlDivergence, KlDivergenceConfig};
+pub use self::mean_absolute_error::{MeanAbsoluteError, MeanAbsoluteErrorConfig};
+pub use self::mean_squared_error::{MeanSquaredError, MeanSquaredErrorConfig};
+pub use self::multi_class_hinge::{MultiClassHinge, MultiClassHingeConfig};
 pub use self::negative_log_likelihood::{NegativeLogLikelihood, NegativeLogLikelihoodConfig};
+pub mod binary_cross_entropy;
+pub mod binary_cross_entropy_with_logits;
+pub mod cross_entropy;
+pub mod huber;
+pub mod kl_divergence;
+pub mod mean_absolute_error;
 pub mod mean_squared_error;
+pub mod multi_class_hinge;
 pub mod negative_log_likelihood;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// How a loss layer combines the losses of all elements into its output.
+///
+/// Every loss layer takes an optional third input, which rescales the loss of every element
+/// before the reduction. The weights either have the dimensions of the losses, or hold one value
+/// for all losses that share the leading dimensions, e.g. `[N]` weighs every sample of losses of
+/// dimensions `[N, T]`. The mean is still divided by the number of losses.
+pub enum LossReduction {
+    /// Output the mean of the losses.
+    ///
+    /// The sum of the losses is divided by the number of losses, unless documented
+    /// otherwise by the loss layer.
+    Mean,
+    /// Output the sum of the losses divided by the batch size, the first dimension of the input.
+    BatchMean,
+    /// Output the sum of the losses.
+    Sum,
+    /// Output the loss of every element, e.g. every pixel of an image.
+    None,
+}
+
+impl Default for LossReduction {
+    fn default() -> LossReduction {
+        LossReduction::Mean
+    }
+}
+
+impl LossReduction {
+    /// Return the corresponding Cap'n Proto value.
+    pub(crate) fn to_capnp(self) -> CapnpLossReduction {
+        match self {
+            LossReduction::Mean => CapnpLossReduction::Mean,
+            LossReduction::BatchMean => CapnpLossReduction::BatchMean,
+            LossReduction::Sum => CapnpLossReduction::Sum,
+            LossReduction::None => CapnpLossReduction::None,
+        }
+    }
+
+    /// Return the enum value for a Cap'n Proto value.
+    pub(crate) fn from_capnp(value: CapnpLossReduction) -> Self {
+        match value {
+            CapnpLossReduction::Mean => LossReduction::Mean,
+            CapnpLossReduction::BatchMean => LossReduction::BatchMean,
+            CapnpLossReduction::Sum => LossReduction::Sum,
+            CapnpLossReduction::None => LossReduction::None,
+        }
+    }
+
+    /// The dimensions of the output for losses of dimensions `losses_shape`.
+    pub(crate) fn output_shape(self, losses_shape: &[usize]) -> Vec<usize> {
+        match self {
+            LossReduction::None => losses_shape.to_vec(),
+            _ => vec![1],
+        }
+    }
+
+    /// The value the sum of the losses is divided by for the mean, `mean_normalizer` for
+    /// `Mean` and the `batch_size` for `BatchMean`.
+    pub(crate) fn normalizer(self, mean_normalizer: f32, batch_size: usize) -> f32 {
+        match self {
+            LossReduction::BatchMean => batch_size as f32,
+            _ => mean_normalizer,
+        }
+    }
+
+    /// Combine the losses, rescaled by their weights, into the output.
+    ///
+    /// `normalizer` is the value the sum of the losses is divided by for the mean, see
+    /// [normalizer](#method.normalizer).
+    pub(crate) fn reduce(self, losses: &[f32], weights: Option<&[f32]>, normalizer: f32) -> Vec<f32> {
+        let weighted: Vec<f32> = match weights {
+            Some(weights) => losses.iter().zip(weights).map(|(loss, weight)| loss * weight).collect(),
+            None => losses.to_vec(),
+        };
+        match self {
+            LossReduction::Mean | LossReduction::BatchMean => {
+                let sum: f32 = weighted.iter().sum();
+                vec![if normalizer > 0f32 { sum / normalizer } else { 0f32 }]
+            }
+            LossReduction::Sum => vec![weighted.iter().sum()],
+            LossReduction::None => weighted,
+        }
+    }
+
+    /// The derivative of the output with respect to each of the `count` losses.
+    ///
+    /// Without reduction the output gradient is used if a following layer computed it,
+    /// otherwise the gradient is that of the sum of all losses. An output gradient that
+    /// does not hold one value per loss is an error.
+    pub(crate) fn losses_gradient(
+        self,
+        count: usize,
+        weights: Option<&[f32]>,
+        normalizer: f32,
+        output_gradient: Option<&SharedTensor<f32>>,
+    ) -> Result<Vec<f32>, String> {
+        let mut gradient = match self {
+            LossReduction::Mean | LossReduction::BatchMean => {
+                vec![if normalizer > 0f32 { 1f32 / normalizer } else { 0f32 }; count]
+            }
+            LossReduction::Sum => vec![1f32; count],
+            LossReduction::None => {
+                let native = native_backend();
+                match output_gradient.and_then(|gradient| gradient.read(native.device()).ok()) {
+                    Some(gradient) if gradient.as_slice::<f32>().len() != count => {
+                        return Err(format!(
+                            "output gradient of {} values does not match {} losses",
+                            gradient.as_slice::<f32>().len(),
+                            count
+                        ));
+                    }
+                    Some(gradient) => gradient.as_slice::<f32>().to_vec(),
+                    None => vec![1f32; count],
+                }
+            }
+        };
+        if let Some(weights) = weights {
+            for (value, weight) in gradient.iter_mut().zip(weights) {
+                *value *= weight;
+            }
+        }
+        Ok(gradient)
+    }
+}
+
+/// The weight of each of the `count` losses, read from the optional third input of a loss layer.
+pub(crate) fn sample_weights(input_data: &[&SharedTensor<f32>], count: usize) -> Option<Vec<f32>> {
+    let weights = input_data.get(2)?;
+    let native = native_backend();
+    let weights = weights.read(native.device()).unwrap().as_slice::<f32>();
+    if weights.is_empty() || count % weights.len() != 0 {
+        panic!("{} sample weights do not match {} losses", weights.len(), count);
+    }
+    let repeat = count / weights.len();
+    Some(
+        weights
+            .iter()
+            .flat_map(|&weight| ::std::iter::repeat(weight).take(repeat))
+            .collect(),
+    )
+}
+
+/// The batch size of an input, its first dimension. A 1-D input holds a single sample.
+pub(crate) fn batch_size(input_shape: &[usize]) -> usize {
+    match input_shape.len() {
+        0 | 1 => 1,
+        _ => input_shape[0],
+    }
+}
+
+/// The number of samples before the class dimension, the number of classes, and the
+/// number of samples after the class dimension of an input.
+///
+/// The classes are the second dimension, a 1-D input holds the classes of a single sample.
+pub(crate) fn class_layout(input_shape: &[usize]) -> (usize, usize, usize) {
+    match input_shape.len() {
+        0 => panic!("Loss layer needs an input with a class dimension"),
+        1 => (1, input_shape[0], 1),
+        _ => (input_shape[0], input_shape[1], input_shape[2..].iter().product()),
+    }
+}
+
+/// The dimensions of the losses of an input with a class dimension, which is removed.
+pub(crate) fn class_sample_shape(input_shape: &[usize]) -> Vec<usize> {
+    match input_shape.len() {
+        0 | 1 => vec![1],
+        _ => {
+            let mut shape = input_shape.to_vec();
+            shape.remove(1);
+            shape
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    //! Helpers to check the output and gradient of loss layers on the native backend.
+
+    use crate::co::{Backend, Native, SharedTensor};
+    use crate::layer::{ComputeInputGradient, ComputeOutput};
+    use crate::util::{native_backend, write_to_memory};
+
+    fn tensor(shape: &[usize], data: &[f32]) -> SharedTensor<f32> {
+        let mut tensor = SharedTensor::new(&shape);
+        write_to_memory(tensor.write_only(native_backend().device()).unwrap(), data);
+        tensor
+    }
+
+    /// The reduced loss of `predictions` of dimensions `shape` for `targets` of dimensions `target_shape`.
+    pub fn loss<L: ComputeOutput<f32, Backend<Native>>>(
+        layer: &L,
+        shape: &[usize],
+        predictions: &[f32],
+        target_shape: &[usize],
+        targets: &[f32],
+    ) -> f32 {
+        let native = native_backend();
+        let predictions = tensor(shape, predictions);
+        let targets = tensor(target_shape, targets);
+        let mut output = SharedTensor::new(&[1]);
+        layer.compute_output(&native, &[], &[&predictions, &targets], &mut [&mut output]);
+        let output = output.read(native.device()).unwrap().as_slice::<f32>();
+        output[0]
+    }
+
+    /// The gradient of the reduced loss with respect to the predictions.
+    pub fn gradient<L: ComputeInputGradient<f32, Backend<Native>>>(
+        layer: &L,
+        shape: &[usize],
+        predictions: &[f32],
+        target_shape: &[usize],
+        targets: &[f32],
+    ) -> Vec<f32> {
+        let native = native_backend();
+        let predictions = tensor(shape, predictions);
+        let targets = tensor(target_shape, targets);
+        let output = SharedTensor::new(&[1]);
+        let mut gradient = SharedTensor::new(&shape);
+        layer.compute_input_gradient(
+            &native,
+            &[],
+            &[&output],
+            &[],
+            &[&predictions, &targets],
+            &mut [&mut gradient],
+        );
+        let gradient = gradient.read(native.device()).unwrap().as_slice::<f32>();
+        gradient.to_vec()
+    }
+
+    /// Asserts that the gradient matches the central differences of the loss.
+    pub fn assert_gradient<L>(layer: &L, shape: &[usize], predictions: &[f32], target_shape: &[usize], targets: &[f32])
+    where
+        L: ComputeOutput<f32, Backend<Native>> + ComputeInputGradient<f32, Backend<Native>>,
+    {
+        let analytic = gradient(layer, shape, predictions, target_shape, targets);
+        let h = 1e-3;
+        for k in 0..predictions.len() {
+            let mut perturbed = predictions.to_vec();
+            perturbed[k] += h;
+            let plus = loss(layer, shape, &perturbed, target_shape, targets);
+            perturbed[k] -= 2.0 * h;
+            let minus = loss(layer, shape, &perturbed, target_shape, targets);
+            let numeric = (plus - minus) / (2.0 * h);
+            assert!(
+                (analytic[k] - numeric).abs() < 1e-2,
+                "gradient[{}] is {}, expected {}",
+                k,
+                analytic[k],
+                numeric
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{class_layout, class_sample_shape, sample_weights, LossReduction};
+    use crate::co::SharedTensor;
+    use crate::util::{native_backend, write_to_memory};
+
+    #[test]
+    fn reduces_weighted_losses() {
+        let losses = [1f32, 2.0, 3.0, 4.0];
+        let weights = [1f32, 0.0, 2.0, 1.0];
+        assert_eq!(vec![2.5], LossReduction::Mean.reduce(&losses, None, 4.0));
+        assert_eq!(vec![11.0], LossReduction::Sum.reduce(&losses, Some(&weights), 4.0));
+        assert_eq!(
+            vec![1.0, 0.0, 6.0, 4.0],
+            LossReduction::None.reduce(&losses, Some(&weights), 4.0)
+        );
+        assert_eq!(vec![0.0], LossReduction::Mean.reduce(&[], None, 0.0));
+    }
+
+    #[test]
+    fn losses_gradient_matches_reduction() {
+        let weights = [1f32, 0.0, 2.0, 1.0];
+        assert_eq!(
+            Ok(vec![0.25; 4]),
+            LossReduction::Mean.losses_gradient(4, None, 4.0, None)
+        );
+        assert_eq!(
+            Ok(weights.to_vec()),
+            LossReduction::Sum.losses_gradient(4, Some(&weights), 4.0, None)
+        );
+        assert_eq!(
+            Ok(vec![1.0; 4]),
+            LossReduction::None.losses_gradient(4, None, 4.0, None)
+        );
+    }
+
+    #[test]
+    fn losses_gradient_uses_output_gradient_without_reduction() {
+        let native = native_backend();
+        let mut output_gradient = SharedTensor::<f32>::new(&[4]);
+        write_to_memory(
+            output_gradient.write_only(native.device()).unwrap(),
+            &[1.0, 2.0, 3.0, 4.0],
+        );
+        assert_eq!(
+            Ok(vec![2.0, 0.0, 6.0, 4.0]),
+            LossReduction::None.losses_gradient(4, Some(&[2.0, 0.0, 2.0, 1.0]), 4.0, Some(&output_gradient))
+        );
+        assert!(LossReduction::None
+            .losses_gradient(3, None, 3.0, Some(&output_gradient))
+            .is_err());
+    }
+
+    #[test]
+    fn legacy_configs_divide_by_batch_size() {
+        use super::{MeanSquaredErrorConfig, NegativeLogLikelihoodConfig};
+        use crate::capnp_util::CapnpRead;
+        use crate::juice_capnp::{mean_squared_error_config, negative_log_likelihood_config};
+
+        // written before the reduction was configurable
+        let mut message = ::capnp::message::Builder::new_default();
+        message
+            .init_root::<negative_log_likelihood_config::Builder>()
+            .set_num_classes(10);
+        let reader = message
+            .get_root_as_reader::<negative_log_likelihood_config::Reader>()
+            .unwrap();
+        assert_eq!(
+            LossReduction::BatchMean,
+            NegativeLogLikelihoodConfig::read_capnp(reader).reduction
+        );
+
+        let mut message = ::capnp::message::Builder::new_default();
+        message.init_root::<mean_squared_error_config::Builder>();
+        let reader = message
+            .get_root_as_reader::<mean_squared_error_config::Reader>()
+            .unwrap();
+        assert_eq!(
+            LossReduction::BatchMean,
+            MeanSquaredErrorConfig::read_capnp(reader).reduction
+        );
+        assert_eq!(LossReduction::BatchMean, MeanSquaredErrorConfig::default().reduction);
+        assert_eq!(LossReduction::BatchMean, NegativeLogLikelihoodConfig::new(10).reduction);
+    }
+
+    #[test]
+    fn defaults_divide_by_batch_size() {
+        use super::testing::loss;
+        use super::{MeanSquaredError, MeanSquaredErrorConfig, NegativeLogLikelihood, NegativeLogLikelihoodConfig};
+
+        let mse = MeanSquaredError::from_config(&MeanSquaredErrorConfig::default());
+        assert_eq!(15.0, loss(&mse, &[2, 2], &[1.0, 2.0, 3.0, 4.0], &[2, 2], &[0.0; 4]));
+
+        let nll = NegativeLogLikelihood::from_config(&NegativeLogLikelihoodConfig::new(3));
+        let log_probabilities = [-1.0, -2.0, -3.0, -4.0, -5.0, -6.0];
+        assert_eq!(3.5, loss(&nll, &[2, 3], &log_probabilities, &[2, 1], &[0.0, 2.0]));
+    }
+
+    #[test]
+    fn sample_weights_repeat_per_sample() {
+        let data = SharedTensor::<f32>::new(&[2, 3]);
+        let mut weights = SharedTensor::<f32>::new(&[2]);
+        write_to_memory(weights.write_only(native_backend().device()).unwrap(), &[2.0, 3.0]);
+        assert_eq!(None, sample_weights(&[&data, &data], 6));
+        assert_eq!(
+            Some(vec![2.0, 2.0, 2.0, 3.0, 3.0, 3.0]),
+            sample_weights(&[&data, &data, &weights], 6)
+        );
+    }
+
+    #[test]
+    fn class_dimension_is_second() {
+        assert_eq!((1, 10, 1), class_layout(&[10]));
+        assert_eq!((4, 10, 1), class_layout(&[4, 10]));
+        assert_eq!((4, 3, 64), class_layout(&[4, 3, 8, 8]));
+        assert_eq!(vec![1], class_sample_shape(&[10]));
+        assert_eq!(vec![4, 8, 8], class_sample_shape(&[4, 3, 8, 8]));
+    }
+}
@@ -0,0 +1,230 @@
+//! Provides Loss & Gradient for the multiclass Hinge Loss
+//!
+//! Calculation of the multiclass [hinge loss][1], which penalizes every class whose score
+//! is not at least `margin` below the score of the target class, as used to train
+//! support vector machines.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Hinge_loss
+//!
+//! ## Input Data
+//!
+//! The first input holds the scores of dimensions `[C]`, `[N, C]` or `[N, C, ...]`, the classes
+//! are the second dimension. The second input holds the class index of every sample.
+//!
+//! ## Output
+//!
+//! The summed violations of every sample divided by the number of classes, combined as
+//! configured by the [LossReduction][reduction].
+//!
+//! [reduction]: ../enum.LossReduction.html
+
+use super::{batch_size, class_layout, class_sample_shape, sample_weights, LossReduction};
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::juice_capnp::multi_class_hinge_config as capnp_config;
+use crate::layer::*;
+use crate::util::{native_backend, write_to_memory, ArcLock};
+
+#[derive(Debug, Clone)]
+#[allow(missing_copy_implementations)]
+/// Multiclass Hinge Loss Layer
+pub struct MultiClassHinge {
+    margin: f32,
+    reduction: LossReduction,
+}
+
+impl MultiClassHinge {
+    /// Create a MultiClassHinge layer from a MultiClassHingeConfig.
+    pub fn from_config(config: &MultiClassHingeConfig) -> MultiClassHinge {
+        MultiClassHinge {
+            margin: config.margin,
+            reduction: config.reduction,
+        }
+    }
+
+    /// The violation of the margin by a class score, relative to the score of the target class.
+    fn violation(&self, score: f32, target_score: f32) -> f32 {
+        self.margin - target_score + score
+    }
+}
+
+impl<B: IBackend> ILayer<B> for MultiClassHinge {
+    impl_ilayer_loss!();
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let input_desc = input_data[0].read().unwrap().desc().clone();
+        let output_shape = self.reduction.output_shape(&class_sample_shape(&input_desc));
+        input_gradient[0].write().unwrap().resize(&input_desc).unwrap();
+        output_data[0].write().unwrap().resize(&output_shape).unwrap();
+        output_gradient[0].write().unwrap().resize(&output_shape).unwrap();
+    }
+}
+
+impl<B: IBackend> ComputeOutput<f32, B> for MultiClassHinge {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        let (outer_num, num_classes, inner_num) = class_layout(input_data[0].desc());
+        let native = native_backend();
+        let scores = input_data[0].read(native.device()).unwrap().as_slice::<f32>();
+        let labels = input_data[1].read(native.device()).unwrap().as_slice::<f32>();
+
+        let mut losses = vec![0f32; outer_num * inner_num];
+        for (sample, &label) in labels.iter().enumerate() {
+            let offset = sample / inner_num * num_classes * inner_num + sample % inner_num;
+            let target_score = scores[offset + label as usize * inner_num];
+            for class in (0..num_classes).filter(|&class| class != label as usize) {
+                let violation = self.violation(scores[offset + class * inner_num], target_score);
+                losses[sample] += violation.max(0f32) / num_classes as f32;
+            }
+        }
+        let weights = sample_weights(input_data, losses.len());
+        let normalizer = self
+            .reduction
+            .normalizer(losses.len() as f32, batch_size(input_data[0].desc()));
+
+        write_to_memory(
+            output_data[0].write_only(native.device()).unwrap(),
+            &self.reduction.reduce(&losses, weights.as_deref(), normalizer),
+        );
+    }
+}
+
+impl<B: IBackend> ComputeInputGradient<f32, B> for MultiClassHinge {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        let (outer_num, num_classes, inner_num) = class_layout(input_data[0].desc());
+        let native = native_backend();
+        let scores = input_data[0].read(native.device()).unwrap().as_slice::<f32>();
+        let labels = input_data[1].read(native.device()).unwrap().as_slice::<f32>();
+        let count = outer_num * inner_num;
+        let weights = sample_weights(input_data, count);
+        let losses_gradient = self
+            .reduction
+            .losses_gradient(
+                count,
+                weights.as_deref(),
+                self.reduction
+                    .normalizer(count as f32, batch_size(input_data[0].desc())),
+                output_gradients.first().copied(),
+            )
+            .unwrap();
+
+        // every violating class raises the loss with its score and lowers it with the target score
+        let mut gradient = vec![0f32; scores.len()];
+        for (sample, &label) in labels.iter().enumerate() {
+            let offset = sample / inner_num * num_classes * inner_num + sample % inner_num;
+            let target_index = offset + label as usize * inner_num;
+            let step = losses_gradient[sample] / num_classes as f32;
+            for class in (0..num_classes).filter(|&class| class != label as usize) {
+                let index = offset + class * inner_num;
+                if self.violation(scores[index], scores[target_index]) > 0f32 {
+                    gradient[index] += step;
+                    gradient[target_index] -= step;
+                }
+            }
+        }
+
+        write_to_memory(input_gradients[0].write_only(native.device()).unwrap(), &gradient);
+    }
+}
+
+impl<B: IBackend> ComputeParametersGradient<f32, B> for MultiClassHinge {}
+
+#[derive(Debug, Copy, Clone)]
+/// Specifies configuration parameters for a MultiClassHinge Layer.
+pub struct MultiClassHingeConfig {
+    /// How far the score of every other class has to be below the score of the target class.
+    ///
+    /// Defaults to `1.0`
+    pub margin: f32,
+    /// How the losses of all samples are combined.
+    pub reduction: LossReduction,
+}
+
+impl Default for MultiClassHingeConfig {
+    fn default() -> MultiClassHingeConfig {
+        MultiClassHingeConfig {
+            margin: 1.0,
+            reduction: LossReduction::default(),
+        }
+    }
+}
+
+impl<'a> CapnpWrite<'a> for MultiClassHingeConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the MultiClassHingeConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.set_margin(self.margin);
+        builder.set_reduction(self.reduction.to_capnp());
+    }
+}
+
+impl<'a> CapnpRead<'a> for MultiClassHingeConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        MultiClassHingeConfig {
+            margin: reader.get_margin(),
+            reduction: LossReduction::from_capnp(reader.get_reduction().unwrap()),
+        }
+    }
+}
+
+impl Into<LayerType> for MultiClassHingeConfig {
+    fn into(self) -> LayerType {
+        LayerType::MultiClassHinge(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::testing::{assert_gradient, gradient, loss};
+    use super::{MultiClassHinge, MultiClassHingeConfig};
+
+    #[test]
+    fn loss_and_gradient() {
+        let layer = MultiClassHinge::from_config(&MultiClassHingeConfig::default());
+        let scores = [2.0, 0.5, 0.2, 1.0, 0.5, 1.0];
+        let labels = [0.0, 2.0];
+        // the second sample violates the margin by 1 and 0.5, divided by 3 classes and 2 samples
+        assert_eq!(0.25, loss(&layer, &[2, 3], &scores, &[2], &labels));
+        assert_gradient(&layer, &[2, 3], &scores, &[2], &labels);
+    }
+
+    #[test]
+    fn no_gradient_at_the_margin() {
+        let layer = MultiClassHinge::from_config(&MultiClassHingeConfig::default());
+        // the second class of the first sample is exactly at the margin, so it does not violate it
+        let scores = [2.0, 1.0, 0.5, 1.0, 0.5, 1.0];
+        let labels = [0.0, 2.0];
+        assert_eq!(0.25, loss(&layer, &[2, 3], &scores, &[2], &labels));
+        let gradient = gradient(&layer, &[2, 3], &scores, &[2], &labels);
+        assert_eq!([0.0, 0.0, 0.0], gradient[..3]);
+        let expected = [1.0 / 6.0, 1.0 / 6.0, -1.0 / 3.0];
+        for (value, expected) in gradient[3..].iter().zip(&expected) {
+            assert!((value - expected).abs() < 1e-6);
+        }
+    }
+}
@@ -1,6 +1,20 @@
-//! TODO: DOC
+//! Provides Loss & Gradient for the Negative Log Likelihood
 //!
+//! Calculation of the negative log likelihood of the target classes, where the input holds
+//! log-probabilities, usually the output of a `LogSoftmax` layer.
+//!
+//! ## Input Data
+//!
+//! The first input holds the log-probabilities of dimensions `[C]`, `[N, C]` or `[N, C, ...]`,
+//! the classes are the second dimension. The second input holds the class index of every sample.
+//!
+//! ## Output
+//!
+//! The loss of every sample, combined as configured by the [LossReduction][reduction].
+//!
+//! [reduction]: ../enum.LossReduction.html
 
+use super::{batch_size, class_layout, class_sample_shape, sample_weights, LossReduction};
 use crate::capnp_util::*;
 use crate::co::{IBackend, ITensorDesc, SharedTensor};
 use crate::juice_capnp::negative_log_likelihood_config as capnp_config;
@@ -12,6 +26,7 @@ use crate::util::{native_backend, ArcLock};
 /// NegativeLogLikelihood Loss Layer
 pub struct NegativeLogLikelihood {
     num_classes: usize,
+    reduction: LossReduction,
 }
 
 impl NegativeLogLikelihood {
@@ -19,23 +34,21 @@ impl NegativeLogLikelihood {
     pub fn from_config(config: &NegativeLogLikelihoodConfig) -> NegativeLogLikelihood {
         NegativeLogLikelihood {
             num_classes: config.num_classes,
+            reduction: config.reduction,
         }
     }
 
-    fn calculate_outer_num(softmax_axis: usize, input_shape: &[usize]) -> usize {
-        input_shape.iter().take(softmax_axis + 1).fold(1, |prod, i| prod * i)
-    }
-
-    fn calculate_inner_num(softmax_axis: usize, input_shape: &[usize]) -> usize {
-        input_shape.iter().skip(softmax_axis + 1).fold(1, |prod, i| prod * i)
-    }
-
-    fn batch_size(input_shape: &[usize]) -> usize {
-        match input_shape.len() {
-            1 => 1,
-            2 => input_shape[0],
-            _ => panic!("NegativeLogLikelihood layer only supports 1D/2D inputs"),
+    /// The number of samples before the class dimension, the number of classes
+    /// and the number of samples after the class dimension.
+    fn class_layout(&self, input_shape: &[usize]) -> (usize, usize, usize) {
+        let (outer_num, num_classes, inner_num) = class_layout(input_shape);
+        if num_classes != self.num_classes {
+            panic!(
+                "NegativeLogLikelihood layer expects {} classes, the input has {}",
+                self.num_classes, num_classes
+            );
         }
+        (outer_num, num_classes, inner_num)
     }
 }
 
@@ -57,10 +70,11 @@ impl<B: IBackend> ILayer<B> for NegativeLogLikelihood {
         output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
     ) {
         let data = input_data[0].read().unwrap();
-        let label = input_data[1].read().unwrap();
+        let output_shape = self.reduction.output_shape(&class_sample_shape(data.desc()));
 
         input_gradient[0].write().unwrap().resize(data.desc()).unwrap();
-        output_data[0].write().unwrap().resize(label.desc()).unwrap();
+        output_data[0].write().unwrap().resize(&output_shape).unwrap();
+        output_gradient[0].write().unwrap().resize(&output_shape).unwrap();
     }
 }
 
@@ -75,23 +89,26 @@ impl<B: IBackend> ComputeOutput<f32, B> for NegativeLogLikelihood {
         let probabilities = input_data[0];
         let labels = input_data[1];
 
-        let batch_size = Self::batch_size(labels.desc());
+        let (_, num_classes, inner_num) = self.class_layout(probabilities.desc());
 
         let native = native_backend();
         let native_labels = labels.read(native.device()).unwrap().as_slice::<f32>();
         let native_probabilities = probabilities.read(native.device()).unwrap().as_slice::<f32>();
 
         let mut writable_loss = Vec::<f32>::new();
-        for &label_value in native_labels {
-            let probability_value = native_probabilities[label_value as usize];
-            writable_loss.push(-probability_value);
+        for (sample, &label_value) in native_labels.iter().enumerate() {
+            let index = (sample / inner_num * num_classes + label_value as usize) * inner_num + sample % inner_num;
+            writable_loss.push(-native_probabilities[index]);
         }
+        let weights = sample_weights(input_data, writable_loss.len());
+        let normalizer = self
+            .reduction
+            .normalizer(writable_loss.len() as f32, batch_size(input_data[0].desc()));
 
-        let mut loss = writable_loss.iter().fold(0f32, |sum, &val| sum + val);
-        loss = loss / (batch_size as f32);
-        writable_loss = vec![loss];
-
-        crate::util::write_to_memory(output_data[0].write_only(native.device()).unwrap(), &writable_loss);
+        crate::util::write_to_memory(
+            output_data[0].write_only(native.device()).unwrap(),
+            &self.reduction.reduce(&writable_loss, weights.as_deref(), normalizer),
+        );
     }
 }
 
@@ -106,16 +123,27 @@ impl<B: IBackend> ComputeInputGradient<f32, B> for NegativeLogLikelihood {
         input_gradients: &mut [&mut SharedTensor<f32>],
     ) {
         let labels = input_data[1];
-        let batch_size = Self::batch_size(input_data[0].desc());
-        let num_classes = self.num_classes;
+        let (outer_num, num_classes, inner_num) = self.class_layout(input_data[0].desc());
+        let count = outer_num * inner_num;
 
         let native = native_backend();
         let native_labels = labels.read(native.device()).unwrap().as_slice::<f32>();
+        let weights = sample_weights(input_data, count);
+        let losses_gradient = self
+            .reduction
+            .losses_gradient(
+                count,
+                weights.as_deref(),
+                self.reduction
+                    .normalizer(count as f32, batch_size(input_data[0].desc())),
+                output_gradients.first().copied(),
+            )
+            .unwrap();
         let mut writable_gradient = vec![0f32; input_gradients[0].desc().size()];
 
-        for (batch_n, &label_value) in native_labels.iter().enumerate() {
-            let index = (num_classes * batch_n) + label_value as usize;
-            writable_gradient[index] = -1f32;
+        for (sample, &label_value) in native_labels.iter().enumerate() {
+            let index = (sample / inner_num * num_classes + label_value as usize) * inner_num + sample % inner_num;
+            writable_gradient[index] = -losses_gradient[sample];
         }
         crate::util::write_to_memory(
             input_gradients[0].write_only(native.device()).unwrap(),
@@ -132,6 +160,21 @@ impl<B: IBackend> ComputeParametersGradient<f32, B> for NegativeLogLikelihood {}
 pub struct NegativeLogLikelihoodConfig {
     /// How many different classes can be classified.
     pub num_classes: usize,
+    /// How the losses of all samples are combined.
+    pub reduction: LossReduction,
+}
+
+impl NegativeLogLikelihoodConfig {
+    /// Create a config for `num_classes` classes.
+    ///
+    /// The losses are combined into their sum divided by the batch size, the loss the
+    /// layer returned before the reduction was configurable.
+    pub fn new(num_classes: usize) -> NegativeLogLikelihoodConfig {
+        NegativeLogLikelihoodConfig {
+            num_classes,
+            reduction: LossReduction::BatchMean,
+        }
+    }
 }
 
 impl<'a> CapnpWrite<'a> for NegativeLogLikelihoodConfig {
@@ -140,6 +183,7 @@ impl<'a> CapnpWrite<'a> for NegativeLogLikelihoodConfig {
     /// Write the NegativeLogLikelihoodConfig into a capnp message.
     fn write_capnp(&self, builder: &mut Self::Builder) {
         builder.set_num_classes(self.num_classes as u64);
+        builder.set_reduction(self.reduction.to_capnp());
     }
 }
 
@@ -148,10 +192,9 @@ impl<'a> CapnpRead<'a> for NegativeLogLikelihoodConfig {
 
     fn read_capnp(reader: Self::Reader) -> Self {
         let num_classes = reader.get_num_classes() as usize;
+        let reduction = LossReduction::from_capnp(reader.get_reduction().unwrap());
 
-        NegativeLogLikelihoodConfig {
-            num_classes: num_classes,
-        }
+        NegativeLogLikelihoodConfig { num_classes, reduction }
     }
 }
 
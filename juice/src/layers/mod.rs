@@ -51,18 +51,32 @@
 /// Implement [ILayer][1] for [activation layers][2].
 /// [1]: ./layer/trait.ILayer.html
 /// [2]: ./layers/activation/index.html
-pub use self::activation::{ReLU, Sigmoid, TanH};
+pub use self::activation::{
+    ELUConfig, HardTanh, HardTanhConfig, LeakyReLU, LeakyReLUConfig, ReLU, Sigmoid, Softplus, Swish, TanH, ELU, GELU,
+    SELU,
+};
 
 pub use self::common::{
-    Convolution, ConvolutionConfig, Dropout, DropoutConfig, Linear, LinearConfig, LogSoftmax, Pooling, PoolingConfig,
-    PoolingMode, Rnn, RnnConfig, Softmax,
+    BatchNorm, BatchNormConfig, Convolution, ConvolutionConfig, ConvolutionTranspose, ConvolutionTransposeConfig,
+    Dropout, DropoutConfig, Embedding, EmbeddingConfig, EmbeddingInit, GroupNorm, GroupNormConfig, LRNConfig,
+    LayerNorm, LayerNormConfig, Linear, LinearConfig, LogSoftmax, MultiHeadAttention, MultiHeadAttentionConfig,
+    Pooling, PoolingConfig, PoolingMode, Rnn, RnnConfig, Softmax, Upsample, UpsampleConfig, UpsampleMode, UpsampleSize,
+    LRN,
 };
 
 pub use self::container::{Sequential, SequentialConfig};
 
-pub use self::loss::{MeanSquaredError, NegativeLogLikelihood, NegativeLogLikelihoodConfig};
+pub use self::loss::{
+    BinaryCrossEntropy, BinaryCrossEntropyConfig, BinaryCrossEntropyWithLogits, BinaryCrossEntropyWithLogitsConfig,
+    CrossEntropy, CrossEntropyConfig, Huber, HuberConfig, KlDivergence, KlDivergenceConfig, LossReduction,
+    MeanAbsoluteError, MeanAbsoluteErrorConfig, MeanSquaredError, MeanSquaredErrorConfig, MultiClassHinge,
+    MultiClassHingeConfig, NegativeLogLikelihood, NegativeLogLikelihoodConfig,
+};
 
-pub use self::utility::{Flatten, Reshape, ReshapeConfig};
+pub use self::utility::{
+    Concat, ConcatConfig, Flatten, Pad, PadConfig, PadMode, Permute, PermuteConfig, Reshape, ReshapeConfig, Split,
+    SplitConfig,
+};
 
 pub mod activation;
 pub mod common;
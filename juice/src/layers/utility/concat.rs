@@ -0,0 +1,137 @@
+//! Utility layer to join several tensors along an axis.
+//!
+//! The inputs are joined in the order they are connected to the layer, e.g.
+//! joining inputs of shape `[N, 16, H, W]` and `[N, 32, H, W]` along the axis `1`
+//! yields a tensor of shape `[N, 48, H, W]`, as used to merge the branches of
+//! Inception-style networks.
+//!
+//! All inputs need to have the same shape, except for the dimension `axis`.
+
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::conn;
+use crate::juice_capnp::concat_config as capnp_config;
+use crate::layer::*;
+use crate::util::ArcLock;
+
+#[derive(Debug, Clone)]
+/// Concat Utility Layer
+pub struct Concat {
+    axis: usize,
+}
+
+impl Concat {
+    /// Create a Concat layer from a ConcatConfig.
+    pub fn from_config(config: &ConcatConfig) -> Concat {
+        Concat { axis: config.axis }
+    }
+}
+
+impl<B: IBackend + conn::Concat<f32>> ILayer<B> for Concat {
+    fn exact_num_output_blobs(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let mut output_shape = input_data[0].read().unwrap().desc().clone();
+        if self.axis >= output_shape.len() {
+            panic!("Concat axis {} exceeds an input of shape {:?}", self.axis, output_shape);
+        }
+        output_shape[self.axis] = 0;
+        for (input_id, input) in input_data.iter().enumerate() {
+            let inp = input.read().unwrap();
+            let input_desc = inp.desc();
+            let matches = input_desc.len() == output_shape.len()
+                && (0..input_desc.len()).all(|d| d == self.axis || input_desc[d] == output_shape[d]);
+            if !matches {
+                panic!(
+                    "Concat input of shape {:?} does not match the other inputs along axis {}",
+                    input_desc, self.axis
+                );
+            }
+            output_shape[self.axis] += input_desc[self.axis];
+            input_gradient[input_id].write().unwrap().resize(input_desc).unwrap();
+        }
+        output_data[0].write().unwrap().resize(&output_shape).unwrap();
+        output_gradient[0].write().unwrap().resize(&output_shape).unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Concat<f32>> ComputeOutput<f32, B> for Concat {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        backend.concat(input_data, output_data[0], self.axis).unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Concat<f32>> ComputeInputGradient<f32, B> for Concat {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        backend
+            .concat_grad(output_gradients[0], input_gradients, self.axis)
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Concat<f32>> ComputeParametersGradient<f32, B> for Concat {}
+
+#[derive(Debug, Copy, Clone)]
+/// Specifies configuration parameters for a Concat Layer.
+pub struct ConcatConfig {
+    /// The dimension along which the inputs are joined.
+    ///
+    /// Defaults to `1`, the feature maps of NCHW inputs.
+    pub axis: usize,
+}
+
+impl Default for ConcatConfig {
+    fn default() -> ConcatConfig {
+        ConcatConfig { axis: 1 }
+    }
+}
+
+impl<'a> CapnpWrite<'a> for ConcatConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the ConcatConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.set_axis(self.axis as u64);
+    }
+}
+
+impl<'a> CapnpRead<'a> for ConcatConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        ConcatConfig {
+            axis: reader.get_axis() as usize,
+        }
+    }
+}
+
+impl Into<LayerType> for ConcatConfig {
+    fn into(self) -> LayerType {
+        LayerType::Concat(self)
+    }
+}
@@ -10,8 +10,16 @@
 //!
 //! [1]: ../../layer/index.html
 
+pub use self::concat::{Concat, ConcatConfig};
 pub use self::flatten::Flatten;
+pub use self::pad::{Pad, PadConfig, PadMode};
+pub use self::permute::{Permute, PermuteConfig};
 pub use self::reshape::{Reshape, ReshapeConfig};
+pub use self::split::{Split, SplitConfig};
 
+pub mod concat;
 pub mod flatten;
+pub mod pad;
+pub mod permute;
 pub mod reshape;
+pub mod split;
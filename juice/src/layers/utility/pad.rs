@@ -0,0 +1,229 @@
+//! Utility layer to pad the trailing dimensions of a tensor.
+//!
+//! The padding is given as a pair of the number of elements added before and after
+//! the input for each of the last dimensions of the input, so padding an input of shape
+//! `[N, C, H, W]` by `[(1, 1), (2, 2)]` yields a tensor of shape `[N, C, H + 2, W + 4]`.
+//! *See [PadMode][pad_mode]*
+//!
+//! [pad_mode]: ./enum.PadMode.html
+//!
+//! Unlike the implicit zero padding of a [Convolution](../../common/convolution/index.html),
+//! reflecting or replicating the border avoids artefacts at the border of images.
+
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::conn;
+use crate::juice_capnp::pad_config as capnp_config;
+use crate::layer::*;
+use crate::util::ArcLock;
+
+#[derive(Debug, Clone)]
+/// Pad Utility Layer
+pub struct Pad {
+    mode: PadMode,
+    padding: Vec<(usize, usize)>,
+}
+
+impl Pad {
+    /// Create a Pad layer from a PadConfig.
+    pub fn from_config(config: &PadConfig) -> Pad {
+        Pad {
+            mode: config.mode,
+            padding: config.padding.clone(),
+        }
+    }
+
+    /// The padding of every dimension of the input, leading dimensions are not padded.
+    fn input_padding(&self, input_shape: &[usize]) -> Vec<(usize, usize)> {
+        if self.padding.len() > input_shape.len() {
+            panic!(
+                "Padding {:?} has more dimensions than an input of shape {:?}",
+                self.padding, input_shape
+            );
+        }
+        let mut padding = vec![(0, 0); input_shape.len() - self.padding.len()];
+        padding.extend(&self.padding);
+        padding
+    }
+
+    fn calculate_output_shape(&self, input_shape: &[usize]) -> Vec<usize> {
+        input_shape
+            .iter()
+            .zip(self.input_padding(input_shape))
+            .map(|(dim, (before, after))| dim + before + after)
+            .collect()
+    }
+}
+
+impl<B: IBackend + conn::Pad<f32>> ILayer<B> for Pad {
+    fn exact_num_output_blobs(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    fn exact_num_input_blobs(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let inp = input_data[0].read().unwrap();
+        let input_desc = inp.desc();
+        let output_shape = self.calculate_output_shape(input_desc);
+        input_gradient[0].write().unwrap().resize(input_desc).unwrap();
+        output_data[0].write().unwrap().resize(&output_shape).unwrap();
+        output_gradient[0].write().unwrap().resize(&output_shape).unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Pad<f32>> ComputeOutput<f32, B> for Pad {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        let padding = self.input_padding(input_data[0].desc());
+        backend
+            .pad(self.mode.to_conn(), input_data[0], &padding, output_data[0])
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Pad<f32>> ComputeInputGradient<f32, B> for Pad {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        let padding = self.input_padding(input_gradients[0].desc());
+        backend
+            .pad_grad(self.mode.to_conn(), output_gradients[0], &padding, input_gradients[0])
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Pad<f32>> ComputeParametersGradient<f32, B> for Pad {}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// The values the border of the output of a Pad Layer is filled with.
+pub enum PadMode {
+    /// Fill the border with a constant value.
+    Constant(f32),
+    /// Mirror the input at its border, without repeating the border element.
+    ///
+    /// The padding has to be smaller than the padded dimension.
+    Reflect,
+    /// Repeat the border element of the input.
+    Replicate,
+}
+
+impl PadMode {
+    /// Return the corresponding coaster-nn value.
+    fn to_conn(&self) -> conn::PadMode<f32> {
+        match *self {
+            PadMode::Constant(value) => conn::PadMode::Constant(value),
+            PadMode::Reflect => conn::PadMode::Reflect,
+            PadMode::Replicate => conn::PadMode::Replicate,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Specifies configuration parameters for a Pad Layer.
+pub struct PadConfig {
+    /// The values the border is filled with
+    pub mode: PadMode,
+    /// The number of elements added before and after the input, for each of the
+    /// trailing dimensions of the input.
+    pub padding: Vec<(usize, usize)>,
+}
+
+impl<'a> CapnpWrite<'a> for PadConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the PadConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        match self.mode {
+            PadMode::Constant(value) => builder.reborrow().init_mode().set_constant(value),
+            PadMode::Reflect => builder.reborrow().init_mode().set_reflect(()),
+            PadMode::Replicate => builder.reborrow().init_mode().set_replicate(()),
+        }
+        {
+            let mut before = builder.reborrow().init_before(self.padding.len() as u32);
+            for (i, &(dim, _)) in self.padding.iter().enumerate() {
+                before.set(i as u32, dim as u64);
+            }
+        }
+        {
+            let mut after = builder.reborrow().init_after(self.padding.len() as u32);
+            for (i, &(_, dim)) in self.padding.iter().enumerate() {
+                after.set(i as u32, dim as u64);
+            }
+        }
+    }
+}
+
+impl<'a> CapnpRead<'a> for PadConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        let mode = match reader.get_mode().which().unwrap() {
+            capnp_config::mode::Which::Constant(value) => PadMode::Constant(value),
+            capnp_config::mode::Which::Reflect(()) => PadMode::Reflect,
+            capnp_config::mode::Which::Replicate(()) => PadMode::Replicate,
+        };
+        let read_before = reader.get_before().unwrap();
+        let read_after = reader.get_after().unwrap();
+        let mut padding = Vec::new();
+        for i in 0..read_before.len() {
+            padding.push((read_before.get(i) as usize, read_after.get(i) as usize))
+        }
+
+        PadConfig { mode, padding }
+    }
+}
+
+impl Into<LayerType> for PadConfig {
+    fn into(self) -> LayerType {
+        LayerType::Pad(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Pad, PadConfig, PadMode};
+
+    #[test]
+    fn pads_trailing_dims() {
+        let layer = Pad::from_config(&PadConfig {
+            mode: PadMode::Reflect,
+            padding: vec![(1, 1), (2, 0)],
+        });
+        assert_eq!(vec![2, 3, 6, 6], layer.calculate_output_shape(&[2, 3, 4, 4]));
+        assert_eq!(vec![(0, 0), (0, 0), (1, 1), (2, 0)], layer.input_padding(&[2, 3, 4, 4]));
+        assert_eq!(vec![6, 6], layer.calculate_output_shape(&[4, 4]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn padding_within_input_dims() {
+        let layer = Pad::from_config(&PadConfig {
+            mode: PadMode::Constant(0.0),
+            padding: vec![(1, 1), (1, 1)],
+        });
+        layer.calculate_output_shape(&[4]);
+    }
+}
@@ -0,0 +1,141 @@
+//! Utility layer to reorder the dimensions of a tensor.
+//!
+//! The output dimension `i` is the input dimension `axes[i]`, so permuting an
+//! input of shape `[N, T, D]` by `[1, 0, 2]` yields a tensor of shape `[T, N, D]`.
+//!
+//! Unlike [Reshape](../reshape/index.html) the data is actually moved around,
+//! so the layer can not be used as an in-place operation.
+
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::conn;
+use crate::juice_capnp::permute_config as capnp_config;
+use crate::layer::*;
+use crate::util::ArcLock;
+
+#[derive(Debug, Clone)]
+/// Permute Utility Layer
+pub struct Permute {
+    axes: Vec<usize>,
+}
+
+impl Permute {
+    /// Create a Permute layer from a PermuteConfig.
+    pub fn from_config(config: &PermuteConfig) -> Permute {
+        Permute {
+            axes: config.axes.clone(),
+        }
+    }
+}
+
+impl<B: IBackend + conn::Permute<f32>> ILayer<B> for Permute {
+    fn exact_num_output_blobs(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    fn exact_num_input_blobs(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let inp = input_data[0].read().unwrap();
+        let input_desc = inp.desc();
+        if self.axes.len() != input_desc.len() {
+            panic!(
+                "Permute axes {:?} do not match an input of shape {:?}",
+                self.axes, input_desc
+            );
+        }
+        let output_shape: Vec<usize> = self.axes.iter().map(|&axis| input_desc[axis]).collect();
+        input_gradient[0].write().unwrap().resize(input_desc).unwrap();
+        output_data[0].write().unwrap().resize(&output_shape).unwrap();
+        output_gradient[0].write().unwrap().resize(&output_shape).unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Permute<f32>> ComputeOutput<f32, B> for Permute {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        backend.permute(input_data[0], output_data[0], &self.axes).unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Permute<f32>> ComputeInputGradient<f32, B> for Permute {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        backend
+            .permute_grad(output_gradients[0], input_gradients[0], &self.axes)
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Permute<f32>> ComputeParametersGradient<f32, B> for Permute {}
+
+#[derive(Debug, Clone)]
+/// Specifies configuration parameters for a Permute Layer.
+pub struct PermuteConfig {
+    /// The input dimension that becomes each output dimension.
+    ///
+    /// Has to contain every dimension of the input exactly once.
+    pub axes: Vec<usize>,
+}
+
+impl PermuteConfig {
+    /// Create a PermuteConfig that describes a Permute layer with the provided axes.
+    pub fn of_axes(axes: &[usize]) -> PermuteConfig {
+        PermuteConfig { axes: axes.to_owned() }
+    }
+}
+
+impl<'a> CapnpWrite<'a> for PermuteConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the PermuteConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        let mut axes = builder.reborrow().init_axes(self.axes.len() as u32);
+        for (i, axis) in self.axes.iter().enumerate() {
+            axes.set(i as u32, *axis as u64);
+        }
+    }
+}
+
+impl<'a> CapnpRead<'a> for PermuteConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        let read_axes = reader.get_axes().unwrap();
+        let mut axes = Vec::new();
+        for i in 0..read_axes.len() {
+            axes.push(read_axes.get(i) as usize)
+        }
+
+        PermuteConfig { axes: axes }
+    }
+}
+
+impl Into<LayerType> for PermuteConfig {
+    fn into(self) -> LayerType {
+        LayerType::Permute(self)
+    }
+}
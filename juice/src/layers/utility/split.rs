@@ -0,0 +1,159 @@
+//! Utility layer to split a tensor into several tensors along an axis.
+//!
+//! The input is split into consecutive sections of the configured sizes, one for
+//! every output of the layer, e.g. splitting an input of shape `[N, 48, H, W]`
+//! into the sections `[16, 32]` along the axis `1` yields tensors of shape
+//! `[N, 16, H, W]` and `[N, 32, H, W]`, which can be fed to separate branches
+//! of a network.
+
+use crate::capnp_util::*;
+use crate::co::{IBackend, SharedTensor};
+use crate::conn;
+use crate::juice_capnp::split_config as capnp_config;
+use crate::layer::*;
+use crate::util::ArcLock;
+
+#[derive(Debug, Clone)]
+/// Split Utility Layer
+pub struct Split {
+    axis: usize,
+    sections: Vec<usize>,
+}
+
+impl Split {
+    /// Create a Split layer from a SplitConfig.
+    pub fn from_config(config: &SplitConfig) -> Split {
+        Split {
+            axis: config.axis,
+            sections: config.sections.clone(),
+        }
+    }
+}
+
+impl<B: IBackend + conn::Split<f32>> ILayer<B> for Split {
+    fn exact_num_output_blobs(&self) -> Option<usize> {
+        Some(self.sections.len())
+    }
+
+    fn exact_num_input_blobs(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    fn reshape(
+        &mut self,
+        backend: ::std::rc::Rc<B>,
+        input_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        input_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        weights_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_data: &mut Vec<ArcLock<SharedTensor<f32>>>,
+        output_gradient: &mut Vec<ArcLock<SharedTensor<f32>>>,
+    ) {
+        let inp = input_data[0].read().unwrap();
+        let input_desc = inp.desc();
+        if self.axis >= input_desc.len() || self.sections.iter().sum::<usize>() != input_desc[self.axis] {
+            panic!(
+                "Split sections {:?} along axis {} do not match an input of shape {:?}",
+                self.sections, self.axis, input_desc
+            );
+        }
+        input_gradient[0].write().unwrap().resize(input_desc).unwrap();
+        let mut output_shape = input_desc.clone();
+        for (output_id, section) in self.sections.iter().enumerate() {
+            output_shape[self.axis] = *section;
+            output_data[output_id].write().unwrap().resize(&output_shape).unwrap();
+            output_gradient[output_id]
+                .write()
+                .unwrap()
+                .resize(&output_shape)
+                .unwrap();
+        }
+    }
+}
+
+impl<B: IBackend + conn::Split<f32>> ComputeOutput<f32, B> for Split {
+    fn compute_output(
+        &self,
+        backend: &B,
+        _weights: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        output_data: &mut [&mut SharedTensor<f32>],
+    ) {
+        backend.split(input_data[0], output_data, self.axis).unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Split<f32>> ComputeInputGradient<f32, B> for Split {
+    fn compute_input_gradient(
+        &self,
+        backend: &B,
+        weights_data: &[&SharedTensor<f32>],
+        output_data: &[&SharedTensor<f32>],
+        output_gradients: &[&SharedTensor<f32>],
+        input_data: &[&SharedTensor<f32>],
+        input_gradients: &mut [&mut SharedTensor<f32>],
+    ) {
+        backend
+            .split_grad(output_gradients, input_gradients[0], self.axis)
+            .unwrap();
+    }
+}
+
+impl<B: IBackend + conn::Split<f32>> ComputeParametersGradient<f32, B> for Split {}
+
+#[derive(Debug, Clone)]
+/// Specifies configuration parameters for a Split Layer.
+pub struct SplitConfig {
+    /// The dimension along which the input is split.
+    pub axis: usize,
+    /// The size of every output along `axis`.
+    ///
+    /// Has to add up to the size of the input along `axis`.
+    pub sections: Vec<usize>,
+}
+
+impl SplitConfig {
+    /// Create a SplitConfig that describes a Split layer into the provided sections.
+    pub fn of_sections(axis: usize, sections: &[usize]) -> SplitConfig {
+        SplitConfig {
+            axis: axis,
+            sections: sections.to_owned(),
+        }
+    }
+}
+
+impl<'a> CapnpWrite<'a> for SplitConfig {
+    type Builder = capnp_config::Builder<'a>;
+
+    /// Write the SplitConfig into a capnp message.
+    fn write_capnp(&self, builder: &mut Self::Builder) {
+        builder.set_axis(self.axis as u64);
+        let mut sections = builder.reborrow().init_sections(self.sections.len() as u32);
+        for (i, section) in self.sections.iter().enumerate() {
+            sections.set(i as u32, *section as u64);
+        }
+    }
+}
+
+impl<'a> CapnpRead<'a> for SplitConfig {
+    type Reader = capnp_config::Reader<'a>;
+
+    fn read_capnp(reader: Self::Reader) -> Self {
+        let read_sections = reader.get_sections().unwrap();
+        let mut sections = Vec::new();
+        for i in 0..read_sections.len() {
+            sections.push(read_sections.get(i) as usize)
+        }
+
+        SplitConfig {
+            axis: reader.get_axis() as usize,
+            sections: sections,
+        }
+    }
+}
+
+impl Into<LayerType> for SplitConfig {
+    fn into(self) -> LayerType {
+        LayerType::Split(self)
+    }
+}
@@ -30,6 +30,10 @@
 //! and can be used for computations on CUDA, OpenCL and native host CPU.
 //! It provides performance optimizations and automatically takes care of memory management and synchronization.
 //!
+//! Layers and Solvers currently operate on `SharedTensor<f32>` only. The backend requirements
+//! `LayerOps` and `SolverOps` and the serialization of weights are generic over the element
+//! type, but running a network in `f64` or half precision is not supported yet.
+//!
 //! A neural network can be created by combining container layers like the `Sequential` Layer.
 //! Those can be nested and allow for bigger neural networks to be constructed while still
 //! retaining the interface of a Layer.
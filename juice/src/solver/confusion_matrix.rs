@@ -1,6 +1,7 @@
 //! TODO: DOC
 
-use crate::co::SharedTensor;
+use crate::co::prelude::*;
+use crate::conn::Reduction;
 use crate::util::native_backend;
 use std::collections::VecDeque;
 use std::fmt;
@@ -51,17 +52,22 @@ impl ConfusionMatrix {
     /// The prediction for each sample of the batch is found by
     /// determining which output value had the smallest loss.
     pub fn get_predictions(&self, network_out: &mut SharedTensor<f32>) -> Vec<usize> {
-        let native_infered = network_out.read(native_backend().device()).unwrap();
-        let predictions_slice = native_infered.as_slice::<f32>();
-
-        let mut predictions = Vec::<usize>::new();
-        for batch_predictions in predictions_slice.chunks(self.num_classes) {
-            let mut enumerated_predictions = batch_predictions.iter().enumerate().collect::<Vec<_>>();
-            enumerated_predictions
-                .sort_by(|&(_, one), &(_, two)| one.partial_cmp(two).unwrap_or(::std::cmp::Ordering::Equal)); // find index of prediction
-            predictions.push(enumerated_predictions.last().unwrap().0)
-        }
-        predictions
+        let native = native_backend();
+        let out_desc = network_out.desc().clone();
+        let batch_size = out_desc.size() / self.num_classes;
+        network_out.reshape(&vec![batch_size, self.num_classes]).unwrap();
+
+        let mut predictions = SharedTensor::<u32>::new(&batch_size);
+        let argmax = native.argmax(network_out, 1, false, &mut predictions);
+        network_out.reshape(&out_desc).unwrap();
+        argmax.unwrap();
+
+        let native_predictions = predictions.read(native.device()).unwrap();
+        native_predictions
+            .as_slice::<u32>()
+            .iter()
+            .map(|&prediction| prediction as usize)
+            .collect()
     }
 
     /// Set the `capacity` of the ConfusionMatrix
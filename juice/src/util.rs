@@ -27,9 +27,19 @@ pub fn write_to_memory<T: NumCast + ::std::marker::Copy>(mem: &mut FlatBox, data
 
 /// Write into a native Coaster Memory with a offset.
 pub fn write_to_memory_offset<T: NumCast + ::std::marker::Copy>(mem: &mut FlatBox, data: &[T], offset: usize) {
-    let mem_buffer = mem.as_mut_slice::<f32>();
+    write_to_memory_offset_as::<f32, T>(mem, data, offset);
+}
+
+/// Write into a native Coaster Memory of elements of type `F` with a offset.
+///
+/// The data is cast to `F`, so e.g. `u8` pixels can be written into a `SharedTensor<f64>`.
+pub fn write_to_memory_offset_as<F, T>(mem: &mut FlatBox, data: &[T], offset: usize)
+where
+    F: NumCast + ::std::marker::Copy,
+    T: NumCast + ::std::marker::Copy,
+{
+    let mem_buffer = mem.as_mut_slice::<F>();
     for (index, datum) in data.iter().enumerate() {
-        // mem_buffer[index + offset] = *datum;
         mem_buffer[index + offset] = cast(*datum).unwrap();
     }
 }
@@ -41,8 +51,8 @@ pub fn write_to_memory_offset<T: NumCast + ::std::marker::Copy>(mem: &mut FlatBo
 /// is assumed to be the batchsize.
 ///
 /// Allocates memory on a Native Backend if neccessary.
-pub fn write_batch_sample<T: NumCast + ::std::marker::Copy>(
-    tensor: &mut SharedTensor<f32>,
+pub fn write_batch_sample<F: NumCast + ::std::marker::Copy, T: NumCast + ::std::marker::Copy>(
+    tensor: &mut SharedTensor<F>,
     data: &[T], i: usize) {
     let native_backend = native_backend();
     let tensor_desc = tensor.desc();
@@ -50,7 +60,7 @@ pub fn write_batch_sample<T: NumCast + ::std::marker::Copy>(
     let batch_sample_size = tensor_desc.size();
     let sample_size = batch_sample_size / batch_size;
 
-    write_to_memory_offset(
+    write_to_memory_offset_as::<F, T>(
         tensor.write_only(native_backend.device()).unwrap(),
         &data,
         i * sample_size,
@@ -61,7 +71,7 @@ pub fn write_batch_sample<T: NumCast + ::std::marker::Copy>(
 pub fn native_scalar<T: NumCast + ::std::marker::Copy>(scalar: T) -> SharedTensor<T> {
     let native = native_backend();
     let mut shared_scalar = SharedTensor::<T>::new(&[1]);
-    write_to_memory(shared_scalar.write_only(native.device()).unwrap(), &[scalar]);
+    write_to_memory_offset_as::<T, T>(shared_scalar.write_only(native.device()).unwrap(), &[scalar], 0);
     shared_scalar
 }
 
@@ -92,7 +102,7 @@ pub trait Axpby<F>: Axpy<F> + Scal<F> {
     }
 }
 
-impl<T: Axpy<f32> + Scal<f32>> Axpby<f32> for T {}
+impl<F, T: Axpy<F> + Scal<F>> Axpby<F> for T {}
 
 /// Encapsulates all traits required by Solvers.
 // pub trait SolverOps<F> : Axpby<F> + Dot<F> + Copy<F> {}
@@ -100,7 +110,7 @@ impl<T: Axpy<f32> + Scal<f32>> Axpby<f32> for T {}
 // impl<T: Axpby<f32> + Dot<f32> + Copy<f32>> SolverOps<f32> for T {}
 pub trait SolverOps<F>: LayerOps<F> + Axpby<F> + Dot<F> + Copy<F> {}
 
-impl<T: LayerOps<f32> + Axpby<f32> + Dot<f32> + Copy<f32>> SolverOps<f32> for T {}
+impl<F, T: LayerOps<F> + Axpby<F> + Dot<F> + Copy<F>> SolverOps<F> for T {}
 
 /// Encapsulates all traits used in Layers.
 pub trait LayerOps<F>:
@@ -113,9 +123,35 @@ pub trait LayerOps<F>:
     + conn::SigmoidPointwise<F>
     + conn::Tanh<F>
     + conn::TanhPointwise<F>
+    + conn::LeakyRelu<F>
+    + conn::LeakyReluPointwise<F>
+    + conn::Elu<F>
+    + conn::EluPointwise<F>
+    + conn::Selu<F>
+    + conn::SeluPointwise<F>
+    + conn::Gelu<F>
+    + conn::Swish<F>
+    + conn::Softplus<F>
+    + conn::SoftplusPointwise<F>
+    + conn::HardTanh<F>
+    + conn::HardTanhPointwise<F>
     + conn::Softmax<F>
     + conn::LogSoftmax<F>
     + conn::Dropout<F>
+    + conn::LRN<F>
+    + conn::BatchNormalization<F>
+    + conn::LayerNorm<F>
+    + conn::GroupNorm<F>
+    + conn::Arithmetic<F>
+    + conn::ArithmeticPointwise<F>
+    + conn::Reduction<F>
+    + conn::Permute<F>
+    + conn::Concat<F>
+    + conn::Split<F>
+    + conn::Embedding<F>
+    + conn::Upsample<F>
+    + conn::Pad<F>
+    + conn::SoftmaxCrossEntropy<F>
     + Gemm<F>
     + Axpby<F>
     + Copy<F>
@@ -123,21 +159,48 @@ pub trait LayerOps<F>:
 }
 
 impl<
-        T: conn::Convolution<f32>
-            + conn::Rnn<f32>
-            + conn::Pooling<f32>
-            + conn::Relu<f32>
-            + conn::ReluPointwise<f32>
-            + conn::Sigmoid<f32>
-            + conn::SigmoidPointwise<f32>
-            + conn::Tanh<f32>
-            + conn::TanhPointwise<f32>
-            + conn::Softmax<f32>
-            + conn::LogSoftmax<f32>
-            + conn::Dropout<f32>
-            + Gemm<f32>
-            + Axpby<f32>
-            + Copy<f32>,
-    > LayerOps<f32> for T
+        F,
+        T: conn::Convolution<F>
+            + conn::Rnn<F>
+            + conn::Pooling<F>
+            + conn::Relu<F>
+            + conn::ReluPointwise<F>
+            + conn::Sigmoid<F>
+            + conn::SigmoidPointwise<F>
+            + conn::Tanh<F>
+            + conn::TanhPointwise<F>
+            + conn::LeakyRelu<F>
+            + conn::LeakyReluPointwise<F>
+            + conn::Elu<F>
+            + conn::EluPointwise<F>
+            + conn::Selu<F>
+            + conn::SeluPointwise<F>
+            + conn::Gelu<F>
+            + conn::Swish<F>
+            + conn::Softplus<F>
+            + conn::SoftplusPointwise<F>
+            + conn::HardTanh<F>
+            + conn::HardTanhPointwise<F>
+            + conn::Softmax<F>
+            + conn::LogSoftmax<F>
+            + conn::Dropout<F>
+            + conn::LRN<F>
+            + conn::BatchNormalization<F>
+            + conn::LayerNorm<F>
+            + conn::GroupNorm<F>
+            + conn::Arithmetic<F>
+            + conn::ArithmeticPointwise<F>
+            + conn::Reduction<F>
+            + conn::Permute<F>
+            + conn::Concat<F>
+            + conn::Split<F>
+            + conn::Embedding<F>
+            + conn::Upsample<F>
+            + conn::Pad<F>
+            + conn::SoftmaxCrossEntropy<F>
+            + Gemm<F>
+            + Axpby<F>
+            + Copy<F>,
+    > LayerOps<F> for T
 {
 }
@@ -165,6 +165,13 @@ pub enum FillerType {
         /// Number of output nodes for each input.
         output_size: usize,
     },
+    /// Fills the weight blob with values drawn from a normal distribution.
+    Gaussian {
+        /// The mean of the distribution.
+        mean: f32,
+        /// The standard deviation of the distribution.
+        std: f32,
+    },
 }
 
 impl FillerType {
@@ -181,6 +188,7 @@ impl FillerType {
                 input_size,
                 output_size,
             } => Self::fill_glorot(weight, input_size, output_size),
+            FillerType::Gaussian { mean, std } => Self::fill_gaussian(weight, mean, std),
         }
     }
 
@@ -206,4 +214,19 @@ impl FillerType {
             *e = between.sample(&mut rng);
         }
     }
+
+    /// Directly use the [Gaussian Filler](#variant.Gaussian).
+    pub fn fill_gaussian(weight: &mut SharedTensor<f32>, mean: f32, std: f32) {
+        let native = native_backend();
+        let native_weight = weight.write_only(native.device()).unwrap();
+
+        let mut rng = thread_rng();
+        for e in native_weight.as_mut_slice::<f32>() {
+            // Box-Muller transform, `1 - u` keeps the logarithm finite
+            let u1: f32 = 1.0 - rng.gen::<f32>();
+            let u2: f32 = rng.gen();
+            let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos();
+            *e = mean + std * z;
+        }
+    }
 }
@@ -49,6 +49,9 @@ mod layer_spec {
         use crate::co::prelude::*;
         use juice::layer::*;
         use juice::layers::*;
+        use juice::util::{write_to_memory, ArcLock};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::{Arc, RwLock};
 
         fn simple_network() -> LayerConfig {
             let mut net_cfg = SequentialConfig::default();
@@ -61,6 +64,91 @@ mod layer_spec {
             LayerConfig::new("network", net_cfg)
         }
 
+        /// Saves a network of `cfg` after a forward pass with `inputs`, loads it again and
+        /// asserts that the weights and the output for `inputs` are unchanged.
+        ///
+        /// Returns the loaded network with the shape and the values of its output.
+        fn assert_save_load_roundtrip(
+            cfg: SequentialConfig,
+            inputs: &[&[f32]],
+        ) -> (Layer<Backend<Native>>, Vec<usize>, Vec<f32>) {
+            static ROUNDTRIPS: AtomicUsize = AtomicUsize::new(0);
+
+            let shapes: Vec<Vec<usize>> = cfg.inputs.iter().map(|(_, shape)| shape.clone()).collect();
+            assert_eq!(shapes.len(), inputs.len());
+            let forward = |layer: &mut Layer<Backend<Native>>| -> (Vec<usize>, Vec<f32>) {
+                let tensors: Vec<_> = shapes
+                    .iter()
+                    .zip(inputs)
+                    .map(|(shape, data)| {
+                        let mut tensor = SharedTensor::<f32>::new(shape);
+                        write_to_memory(tensor.write_only(native_backend().device()).unwrap(), data);
+                        Arc::new(RwLock::new(tensor))
+                    })
+                    .collect();
+                let output = layer.forward(&tensors)[0].clone();
+                let output = output.read().unwrap();
+                let values = output
+                    .read(native_backend().device())
+                    .unwrap()
+                    .as_slice::<f32>()
+                    .to_vec();
+                (output.desc().clone(), values)
+            };
+            let values = |weights: Vec<ArcLock<SharedTensor<f32>>>| -> Vec<Vec<f32>> {
+                weights
+                    .iter()
+                    .map(|weight| {
+                        let weight = weight.read().unwrap();
+                        let weight = weight.read(native_backend().device()).unwrap();
+                        weight.as_slice::<f32>().to_vec()
+                    })
+                    .collect()
+            };
+
+            let mut original_layer = Layer::from_config(native_backend(), &LayerConfig::new("network", cfg));
+            let original_output = forward(&mut original_layer);
+
+            let mut tmpfile = std::env::temp_dir();
+            tmpfile.push(format!(
+                "tmpnet_roundtrip_{}_{}",
+                std::process::id(),
+                ROUNDTRIPS.fetch_add(1, Ordering::SeqCst)
+            ));
+            original_layer.save(&tmpfile).unwrap();
+            let mut loaded_layer = Layer::<Backend<Native>>::load(native_backend(), &tmpfile).unwrap();
+
+            assert_eq!(original_layer.input_blob_names(), loaded_layer.input_blob_names());
+            assert_eq!(
+                original_layer.learnable_weights_names(),
+                loaded_layer.learnable_weights_names()
+            );
+            assert_eq!(
+                values(original_layer.learnable_weights_data()),
+                values(loaded_layer.learnable_weights_data())
+            );
+            assert_eq!(
+                original_layer.non_learnable_weights_names(),
+                loaded_layer.non_learnable_weights_names()
+            );
+            assert_eq!(
+                values(original_layer.non_learnable_weights_data()),
+                values(loaded_layer.non_learnable_weights_data())
+            );
+
+            let (shape, output) = forward(&mut loaded_layer);
+            assert_eq!(original_output, (shape.clone(), output.clone()));
+            (loaded_layer, shape, output)
+        }
+
+        /// Asserts that `output` is within `1e-5` of `expected`.
+        fn assert_close(output: &[f32], expected: &[f32]) {
+            assert_eq!(output.len(), expected.len());
+            for (out, exp) in output.iter().zip(expected) {
+                assert!((out - exp).abs() < 1e-5, "{:?} != {:?}", output, expected);
+            }
+        }
+
         #[test]
         fn xor_forward() {
             // let _ = env_logger::init();
@@ -115,6 +203,430 @@ mod layer_spec {
 
             assert_eq!(original_weight, loaded_weight);
         }
+
+        #[test]
+        fn save_and_load_layer_roundtrip() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &vec![1, 1, 28, 28]);
+            net_cfg.add_layer(LayerConfig::new(
+                "linear",
+                LayerType::Linear(LinearConfig { output_size: 10 }),
+            ));
+
+            let input: Vec<f32> = (0..784).map(|i| i as f32 / 784.0).collect();
+            let (_, shape, _) = assert_save_load_roundtrip(net_cfg, &[&input]);
+            assert_eq!(shape, vec![1, 10]);
+        }
+
+        #[test]
+        fn save_and_load_linear_bias() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[1, 3]);
+            net_cfg.add_layer(LayerConfig::new("linear", LinearConfig { output_size: 2 }));
+            let cfg = LayerConfig::new("network", net_cfg);
+
+            let mut original_layer = Layer::from_config(native_backend(), &cfg);
+            // weight and bias
+            assert_eq!(original_layer.learnable_weights_names().len(), 2);
+            let mut value = 0f32;
+            for weight in original_layer.learnable_weights_data() {
+                let mut weight = weight.write().unwrap();
+                for x in weight
+                    .write_only(native_backend().device())
+                    .unwrap()
+                    .as_mut_slice::<f32>()
+                {
+                    value += 1.0;
+                    *x = value;
+                }
+            }
+
+            let mut tmpfile = std::env::temp_dir();
+            tmpfile.push("tmpnet_linear_bias");
+            original_layer.save(&tmpfile).unwrap();
+            let loaded_layer = Layer::<Backend<Native>>::load(native_backend(), &tmpfile).unwrap();
+
+            let weights = |layer: &Layer<Backend<Native>>| -> Vec<Vec<f32>> {
+                layer
+                    .learnable_weights_data()
+                    .iter()
+                    .map(|weight| {
+                        let weight = weight.read().unwrap();
+                        let weight = weight.read(native_backend().device()).unwrap();
+                        weight.as_slice::<f32>().to_vec()
+                    })
+                    .collect()
+            };
+            assert_eq!(
+                original_layer.learnable_weights_names(),
+                loaded_layer.learnable_weights_names()
+            );
+            assert_eq!(weights(&original_layer), weights(&loaded_layer));
+        }
+
+        #[test]
+        fn save_and_load_linear_bias_roundtrip() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[1, 3]);
+            net_cfg.add_layer(LayerConfig::new("linear", LinearConfig { output_size: 2 }));
+
+            let (layer, _, _) = assert_save_load_roundtrip(net_cfg, &[&[1f32, 2.0, 3.0]]);
+            // weight and bias
+            assert_eq!(layer.learnable_weights_names().len(), 2);
+        }
+
+        #[test]
+        fn save_and_load_average_pooling() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[1, 1, 4, 4]);
+            net_cfg.add_layer(LayerConfig::new(
+                "pooling",
+                PoolingConfig::of_window(PoolingMode::Average, &[2], &[2], &[0]),
+            ));
+
+            let input: Vec<f32> = (0..16).map(|i| i as f32).collect();
+            let (_, _, output) = assert_save_load_roundtrip(net_cfg, &[&input]);
+            assert_eq!(output, vec![2.5, 4.5, 10.5, 12.5]);
+        }
+
+        #[test]
+        fn save_and_load_average_pooling_padding() {
+            // every window holds a single input element and three padded ones
+            for &(include_padding, expected) in &[(false, [1f32, 2.0, 3.0, 4.0]), (true, [0.25, 0.5, 0.75, 1.0])] {
+                let mut net_cfg = SequentialConfig::default();
+                net_cfg.add_input("data", &[1, 1, 2, 2]);
+                let mut pooling_cfg = PoolingConfig::of_window(PoolingMode::Average, &[2], &[2], &[1]);
+                pooling_cfg.include_padding = include_padding;
+                net_cfg.add_layer(LayerConfig::new("pooling", pooling_cfg));
+
+                let (_, _, output) = assert_save_load_roundtrip(net_cfg, &[&[1.0, 2.0, 3.0, 4.0]]);
+                assert_eq!(output, expected.to_vec());
+            }
+        }
+
+        #[test]
+        fn save_and_load_global_and_adaptive_pooling() {
+            let test = |mode: PoolingMode, output_shape: Vec<usize>, expected_shape: Vec<usize>, expected: &[f32]| {
+                let mut net_cfg = SequentialConfig::default();
+                net_cfg.add_input("data", &[1, 1, 4, 4]);
+                net_cfg.add_layer(LayerConfig::new(
+                    "pooling",
+                    PoolingConfig::of_output_shape(mode, &output_shape),
+                ));
+
+                let input: Vec<f32> = (0..16).map(|i| i as f32).collect();
+                let (_, shape, output) = assert_save_load_roundtrip(net_cfg, &[&input]);
+                assert_eq!(shape, expected_shape);
+                assert_eq!(output, expected);
+            };
+
+            test(PoolingMode::GlobalAverage, vec![], vec![1, 1, 1, 1], &[7.5]);
+            test(PoolingMode::GlobalMax, vec![], vec![1, 1, 1, 1], &[15.0]);
+            test(
+                PoolingMode::AdaptiveMax,
+                vec![2],
+                vec![1, 1, 2, 2],
+                &[5.0, 7.0, 13.0, 15.0],
+            );
+            test(PoolingMode::AdaptiveAverage, vec![1, 2], vec![1, 1, 1, 2], &[6.5, 8.5]);
+        }
+
+        #[test]
+        fn save_and_load_dilated_grouped_convolution() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[1, 4, 6, 6]);
+            net_cfg.add_layer(LayerConfig::new(
+                "conv",
+                ConvolutionConfig {
+                    num_output: 4,
+                    filter_shape: vec![3],
+                    stride: vec![1],
+                    padding: vec![2],
+                    dilation: vec![2],
+                    groups: 4,
+                },
+            ));
+
+            let input: Vec<f32> = (0..144).map(|i| i as f32 / 144.0).collect();
+            let (_, shape, _) = assert_save_load_roundtrip(net_cfg, &[&input]);
+            assert_eq!(shape, vec![1, 4, 6, 6]);
+        }
+
+        #[test]
+        fn save_and_load_convolution_transpose() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[1, 3, 4, 4]);
+            net_cfg.add_layer(LayerConfig::new(
+                "deconv",
+                ConvolutionTransposeConfig {
+                    num_output: 2,
+                    filter_shape: vec![3],
+                    stride: vec![2],
+                    padding: vec![1],
+                    output_padding: vec![1],
+                },
+            ));
+
+            let input: Vec<f32> = (0..48).map(|i| i as f32 / 48.0).collect();
+            let (_, shape, _) = assert_save_load_roundtrip(net_cfg, &[&input]);
+            assert_eq!(shape, vec![1, 2, 8, 8]);
+        }
+
+        #[test]
+        fn save_and_load_embedding() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[2, 3]);
+            net_cfg.add_layer(LayerConfig::new(
+                "embedding",
+                EmbeddingConfig {
+                    vocab_size: 5,
+                    embedding_dim: 4,
+                    padding_idx: Some(0),
+                    init: EmbeddingInit::Normal { std: 0.1 },
+                },
+            ));
+
+            let (_, shape, output) = assert_save_load_roundtrip(net_cfg, &[&[0f32, 3.0, 1.0, 3.0, 4.0, 0.0]]);
+            assert_eq!(shape, vec![2, 3, 4]);
+            // the padding index maps to zeros, repeated indices to the same vector
+            assert_eq!(&output[0..4], &[0f32; 4]);
+            assert_eq!(&output[20..24], &[0f32; 4]);
+            assert_eq!(output[4..8], output[12..16]);
+        }
+
+        #[test]
+        fn save_and_load_multi_head_attention() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[2, 3, 4]);
+            net_cfg.add_layer(LayerConfig::new(
+                "attention",
+                MultiHeadAttentionConfig {
+                    num_heads: 2,
+                    causal: true,
+                },
+            ));
+
+            let input: Vec<f32> = (0..24).map(|i| (i as f32 * 0.3).sin()).collect();
+            let (_, shape, _) = assert_save_load_roundtrip(net_cfg, &[&input]);
+            assert_eq!(shape, vec![2, 3, 4]);
+        }
+
+        #[test]
+        fn save_and_load_upsample() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[1, 1, 2, 2]);
+            net_cfg.add_layer(LayerConfig::new(
+                "upsample",
+                UpsampleConfig {
+                    mode: UpsampleMode::Bilinear,
+                    size: UpsampleSize::Scale(vec![2.0, 1.5]),
+                },
+            ));
+
+            let (_, shape, output) = assert_save_load_roundtrip(net_cfg, &[&[0f32, 4.0, 8.0, 12.0]]);
+            assert_eq!(shape, vec![1, 1, 4, 3]);
+            // the rows are interpolated from `[0, 8]` to `[0, 2, 6, 8]`
+            let first_column: Vec<f32> = output.iter().step_by(3).cloned().collect();
+            assert_eq!(first_column, vec![0.0, 2.0, 6.0, 8.0]);
+        }
+
+        #[test]
+        fn save_and_load_pad() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[1, 1, 2, 3]);
+            net_cfg.add_layer(LayerConfig::new(
+                "pad",
+                PadConfig {
+                    mode: PadMode::Reflect,
+                    padding: vec![(1, 0), (1, 1)],
+                },
+            ));
+
+            let (_, shape, output) = assert_save_load_roundtrip(net_cfg, &[&[1f32, 2.0, 3.0, 4.0, 5.0, 6.0]]);
+            assert_eq!(shape, vec![1, 1, 3, 5]);
+            assert_eq!(
+                output,
+                vec![5f32, 4.0, 5.0, 6.0, 5.0, 2.0, 1.0, 2.0, 3.0, 2.0, 5.0, 4.0, 5.0, 6.0, 5.0]
+            );
+        }
+
+        #[test]
+        fn save_and_load_cross_entropy() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("network_out", &[2, 3]);
+            net_cfg.add_input("label", &[2, 1]);
+            net_cfg.add_layer(LayerConfig::new(
+                "cross_entropy",
+                CrossEntropyConfig {
+                    class_weights: Some(vec![1.0, 2.0, 3.0]),
+                    ignore_index: Some(0),
+                    reduction: LossReduction::Mean,
+                },
+            ));
+
+            // the softmax of the first sample is `[1/8, 2/8, 5/8]`, the second sample is ignored
+            let scores = [0f32, 2f32.ln(), 5f32.ln(), 0.0, 0.0, 0.0];
+            let (_, _, output) = assert_save_load_roundtrip(net_cfg, &[&scores, &[2f32, 0.0]]);
+            assert!((output[0] + (5f32 / 8.0).ln()).abs() < 1e-6);
+        }
+
+        #[test]
+        fn save_and_load_huber() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("network_out", &[2]);
+            net_cfg.add_input("label", &[2]);
+            net_cfg.add_layer(LayerConfig::new(
+                "huber",
+                HuberConfig {
+                    delta: 0.5,
+                    reduction: LossReduction::Mean,
+                },
+            ));
+
+            // the first error is within the delta, the second one beyond
+            let (_, _, output) = assert_save_load_roundtrip(net_cfg, &[&[0f32, 2.0], &[0.25f32, 0.0]]);
+            assert!((output[0] - (0.03125 + 0.875) / 2.0).abs() < 1e-6);
+        }
+
+        #[test]
+        fn save_and_load_loss_reduction() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("network_out", &[2, 1, 2]);
+            net_cfg.add_input("label", &[2, 1, 2]);
+            net_cfg.add_input("sample_weights", &[2]);
+            net_cfg.add_layer(LayerConfig::new(
+                "mse",
+                MeanSquaredErrorConfig {
+                    reduction: LossReduction::None,
+                },
+            ));
+
+            // the loss of every element, weighted by the weight of its sample
+            let (_, shape, output) =
+                assert_save_load_roundtrip(net_cfg, &[&[1f32, 2.0, 3.0, 4.0], &[0f32, 0.0, 1.0, 1.0], &[1f32, 0.5]]);
+            assert_eq!(shape, vec![2, 1, 2]);
+            assert_eq!(output, vec![1f32, 4.0, 2.0, 4.5]);
+        }
+
+        #[test]
+        fn save_and_load_lrn() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[1, 3, 1, 1]);
+            net_cfg.add_layer(LayerConfig::new(
+                "lrn",
+                LRNConfig {
+                    n: 3,
+                    alpha: 0.3,
+                    beta: 0.75,
+                    k: 2.0,
+                },
+            ));
+
+            let (_, _, output) = assert_save_load_roundtrip(net_cfg, &[&[1f32, 2.0, 3.0]]);
+            assert_close(
+                &output,
+                &[2.5f32.powf(-0.75), 2.0 * 3.4f32.powf(-0.75), 3.0 * 3.3f32.powf(-0.75)],
+            );
+        }
+
+        #[test]
+        fn save_and_load_parameterised_activations() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[1, 4]);
+            net_cfg.add_layer(LayerConfig::new("leaky_relu", LeakyReLUConfig { slope: 0.5 }));
+            net_cfg.add_layer(LayerConfig::new("elu", ELUConfig { alpha: 2.0 }));
+            net_cfg.add_layer(LayerConfig::new(
+                "hard_tanh",
+                HardTanhConfig {
+                    min_value: -1.5,
+                    max_value: 2.0,
+                },
+            ));
+
+            let (_, _, output) = assert_save_load_roundtrip(net_cfg, &[&[-1f32, -8.0, 1.0, 3.0]]);
+            assert_close(&output, &[2.0 * (-0.5f32).exp_m1(), -1.5, 1.0, 2.0]);
+        }
+
+        #[test]
+        fn save_and_load_layer_norm() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[2, 3]);
+            net_cfg.add_layer(LayerConfig::new("layer_norm", LayerNormConfig::default()));
+
+            let (_, _, output) = assert_save_load_roundtrip(net_cfg, &[&[1f32, 2.0, 3.0, -1.0, 2.0, 2.0]]);
+            let (std_0, std_1) = ((2f32 / 3.0 + 1e-5).sqrt(), (2f32 + 1e-5).sqrt());
+            assert_close(
+                &output,
+                &[-1.0 / std_0, 0.0, 1.0 / std_0, -2.0 / std_1, 1.0 / std_1, 1.0 / std_1],
+            );
+        }
+
+        #[test]
+        fn save_and_load_group_norm() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[1, 4, 1]);
+            net_cfg.add_layer(LayerConfig::new(
+                "group_norm",
+                GroupNormConfig {
+                    groups: 2,
+                    epsilon: 1e-5,
+                },
+            ));
+
+            let (_, _, output) = assert_save_load_roundtrip(net_cfg, &[&[1f32, 3.0, -2.0, 0.0]]);
+            let scale = (1f32 + 1e-5).sqrt().recip();
+            assert_close(&output, &[-scale, scale, -scale, scale]);
+        }
+
+        #[test]
+        fn save_and_load_permute() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[2, 3]);
+            net_cfg.add_layer(LayerConfig::new("permute", PermuteConfig::of_axes(&[1, 0])));
+
+            let (_, shape, output) = assert_save_load_roundtrip(net_cfg, &[&[1f32, 2.0, 3.0, 4.0, 5.0, 6.0]]);
+            assert_eq!(shape, vec![3, 2]);
+            assert_eq!(output, vec![1f32, 4.0, 2.0, 5.0, 3.0, 6.0]);
+        }
+
+        #[test]
+        fn split_and_concat_branches() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[2, 3]);
+            let mut split_cfg = LayerConfig::new("split", SplitConfig::of_sections(1, &[1, 2]));
+            split_cfg.add_output("left");
+            split_cfg.add_output("right");
+            net_cfg.add_layer(split_cfg);
+            let mut concat_cfg = LayerConfig::new("concat", ConcatConfig { axis: 1 });
+            concat_cfg.add_input("left");
+            concat_cfg.add_input("right");
+            net_cfg.add_layer(concat_cfg);
+
+            let (_, shape, output) = assert_save_load_roundtrip(net_cfg, &[&[1f32, 2.0, 3.0, 4.0, 5.0, 6.0]]);
+            assert_eq!(shape, vec![2, 3]);
+            assert_eq!(output, vec![1f32, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        }
+
+        #[test]
+        fn save_and_load_batch_norm() {
+            let mut net_cfg = SequentialConfig::default();
+            net_cfg.add_input("data", &[4, 2]);
+            net_cfg.add_layer(LayerConfig::new("batch_norm", BatchNormConfig::default()));
+
+            // the forward passes before saving and after loading update the running statistics,
+            // the means of the channels are `4` and `5`
+            let (layer, _, _) = assert_save_load_roundtrip(net_cfg, &[&[1f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]]);
+            let stats: Vec<f32> = layer
+                .non_learnable_weights_data()
+                .iter()
+                .flat_map(|weight| {
+                    let weight = weight.read().unwrap();
+                    let weight = weight.read(native_backend().device()).unwrap();
+                    weight.as_slice::<f32>().to_vec()
+                })
+                .collect();
+            assert_close(&stats[0..2], &[0.76, 0.95]);
+        }
     }
 
     #[cfg(feature = "cuda")]
@@ -153,6 +665,18 @@ mod layer_spec {
             Layer::from_config(cuda_backend(), &LayerConfig::new("model", LayerType::Sequential(model)));
         }
 
+        #[test]
+        #[should_panic(expected = "Adaptive pooling from [5] to [3] is not supported")]
+        fn rejects_uneven_adaptive_pooling() {
+            let mut model = SequentialConfig::default();
+            model.add_input("data", &[1, 1, 5]);
+            model.add_layer(LayerConfig::new(
+                "pooling",
+                PoolingConfig::of_output_shape(PoolingMode::AdaptiveAverage, &[3]),
+            ));
+            Layer::from_config(cuda_backend(), &LayerConfig::new("model", LayerType::Sequential(model)));
+        }
+
         #[test]
         fn can_create_single_layer_sequential_layer() {
             let mut model = SequentialConfig::default();
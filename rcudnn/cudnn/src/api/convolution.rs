@@ -347,6 +347,14 @@ impl API {
         }
     }
 
+    /// Sets the number of groups the channels of a CUDA cuDNN Convolution Descriptor are split into.
+    pub fn set_convolution_group_count(
+        desc: cudnnConvolutionDescriptor_t,
+        group_count: ::libc::c_int,
+    ) -> Result<(), Error> {
+        unsafe { API::ffi_set_convolution_group_count(desc, group_count) }
+    }
+
     /// Computes a convolution forward function.
     #[allow(clippy::too_many_arguments)]
     pub fn convolution_forward(
@@ -513,6 +521,21 @@ impl API {
         }
     }
 
+    unsafe fn ffi_set_convolution_group_count(
+        desc: cudnnConvolutionDescriptor_t,
+        group_count: ::libc::c_int,
+    ) -> Result<(), Error> {
+        match cudnnSetConvolutionGroupCount(desc, group_count) {
+            cudnnStatus_t::CUDNN_STATUS_SUCCESS => Ok(()),
+            cudnnStatus_t::CUDNN_STATUS_BAD_PARAM => Err(Error::BadParam(
+                "`desc` is NULL or `group_count` is not positive.",
+            )),
+            _ => Err(Error::Unknown(
+                "Unable to set the group count of CUDA cuDNN Convolution Descriptor.",
+            )),
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     unsafe fn ffi_convolution_forward(
         handle: cudnnHandle_t,
@@ -1,6 +1,7 @@
 //! Provides the normalization functionality from the CUDA cuDNN API.
 //!
-//! This includes divisive normalization and Local Response Normalization.
+//! This includes divisive normalization, Local Response Normalization and
+//! Batch Normalization.
 
 use crate::ffi::*;
 use crate::{Error, API};
@@ -135,6 +136,143 @@ impl API {
         }
     }
 
+    /// Computes a batch normalization forward function for training.
+    ///
+    /// Normalizes with the statistics of the batch, updates the running averages
+    /// and saves the batch mean and inverse variance for the backward pass.
+    #[allow(clippy::too_many_arguments)]
+    pub fn batch_normalization_forward_training(
+        handle: cudnnHandle_t,
+        mode: cudnnBatchNormMode_t,
+        alpha: *const ::libc::c_void,
+        beta: *const ::libc::c_void,
+        x_desc: cudnnTensorDescriptor_t,
+        x: *const ::libc::c_void,
+        y_desc: cudnnTensorDescriptor_t,
+        y: *mut ::libc::c_void,
+        bn_scale_bias_mean_var_desc: cudnnTensorDescriptor_t,
+        bn_scale: *const ::libc::c_void,
+        bn_bias: *const ::libc::c_void,
+        exponential_average_factor: ::libc::c_double,
+        result_running_mean: *mut ::libc::c_void,
+        result_running_variance: *mut ::libc::c_void,
+        epsilon: ::libc::c_double,
+        result_save_mean: *mut ::libc::c_void,
+        result_save_inv_variance: *mut ::libc::c_void,
+    ) -> Result<(), Error> {
+        unsafe {
+            API::ffi_batch_normalization_forward_training(
+                handle,
+                mode,
+                alpha,
+                beta,
+                x_desc,
+                x,
+                y_desc,
+                y,
+                bn_scale_bias_mean_var_desc,
+                bn_scale,
+                bn_bias,
+                exponential_average_factor,
+                result_running_mean,
+                result_running_variance,
+                epsilon,
+                result_save_mean,
+                result_save_inv_variance,
+            )
+        }
+    }
+
+    /// Computes a batch normalization forward function for inference.
+    ///
+    /// Normalizes with the previously estimated mean and variance.
+    #[allow(clippy::too_many_arguments)]
+    pub fn batch_normalization_forward_inference(
+        handle: cudnnHandle_t,
+        mode: cudnnBatchNormMode_t,
+        alpha: *const ::libc::c_void,
+        beta: *const ::libc::c_void,
+        x_desc: cudnnTensorDescriptor_t,
+        x: *const ::libc::c_void,
+        y_desc: cudnnTensorDescriptor_t,
+        y: *mut ::libc::c_void,
+        bn_scale_bias_mean_var_desc: cudnnTensorDescriptor_t,
+        bn_scale: *const ::libc::c_void,
+        bn_bias: *const ::libc::c_void,
+        estimated_mean: *const ::libc::c_void,
+        estimated_variance: *const ::libc::c_void,
+        epsilon: ::libc::c_double,
+    ) -> Result<(), Error> {
+        unsafe {
+            API::ffi_batch_normalization_forward_inference(
+                handle,
+                mode,
+                alpha,
+                beta,
+                x_desc,
+                x,
+                y_desc,
+                y,
+                bn_scale_bias_mean_var_desc,
+                bn_scale,
+                bn_bias,
+                estimated_mean,
+                estimated_variance,
+                epsilon,
+            )
+        }
+    }
+
+    /// Computes a batch normalization backward function.
+    ///
+    /// Computes the gradients with respect to the data, the scale and the bias.
+    #[allow(clippy::too_many_arguments)]
+    pub fn batch_normalization_backward(
+        handle: cudnnHandle_t,
+        mode: cudnnBatchNormMode_t,
+        alpha_data_diff: *const ::libc::c_void,
+        beta_data_diff: *const ::libc::c_void,
+        alpha_param_diff: *const ::libc::c_void,
+        beta_param_diff: *const ::libc::c_void,
+        x_desc: cudnnTensorDescriptor_t,
+        x: *const ::libc::c_void,
+        dy_desc: cudnnTensorDescriptor_t,
+        dy: *const ::libc::c_void,
+        dx_desc: cudnnTensorDescriptor_t,
+        dx: *mut ::libc::c_void,
+        d_bn_scale_bias_desc: cudnnTensorDescriptor_t,
+        bn_scale: *const ::libc::c_void,
+        d_bn_scale_result: *mut ::libc::c_void,
+        d_bn_bias_result: *mut ::libc::c_void,
+        epsilon: ::libc::c_double,
+        saved_mean: *const ::libc::c_void,
+        saved_inv_variance: *const ::libc::c_void,
+    ) -> Result<(), Error> {
+        unsafe {
+            API::ffi_batch_normalization_backward(
+                handle,
+                mode,
+                alpha_data_diff,
+                beta_data_diff,
+                alpha_param_diff,
+                beta_param_diff,
+                x_desc,
+                x,
+                dy_desc,
+                dy,
+                dx_desc,
+                dx,
+                d_bn_scale_bias_desc,
+                bn_scale,
+                d_bn_scale_result,
+                d_bn_bias_result,
+                epsilon,
+                saved_mean,
+                saved_inv_variance,
+            )
+        }
+    }
+
     unsafe fn ffi_create_lrn_descriptor() -> Result<cudnnLRNDescriptor_t, Error> {
         let mut desc: cudnnLRNDescriptor_t = ::std::ptr::null_mut();
         match cudnnCreateLRNDescriptor(&mut desc) {
@@ -270,4 +408,90 @@ impl API {
             _ => Err(Error::Unknown("Unable to compute divisive normalization backward.")),
         }
     }
+
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn ffi_batch_normalization_forward_training(
+        handle: cudnnHandle_t,
+        mode: cudnnBatchNormMode_t,
+        alpha: *const ::libc::c_void,
+        beta: *const ::libc::c_void,
+        x_desc: cudnnTensorDescriptor_t,
+        x: *const ::libc::c_void,
+        y_desc: cudnnTensorDescriptor_t,
+        y: *mut ::libc::c_void,
+        bn_scale_bias_mean_var_desc: cudnnTensorDescriptor_t,
+        bn_scale: *const ::libc::c_void,
+        bn_bias: *const ::libc::c_void,
+        exponential_average_factor: ::libc::c_double,
+        result_running_mean: *mut ::libc::c_void,
+        result_running_variance: *mut ::libc::c_void,
+        epsilon: ::libc::c_double,
+        result_save_mean: *mut ::libc::c_void,
+        result_save_inv_variance: *mut ::libc::c_void,
+    ) -> Result<(), Error> {
+        match cudnnBatchNormalizationForwardTraining(handle, mode, alpha, beta, x_desc, x, y_desc, y, bn_scale_bias_mean_var_desc, bn_scale, bn_bias, exponential_average_factor, result_running_mean, result_running_variance, epsilon, result_save_mean, result_save_inv_variance) {
+            cudnnStatus_t::CUDNN_STATUS_SUCCESS => Ok(()),
+            cudnnStatus_t::CUDNN_STATUS_BAD_PARAM => Err(Error::BadParam("At least one of the following conditions are met: One of the pointers `alpha`, `beta`, `x`, `y`, `bn_scale`, `bn_bias` is NULL. Number of `x_desc` or `y_desc` tensor descriptor dimensions is not within the [4,5] range. `bn_scale_bias_mean_var_desc` dimensions are not 1xCx1x1 for spatial or 1xCxHxW for per-activation mode. Exactly one of `result_save_mean`, `result_save_inv_variance` is NULL. Exactly one of `result_running_mean`, `result_running_variance` is NULL. `epsilon` is less than CUDNN_BN_MIN_EPSILON. Dimensions or data types mismatch for `x_desc` and `y_desc`.")),
+            cudnnStatus_t::CUDNN_STATUS_NOT_SUPPORTED => Err(Error::NotSupported("The function does not support the provided configuration.")),
+            cudnnStatus_t::CUDNN_STATUS_EXECUTION_FAILED => Err(Error::ExecutionFailed("Execution failed to launch on GPU.")),
+            _ => Err(Error::Unknown("Unable to compute batch normalization forward for training.")),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn ffi_batch_normalization_forward_inference(
+        handle: cudnnHandle_t,
+        mode: cudnnBatchNormMode_t,
+        alpha: *const ::libc::c_void,
+        beta: *const ::libc::c_void,
+        x_desc: cudnnTensorDescriptor_t,
+        x: *const ::libc::c_void,
+        y_desc: cudnnTensorDescriptor_t,
+        y: *mut ::libc::c_void,
+        bn_scale_bias_mean_var_desc: cudnnTensorDescriptor_t,
+        bn_scale: *const ::libc::c_void,
+        bn_bias: *const ::libc::c_void,
+        estimated_mean: *const ::libc::c_void,
+        estimated_variance: *const ::libc::c_void,
+        epsilon: ::libc::c_double,
+    ) -> Result<(), Error> {
+        match cudnnBatchNormalizationForwardInference(handle, mode, alpha, beta, x_desc, x, y_desc, y, bn_scale_bias_mean_var_desc, bn_scale, bn_bias, estimated_mean, estimated_variance, epsilon) {
+            cudnnStatus_t::CUDNN_STATUS_SUCCESS => Ok(()),
+            cudnnStatus_t::CUDNN_STATUS_BAD_PARAM => Err(Error::BadParam("At least one of the following conditions are met: One of the pointers `alpha`, `beta`, `x`, `y`, `bn_scale`, `bn_bias`, `estimated_mean`, `estimated_variance` is NULL. Number of `x_desc` or `y_desc` tensor descriptor dimensions is not within the [4,5] range. `bn_scale_bias_mean_var_desc` dimensions are not 1xCx1x1 for spatial or 1xCxHxW for per-activation mode. `epsilon` is less than CUDNN_BN_MIN_EPSILON. Dimensions or data types mismatch for `x_desc` and `y_desc`.")),
+            cudnnStatus_t::CUDNN_STATUS_NOT_SUPPORTED => Err(Error::NotSupported("The function does not support the provided configuration.")),
+            cudnnStatus_t::CUDNN_STATUS_EXECUTION_FAILED => Err(Error::ExecutionFailed("Execution failed to launch on GPU.")),
+            _ => Err(Error::Unknown("Unable to compute batch normalization forward for inference.")),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn ffi_batch_normalization_backward(
+        handle: cudnnHandle_t,
+        mode: cudnnBatchNormMode_t,
+        alpha_data_diff: *const ::libc::c_void,
+        beta_data_diff: *const ::libc::c_void,
+        alpha_param_diff: *const ::libc::c_void,
+        beta_param_diff: *const ::libc::c_void,
+        x_desc: cudnnTensorDescriptor_t,
+        x: *const ::libc::c_void,
+        dy_desc: cudnnTensorDescriptor_t,
+        dy: *const ::libc::c_void,
+        dx_desc: cudnnTensorDescriptor_t,
+        dx: *mut ::libc::c_void,
+        d_bn_scale_bias_desc: cudnnTensorDescriptor_t,
+        bn_scale: *const ::libc::c_void,
+        d_bn_scale_result: *mut ::libc::c_void,
+        d_bn_bias_result: *mut ::libc::c_void,
+        epsilon: ::libc::c_double,
+        saved_mean: *const ::libc::c_void,
+        saved_inv_variance: *const ::libc::c_void,
+    ) -> Result<(), Error> {
+        match cudnnBatchNormalizationBackward(handle, mode, alpha_data_diff, beta_data_diff, alpha_param_diff, beta_param_diff, x_desc, x, dy_desc, dy, dx_desc, dx, d_bn_scale_bias_desc, bn_scale, d_bn_scale_result, d_bn_bias_result, epsilon, saved_mean, saved_inv_variance) {
+            cudnnStatus_t::CUDNN_STATUS_SUCCESS => Ok(()),
+            cudnnStatus_t::CUDNN_STATUS_BAD_PARAM => Err(Error::BadParam("At least one of the following conditions are met: Any of the pointers `alpha_data_diff`, `beta_data_diff`, `alpha_param_diff`, `beta_param_diff`, `x`, `dy`, `dx`, `bn_scale`, `d_bn_scale_result`, `d_bn_bias_result` is NULL. Number of `x_desc`, `dy_desc` or `dx_desc` tensor descriptor dimensions is not within the [4,5] range. `d_bn_scale_bias_desc` dimensions are not 1xCx1x1 for spatial or 1xCxHxW for per-activation mode. Exactly one of `saved_mean`, `saved_inv_variance` is NULL. `epsilon` is less than CUDNN_BN_MIN_EPSILON. Dimensions or data types mismatch for any pair of `x_desc`, `dy_desc`, `dx_desc`.")),
+            cudnnStatus_t::CUDNN_STATUS_NOT_SUPPORTED => Err(Error::NotSupported("The function does not support the provided configuration.")),
+            cudnnStatus_t::CUDNN_STATUS_EXECUTION_FAILED => Err(Error::ExecutionFailed("Execution failed to launch on GPU.")),
+            _ => Err(Error::Unknown("Unable to compute batch normalization backward.")),
+        }
+    }
 }
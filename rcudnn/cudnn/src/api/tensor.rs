@@ -127,6 +127,147 @@ impl API {
         unsafe { API::ffi_scale_tensor(handle, src_dest_desc, src_dest_data, alpha) }
     }
 
+    /// Creates a generic CUDA cuDNN Op Tensor Descriptor.
+    pub fn create_op_tensor_descriptor() -> Result<cudnnOpTensorDescriptor_t, Error> {
+        unsafe { API::ffi_create_op_tensor_descriptor() }
+    }
+
+    /// Destroys a CUDA cuDNN Op Tensor Descriptor.
+    ///
+    /// Should be called when freeing a CUDA::Descriptor to not trash up the CUDA device.
+    pub fn destroy_op_tensor_descriptor(desc: cudnnOpTensorDescriptor_t) -> Result<(), Error> {
+        unsafe { API::ffi_destroy_op_tensor_descriptor(desc) }
+    }
+
+    /// Initializes a generic CUDA cuDNN Op Tensor Descriptor with specific properties.
+    pub fn set_op_tensor_descriptor(
+        desc: cudnnOpTensorDescriptor_t,
+        op: cudnnOpTensorOp_t,
+        comp_type: cudnnDataType_t,
+        nan_opt: cudnnNanPropagation_t,
+    ) -> Result<(), Error> {
+        unsafe { API::ffi_set_op_tensor_descriptor(desc, op, comp_type, nan_opt) }
+    }
+
+    /// Computes an elementwise operation of two CUDA cuDNN Tensors.
+    ///
+    /// Computes `dest = op(alpha1 * a, alpha2 * b) + beta * dest`. Each dimension of `a` must
+    /// match the corresponding dimension of `dest`, each dimension of `b` must match it or be
+    /// equal to 1, in which case the values of `b` are broadcast along that dimension.
+    #[allow(clippy::too_many_arguments)]
+    pub fn op_tensor(
+        handle: cudnnHandle_t,
+        op_tensor_desc: cudnnOpTensorDescriptor_t,
+        alpha1: *const ::libc::c_void,
+        a_desc: cudnnTensorDescriptor_t,
+        a_data: *const ::libc::c_void,
+        alpha2: *const ::libc::c_void,
+        b_desc: cudnnTensorDescriptor_t,
+        b_data: *const ::libc::c_void,
+        beta: *const ::libc::c_void,
+        dest_desc: cudnnTensorDescriptor_t,
+        dest_data: *mut ::libc::c_void,
+    ) -> Result<(), Error> {
+        unsafe {
+            API::ffi_op_tensor(
+                handle,
+                op_tensor_desc,
+                alpha1,
+                a_desc,
+                a_data,
+                alpha2,
+                b_desc,
+                b_data,
+                beta,
+                dest_desc,
+                dest_data,
+            )
+        }
+    }
+
+    /// Creates a generic CUDA cuDNN Reduce Tensor Descriptor.
+    pub fn create_reduce_tensor_descriptor() -> Result<cudnnReduceTensorDescriptor_t, Error> {
+        unsafe { API::ffi_create_reduce_tensor_descriptor() }
+    }
+
+    /// Destroys a CUDA cuDNN Reduce Tensor Descriptor.
+    ///
+    /// Should be called when freeing a CUDA::Descriptor to not trash up the CUDA device.
+    pub fn destroy_reduce_tensor_descriptor(desc: cudnnReduceTensorDescriptor_t) -> Result<(), Error> {
+        unsafe { API::ffi_destroy_reduce_tensor_descriptor(desc) }
+    }
+
+    /// Initializes a generic CUDA cuDNN Reduce Tensor Descriptor with specific properties.
+    pub fn set_reduce_tensor_descriptor(
+        desc: cudnnReduceTensorDescriptor_t,
+        op: cudnnReduceTensorOp_t,
+        comp_type: cudnnDataType_t,
+        nan_opt: cudnnNanPropagation_t,
+        indices: cudnnReduceTensorIndices_t,
+        indices_type: cudnnIndicesType_t,
+    ) -> Result<(), Error> {
+        unsafe {
+            API::ffi_set_reduce_tensor_descriptor(desc, op, comp_type, nan_opt, indices, indices_type)
+        }
+    }
+
+    /// Returns the size of the memory needed for the indices of a reduction in bytes.
+    pub fn get_reduction_indices_size(
+        handle: cudnnHandle_t,
+        reduce_tensor_desc: cudnnReduceTensorDescriptor_t,
+        a_desc: cudnnTensorDescriptor_t,
+        dest_desc: cudnnTensorDescriptor_t,
+    ) -> Result<usize, Error> {
+        unsafe { API::ffi_get_reduction_indices_size(handle, reduce_tensor_desc, a_desc, dest_desc) }
+    }
+
+    /// Returns the size of the workspace needed for a reduction in bytes.
+    pub fn get_reduction_workspace_size(
+        handle: cudnnHandle_t,
+        reduce_tensor_desc: cudnnReduceTensorDescriptor_t,
+        a_desc: cudnnTensorDescriptor_t,
+        dest_desc: cudnnTensorDescriptor_t,
+    ) -> Result<usize, Error> {
+        unsafe { API::ffi_get_reduction_workspace_size(handle, reduce_tensor_desc, a_desc, dest_desc) }
+    }
+
+    /// Reduces a CUDA cuDNN Tensor along the dimensions the destination Tensor is of size 1.
+    ///
+    /// Computes `dest = alpha * reduce(a) + beta * dest`. Each dimension of `dest` must
+    /// either match the corresponding dimension of `a` or be equal to 1.
+    #[allow(clippy::too_many_arguments)]
+    pub fn reduce_tensor(
+        handle: cudnnHandle_t,
+        reduce_tensor_desc: cudnnReduceTensorDescriptor_t,
+        indices: *mut ::libc::c_void,
+        indices_size_in_bytes: usize,
+        workspace: *mut ::libc::c_void,
+        workspace_size_in_bytes: usize,
+        alpha: *const ::libc::c_void,
+        a_desc: cudnnTensorDescriptor_t,
+        a_data: *const ::libc::c_void,
+        beta: *const ::libc::c_void,
+        dest_desc: cudnnTensorDescriptor_t,
+        dest_data: *mut ::libc::c_void,
+    ) -> Result<(), Error> {
+        unsafe {
+            API::ffi_reduce_tensor(
+                handle,
+                reduce_tensor_desc,
+                indices,
+                indices_size_in_bytes,
+                workspace,
+                workspace_size_in_bytes,
+                alpha,
+                a_desc,
+                a_data,
+                beta,
+                dest_desc,
+                dest_data,
+            )
+        }
+    }
+
     unsafe fn ffi_create_tensor_descriptor() -> Result<cudnnTensorDescriptor_t, Error> {
         let mut tensor_desc: cudnnTensorDescriptor_t = ::std::ptr::null_mut();
         match cudnnCreateTensorDescriptor(&mut tensor_desc) {
@@ -271,4 +412,161 @@ impl API {
             _ => Err(Error::Unknown("Unable to scale CUDA cuDNN Tensor.")),
         }
     }
+
+    unsafe fn ffi_create_op_tensor_descriptor() -> Result<cudnnOpTensorDescriptor_t, Error> {
+        let mut desc: cudnnOpTensorDescriptor_t = ::std::ptr::null_mut();
+        match cudnnCreateOpTensorDescriptor(&mut desc) {
+            cudnnStatus_t::CUDNN_STATUS_SUCCESS => Ok(desc),
+            cudnnStatus_t::CUDNN_STATUS_ALLOC_FAILED => {
+                Err(Error::AllocFailed("The resources could not be allocated."))
+            }
+            _ => Err(Error::Unknown(
+                "Unable to create generic CUDA cuDNN Op Tensor Descriptor.",
+            )),
+        }
+    }
+
+    unsafe fn ffi_destroy_op_tensor_descriptor(
+        desc: cudnnOpTensorDescriptor_t,
+    ) -> Result<(), Error> {
+        match cudnnDestroyOpTensorDescriptor(desc) {
+            cudnnStatus_t::CUDNN_STATUS_SUCCESS => Ok(()),
+            _ => Err(Error::Unknown(
+                "Unable to destroy CUDA cuDNN Op Tensor Descriptor.",
+            )),
+        }
+    }
+
+    unsafe fn ffi_set_op_tensor_descriptor(
+        desc: cudnnOpTensorDescriptor_t,
+        op: cudnnOpTensorOp_t,
+        comp_type: cudnnDataType_t,
+        nan_opt: cudnnNanPropagation_t,
+    ) -> Result<(), Error> {
+        match cudnnSetOpTensorDescriptor(desc, op, comp_type, nan_opt) {
+            cudnnStatus_t::CUDNN_STATUS_SUCCESS => Ok(()),
+            cudnnStatus_t::CUDNN_STATUS_BAD_PARAM => Err(Error::BadParam(
+                "`op`, `comp_type` or `nan_opt` is invalid.",
+            )),
+            _ => Err(Error::Unknown(
+                "Unable to set CUDA cuDNN Op Tensor Descriptor.",
+            )),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn ffi_op_tensor(
+        handle: cudnnHandle_t,
+        op_tensor_desc: cudnnOpTensorDescriptor_t,
+        alpha1: *const ::libc::c_void,
+        a_desc: cudnnTensorDescriptor_t,
+        a_data: *const ::libc::c_void,
+        alpha2: *const ::libc::c_void,
+        b_desc: cudnnTensorDescriptor_t,
+        b_data: *const ::libc::c_void,
+        beta: *const ::libc::c_void,
+        dest_desc: cudnnTensorDescriptor_t,
+        dest_data: *mut ::libc::c_void,
+    ) -> Result<(), Error> {
+        match cudnnOpTensor(handle, op_tensor_desc, alpha1, a_desc, a_data, alpha2, b_desc, b_data, beta, dest_desc, dest_data) {
+            cudnnStatus_t::CUDNN_STATUS_SUCCESS => Ok(()),
+            cudnnStatus_t::CUDNN_STATUS_BAD_PARAM => Err(Error::BadParam("The dimensions of `b` can not be broadcast to `dest`, the dimensions of `a` and `dest` differ or the data types of the tensors are incompatible.")),
+            cudnnStatus_t::CUDNN_STATUS_NOT_SUPPORTED => Err(Error::NotSupported("The data types of the tensors and the compute type are not a supported combination.")),
+            cudnnStatus_t::CUDNN_STATUS_EXECUTION_FAILED => Err(Error::ExecutionFailed("Execution failed to launch on GPU.")),
+            _ => Err(Error::Unknown("Unable to compute CUDA cuDNN Op Tensor.")),
+        }
+    }
+
+    unsafe fn ffi_create_reduce_tensor_descriptor() -> Result<cudnnReduceTensorDescriptor_t, Error> {
+        let mut desc: cudnnReduceTensorDescriptor_t = ::std::ptr::null_mut();
+        match cudnnCreateReduceTensorDescriptor(&mut desc) {
+            cudnnStatus_t::CUDNN_STATUS_SUCCESS => Ok(desc),
+            cudnnStatus_t::CUDNN_STATUS_ALLOC_FAILED => {
+                Err(Error::AllocFailed("The resources could not be allocated."))
+            }
+            _ => Err(Error::Unknown(
+                "Unable to create generic CUDA cuDNN Reduce Tensor Descriptor.",
+            )),
+        }
+    }
+
+    unsafe fn ffi_destroy_reduce_tensor_descriptor(
+        desc: cudnnReduceTensorDescriptor_t,
+    ) -> Result<(), Error> {
+        match cudnnDestroyReduceTensorDescriptor(desc) {
+            cudnnStatus_t::CUDNN_STATUS_SUCCESS => Ok(()),
+            _ => Err(Error::Unknown(
+                "Unable to destroy CUDA cuDNN Reduce Tensor Descriptor.",
+            )),
+        }
+    }
+
+    unsafe fn ffi_set_reduce_tensor_descriptor(
+        desc: cudnnReduceTensorDescriptor_t,
+        op: cudnnReduceTensorOp_t,
+        comp_type: cudnnDataType_t,
+        nan_opt: cudnnNanPropagation_t,
+        indices: cudnnReduceTensorIndices_t,
+        indices_type: cudnnIndicesType_t,
+    ) -> Result<(), Error> {
+        match cudnnSetReduceTensorDescriptor(desc, op, comp_type, nan_opt, indices, indices_type) {
+            cudnnStatus_t::CUDNN_STATUS_SUCCESS => Ok(()),
+            cudnnStatus_t::CUDNN_STATUS_BAD_PARAM => Err(Error::BadParam(
+                "`op`, `comp_type`, `nan_opt`, `indices` or `indices_type` is invalid.",
+            )),
+            _ => Err(Error::Unknown(
+                "Unable to set CUDA cuDNN Reduce Tensor Descriptor.",
+            )),
+        }
+    }
+
+    unsafe fn ffi_get_reduction_indices_size(
+        handle: cudnnHandle_t,
+        reduce_tensor_desc: cudnnReduceTensorDescriptor_t,
+        a_desc: cudnnTensorDescriptor_t,
+        dest_desc: cudnnTensorDescriptor_t,
+    ) -> Result<usize, Error> {
+        let mut size: usize = 0;
+        match cudnnGetReductionIndicesSize(handle, reduce_tensor_desc, a_desc, dest_desc, &mut size) {
+            cudnnStatus_t::CUDNN_STATUS_SUCCESS => Ok(size),
+            _ => Err(Error::Unknown("Unable to get CUDA cuDNN reduction indices size.")),
+        }
+    }
+
+    unsafe fn ffi_get_reduction_workspace_size(
+        handle: cudnnHandle_t,
+        reduce_tensor_desc: cudnnReduceTensorDescriptor_t,
+        a_desc: cudnnTensorDescriptor_t,
+        dest_desc: cudnnTensorDescriptor_t,
+    ) -> Result<usize, Error> {
+        let mut size: usize = 0;
+        match cudnnGetReductionWorkspaceSize(handle, reduce_tensor_desc, a_desc, dest_desc, &mut size) {
+            cudnnStatus_t::CUDNN_STATUS_SUCCESS => Ok(size),
+            _ => Err(Error::Unknown("Unable to get CUDA cuDNN reduction workspace size.")),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn ffi_reduce_tensor(
+        handle: cudnnHandle_t,
+        reduce_tensor_desc: cudnnReduceTensorDescriptor_t,
+        indices: *mut ::libc::c_void,
+        indices_size_in_bytes: usize,
+        workspace: *mut ::libc::c_void,
+        workspace_size_in_bytes: usize,
+        alpha: *const ::libc::c_void,
+        a_desc: cudnnTensorDescriptor_t,
+        a_data: *const ::libc::c_void,
+        beta: *const ::libc::c_void,
+        dest_desc: cudnnTensorDescriptor_t,
+        dest_data: *mut ::libc::c_void,
+    ) -> Result<(), Error> {
+        match cudnnReduceTensor(handle, reduce_tensor_desc, indices, indices_size_in_bytes, workspace, workspace_size_in_bytes, alpha, a_desc, a_data, beta, dest_desc, dest_data) {
+            cudnnStatus_t::CUDNN_STATUS_SUCCESS => Ok(()),
+            cudnnStatus_t::CUDNN_STATUS_BAD_PARAM => Err(Error::BadParam("The dimensions of `dest` do not match or divide the dimensions of `a` or the data types of the tensors are incompatible.")),
+            cudnnStatus_t::CUDNN_STATUS_NOT_SUPPORTED => Err(Error::NotSupported("The dimensions of the tensors are above 8 or the indices are not of 32 bit.")),
+            cudnnStatus_t::CUDNN_STATUS_EXECUTION_FAILED => Err(Error::ExecutionFailed("Execution failed to launch on GPU.")),
+            _ => Err(Error::Unknown("Unable to reduce CUDA cuDNN Tensor.")),
+        }
+    }
 }
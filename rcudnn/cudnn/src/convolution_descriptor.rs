@@ -26,54 +26,39 @@ impl ConvolutionDescriptor {
         pad: &[i32],
         filter_stride: &[i32],
         data_type: DataType,
+    ) -> Result<ConvolutionDescriptor, Error> {
+        let dilation: Vec<i32> = ::std::iter::repeat(1i32).take(pad.len()).collect();
+        ConvolutionDescriptor::new_grouped(pad, filter_stride, &dilation, 1, data_type)
+    }
+
+    /// Initializes a new CUDA cuDNN ConvolutionDescriptor with dilated filters,
+    /// which splits the channels into `groups` independent convolutions.
+    pub fn new_grouped(
+        pad: &[i32],
+        filter_stride: &[i32],
+        dilation: &[i32],
+        groups: i32,
+        data_type: DataType,
     ) -> Result<ConvolutionDescriptor, Error> {
         let array_length = pad.len() as i32;
-        let upscale: Vec<i32> = ::std::iter::repeat(1i32)
-            .take(array_length as usize)
-            .collect();
+        let d_type = match data_type {
+            DataType::Float => cudnnDataType_t::CUDNN_DATA_FLOAT,
+            DataType::Double => cudnnDataType_t::CUDNN_DATA_DOUBLE,
+            DataType::Half => cudnnDataType_t::CUDNN_DATA_FLOAT,
+        };
 
         let generic_convolution_desc = API::create_convolution_descriptor()?;
-        match data_type {
-            DataType::Float => {
-                let d_type = cudnnDataType_t::CUDNN_DATA_FLOAT;
-                API::set_convolution_descriptor(
-                    generic_convolution_desc,
-                    d_type,
-                    cudnnConvolutionMode_t::CUDNN_CONVOLUTION,
-                    array_length,
-                    pad.as_ptr(),
-                    filter_stride.as_ptr(),
-                    upscale.as_ptr(),
-                )?;
-                Ok(ConvolutionDescriptor::from_c(generic_convolution_desc))
-            }
-            DataType::Double => {
-                let d_type = cudnnDataType_t::CUDNN_DATA_DOUBLE;
-                API::set_convolution_descriptor(
-                    generic_convolution_desc,
-                    d_type,
-                    cudnnConvolutionMode_t::CUDNN_CONVOLUTION,
-                    array_length,
-                    pad.as_ptr(),
-                    filter_stride.as_ptr(),
-                    upscale.as_ptr(),
-                )?;
-                Ok(ConvolutionDescriptor::from_c(generic_convolution_desc))
-            }
-            DataType::Half => {
-                let d_type = cudnnDataType_t::CUDNN_DATA_FLOAT;
-                API::set_convolution_descriptor(
-                    generic_convolution_desc,
-                    d_type,
-                    cudnnConvolutionMode_t::CUDNN_CONVOLUTION,
-                    array_length,
-                    pad.as_ptr(),
-                    filter_stride.as_ptr(),
-                    upscale.as_ptr(),
-                )?;
-                Ok(ConvolutionDescriptor::from_c(generic_convolution_desc))
-            }
-        }
+        API::set_convolution_descriptor(
+            generic_convolution_desc,
+            d_type,
+            cudnnConvolutionMode_t::CUDNN_CONVOLUTION,
+            array_length,
+            pad.as_ptr(),
+            filter_stride.as_ptr(),
+            dilation.as_ptr(),
+        )?;
+        API::set_convolution_group_count(generic_convolution_desc, groups)?;
+        Ok(ConvolutionDescriptor::from_c(generic_convolution_desc))
     }
 
     /// Initializes a new CUDA cuDNN ConvolutionDescriptor from its C type.
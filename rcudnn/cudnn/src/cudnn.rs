@@ -5,8 +5,8 @@
 //! stores the handle and manages future calls.
 
 use super::utils::{
-    ActivationConfig, ConvolutionConfig, DataTypeInfo, DropoutConfig, NormalizationConfig,
-    PoolingConfig, ScalParams, RnnConfig
+    ActivationConfig, BatchNormalizationConfig, ConvolutionConfig, DataTypeInfo, DropoutConfig,
+    NormalizationConfig, PoolingConfig, ScalParams, RnnConfig
 };
 use super::*;
 
@@ -138,6 +138,25 @@ impl Cudnn {
         Ok(NormalizationConfig::new(norm_desc))
     }
 
+    /// Initializes the parameters for running CUDA cuDNN Batch Normalization operations.
+    ///
+    /// `epsilon` must not be smaller than `CUDNN_BN_MIN_EPSILON`.
+    pub fn init_batch_normalization(
+        &self,
+        mode: cudnnBatchNormMode_t,
+        epsilon: f64,
+        exponential_average_factor: f64,
+    ) -> Result<BatchNormalizationConfig, Error> {
+        if epsilon < CUDNN_BN_MIN_EPSILON {
+            return Err(Error::BadParam("`epsilon` is less than CUDNN_BN_MIN_EPSILON."));
+        }
+        Ok(BatchNormalizationConfig::new(
+            mode,
+            epsilon,
+            exponential_average_factor,
+        ))
+    }
+
     /// Initializes the parameters and configurations for running CUDA cuDNN Pooling operations.
     pub fn init_pooling(
         &self,
@@ -893,6 +912,138 @@ impl Cudnn {
         )
     }
 
+    /// Computes the forward batch normalization function for training.
+    ///
+    /// Writes the result of the computation to `dest_data`, updates the running
+    /// mean and variance and saves the batch statistics for the backward pass.
+    #[allow(clippy::too_many_arguments)]
+    pub fn batch_normalization_forward_training<T>(
+        &self,
+        bn_conf: &BatchNormalizationConfig,
+        src_desc: &TensorDescriptor,
+        src_data: *const ::libc::c_void,
+        dest_desc: &TensorDescriptor,
+        dest_data: *mut ::libc::c_void,
+        param_desc: &TensorDescriptor,
+        scale_data: *const ::libc::c_void,
+        bias_data: *const ::libc::c_void,
+        running_mean_data: *mut ::libc::c_void,
+        running_variance_data: *mut ::libc::c_void,
+        saved_mean_data: *mut ::libc::c_void,
+        saved_inv_variance_data: *mut ::libc::c_void,
+        scale: ScalParams<T>,
+    ) -> Result<(), Error>
+    where
+        T: Float + DataTypeInfo,
+    {
+        API::batch_normalization_forward_training(
+            *self.id_c(),
+            bn_conf.mode(),
+            unsafe { transmute_copy(&&scale.a) },
+            unsafe { transmute_copy(&&scale.b) },
+            *src_desc.id_c(),
+            src_data,
+            *dest_desc.id_c(),
+            dest_data,
+            *param_desc.id_c(),
+            scale_data,
+            bias_data,
+            bn_conf.exponential_average_factor(),
+            running_mean_data,
+            running_variance_data,
+            bn_conf.epsilon(),
+            saved_mean_data,
+            saved_inv_variance_data,
+        )
+    }
+
+    /// Computes the forward batch normalization function for inference.
+    ///
+    /// Writes the result of the computation to `dest_data`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn batch_normalization_forward_inference<T>(
+        &self,
+        bn_conf: &BatchNormalizationConfig,
+        src_desc: &TensorDescriptor,
+        src_data: *const ::libc::c_void,
+        dest_desc: &TensorDescriptor,
+        dest_data: *mut ::libc::c_void,
+        param_desc: &TensorDescriptor,
+        scale_data: *const ::libc::c_void,
+        bias_data: *const ::libc::c_void,
+        mean_data: *const ::libc::c_void,
+        variance_data: *const ::libc::c_void,
+        scale: ScalParams<T>,
+    ) -> Result<(), Error>
+    where
+        T: Float + DataTypeInfo,
+    {
+        API::batch_normalization_forward_inference(
+            *self.id_c(),
+            bn_conf.mode(),
+            unsafe { transmute_copy(&&scale.a) },
+            unsafe { transmute_copy(&&scale.b) },
+            *src_desc.id_c(),
+            src_data,
+            *dest_desc.id_c(),
+            dest_data,
+            *param_desc.id_c(),
+            scale_data,
+            bias_data,
+            mean_data,
+            variance_data,
+            bn_conf.epsilon(),
+        )
+    }
+
+    /// Computes the backward batch normalization function.
+    ///
+    /// Writes the result of the computation to `src_diff_data`, `scale_diff_data`
+    /// and `bias_diff_data`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn batch_normalization_backward<T>(
+        &self,
+        bn_conf: &BatchNormalizationConfig,
+        src_desc: &TensorDescriptor,
+        src_data: *const ::libc::c_void,
+        dest_diff_desc: &TensorDescriptor,
+        dest_diff_data: *const ::libc::c_void,
+        src_diff_desc: &TensorDescriptor,
+        src_diff_data: *mut ::libc::c_void,
+        param_desc: &TensorDescriptor,
+        scale_data: *const ::libc::c_void,
+        scale_diff_data: *mut ::libc::c_void,
+        bias_diff_data: *mut ::libc::c_void,
+        saved_mean_data: *const ::libc::c_void,
+        saved_inv_variance_data: *const ::libc::c_void,
+        scale: ScalParams<T>,
+    ) -> Result<(), Error>
+    where
+        T: Float + DataTypeInfo,
+    {
+        API::batch_normalization_backward(
+            *self.id_c(),
+            bn_conf.mode(),
+            unsafe { transmute_copy(&&scale.a) },
+            unsafe { transmute_copy(&&scale.b) },
+            unsafe { transmute_copy(&&scale.a) },
+            unsafe { transmute_copy(&&scale.b) },
+            *src_desc.id_c(),
+            src_data,
+            *dest_diff_desc.id_c(),
+            dest_diff_data,
+            *src_diff_desc.id_c(),
+            src_diff_data,
+            *param_desc.id_c(),
+            scale_data,
+            scale_diff_data,
+            bias_diff_data,
+            bn_conf.epsilon(),
+            saved_mean_data,
+            saved_inv_variance_data,
+        )
+    }
+
     /// Computes the forward average pooling function.
     ///
     /// Writes the result of the computation to `dest_data`.
@@ -1070,4 +1221,124 @@ impl Cudnn {
             dropout_conf.reserved_space().size(),
         )
     }
+
+    /// Copies the scaled data of a tensor to a tensor with a different layout.
+    ///
+    /// Writes the result of the computation to `dest_data`. Both descriptors need
+    /// to have the same dimensions, but not necessarily the same strides.
+    pub fn transform_tensor<T>(
+        &self,
+        src_desc: &TensorDescriptor,
+        src_data: *const ::libc::c_void,
+        dest_desc: &TensorDescriptor,
+        dest_data: *mut ::libc::c_void,
+        scale: ScalParams<T>,
+    ) -> Result<(), Error>
+    where
+        T: Float + DataTypeInfo,
+    {
+        API::transform_tensor(
+            *self.id_c(),
+            unsafe { transmute_copy(&&scale.a) },
+            *src_desc.id_c(),
+            src_data,
+            unsafe { transmute_copy(&&scale.b) },
+            *dest_desc.id_c(),
+            dest_data,
+        )
+    }
+
+    /// Computes an elementwise operation of two tensors.
+    ///
+    /// Writes `op(alpha1 * a, alpha2 * b) + beta * dest` to `dest_data`, with `b`
+    /// broadcast along its dimensions of size 1.
+    #[allow(clippy::too_many_arguments)]
+    pub fn op_tensor<T>(
+        &self,
+        op_tensor_desc: &OpTensorDescriptor,
+        a_desc: &TensorDescriptor,
+        a_data: *const ::libc::c_void,
+        alpha1: T,
+        b_desc: &TensorDescriptor,
+        b_data: *const ::libc::c_void,
+        alpha2: T,
+        dest_desc: &TensorDescriptor,
+        dest_data: *mut ::libc::c_void,
+        beta: T,
+    ) -> Result<(), Error>
+    where
+        T: Float + DataTypeInfo,
+    {
+        API::op_tensor(
+            *self.id_c(),
+            *op_tensor_desc.id_c(),
+            unsafe { transmute_copy(&&alpha1) },
+            *a_desc.id_c(),
+            a_data,
+            unsafe { transmute_copy(&&alpha2) },
+            *b_desc.id_c(),
+            b_data,
+            unsafe { transmute_copy(&&beta) },
+            *dest_desc.id_c(),
+            dest_data,
+        )
+    }
+
+    /// Returns the size of the memory needed for the indices of a reduction in bytes.
+    pub fn reduction_indices_size(
+        &self,
+        reduce_tensor_desc: &ReduceTensorDescriptor,
+        src_desc: &TensorDescriptor,
+        dest_desc: &TensorDescriptor,
+    ) -> Result<usize, Error> {
+        API::get_reduction_indices_size(
+            *self.id_c(),
+            *reduce_tensor_desc.id_c(),
+            *src_desc.id_c(),
+            *dest_desc.id_c(),
+        )
+    }
+
+    /// Reduces a tensor along all dimensions `dest_desc` is of size 1.
+    ///
+    /// Writes the result of the computation to `dest_data` and, if requested by
+    /// `reduce_tensor_desc`, the indices of the reduced elements to `indices`.
+    /// The workspace needed is allocated for the duration of the call.
+    #[allow(clippy::too_many_arguments)]
+    pub fn reduce_tensor<T>(
+        &self,
+        reduce_tensor_desc: &ReduceTensorDescriptor,
+        src_desc: &TensorDescriptor,
+        src_data: *const ::libc::c_void,
+        dest_desc: &TensorDescriptor,
+        dest_data: *mut ::libc::c_void,
+        indices: *mut ::libc::c_void,
+        indices_size: usize,
+        scale: ScalParams<T>,
+    ) -> Result<(), Error>
+    where
+        T: Float + DataTypeInfo,
+    {
+        let workspace_size = API::get_reduction_workspace_size(
+            *self.id_c(),
+            *reduce_tensor_desc.id_c(),
+            *src_desc.id_c(),
+            *dest_desc.id_c(),
+        )?;
+        let workspace = CudaDeviceMemory::new(usize::max(1, workspace_size))?;
+        API::reduce_tensor(
+            *self.id_c(),
+            *reduce_tensor_desc.id_c(),
+            indices,
+            indices_size,
+            *workspace.id_c(),
+            workspace_size,
+            unsafe { transmute_copy(&&scale.a) },
+            *src_desc.id_c(),
+            src_data,
+            unsafe { transmute_copy(&&scale.b) },
+            *dest_desc.id_c(),
+            dest_data,
+        )
+    }
 }
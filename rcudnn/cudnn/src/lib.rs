@@ -81,7 +81,9 @@ pub use self::dropout_descriptor::DropoutDescriptor;
 pub use self::error::Error;
 pub use self::filter_descriptor::FilterDescriptor;
 pub use self::normalization_descriptor::NormalizationDescriptor;
+pub use self::op_tensor_descriptor::OpTensorDescriptor;
 pub use self::pooling_descriptor::PoolingDescriptor;
+pub use self::reduce_tensor_descriptor::ReduceTensorDescriptor;
 pub use self::tensor_descriptor::TensorDescriptor;
 pub use self::rnn_descriptor::RnnDescriptor;
 pub use crate::ffi::*;
@@ -99,7 +101,9 @@ mod dropout_descriptor;
 mod error;
 mod filter_descriptor;
 mod normalization_descriptor;
+mod op_tensor_descriptor;
 mod pooling_descriptor;
+mod reduce_tensor_descriptor;
 mod tensor_descriptor;
 mod rnn_descriptor;
 pub mod utils;
@@ -0,0 +1,53 @@
+//! Defines a Op Tensor Descriptor.
+//!
+//! A Op Tensor Descriptor is used to hold information about the elementwise
+//! operation, which is applied to the tensors.
+
+use super::utils::DataType;
+use super::{Error, API};
+use crate::ffi::*;
+
+#[derive(Debug, Clone)]
+/// Describes a OpTensorDescriptor.
+pub struct OpTensorDescriptor {
+    id: cudnnOpTensorDescriptor_t,
+}
+
+impl Drop for OpTensorDescriptor {
+    #[allow(unused_must_use)]
+    fn drop(&mut self) {
+        API::destroy_op_tensor_descriptor(*self.id_c());
+    }
+}
+
+impl OpTensorDescriptor {
+    /// Initializes a new CUDA cuDNN Op Tensor Descriptor.
+    ///
+    /// The operation is computed with the precision of `data_type`.
+    pub fn new(op: cudnnOpTensorOp_t, data_type: DataType) -> Result<OpTensorDescriptor, Error> {
+        let comp_type = match data_type {
+            DataType::Float => cudnnDataType_t::CUDNN_DATA_FLOAT,
+            DataType::Double => cudnnDataType_t::CUDNN_DATA_DOUBLE,
+            // half precision tensors are computed in single precision
+            DataType::Half => cudnnDataType_t::CUDNN_DATA_FLOAT,
+        };
+        let generic_op_tensor_desc = API::create_op_tensor_descriptor()?;
+        API::set_op_tensor_descriptor(
+            generic_op_tensor_desc,
+            op,
+            comp_type,
+            cudnnNanPropagation_t::CUDNN_PROPAGATE_NAN,
+        )?;
+        Ok(OpTensorDescriptor::from_c(generic_op_tensor_desc))
+    }
+
+    /// Initializes a new CUDA cuDNN Op Tensor Descriptor from its C type.
+    pub fn from_c(id: cudnnOpTensorDescriptor_t) -> OpTensorDescriptor {
+        OpTensorDescriptor { id }
+    }
+
+    /// Returns the CUDA cuDNN Op Tensor Descriptor as its C type.
+    pub fn id_c(&self) -> &cudnnOpTensorDescriptor_t {
+        &self.id
+    }
+}
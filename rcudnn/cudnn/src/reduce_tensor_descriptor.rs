@@ -0,0 +1,61 @@
+//! Defines a Reduce Tensor Descriptor.
+//!
+//! A Reduce Tensor Descriptor is used to hold information about the reduction,
+//! which is applied to the tensor, and if the indices of the reduced
+//! elements are computed as well.
+
+use super::utils::DataType;
+use super::{Error, API};
+use crate::ffi::*;
+
+#[derive(Debug, Clone)]
+/// Describes a ReduceTensorDescriptor.
+pub struct ReduceTensorDescriptor {
+    id: cudnnReduceTensorDescriptor_t,
+}
+
+impl Drop for ReduceTensorDescriptor {
+    #[allow(unused_must_use)]
+    fn drop(&mut self) {
+        API::destroy_reduce_tensor_descriptor(*self.id_c());
+    }
+}
+
+impl ReduceTensorDescriptor {
+    /// Initializes a new CUDA cuDNN Reduce Tensor Descriptor.
+    ///
+    /// The reduction is computed with the precision of `data_type`. The indices, if
+    /// requested, are 32 bit unsigned integers.
+    pub fn new(
+        op: cudnnReduceTensorOp_t,
+        data_type: DataType,
+        indices: cudnnReduceTensorIndices_t,
+    ) -> Result<ReduceTensorDescriptor, Error> {
+        let comp_type = match data_type {
+            DataType::Float => cudnnDataType_t::CUDNN_DATA_FLOAT,
+            DataType::Double => cudnnDataType_t::CUDNN_DATA_DOUBLE,
+            // half precision tensors are reduced in single precision
+            DataType::Half => cudnnDataType_t::CUDNN_DATA_FLOAT,
+        };
+        let generic_reduce_tensor_desc = API::create_reduce_tensor_descriptor()?;
+        API::set_reduce_tensor_descriptor(
+            generic_reduce_tensor_desc,
+            op,
+            comp_type,
+            cudnnNanPropagation_t::CUDNN_PROPAGATE_NAN,
+            indices,
+            cudnnIndicesType_t::CUDNN_32BIT_INDICES,
+        )?;
+        Ok(ReduceTensorDescriptor::from_c(generic_reduce_tensor_desc))
+    }
+
+    /// Initializes a new CUDA cuDNN Reduce Tensor Descriptor from its C type.
+    pub fn from_c(id: cudnnReduceTensorDescriptor_t) -> ReduceTensorDescriptor {
+        ReduceTensorDescriptor { id }
+    }
+
+    /// Returns the CUDA cuDNN Reduce Tensor Descriptor as its C type.
+    pub fn id_c(&self) -> &cudnnReduceTensorDescriptor_t {
+        &self.id
+    }
+}
@@ -157,6 +157,47 @@ impl NormalizationConfig {
     }
 }
 
+#[derive(Debug, Copy, Clone)]
+/// Provides a convenient interface to the parameters of cuDNN's Batch Normalization.
+///
+/// You woudn't use this struct yourself, but rather obtain it through `Cudnn.init_batch_normalization()`.
+pub struct BatchNormalizationConfig {
+    mode: cudnnBatchNormMode_t,
+    epsilon: f64,
+    exponential_average_factor: f64,
+}
+
+impl BatchNormalizationConfig {
+    /// Returns a new Batch Normalization Config.
+    pub fn new(
+        mode: cudnnBatchNormMode_t,
+        epsilon: f64,
+        exponential_average_factor: f64,
+    ) -> BatchNormalizationConfig {
+        BatchNormalizationConfig {
+            mode,
+            epsilon,
+            exponential_average_factor,
+        }
+    }
+
+    /// Returns `mode`.
+    pub fn mode(&self) -> cudnnBatchNormMode_t {
+        self.mode
+    }
+
+    /// Returns `epsilon`, which is added to the variance for numerical stability.
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    /// Returns `exponential_average_factor`, the weight of the current batch
+    /// in the running mean and variance.
+    pub fn exponential_average_factor(&self) -> f64 {
+        self.exponential_average_factor
+    }
+}
+
 #[allow(missing_debug_implementations, missing_copy_implementations)]
 /// Provides a convenient interface to access cuDNN's Pooling Descriptor.
 ///